@@ -0,0 +1,32 @@
+use std::env;
+use std::process::Command;
+
+fn command_output(program: &str, arguments: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(arguments).output().ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        .filter(|output| !output.is_empty())
+}
+
+/// Embeds the commit and compiler of the build, which the debug endpoint of the HTTP API reports.
+fn main() {
+    // Builds without the repository, e.g. in CI, can pass the commit explicitly.
+    let git_sha = env::var("GIT_SHA")
+        .ok()
+        .filter(|git_sha| !git_sha.is_empty())
+        .or_else(|| command_output("git", &["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_owned());
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let rustc_version =
+        command_output(rustc.as_str(), &["--version"]).unwrap_or_else(|| "unknown".to_owned());
+
+    println!("cargo:rustc-env=PREVIEWBOT_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=PREVIEWBOT_RUSTC_VERSION={}", rustc_version);
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+}
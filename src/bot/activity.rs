@@ -0,0 +1,67 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serenity::all::{ChannelId, GuildId, MessageId};
+use tokio::sync::broadcast;
+
+/// Subscribers that fall further behind than this skip the oldest events.
+const ACTIVITY_CHANNEL_CAPACITY: usize = 256;
+
+/// Structured events describing what the bot is doing, streamed live by the HTTP API.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ActivityEvent {
+    FilePreviewCreated {
+        guild_id: Option<GuildId>,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        url: String,
+    },
+    JuxtaposeCreated {
+        guild_id: Option<GuildId>,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    },
+    FilePreviewFailed {
+        guild_id: Option<GuildId>,
+        channel_id: ChannelId,
+        error: String,
+    },
+    CommandFailed {
+        guild_id: Option<GuildId>,
+        channel_id: ChannelId,
+        command: String,
+        error: String,
+    },
+}
+
+impl ActivityEvent {
+    pub(crate) fn guild_id(&self) -> Option<GuildId> {
+        match self {
+            Self::FilePreviewCreated { guild_id, .. }
+            | Self::JuxtaposeCreated { guild_id, .. }
+            | Self::FilePreviewFailed { guild_id, .. }
+            | Self::CommandFailed { guild_id, .. } => *guild_id,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::FilePreviewCreated { .. } => "file_preview_created",
+            Self::JuxtaposeCreated { .. } => "juxtapose_created",
+            Self::FilePreviewFailed { .. } => "file_preview_failed",
+            Self::CommandFailed { .. } => "command_failed",
+        }
+    }
+}
+
+static ACTIVITY_SENDER: Lazy<broadcast::Sender<ActivityEvent>> =
+    Lazy::new(|| broadcast::channel(ACTIVITY_CHANNEL_CAPACITY).0);
+
+/// Publishes an event to all current subscribers. Events are dropped if nobody is subscribed.
+pub(crate) fn publish_activity(event: ActivityEvent) {
+    let _ = ACTIVITY_SENDER.send(event);
+}
+
+pub(crate) fn subscribe_activity() -> broadcast::Receiver<ActivityEvent> {
+    ACTIVITY_SENDER.subscribe()
+}
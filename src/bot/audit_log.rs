@@ -0,0 +1,150 @@
+use serenity::all::{
+    ChannelId, Colour, CreateEmbed, CreateMessage, GuildId, MessageBuilder, MessageId, Timestamp,
+    UserId,
+};
+use serenity::http::Http;
+
+use super::guild_config::GuildConfig;
+
+pub(crate) enum AuditLogEntry<'a> {
+    FilePreviewPosted {
+        author_id: UserId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        url: &'a str,
+    },
+    FilePreviewDeleted {
+        deleted_by_id: UserId,
+        channel_id: ChannelId,
+        is_moderation: bool,
+    },
+    FilePreviewFiltered {
+        author_id: UserId,
+        channel_id: ChannelId,
+        url: &'a str,
+        is_blocked: bool,
+        matched_patterns: &'a [&'a str],
+    },
+    JuxtaposeCreated {
+        author_id: UserId,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    },
+    ConfigChanged {
+        changed_by_id: UserId,
+        change: &'a str,
+    },
+}
+
+fn message_link(guild_id: GuildId, channel_id: ChannelId, message_id: MessageId) -> String {
+    format!(
+        "https://discord.com/channels/{}/{}/{}",
+        guild_id, channel_id, message_id
+    )
+}
+
+impl AuditLogEntry<'_> {
+    fn to_embed(&self, guild_id: GuildId) -> CreateEmbed<'static> {
+        let (colour, description) = match self {
+            Self::FilePreviewPosted {
+                author_id,
+                channel_id,
+                message_id,
+                url,
+            } => (
+                Colour::DARK_GREEN,
+                format!(
+                    "📄 [File preview]({}) for <@{}> posted in <#{}>\n{}",
+                    message_link(guild_id, *channel_id, *message_id),
+                    author_id,
+                    channel_id,
+                    url
+                ),
+            ),
+            Self::FilePreviewDeleted {
+                deleted_by_id,
+                channel_id,
+                is_moderation,
+            } => (
+                Colour::DARK_GREY,
+                format!(
+                    "🗑 Preview in <#{}> {} <@{}>",
+                    channel_id,
+                    if *is_moderation {
+                        "removed by moderator"
+                    } else {
+                        "deleted by"
+                    },
+                    deleted_by_id
+                ),
+            ),
+            Self::FilePreviewFiltered {
+                author_id,
+                channel_id,
+                url,
+                is_blocked,
+                matched_patterns,
+            } => (
+                Colour::ORANGE,
+                format!(
+                    "🚫 File preview for <@{}> in <#{}> {} by the keyword filter\n{}\n{}",
+                    author_id,
+                    channel_id,
+                    if *is_blocked { "blocked" } else { "redacted" },
+                    url,
+                    MessageBuilder::new()
+                        .push_codeblock_safe(matched_patterns.join("\n"), None)
+                        .build()
+                ),
+            ),
+            Self::JuxtaposeCreated {
+                author_id,
+                channel_id,
+                message_id,
+            } => (
+                Colour::BLUE,
+                format!(
+                    "🖼 [Juxtapose]({}) created by <@{}> in <#{}>",
+                    message_link(guild_id, *channel_id, *message_id),
+                    author_id,
+                    channel_id
+                ),
+            ),
+            Self::ConfigChanged {
+                changed_by_id,
+                change,
+            } => (
+                Colour::BLURPLE,
+                format!(
+                    "⚙ Configuration changed by <@{}>: `{}`",
+                    changed_by_id, change
+                ),
+            ),
+        };
+
+        CreateEmbed::new()
+            .colour(colour)
+            .description(description)
+            .timestamp(Timestamp::now())
+    }
+}
+
+/// Posts an entry to the audit channel of the guild, if one is configured.
+/// Failures are only logged, as auditing must never prevent the action itself.
+pub(crate) async fn send_audit_log(
+    http: &Http,
+    guild_id: Option<GuildId>,
+    guild_config: &GuildConfig,
+    entry: AuditLogEntry<'_>,
+) {
+    let (Some(guild_id), Some(audit_channel_id)) = (guild_id, guild_config.audit_channel_id) else {
+        return;
+    };
+
+    if let Err(error) = audit_channel_id
+        .send_message(http, CreateMessage::new().embed(entry.to_embed(guild_id)))
+        .await
+    {
+        println!("Error while sending audit log entry: {:?}", error);
+    }
+}
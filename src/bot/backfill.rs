@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use redis::AsyncCommands;
+use serenity::all::{ChannelId, ChannelType, GetMessages, Guild, GuildId};
+use serenity::prelude::*;
+use tokio::sync::Semaphore;
+
+use crate::bot::blocklist::is_blocked;
+use crate::bot::file_preview::{send_message_file_previews, PreviewTarget};
+use crate::config::config;
+use crate::SerenityGlobalData;
+
+/// Discord returns at most 100 messages per request.
+pub(crate) const BACKFILL_MAX_MESSAGES: u8 = 100;
+
+/// Number of the most recently active text channels scanned when joining a guild.
+const BACKFILL_JOIN_MAX_CHANNELS: usize = 5;
+
+/// Delay after each message with previews, keeping backfills well below Discord's rate limits.
+const BACKFILL_PREVIEW_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Each guild may start one backfill per cooldown, either on join or using `/backfill`.
+const BACKFILL_COOLDOWN: u64 = 60 * 60;
+
+/// Backfills run one at a time across all guilds, as they can post many messages in a short time.
+static BACKFILL_SEMAPHORE: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(1));
+
+fn redis_backfill_cooldown_key(guild_id: GuildId) -> String {
+    format!("backfill_cooldown:{}", guild_id)
+}
+
+/// Starts the cooldown of the guild, returning the remaining cooldown if it is already active.
+pub(crate) async fn redis_start_backfill_cooldown(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+) -> Result<Option<Duration>, redis::RedisError> {
+    let response: Option<String> = redis::cmd("SET")
+        .arg(redis_backfill_cooldown_key(guild_id))
+        .arg(1)
+        .arg("NX")
+        .arg("EX")
+        .arg(BACKFILL_COOLDOWN)
+        .query_async(connection)
+        .await?;
+
+    if response.is_some() {
+        return Ok(None);
+    }
+
+    let remaining_seconds: i64 = connection
+        .ttl(redis_backfill_cooldown_key(guild_id))
+        .await?;
+
+    Ok(Some(Duration::from_secs(remaining_seconds.max(1) as u64)))
+}
+
+/// Posts previews for links in the most recent messages of the channel, oldest first.
+/// Messages the bot already replied to are skipped. Returns the number of previewed links.
+pub(crate) async fn backfill_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    limit: u8,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let _permit = BACKFILL_SEMAPHORE.acquire().await?;
+
+    let messages = channel_id
+        .messages(
+            &ctx.http,
+            GetMessages::new().limit(limit.min(BACKFILL_MAX_MESSAGES)),
+        )
+        .await?;
+
+    let current_user_id = ctx.cache.current_user().id;
+
+    let replied_message_ids: HashSet<_> = messages
+        .iter()
+        .filter(|message| message.author.id == current_user_id)
+        .filter_map(|message| message.message_reference.as_ref()?.message_id)
+        .collect();
+
+    let mut file_preview_count = 0;
+
+    for message in messages.iter().rev() {
+        if message.author.bot()
+            || replied_message_ids.contains(&message.id)
+            || is_blocked(message.author.id, Some(guild_id))
+        {
+            continue;
+        }
+
+        // Messages fetched over HTTP don't carry the guild ID.
+        let target = PreviewTarget {
+            guild_id: Some(guild_id),
+            ..PreviewTarget::from(message)
+        };
+
+        match send_message_file_previews(ctx, &target, message).await {
+            Ok(0) => continue,
+            Ok(count) => file_preview_count += count,
+            Err(error) => println!("Error while backfilling file preview: {:?}", error),
+        }
+
+        tokio::time::sleep(BACKFILL_PREVIEW_INTERVAL).await;
+    }
+
+    Ok(file_preview_count)
+}
+
+/// Scans the most recently active text channels of a newly joined guild, if enabled by `BACKFILL_ON_JOIN_LIMIT`.
+pub(crate) fn spawn_join_backfill(ctx: &Context, guild: &Guild) {
+    let limit = config().backfill_on_join_limit;
+
+    if limit == 0 {
+        return;
+    }
+
+    let mut text_channels: Vec<_> = guild
+        .channels
+        .iter()
+        .filter(|channel| {
+            matches!(
+                channel.kind,
+                ChannelType::Text | ChannelType::News | ChannelType::Voice | ChannelType::Stage
+            )
+        })
+        .filter_map(|channel| Some((channel.id, channel.last_message_id?)))
+        .collect();
+
+    text_channels.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+    text_channels.truncate(BACKFILL_JOIN_MAX_CHANNELS);
+
+    let ctx = ctx.clone();
+    let guild_id = guild.id;
+
+    tokio::spawn(async move {
+        let mut redis_connection_manager = ctx
+            .data::<SerenityGlobalData>()
+            .redis_connection_manager
+            .clone();
+
+        match redis_start_backfill_cooldown(&mut redis_connection_manager, guild_id).await {
+            Ok(None) => {}
+            Ok(Some(_)) => return,
+            Err(error) => {
+                println!("Error while starting backfill cooldown: {:?}", error);
+                return;
+            }
+        }
+
+        for (channel_id, _) in text_channels {
+            match backfill_channel(&ctx, guild_id, channel_id, limit).await {
+                Ok(file_preview_count) => println!(
+                    "Backfilled {} file previews in channel {} of guild {}.",
+                    file_preview_count, channel_id, guild_id
+                ),
+                Err(error) => println!(
+                    "Error while backfilling channel {} of guild {}: {:?}",
+                    channel_id, guild_id, error
+                ),
+            }
+        }
+    });
+}
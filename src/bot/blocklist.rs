@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use redis::AsyncCommands;
+use serenity::all::{GuildId, UserId};
+
+const REDIS_BLOCKED_USERS_KEY: &str = "blocklist:users";
+const REDIS_BLOCKED_GUILDS_KEY: &str = "blocklist:guilds";
+
+/// The blocklist is checked for every event, so it is kept in memory and reloaded periodically
+/// to pick up changes made by other instances sharing Redis.
+const BLOCKLIST_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+static IS_REFRESH_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default)]
+struct Blocklist {
+    user_ids: HashSet<UserId>,
+    guild_ids: HashSet<GuildId>,
+}
+
+static BLOCKLIST: Lazy<RwLock<Blocklist>> = Lazy::new(|| RwLock::new(Blocklist::default()));
+
+/// A user or guild that the bot ignores entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BlocklistEntry {
+    User(UserId),
+    Guild(GuildId),
+}
+
+impl BlocklistEntry {
+    fn redis_key_and_id(&self) -> (&'static str, u64) {
+        match self {
+            Self::User(user_id) => (REDIS_BLOCKED_USERS_KEY, user_id.get()),
+            Self::Guild(guild_id) => (REDIS_BLOCKED_GUILDS_KEY, guild_id.get()),
+        }
+    }
+}
+
+/// Checks whether events of the user, or in the guild, must be ignored.
+pub(crate) fn is_blocked(user_id: UserId, guild_id: Option<GuildId>) -> bool {
+    let blocklist = BLOCKLIST.read().unwrap();
+
+    blocklist.user_ids.contains(&user_id)
+        || guild_id.is_some_and(|guild_id| blocklist.guild_ids.contains(&guild_id))
+}
+
+pub(crate) fn is_guild_blocked(guild_id: GuildId) -> bool {
+    BLOCKLIST.read().unwrap().guild_ids.contains(&guild_id)
+}
+
+async fn redis_load_blocklist(
+    connection: &mut redis::aio::ConnectionManager,
+) -> Result<(), redis::RedisError> {
+    let user_ids: Vec<u64> = connection.smembers(REDIS_BLOCKED_USERS_KEY).await?;
+    let guild_ids: Vec<u64> = connection.smembers(REDIS_BLOCKED_GUILDS_KEY).await?;
+
+    *BLOCKLIST.write().unwrap() = Blocklist {
+        user_ids: user_ids
+            .into_iter()
+            .filter(|user_id| *user_id != 0)
+            .map(UserId::new)
+            .collect(),
+        guild_ids: guild_ids
+            .into_iter()
+            .filter(|guild_id| *guild_id != 0)
+            .map(GuildId::new)
+            .collect(),
+    };
+
+    Ok(())
+}
+
+/// Adds the entry to the blocklist, returning whether it wasn't blocked before.
+pub(crate) async fn redis_block(
+    connection: &mut redis::aio::ConnectionManager,
+    entry: BlocklistEntry,
+) -> Result<bool, redis::RedisError> {
+    let (key, id) = entry.redis_key_and_id();
+    let added_count: usize = connection.sadd(key, id).await?;
+
+    let mut blocklist = BLOCKLIST.write().unwrap();
+
+    match entry {
+        BlocklistEntry::User(user_id) => blocklist.user_ids.insert(user_id),
+        BlocklistEntry::Guild(guild_id) => blocklist.guild_ids.insert(guild_id),
+    };
+
+    Ok(added_count > 0)
+}
+
+/// Removes the entry from the blocklist, returning whether it was blocked before.
+pub(crate) async fn redis_unblock(
+    connection: &mut redis::aio::ConnectionManager,
+    entry: BlocklistEntry,
+) -> Result<bool, redis::RedisError> {
+    let (key, id) = entry.redis_key_and_id();
+    let removed_count: usize = connection.srem(key, id).await?;
+
+    let mut blocklist = BLOCKLIST.write().unwrap();
+
+    match entry {
+        BlocklistEntry::User(user_id) => blocklist.user_ids.remove(&user_id),
+        BlocklistEntry::Guild(guild_id) => blocklist.guild_ids.remove(&guild_id),
+    };
+
+    Ok(removed_count > 0)
+}
+
+/// Loads the blocklist and keeps reloading it in the background, once per process.
+pub(crate) fn spawn_blocklist_refresh(mut connection: redis::aio::ConnectionManager) {
+    if IS_REFRESH_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BLOCKLIST_REFRESH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(error) = redis_load_blocklist(&mut connection).await {
+                println!("Error while loading the blocklist: {:?}", error);
+            }
+        }
+    });
+}
@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header;
+use serde_json::json;
+use serenity::all::{Cache, UserId};
+
+use crate::config::config;
+use crate::HTTP_CLIENT;
+
+/// Guilds are only counted once their data has arrived, which can take a while after connecting.
+const POSTING_INITIAL_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Bot lists only show the counts on their listing, which doesn't need to be more up to date than this.
+const POSTING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+static IS_POSTING_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy)]
+enum BotList {
+    TopGg,
+    Discords,
+}
+
+impl BotList {
+    fn name(self) -> &'static str {
+        match self {
+            Self::TopGg => "top.gg",
+            Self::Discords => "discords.com",
+        }
+    }
+
+    fn stats_url(self, bot_id: UserId) -> String {
+        match self {
+            Self::TopGg => format!("https://top.gg/api/bots/{}/stats", bot_id),
+            Self::Discords => format!("https://discords.com/bots/api/bot/{}", bot_id),
+        }
+    }
+
+    /// Only top.gg accepts the guild counts of individual shards.
+    fn stats_body(self, guild_counts: &GuildCounts) -> serde_json::Value {
+        match self {
+            Self::TopGg => json!({
+                "server_count": guild_counts.total,
+                "shards": guild_counts.per_shard,
+                "shard_count": guild_counts.per_shard.len(),
+            }),
+            Self::Discords => json!({ "server_count": guild_counts.total }),
+        }
+    }
+}
+
+struct GuildCounts {
+    total: u64,
+    /// Indexed by the shard ID.
+    per_shard: Vec<u64>,
+}
+
+/// Counts the cached guilds, assigning them to shards the way Discord does.
+fn count_guilds(cache: &Cache) -> GuildCounts {
+    let shard_count = u64::from(cache.shard_count().get());
+    let mut per_shard = vec![0; shard_count as usize];
+
+    for guild_id in cache.guilds() {
+        per_shard[((guild_id.get() >> 22) % shard_count) as usize] += 1;
+    }
+
+    GuildCounts {
+        total: per_shard.iter().sum(),
+        per_shard,
+    }
+}
+
+async fn post_guild_counts(
+    bot_list: BotList,
+    token: &str,
+    bot_id: UserId,
+    guild_counts: &GuildCounts,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let response = HTTP_CLIENT
+        .post(bot_list.stats_url(bot_id))
+        .header(header::AUTHORIZATION, token)
+        .json(&bot_list.stats_body(guild_counts))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(format!("API request failed with status {}.", response.status()).into());
+    }
+
+    Ok(())
+}
+
+/// Starts posting the guild count to the bot lists that a token is configured for, once per process.
+pub(crate) fn spawn_bot_list_posting(cache: Arc<Cache>, bot_id: UserId) {
+    let bot_lists: Vec<(BotList, &'static str)> = [
+        (BotList::TopGg, config().topgg_token.as_deref()),
+        (BotList::Discords, config().discords_token.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(bot_list, token)| Some((bot_list, token?)))
+    .collect();
+
+    if bot_lists.is_empty() || IS_POSTING_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        tokio::time::sleep(POSTING_INITIAL_DELAY).await;
+
+        let mut interval = tokio::time::interval(POSTING_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let guild_counts = count_guilds(&cache);
+
+            for (bot_list, token) in &bot_lists {
+                if let Err(error) = post_guild_counts(*bot_list, token, bot_id, &guild_counts).await
+                {
+                    println!(
+                        "Error while posting guild count to {}: {:?}",
+                        bot_list.name(),
+                        error
+                    );
+                }
+            }
+        }
+    });
+}
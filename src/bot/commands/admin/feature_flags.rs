@@ -0,0 +1,106 @@
+use serenity::all::{GuildId, ResolvedOption, ResolvedValue};
+
+use crate::bot::commands::options::get_string_option;
+use crate::feature_flags::{
+    get_default_feature_flag_state, redis_get_feature_flag_overrides,
+    redis_set_feature_flag_override, FeatureFlag, FeatureFlagState,
+};
+
+async fn run_set(
+    connection: &mut redis::aio::ConnectionManager,
+    options: &[ResolvedOption<'_>],
+) -> Result<String, String> {
+    let feature_flag = get_string_option(options, "flag")
+        .and_then(FeatureFlag::parse)
+        .ok_or("Unknown feature flag.")?;
+
+    let guild_id = get_string_option(options, "guild_id")
+        .map(|guild_id| {
+            guild_id
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .filter(|guild_id| *guild_id != 0)
+                .map(GuildId::new)
+                .ok_or("The guild ID is malformed.")
+        })
+        .transpose()?;
+
+    let state = match get_string_option(options, "state").unwrap_or_default() {
+        "reset" => None,
+        state => Some(
+            FeatureFlagState::parse(state)
+                .ok_or("The state must be on, off, reset or a percentage such as 25%.")?,
+        ),
+    };
+
+    if guild_id.is_some() && matches!(state, Some(FeatureFlagState::Rollout(_))) {
+        return Err("The state of a single guild must be on, off or reset.".to_owned());
+    }
+
+    redis_set_feature_flag_override(connection, feature_flag, guild_id, state)
+        .await
+        .map_err(|_| "Failed to store the feature flag.")?;
+
+    let scope = match guild_id {
+        Some(guild_id) => format!("guild {}", guild_id),
+        None => "all guilds".to_owned(),
+    };
+
+    Ok(match state {
+        Some(state) => format!("Set {} to {} for {}.", feature_flag.as_str(), state, scope),
+        None => format!(
+            "Removed the override of {} for {}.",
+            feature_flag.as_str(),
+            scope
+        ),
+    })
+}
+
+async fn run_list(connection: &mut redis::aio::ConnectionManager) -> Result<String, String> {
+    let mut lines = Vec::with_capacity(FeatureFlag::ALL.len());
+
+    for feature_flag in FeatureFlag::ALL {
+        let (global_state, guild_override_count) =
+            redis_get_feature_flag_overrides(connection, feature_flag)
+                .await
+                .map_err(|_| "Failed to load the feature flags.")?;
+
+        let mut line = format!(
+            "`{}`: {}",
+            feature_flag.as_str(),
+            get_default_feature_flag_state(feature_flag)
+        );
+
+        if let Some(global_state) = global_state {
+            line.push_str(format!(", overridden to {}", global_state).as_str());
+        }
+
+        if guild_override_count > 0 {
+            line.push_str(format!(", {} guild overrides", guild_override_count).as_str());
+        }
+
+        lines.push(line);
+    }
+
+    Ok(lines.join("\n"))
+}
+
+pub(super) async fn run_feature_flag(
+    connection: &mut redis::aio::ConnectionManager,
+    options: &[ResolvedOption<'_>],
+) -> Result<String, String> {
+    match options.first() {
+        Some(ResolvedOption {
+            name: "set",
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) => run_set(connection, options).await,
+        Some(ResolvedOption {
+            name: "list",
+            value: ResolvedValue::SubCommand(_),
+            ..
+        }) => run_list(connection).await,
+        _ => Err("Unknown subcommand.".to_owned()),
+    }
+}
@@ -0,0 +1,222 @@
+use reqwest::Url;
+use serenity::all::{
+    CommandInteraction, EditInteractionResponse, GuildId, MessageBuilder, ResolvedOption,
+    ResolvedValue, UserId,
+};
+use serenity::prelude::*;
+
+use crate::bot::blocklist::{redis_block, redis_unblock, BlocklistEntry};
+use crate::bot::commands::options::get_string_option;
+use crate::bot::owner::is_bot_owner;
+use crate::bot::user_data::redis_forget_user;
+use crate::config::set_juxtapose_base_url;
+use crate::web::cache_purge::redis_purge_cache;
+use crate::SerenityGlobalData;
+
+mod feature_flags;
+mod structure;
+mod viewer;
+pub(crate) use structure::register;
+
+fn parse_http_url(url: &str, name: &str) -> Result<Url, String> {
+    Url::parse(url.trim())
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+        .ok_or_else(|| format!("The {} must be an HTTP or HTTPS URL.", name))
+}
+
+async fn run_purge_cache(ctx: &Context, options: &[ResolvedOption<'_>]) -> Result<String, String> {
+    let pattern = get_string_option(options, "pattern").unwrap_or_default();
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let deleted_count = redis_purge_cache(&mut redis_connection_manager, pattern).await?;
+
+    Ok(MessageBuilder::new()
+        .push(format!("Deleted {} cache entries matching ", deleted_count))
+        .push_mono_safe(pattern)
+        .push(".")
+        .build())
+}
+
+async fn run_forget_user(ctx: &Context, options: &[ResolvedOption<'_>]) -> Result<String, String> {
+    let user_id = get_string_option(options, "user_id")
+        .and_then(|user_id| user_id.trim().parse::<u64>().ok())
+        .filter(|user_id| *user_id != 0)
+        .map(UserId::new)
+        .ok_or("The user ID is malformed.")?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let summary = redis_forget_user(&mut redis_connection_manager, user_id)
+        .await
+        .map_err(|_| "Failed to delete the data of the user.")?;
+
+    Ok(format!(
+        "Deleted {} entries of {} juxtaposes and {} albums for user {}.",
+        summary.deleted_key_count, summary.juxtapose_count, summary.album_count, user_id
+    ))
+}
+
+async fn run_viewer(ctx: &Context, options: &[ResolvedOption<'_>]) -> Result<String, String> {
+    let (subcommand_name, subcommand_options) = match options.first() {
+        Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => (*name, subcommand_options.as_slice()),
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    match subcommand_name {
+        "set-url" => {
+            let viewer_url = parse_http_url(
+                get_string_option(subcommand_options, "url").ok_or("A URL is required.")?,
+                "viewer URL",
+            )?;
+
+            set_juxtapose_base_url(viewer_url);
+
+            Ok(format!(
+                "Changed the URL of the viewer in memory only. Reloading the configuration or restarting the bot resets it to JUXTAPOSE_BASE_URL, so change that variable as well to keep it.\n\n{}",
+                viewer::run_viewer_self_test(&mut redis_connection_manager, None).await
+            ))
+        }
+        "test" => {
+            let api_url = get_string_option(subcommand_options, "api_url")
+                .map(|api_url| parse_http_url(api_url, "API URL"))
+                .transpose()?;
+
+            Ok(viewer::run_viewer_self_test(&mut redis_connection_manager, api_url).await)
+        }
+        _ => Err("Unknown subcommand.".to_owned()),
+    }
+}
+
+async fn run_block(
+    ctx: &Context,
+    options: &[ResolvedOption<'_>],
+    is_unblock: bool,
+) -> Result<String, String> {
+    let id = get_string_option(options, "id")
+        .and_then(|id| id.trim().parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .ok_or("The ID is malformed.")?;
+
+    let entry = match get_string_option(options, "kind") {
+        Some("guild") => BlocklistEntry::Guild(GuildId::new(id)),
+        _ => BlocklistEntry::User(UserId::new(id)),
+    };
+
+    if let BlocklistEntry::User(user_id) = entry {
+        if !is_unblock && is_bot_owner(ctx, user_id).await {
+            return Err("The owners of the bot can't be blocked.".to_owned());
+        }
+    }
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let is_changed = if is_unblock {
+        redis_unblock(&mut redis_connection_manager, entry).await
+    } else {
+        redis_block(&mut redis_connection_manager, entry).await
+    }
+    .map_err(|_| "Failed to update the blocklist.")?;
+
+    let description = match entry {
+        BlocklistEntry::User(user_id) => format!("User {}", user_id),
+        BlocklistEntry::Guild(guild_id) => format!("Guild {}", guild_id),
+    };
+
+    Ok(match (is_unblock, is_changed) {
+        (false, true) => format!("{} is now blocked.", description),
+        (false, false) => format!("{} was already blocked.", description),
+        (true, true) => format!("{} is no longer blocked.", description),
+        (true, false) => format!("{} wasn't blocked.", description),
+    })
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer_ephemeral(&ctx.http).await {
+        println!("Failed to defer admin interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Check Permissions */
+
+    if !is_bot_owner(ctx, interaction.user.id).await {
+        return Err("Only the owners of the bot can use this command.".to_owned());
+    }
+
+    /* Run Subcommand */
+
+    let options = interaction.data.options();
+
+    let content = match options.first() {
+        Some(ResolvedOption {
+            name: "purge-cache",
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) => run_purge_cache(ctx, options).await?,
+        Some(ResolvedOption {
+            name: "forget-user",
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) => run_forget_user(ctx, options).await?,
+        Some(ResolvedOption {
+            name: "block",
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) => run_block(ctx, options, false).await?,
+        Some(ResolvedOption {
+            name: "unblock",
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) => run_block(ctx, options, true).await?,
+        Some(ResolvedOption {
+            name: "viewer",
+            value: ResolvedValue::SubCommandGroup(options),
+            ..
+        }) => run_viewer(ctx, options).await?,
+        Some(ResolvedOption {
+            name: "feature-flag",
+            value: ResolvedValue::SubCommandGroup(options),
+            ..
+        }) => {
+            let mut redis_connection_manager = ctx
+                .data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone();
+
+            feature_flags::run_feature_flag(&mut redis_connection_manager, options).await?
+        }
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    println!("Admin command by {}: {}", interaction.user.id, content);
+
+    /* Reply */
+
+    interaction
+        .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+        .await
+        .map_err(|_| "Failed to send the result.")?;
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption, Permissions};
+
+use crate::bot::i18n::{localized_command, localized_option, LocalizedChoices};
+use crate::feature_flags::FeatureFlag;
+
+fn blocklist_options(subcommand: CreateCommandOption<'static>) -> CreateCommandOption<'static> {
+    subcommand
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::String,
+                "kind",
+                "Whether the ID belongs to a user or a guild.",
+            )
+            .add_localized_string_choice("User", "user")
+            .add_localized_string_choice("Guild", "guild")
+            .required(true),
+        )
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::String,
+                "id",
+                "The ID of the user or guild.",
+            )
+            .max_length(20)
+            .required(true),
+        )
+}
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("admin", "Maintenance commands for the owners of the bot.")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "purge-cache",
+                "Delete cache entries matching a pattern, e.g. for takedown requests.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::String,
+                    "pattern",
+                    "Redis glob pattern, e.g. juxtapose_payload:*.",
+                )
+                .max_length(200)
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "forget-user",
+                "Delete all data stored about a user, like /forgetme.",
+            )
+            .add_sub_option(
+                localized_option(CommandOptionType::String, "user_id", "The ID of the user.")
+                    .max_length(20)
+                    .required(true),
+            ),
+        )
+        .add_option(blocklist_options(localized_option(
+            CommandOptionType::SubCommand,
+            "block",
+            "Ignore all messages and interactions of a user or in a guild.",
+        )))
+        .add_option(blocklist_options(localized_option(
+            CommandOptionType::SubCommand,
+            "unblock",
+            "Remove a user or guild from the blocklist.",
+        )))
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommandGroup,
+                "viewer",
+                "Configure and diagnose the juxtapose viewer.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "set-url",
+                    "Change the URL of the viewer until the next restart, then test it.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "url",
+                        "Public URL of the viewer, e.g. https://example.com/view.",
+                    )
+                    .max_length(200)
+                    .required(true),
+                ),
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "test",
+                    "Check that the viewer and its API can be reached and accept signed links.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "api_url",
+                        "URL of the API if it isn't served next to the viewer, e.g. https://example.com/url.",
+                    )
+                    .max_length(200),
+                ),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommandGroup,
+                "feature-flag",
+                "Roll out features gradually or turn them off.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "set",
+                    "Override the state of a feature for all guilds or a single guild.",
+                )
+                .add_sub_option(
+                    FeatureFlag::ALL.into_iter().fold(
+                        localized_option(CommandOptionType::String, "flag", "The feature.")
+                            .required(true),
+                        |option, feature_flag| {
+                            option.add_string_choice(feature_flag.as_str(), feature_flag.as_str())
+                        },
+                    ),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "state",
+                        "on, off, reset or a percentage of guilds such as 25%.",
+                    )
+                    .max_length(5)
+                    .required(true),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "guild_id",
+                        "The ID of the guild, all guilds if empty.",
+                    )
+                    .max_length(20),
+                ),
+            )
+            .add_sub_option(localized_option(
+                CommandOptionType::SubCommand,
+                "list",
+                "Show the state of all features.",
+            )),
+        )
+}
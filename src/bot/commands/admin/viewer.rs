@@ -0,0 +1,185 @@
+use std::error::Error;
+
+use once_cell::sync::Lazy;
+use reqwest::{header, StatusCode, Url};
+use serenity::all::MessageBuilder;
+
+use crate::config::runtime_config;
+use crate::signing::{EncodedSignedPayload, SignedPayload};
+use crate::web::api_juxtapose_response::APIJuxtaposePayload;
+use crate::web::cache_warming::redis_get_latest_recent_juxtapose;
+use crate::{BLAKE3_JUXTAPOSE_KEY, USER_AGENT};
+
+/// The viewer and the API often run on the same host or network as the bot, which the outbound guard of
+/// [`crate::HTTP_CLIENT`] rejects. Only URLs that the owners of the bot entered are requested with this client.
+static SELF_TEST_HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::ClientBuilder::new()
+        .user_agent(USER_AGENT)
+        .build()
+        .expect("Failed to build HTTP client.")
+});
+
+fn format_check(passed: bool, description: &str) -> String {
+    format!("{} {}", if passed { "✅" } else { "❌" }, description)
+}
+
+/// Includes the cause of a request error, as its own message only names the URL.
+fn describe_request_error(error: &reqwest::Error) -> String {
+    match error.source() {
+        Some(source) => format!("{} ({})", error, source),
+        None => error.to_string(),
+    }
+}
+
+async fn check_viewer(viewer_url: &Url) -> String {
+    match SELF_TEST_HTTP_CLIENT.get(viewer_url.clone()).send().await {
+        Ok(response) if response.status().is_success() => format_check(
+            true,
+            format!("The viewer responded with {}.", response.status()).as_str(),
+        ),
+        Ok(response) => format_check(
+            false,
+            format!("The viewer responded with {}.", response.status()).as_str(),
+        ),
+        Err(error) => format_check(
+            false,
+            format!(
+                "The viewer can't be reached: {}",
+                describe_request_error(&error)
+            )
+            .as_str(),
+        ),
+    }
+}
+
+/// Requests the latest juxtapose from the API as the viewer would, checking its response and CORS headers.
+async fn check_api(
+    redis_connection_manager: &mut redis::aio::ConnectionManager,
+    api_url: &Url,
+    viewer_origin: &str,
+) -> Vec<String> {
+    let juxtapose_data = match redis_get_latest_recent_juxtapose(redis_connection_manager).await {
+        Ok(Some(juxtapose_data)) => juxtapose_data,
+        Ok(None) => {
+            return vec![format_check(
+                false,
+                "No juxtapose was created within the last day, so the API can't be tested with a real link. Create one and test again.",
+            )]
+        }
+        Err(error) => {
+            return vec![format_check(
+                false,
+                format!("The latest juxtapose can't be read from Redis: {}", error).as_str(),
+            )]
+        }
+    };
+
+    // The data was stored by the bot after signing it, so it only needs to be signed again.
+    let Ok(payload) = SignedPayload::decode_trusted_data(juxtapose_data.as_str()) else {
+        return vec![format_check(
+            false,
+            "The data of the latest juxtapose is malformed.",
+        )];
+    };
+
+    let EncodedSignedPayload { data, mac } = payload.encode(&BLAKE3_JUXTAPOSE_KEY);
+
+    let mut test_url = api_url.clone();
+    test_url
+        .query_pairs_mut()
+        .append_pair("d", data.as_str())
+        .append_pair("m", mac.as_str());
+
+    let response = match SELF_TEST_HTTP_CLIENT
+        .get(test_url)
+        .header(header::ORIGIN, viewer_origin)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(error) => {
+            return vec![format_check(
+                false,
+                format!(
+                    "The API can't be reached: {}",
+                    describe_request_error(&error)
+                )
+                .as_str(),
+            )]
+        }
+    };
+
+    let allowed_origin = response
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .and_then(|allowed_origin| allowed_origin.to_str().ok());
+
+    let cors_check = match allowed_origin {
+        Some(allowed_origin) if allowed_origin == "*" || allowed_origin == viewer_origin => {
+            format_check(
+                true,
+                format!("CORS allows requests from {}.", viewer_origin).as_str(),
+            )
+        }
+        _ => format_check(
+            false,
+            format!(
+                "CORS doesn't allow requests from {}. Add it to CORS_ORIGIN.",
+                viewer_origin
+            )
+            .as_str(),
+        ),
+    };
+
+    let payload_check = match response.status() {
+        StatusCode::OK => match response.json::<APIJuxtaposePayload>().await {
+            Ok(_) => format_check(true, "The API returned the latest juxtapose."),
+            Err(_) => format_check(
+                false,
+                "The API responded with 200 OK, but not with a juxtapose. Check that the URL points to the /url route.",
+            ),
+        },
+        StatusCode::BAD_REQUEST => format_check(
+            false,
+            "The API rejected the link of the latest juxtapose. It belongs to another instance or uses another BLAKE3_KEY_MATERIAL.",
+        ),
+        StatusCode::NOT_FOUND => format_check(
+            false,
+            "The API couldn't find the latest juxtapose. Check that the URL points to the /url route, or test again if its message was deleted.",
+        ),
+        status => format_check(
+            false,
+            format!("The API responded with {} instead of 200 OK.", status).as_str(),
+        ),
+    };
+
+    vec![payload_check, cors_check]
+}
+
+/// Checks that the viewer at `JUXTAPOSE_BASE_URL` and the API it uses can be reached from the internet,
+/// and that the API returns the latest juxtapose for a link signed by this instance. The API is expected next to
+/// the viewer, like the viewer served at `/view` requests `/url`, unless its URL is given.
+pub(super) async fn run_viewer_self_test(
+    redis_connection_manager: &mut redis::aio::ConnectionManager,
+    api_url: Option<Url>,
+) -> String {
+    let viewer_url = runtime_config().juxtapose_base_url.clone();
+    let api_url = api_url.unwrap_or_else(|| viewer_url.join("url").unwrap_or(viewer_url.clone()));
+    let viewer_origin = viewer_url.origin().ascii_serialization();
+
+    let mut lines = vec![
+        MessageBuilder::new()
+            .push("Viewer: ")
+            .push_mono_safe(viewer_url.as_str())
+            .build(),
+        MessageBuilder::new()
+            .push("API: ")
+            .push_mono_safe(api_url.as_str())
+            .build(),
+        check_viewer(&viewer_url).await,
+    ];
+
+    lines.extend(check_api(redis_connection_manager, &api_url, viewer_origin.as_str()).await);
+
+    lines.join("\n")
+}
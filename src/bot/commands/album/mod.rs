@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use futures_util::future::try_join_all;
+use once_cell::sync::Lazy;
+use serenity::all::{
+    ChannelId, CommandInteraction, CreateActionRow, CreateAllowedMentions, CreateAttachment,
+    CreateButton, EditAttachments, EditInteractionResponse, MessageId,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::{download_image_from_attachment, get_upload_limit};
+use crate::bot::commands::options::{get_attachment_option, get_string_option};
+use crate::bot::commands::rate_limit::{format_cooldown_error, UserCooldown};
+use crate::bot::user_data::redis_record_user_album;
+use crate::config::runtime_config;
+use crate::signing::{EncodedSignedPayload, SignedPayload};
+use crate::web::api_album_response::{APIAlbumImage, APIAlbumResponse, ALBUM_MAX_IMAGES};
+use crate::{SerenityGlobalData, BLAKE3_ALBUM_KEY};
+
+mod structure;
+pub(crate) use structure::register;
+
+static ALBUM_COOLDOWN: Lazy<UserCooldown> =
+    Lazy::new(|| UserCooldown::new(Duration::from_secs(10)));
+
+const IMAGE_MAX_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Creates the signed URL of the gallery for an album message.
+/// Also returns the encoded data, which is used as the key of the API cache.
+fn create_album_url(message_id: MessageId, channel_id: ChannelId) -> (reqwest::Url, String) {
+    let EncodedSignedPayload {
+        data: album_url_data,
+        mac: album_url_mac,
+    } = SignedPayload::new(message_id, channel_id).encode(&BLAKE3_ALBUM_KEY);
+
+    let mut album_url = runtime_config().album_base_url.clone();
+    album_url.query_pairs_mut().extend_pairs(&[
+        ("d", album_url_data.as_str()),
+        ("m", album_url_mac.as_str()),
+    ]);
+
+    (album_url, album_url_data)
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let image_attachments: Vec<_> = (1..=ALBUM_MAX_IMAGES)
+        .filter_map(|index| get_attachment_option(&options, format!("image_{}", index).as_str()))
+        .collect();
+    let title = get_string_option(&options, "title");
+
+    if image_attachments.len() < 2 {
+        return Err("An album must contain at least two images.".to_owned());
+    }
+
+    /* Check Cooldown */
+
+    ALBUM_COOLDOWN
+        .check(interaction.user.id)
+        .map_err(format_cooldown_error)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer album interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Limit Image Size */
+
+    for (index, image_attachment) in image_attachments.iter().enumerate() {
+        if image_attachment.size as u64 > IMAGE_MAX_FILE_SIZE {
+            return Err(format!(
+                "Image number {} must not be bigger than 16 MB.",
+                index + 1
+            ));
+        }
+
+        if image_attachment.width.is_none() || image_attachment.height.is_none() {
+            return Err(format!(
+                "Image number {} is not a supported image.",
+                index + 1
+            ));
+        }
+    }
+
+    let upload_limit = get_upload_limit(ctx, interaction.guild_id).await;
+    let total_size: u64 = image_attachments
+        .iter()
+        .map(|image_attachment| image_attachment.size as u64)
+        .sum();
+
+    if total_size > upload_limit as u64 {
+        return Err("The images are too large to be uploaded in this server.".to_owned());
+    }
+
+    /* Download Images */
+
+    let images = try_join_all(image_attachments.iter().map(|image_attachment| {
+        download_image_from_attachment(
+            image_attachment,
+            image_attachment.width.map_or(0, |width| width.get()),
+            image_attachment.height.map_or(0, |height| height.get()),
+        )
+    }))
+    .await?;
+
+    let edit_attachments = image_attachments.iter().zip(images).fold(
+        EditAttachments::new(),
+        |edit_attachments, (image_attachment, (image_bytes, _))| {
+            let mut create_attachment =
+                CreateAttachment::bytes(image_bytes, image_attachment.filename.to_owned());
+
+            if let Some(ref description) = image_attachment.description {
+                create_attachment = create_attachment.description(description.to_string());
+            }
+
+            edit_attachments.add(create_attachment)
+        },
+    );
+
+    /* Reply */
+
+    let reply = interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .content(title.unwrap_or_default())
+                .allowed_mentions(CreateAllowedMentions::new())
+                .attachments(edit_attachments),
+        )
+        .await
+        .map_err(|_| "Failed to upload images to Discord. Perhaps they are too large?")?;
+
+    /* Encode Data */
+
+    let (album_url, album_url_data) = create_album_url(reply.id, interaction.channel_id);
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().components(&[CreateActionRow::buttons(&[
+                CreateButton::new_link(album_url.as_str())
+                    .emoji('🔗')
+                    .label("Open"),
+            ])]),
+        )
+        .await
+        .map_err(|_| "Failed to add button containing the album URL.")?;
+
+    /* Warm Cache */
+
+    let album_cache_data = APIAlbumResponse {
+        title: title.map(ToOwned::to_owned),
+        images: reply
+            .attachments
+            .iter()
+            .map(|attachment| APIAlbumImage {
+                url: attachment.url.to_string(),
+                label: attachment.description.as_ref().map(ToString::to_string),
+            })
+            .collect(),
+    };
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    if let Err(error) = redis_record_user_album(
+        &mut redis_connection_manager,
+        interaction.user.id,
+        album_url_data.as_str(),
+    )
+    .await
+    {
+        println!("Error while recording album of user: {:?}", error);
+    }
+
+    // The cache is rebuilt from the message on the first request if this fails.
+    let _ = album_cache_data
+        .redis_cache_set(&mut redis_connection_manager, album_url_data.as_str())
+        .await;
+
+    Ok(())
+}
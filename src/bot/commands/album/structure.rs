@@ -0,0 +1,38 @@
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption};
+
+use crate::bot::i18n::{localizations, localized_command, localized_option};
+use crate::web::api_album_response::ALBUM_MAX_IMAGES;
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    let mut command = localized_command(
+        "album",
+        "Create an album of up to 10 images that can be viewed as a gallery.",
+    );
+
+    // Discord requires the required options to come first.
+    for index in 1..=ALBUM_MAX_IMAGES {
+        let description = "Image number {} of the album.";
+        let option = localizations(description).fold(
+            CreateCommandOption::new(
+                CommandOptionType::Attachment,
+                format!("image_{}", index),
+                description.replace("{}", &index.to_string()),
+            ),
+            |option, (locale, translation)| {
+                option.description_localized(locale, translation.replace("{}", &index.to_string()))
+            },
+        );
+
+        command = command.add_option(option.required(index <= 2));
+    }
+
+    command.add_option(
+        localized_option(
+            CommandOptionType::String,
+            "title",
+            "The title of the album.",
+        )
+        .max_length(100)
+        .required(false),
+    )
+}
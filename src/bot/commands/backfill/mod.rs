@@ -0,0 +1,64 @@
+use serenity::all::{CommandInteraction, EditInteractionResponse};
+use serenity::prelude::*;
+
+use crate::bot::backfill::{
+    backfill_channel, redis_start_backfill_cooldown, BACKFILL_MAX_MESSAGES,
+};
+use crate::bot::commands::options::{get_channel_option, get_integer_option};
+use crate::bot::commands::rate_limit::format_cooldown_error;
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::register;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let channel = get_channel_option(&options, "channel").ok_or("A channel is required.")?;
+    let limit = get_integer_option(&options, "limit")
+        .unwrap_or(50)
+        .clamp(1, BACKFILL_MAX_MESSAGES as i64) as u8;
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or("This command can only be used in servers.")?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    if let Some(remaining_cooldown) =
+        redis_start_backfill_cooldown(&mut redis_connection_manager, guild_id)
+            .await
+            .map_err(|_| "Failed to start the backfill.")?
+    {
+        return Err(format_cooldown_error(remaining_cooldown));
+    }
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer_ephemeral(&ctx.http).await {
+        println!("Failed to defer backfill interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Backfill */
+
+    let file_preview_count = backfill_channel(ctx, guild_id, channel.id, limit)
+        .await
+        .map_err(|error| format!("Failed to scan the channel: {}", error))?;
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().content(format!(
+                "Scanned the last {} messages of <#{}> and posted {} previews.",
+                limit, channel.id, file_preview_count
+            )),
+        )
+        .await
+        .map_err(|_| "Failed to confirm the backfill.")?;
+
+    Ok(())
+}
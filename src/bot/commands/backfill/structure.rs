@@ -0,0 +1,39 @@
+use serenity::all::{ChannelType, CommandOptionType, CreateCommand, Permissions};
+
+use crate::bot::backfill::BACKFILL_MAX_MESSAGES;
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "backfill",
+        "Post previews for links in the recent messages of a channel.",
+    )
+    .default_member_permissions(Permissions::MANAGE_GUILD)
+    .add_option(
+        localized_option(
+            CommandOptionType::Channel,
+            "channel",
+            "The channel to scan.",
+        )
+        .channel_types(vec![
+            ChannelType::Text,
+            ChannelType::News,
+            ChannelType::Voice,
+            ChannelType::Stage,
+            ChannelType::PublicThread,
+            ChannelType::PrivateThread,
+            ChannelType::NewsThread,
+        ])
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::Integer,
+            "limit",
+            "The number of recent messages to scan. Defaults to 50.",
+        )
+        .min_int_value(1)
+        .max_int_value(BACKFILL_MAX_MESSAGES as u64)
+        .required(false),
+    )
+}
@@ -0,0 +1,486 @@
+use std::error::Error;
+
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serenity::all::{
+    ActionRowComponent, ButtonStyle, Colour, CommandInteraction, ComponentInteraction,
+    ComponentInteractionDataKind, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage, CreateModal,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
+    InputTextStyle, InteractionId, MessageBuilder, ModalInteraction, UserId,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::options::get_string_option;
+use crate::bot::commands::permalink::{build_permalink, get_line_fragment};
+use crate::bot::custom_id::sign_custom_id;
+use crate::bot::file_preview::backpressure::SendPriority;
+use crate::bot::file_preview::{send_file_preview_for_url, PreviewTarget};
+use crate::bot::github_api::{
+    get_tree_entries, parse_repository, resolve_commit_sha, TreeEntry, TreeEntryKind,
+};
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::register;
+
+/// Prefix shared by the custom IDs of all components and modals of the browser.
+pub(crate) const CUSTOM_ID_PREFIX: &str = "browse";
+
+/// Discord limits select menus to 25 options.
+const ENTRIES_PER_PAGE: usize = 25;
+
+/// Sessions expire together with the interaction token of the command.
+const SESSION_TTL: u64 = 15 * 60;
+
+/// Upper bound for the number of entries kept for a single directory.
+const MAX_DIRECTORY_ENTRIES: usize = 40 * ENTRIES_PER_PAGE;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BrowseSession {
+    user_id: UserId,
+    owner: String,
+    repository: String,
+    commit_sha: String,
+    /// Names and tree SHAs of the directories from the root to the current directory.
+    directories: Vec<(String, String)>,
+    entries: Vec<TreeEntry>,
+    page: usize,
+    selected_file: Option<String>,
+}
+
+impl BrowseSession {
+    fn redis_key(session_id: InteractionId) -> String {
+        format!("browse_session:{}", session_id)
+    }
+
+    async fn redis_get(
+        connection: &mut redis::aio::ConnectionManager,
+        session_id: InteractionId,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        let data: Option<String> = connection.get(Self::redis_key(session_id)).await?;
+
+        Ok(match data {
+            Some(data) => Some(serde_json::from_str(data.as_str())?),
+            None => None,
+        })
+    }
+
+    async fn redis_set(
+        &self,
+        connection: &mut redis::aio::ConnectionManager,
+        session_id: InteractionId,
+    ) -> Result<(), redis::RedisError> {
+        let data = serde_json::to_string(self).expect("Failed to serialize browse session.");
+        connection
+            .set_ex(Self::redis_key(session_id), data, SESSION_TTL)
+            .await
+    }
+
+    fn current_path(&self) -> String {
+        self.directories
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn file_path(&self, file_name: &str) -> String {
+        match self.current_path().as_str() {
+            "" => file_name.to_owned(),
+            current_path => format!("{}/{}", current_path, file_name),
+        }
+    }
+
+    fn page_count(&self) -> usize {
+        self.entries.len().div_ceil(ENTRIES_PER_PAGE).max(1)
+    }
+
+    async fn load_directory(&mut self, tree_sha: &str) -> Result<(), String> {
+        let mut entries: Vec<TreeEntry> =
+            get_tree_entries(self.owner.as_str(), self.repository.as_str(), tree_sha)
+                .await?
+                .into_iter()
+                .filter(|entry| entry.kind != TreeEntryKind::Commit)
+                .collect();
+
+        // Directories first, like on GitHub.
+        entries.sort_by(|a, b| {
+            (a.kind != TreeEntryKind::Tree, a.path.to_lowercase())
+                .cmp(&(b.kind != TreeEntryKind::Tree, b.path.to_lowercase()))
+        });
+        entries.truncate(MAX_DIRECTORY_ENTRIES);
+
+        self.entries = entries;
+        self.page = 0;
+
+        Ok(())
+    }
+
+    fn create_embed(&self) -> CreateEmbed<'static> {
+        let description = MessageBuilder::new()
+            .push_mono_safe(format!("/{}", self.current_path()))
+            .push(" at ")
+            .push_mono_safe(&self.commit_sha[..self.commit_sha.len().min(7)])
+            .push_line("")
+            .push(match self.entries.is_empty() {
+                true => "This directory is empty.",
+                false => "Select a directory to open it, or a file to preview it.",
+            })
+            .build();
+
+        CreateEmbed::new()
+            .title(format!("{}/{}", self.owner, self.repository))
+            .colour(Colour::BLURPLE)
+            .description(description)
+            .footer(CreateEmbedFooter::new(format!(
+                "Page {}/{}",
+                self.page + 1,
+                self.page_count()
+            )))
+    }
+
+    fn create_components(&self, session_id: InteractionId) -> Vec<CreateActionRow<'static>> {
+        let custom_id = |action: &str| {
+            sign_custom_id(format!("{}{}:{}", CUSTOM_ID_PREFIX, action, session_id).as_str())
+        };
+
+        let mut components = Vec::new();
+
+        let select_options: Vec<CreateSelectMenuOption> = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(self.page * ENTRIES_PER_PAGE)
+            .take(ENTRIES_PER_PAGE)
+            .map(|(index, entry)| {
+                let label = format!(
+                    "{} {}",
+                    match entry.kind {
+                        TreeEntryKind::Tree => "📁",
+                        _ => "📄",
+                    },
+                    entry.path.chars().take(95).collect::<String>()
+                );
+
+                CreateSelectMenuOption::new(label, index.to_string())
+            })
+            .collect();
+
+        if !select_options.is_empty() {
+            components.push(CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    custom_id("Select"),
+                    CreateSelectMenuKind::String {
+                        options: select_options.into(),
+                    },
+                )
+                .placeholder("Open a directory or file"),
+            ));
+        }
+
+        components.push(CreateActionRow::Buttons(
+            vec![
+                CreateButton::new(custom_id("Up"))
+                    .label("Up")
+                    .style(ButtonStyle::Secondary)
+                    .disabled(self.directories.is_empty()),
+                CreateButton::new(custom_id("Previous"))
+                    .label("Previous")
+                    .style(ButtonStyle::Secondary)
+                    .disabled(self.page == 0),
+                CreateButton::new(custom_id("Next"))
+                    .label("Next")
+                    .style(ButtonStyle::Secondary)
+                    .disabled(self.page + 1 >= self.page_count()),
+            ]
+            .into(),
+        ));
+
+        components
+    }
+}
+
+/// Splits a custom ID in the format `browse<Action>:<session ID>`.
+fn parse_custom_id(custom_id: &str) -> Option<(&str, InteractionId)> {
+    let (action, session_id) = custom_id.strip_prefix(CUSTOM_ID_PREFIX)?.split_once(':')?;
+
+    Some((action, InteractionId::new(session_id.parse().ok()?)))
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let repository = get_string_option(&options, "repo").unwrap_or_default();
+    let reference = get_string_option(&options, "ref").unwrap_or("HEAD");
+
+    let (owner, repository) = parse_repository(repository)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer browse interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Load Root Directory */
+
+    let commit_sha = resolve_commit_sha(owner, repository, reference.trim()).await?;
+
+    let mut session = BrowseSession {
+        user_id: interaction.user.id,
+        owner: owner.to_owned(),
+        repository: repository.to_owned(),
+        commit_sha: commit_sha.clone(),
+        directories: Vec::new(),
+        entries: Vec::new(),
+        page: 0,
+        selected_file: None,
+    };
+
+    session.load_directory(commit_sha.as_str()).await?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    session
+        .redis_set(&mut redis_connection_manager, interaction.id)
+        .await
+        .map_err(|_| "Failed to store the browser.")?;
+
+    /* Reply */
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .embed(session.create_embed())
+                .components(session.create_components(interaction.id)),
+        )
+        .await
+        .map_err(|_| "Failed to send the browser.")?;
+
+    Ok(())
+}
+
+async fn respond_ephemeral(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    content: &str,
+) -> Result<(), serenity::Error> {
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await
+}
+
+pub async fn handle_browse_component(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (action, session_id) =
+        parse_custom_id(custom_id).ok_or("Failed to retrieve session ID from custom ID.")?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let Some(mut session) =
+        BrowseSession::redis_get(&mut redis_connection_manager, session_id).await?
+    else {
+        respond_ephemeral(ctx, interaction, "This browser has expired.").await?;
+        return Ok(());
+    };
+
+    if session.user_id != interaction.user.id {
+        respond_ephemeral(
+            ctx,
+            interaction,
+            "Only the user who used the command can navigate this browser.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let navigation_result = match (action, &interaction.data.kind) {
+        ("Select", ComponentInteractionDataKind::StringSelect { values }) => {
+            let entry = values
+                .first()
+                .and_then(|value| value.parse::<usize>().ok())
+                .and_then(|index| session.entries.get(index))
+                .ok_or("Selected entry not found.")?
+                .clone();
+
+            if entry.kind == TreeEntryKind::Blob {
+                session.selected_file = Some(session.file_path(entry.path.as_str()));
+                session
+                    .redis_set(&mut redis_connection_manager, session_id)
+                    .await?;
+
+                let lines_input = CreateInputText::new(InputTextStyle::Short, "Lines", "lines")
+                    .placeholder("A line number or a range like 10-20")
+                    .max_length(20)
+                    .required(true);
+
+                interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Modal(
+                            CreateModal::new(
+                                sign_custom_id(
+                                    format!("{}Lines:{}", CUSTOM_ID_PREFIX, session_id).as_str(),
+                                ),
+                                "Preview File",
+                            )
+                            .components(vec![CreateActionRow::InputText(lines_input)]),
+                        ),
+                    )
+                    .await?;
+
+                return Ok(());
+            }
+
+            session.directories.push((entry.path, entry.sha.clone()));
+            session.load_directory(entry.sha.as_str()).await
+        }
+        ("Up", _) => {
+            session.directories.pop();
+
+            let tree_sha = match session.directories.last() {
+                Some((_, tree_sha)) => tree_sha.clone(),
+                None => session.commit_sha.clone(),
+            };
+
+            session.load_directory(tree_sha.as_str()).await
+        }
+        ("Previous", _) => {
+            session.page = session.page.saturating_sub(1);
+            Ok(())
+        }
+        ("Next", _) => {
+            session.page = (session.page + 1).min(session.page_count() - 1);
+            Ok(())
+        }
+        _ => return Err("Unknown browser action.".into()),
+    };
+
+    if let Err(error) = navigation_result {
+        respond_ephemeral(ctx, interaction, error.as_str()).await?;
+        return Ok(());
+    }
+
+    session
+        .redis_set(&mut redis_connection_manager, session_id)
+        .await?;
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(session.create_embed())
+                    .components(session.create_components(session_id)),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn handle_browse_modal(
+    ctx: &Context,
+    interaction: &ModalInteraction,
+    custom_id: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (_, session_id) =
+        parse_custom_id(custom_id).ok_or("Failed to retrieve session ID from custom ID.")?;
+
+    let lines = interaction
+        .data
+        .components
+        .iter()
+        .flat_map(|action_row| action_row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input_text) if input_text.custom_id == "lines" => {
+                input_text.value.as_deref()
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let session = BrowseSession::redis_get(&mut redis_connection_manager, session_id).await?;
+
+    let permalink_result = match (
+        &session,
+        session
+            .as_ref()
+            .and_then(|session| session.selected_file.as_deref()),
+        get_line_fragment(lines),
+    ) {
+        (Some(session), Some(selected_file), Ok(line_fragment)) => Ok(build_permalink(
+            session.owner.as_str(),
+            session.repository.as_str(),
+            session.commit_sha.as_str(),
+            selected_file,
+            line_fragment.as_str(),
+        )),
+        (_, _, Err(error)) => Err(error),
+        _ => Err("This browser has expired.".to_owned()),
+    };
+
+    let permalink = match permalink_result {
+        Ok(permalink) => permalink,
+        Err(error) => {
+            interaction
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(error)
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new().content(permalink.as_str()),
+            ),
+        )
+        .await?;
+
+    let reply = interaction.get_response(&ctx.http).await?;
+
+    send_file_preview_for_url(
+        ctx,
+        &PreviewTarget {
+            author_id: interaction.user.id,
+            channel_id: interaction.channel_id,
+            guild_id: interaction.guild_id,
+            reference_message: &reply,
+            priority: SendPriority::Urgent,
+        },
+        permalink.as_str(),
+    )
+    .await
+}
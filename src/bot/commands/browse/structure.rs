@@ -0,0 +1,28 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "browse",
+        "Browse the files of a GitHub repository and preview one of them.",
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "repo",
+            "The repository in the format owner/repo.",
+        )
+        .max_length(140)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "ref",
+            "The branch, tag or commit to browse. Defaults to the default branch.",
+        )
+        .max_length(250)
+        .required(false),
+    )
+}
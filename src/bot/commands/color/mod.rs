@@ -0,0 +1,98 @@
+use image::{DynamicImage, RgbaImage};
+use imageproc::drawing::Blend;
+use serenity::all::{
+    Colour, CommandInteraction, CreateAttachment, CreateEmbed, EditAttachments,
+    EditInteractionResponse,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::encode::encode_preview_image;
+use crate::bot::commands::juxtapose::get_upload_limit;
+use crate::bot::commands::juxtapose::preview::{draw_label, LabelPosition};
+use crate::bot::commands::options::get_string_option;
+
+pub(crate) mod parse;
+mod structure;
+pub(crate) use structure::register;
+
+use parse::RgbColor;
+
+const SWATCH_SIZE: u32 = 256;
+
+const SWATCH_LABEL_SCALE: f32 = 24.0;
+const SWATCH_LABEL_MARGIN: i32 = 6;
+
+/// Fills an image with the color, optionally labeling it in the bottom left corner.
+pub(crate) fn render_swatch(
+    color: RgbColor,
+    width: u32,
+    height: u32,
+    label: Option<&str>,
+) -> DynamicImage {
+    let mut swatch = Blend(DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+        width,
+        height,
+        color.to_rgba(),
+    )));
+
+    if let Some(label) = label {
+        draw_label(
+            &mut swatch,
+            LabelPosition::BottomLeft,
+            SWATCH_LABEL_SCALE,
+            label,
+            SWATCH_LABEL_MARGIN,
+        );
+    }
+
+    swatch.0
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let value = get_string_option(&options, "value").ok_or("No color specified.")?;
+    let color = RgbColor::parse(value).ok_or(
+        "The color is invalid. Use hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+    )?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer color interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Render Swatch */
+
+    let swatch = render_swatch(color, SWATCH_SIZE, SWATCH_SIZE, None);
+    let upload_limit = get_upload_limit(ctx, interaction.guild_id).await;
+    let swatch_encoded = encode_preview_image(&swatch, upload_limit)?;
+    let swatch_file_name = format!("swatch.{}", swatch_encoded.extension);
+
+    /* Reply */
+
+    let embed = CreateEmbed::new()
+        .colour(Colour::new(color.to_u32()))
+        .title(color.to_hex())
+        .field("Hex", format!("`{}`", color.to_hex()), true)
+        .field("RGB", format!("`{}`", color.to_rgb_notation()), true)
+        .field("HSL", format!("`{}`", color.to_hsl_notation()), true)
+        .field("Integer", format!("`{}`", color.to_u32()), true)
+        .thumbnail(format!("attachment://{}", swatch_file_name));
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .embed(embed)
+                .attachments(EditAttachments::new().add(CreateAttachment::bytes(
+                    swatch_encoded.data,
+                    swatch_file_name,
+                ))),
+        )
+        .await
+        .map_err(|_| "Failed to send the color swatch.")?;
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use image::Rgba;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RgbColor {
+    pub(crate) red: u8,
+    pub(crate) green: u8,
+    pub(crate) blue: u8,
+}
+
+impl RgbColor {
+    /// Parses a color in hex (`#ff8800`, `#f80`), `rgb(255, 136, 0)` or `hsl(32, 100%, 50%)` notation.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+
+        if let Some(arguments) = strip_function(&value, "rgb") {
+            let [red, green, blue] = parse_arguments(arguments)?;
+
+            return Some(Self {
+                red: parse_channel(red)?,
+                green: parse_channel(green)?,
+                blue: parse_channel(blue)?,
+            });
+        }
+
+        if let Some(arguments) = strip_function(&value, "hsl") {
+            let [hue, saturation, lightness] = parse_arguments(arguments)?;
+
+            return Some(Self::from_hsl(
+                hue.strip_suffix("deg")
+                    .unwrap_or(hue)
+                    .parse()
+                    .ok()
+                    .filter(|hue: &f32| hue.is_finite())?,
+                parse_percentage(saturation)?,
+                parse_percentage(lightness)?,
+            ));
+        }
+
+        let hex = value.strip_prefix('#').unwrap_or(&value);
+
+        if !hex.chars().all(|char| char.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        match hex.len() {
+            3 => {
+                let mut digits = hex
+                    .chars()
+                    .map(|digit| digit.to_digit(16).unwrap() as u8 * 17);
+
+                Some(Self {
+                    red: digits.next()?,
+                    green: digits.next()?,
+                    blue: digits.next()?,
+                })
+            }
+            6 => Some(Self {
+                red: u8::from_str_radix(&hex[0..2], 16).ok()?,
+                green: u8::from_str_radix(&hex[2..4], 16).ok()?,
+                blue: u8::from_str_radix(&hex[4..6], 16).ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Hue in degrees, saturation and lightness between 0 and 1.
+    pub(crate) fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let hue = hue.rem_euclid(360.0);
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let secondary = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+        let offset = lightness - chroma / 2.0;
+
+        let (red, green, blue) = match hue as u32 / 60 {
+            0 => (chroma, secondary, 0.0),
+            1 => (secondary, chroma, 0.0),
+            2 => (0.0, chroma, secondary),
+            3 => (0.0, secondary, chroma),
+            4 => (secondary, 0.0, chroma),
+            _ => (chroma, 0.0, secondary),
+        };
+
+        let to_channel = |value: f32| ((value + offset) * 255.0).round() as u8;
+
+        Self {
+            red: to_channel(red),
+            green: to_channel(green),
+            blue: to_channel(blue),
+        }
+    }
+
+    /// Hue in degrees, saturation and lightness between 0 and 1.
+    pub(crate) fn to_hsl(self) -> (f32, f32, f32) {
+        let red = self.red as f32 / 255.0;
+        let green = self.green as f32 / 255.0;
+        let blue = self.blue as f32 / 255.0;
+
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let delta = max - min;
+        let lightness = (max + min) / 2.0;
+
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == red {
+            60.0 * ((green - blue) / delta).rem_euclid(6.0)
+        } else if max == green {
+            60.0 * ((blue - red) / delta + 2.0)
+        } else {
+            60.0 * ((red - green) / delta + 4.0)
+        };
+
+        (hue, saturation, lightness)
+    }
+
+    pub(crate) fn to_hex(self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.red, self.green, self.blue)
+    }
+
+    pub(crate) fn to_rgb_notation(self) -> String {
+        format!("rgb({}, {}, {})", self.red, self.green, self.blue)
+    }
+
+    pub(crate) fn to_hsl_notation(self) -> String {
+        let (hue, saturation, lightness) = self.to_hsl();
+
+        format!(
+            "hsl({:.0}, {:.0}%, {:.0}%)",
+            hue,
+            saturation * 100.0,
+            lightness * 100.0
+        )
+    }
+
+    pub(crate) fn to_u32(self) -> u32 {
+        ((self.red as u32) << 16) | ((self.green as u32) << 8) | self.blue as u32
+    }
+
+    pub(crate) fn to_rgba(self) -> Rgba<u8> {
+        Rgba([self.red, self.green, self.blue, 255])
+    }
+}
+
+fn strip_function<'a>(value: &'a str, name: &str) -> Option<&'a str> {
+    value
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Splits the arguments of a color function, which may be separated by commas or whitespace.
+fn parse_arguments(arguments: &str) -> Option<[&str; 3]> {
+    let mut arguments = arguments
+        .split(|char: char| char == ',' || char.is_whitespace())
+        .filter(|argument| !argument.is_empty());
+
+    let parsed_arguments = [arguments.next()?, arguments.next()?, arguments.next()?];
+
+    arguments.next().is_none().then_some(parsed_arguments)
+}
+
+fn parse_channel(value: &str) -> Option<u8> {
+    match value.strip_suffix('%') {
+        Some(percentage) => {
+            let percentage: f32 = percentage.parse().ok()?;
+
+            (0.0..=100.0)
+                .contains(&percentage)
+                .then(|| (percentage * 2.55).round() as u8)
+        }
+        None => value.parse().ok(),
+    }
+}
+
+fn parse_percentage(value: &str) -> Option<f32> {
+    let percentage: f32 = value.strip_suffix('%').unwrap_or(value).parse().ok()?;
+
+    (0.0..=100.0)
+        .contains(&percentage)
+        .then_some(percentage / 100.0)
+}
@@ -0,0 +1,15 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("color", "Show a color swatch and its conversions.").add_option(
+        localized_option(
+            CommandOptionType::String,
+            "value",
+            "The color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        )
+        .max_length(100)
+        .required(true),
+    )
+}
@@ -0,0 +1,620 @@
+use serenity::all::{
+    Colour, CommandInteraction, CreateEmbed, EditInteractionResponse, MessageBuilder,
+    ResolvedOption, ResolvedValue,
+};
+use serenity::prelude::*;
+
+use crate::bot::audit_log::{send_audit_log, AuditLogEntry};
+use crate::bot::commands::color::parse::RgbColor;
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::commands::options::{
+    get_boolean_option, get_channel_option, get_integer_option, get_string_option,
+};
+use crate::bot::file_preview::keyword_filter::{
+    compile_keyword_filter, KEYWORD_FILTER_MAX_PATTERNS,
+};
+use crate::bot::guild_config::{
+    normalize_alias_extension, validate_language_alias, AttachmentTheme, GuildConfig,
+    JuxtaposePreset, KeywordFilterAction, PreviewDisplayStyle, PreviewSource, WhitespaceIndicators,
+    DISABLED_CHANNELS_MAX_COUNT, JUXTAPOSE_PRESETS_MAX_COUNT, LANGUAGE_ALIASES_MAX_COUNT,
+};
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::register;
+
+pub(crate) fn create_config_embed(guild_config: &GuildConfig) -> CreateEmbed<'static> {
+    let keyword_filters = if guild_config.keyword_filters.is_empty() {
+        "None".to_owned()
+    } else {
+        MessageBuilder::new()
+            .push_codeblock_safe(guild_config.keyword_filters.join("\n"), None)
+            .build()
+    };
+
+    CreateEmbed::new()
+        .title("Configuration")
+        .colour(Colour::BLURPLE)
+        .field(
+            "Audit Channel",
+            guild_config
+                .audit_channel_id
+                .map_or("None".to_owned(), |channel_id| format!("<#{}>", channel_id)),
+            true,
+        )
+        .field(
+            "Digest Channel",
+            guild_config
+                .digest_channel_id
+                .map_or("None".to_owned(), |channel_id| format!("<#{}>", channel_id)),
+            true,
+        )
+        .field(
+            "Display Style",
+            guild_config.preview_display_style.as_str(),
+            true,
+        )
+        .field(
+            "Attachment Theme",
+            guild_config
+                .attachment_theme
+                .map_or("None", |attachment_theme| attachment_theme.as_str()),
+            true,
+        )
+        .field(
+            "Whitespace Indicators",
+            guild_config.whitespace_indicators.as_str(),
+            true,
+        )
+        .field(
+            "Keyword Filter Action",
+            guild_config.keyword_filter_action.as_str(),
+            true,
+        )
+        .field(
+            "Disabled Preview Sources",
+            if guild_config.disabled_preview_sources.is_empty() {
+                "None".to_owned()
+            } else {
+                guild_config
+                    .disabled_preview_sources
+                    .iter()
+                    .map(PreviewSource::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+            true,
+        )
+        .field(
+            "Disabled Channels",
+            if guild_config.disabled_channel_ids.is_empty() {
+                "None".to_owned()
+            } else {
+                guild_config
+                    .disabled_channel_ids
+                    .iter()
+                    .map(|channel_id| format!("<#{}>", channel_id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+            false,
+        )
+        .field(
+            "Repository Metadata",
+            if guild_config.show_repository_metadata {
+                "Shown"
+            } else {
+                "Hidden"
+            },
+            true,
+        )
+        .field(
+            "Link Screening",
+            if guild_config.link_screening {
+                "Enabled"
+            } else {
+                "Disabled"
+            },
+            true,
+        )
+        .field(
+            "Preview Recycling",
+            if guild_config.recycle_previews {
+                "Enabled"
+            } else {
+                "Disabled"
+            },
+            true,
+        )
+        .field(
+            "Content Hash",
+            if guild_config.show_content_hash {
+                "Shown"
+            } else {
+                "Hidden"
+            },
+            true,
+        )
+        .field(
+            "Juxtapose Watermark",
+            guild_config
+                .juxtapose_watermark
+                .as_ref()
+                .map_or("None".to_owned(), |watermark| {
+                    MessageBuilder::new().push_mono_safe(watermark).build()
+                }),
+            true,
+        )
+        .field(
+            "Juxtapose Presets",
+            if guild_config.juxtapose_presets.is_empty() {
+                "None".to_owned()
+            } else {
+                guild_config
+                    .juxtapose_presets
+                    .iter()
+                    .map(|preset| {
+                        MessageBuilder::new()
+                            .push_mono_safe(preset.name.as_str())
+                            .build()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+            false,
+        )
+        .field(
+            "Language Aliases",
+            if guild_config.language_aliases.is_empty() {
+                "None".to_owned()
+            } else {
+                guild_config
+                    .language_aliases
+                    .iter()
+                    .map(|(extension, language)| {
+                        MessageBuilder::new()
+                            .push_mono_safe(format!(".{} → {}", extension, language))
+                            .build()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+            false,
+        )
+        .field("Keyword Filters", keyword_filters, false)
+}
+
+/// Joins the names of the invoked subcommand group and subcommand, e.g. `keyword_filter add`.
+fn get_subcommand_path(options: &[ResolvedOption<'_>]) -> String {
+    match options.first() {
+        Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommandGroup(options),
+            ..
+        }) => format!("{} {}", name, get_subcommand_path(options)),
+        Some(ResolvedOption { name, .. }) => (*name).to_owned(),
+        None => String::new(),
+    }
+}
+
+fn run_keyword_filter(
+    guild_config: &mut GuildConfig,
+    options: &[ResolvedOption<'_>],
+) -> Result<(), String> {
+    let (subcommand_name, subcommand_options) = match options.first() {
+        Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => (*name, subcommand_options.as_slice()),
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    match subcommand_name {
+        "add" => {
+            let pattern =
+                get_string_option(subcommand_options, "pattern").ok_or("A pattern is required.")?;
+
+            compile_keyword_filter(pattern)
+                .map_err(|error| format!("The pattern is invalid: {}", error))?;
+
+            if guild_config.keyword_filters.len() >= KEYWORD_FILTER_MAX_PATTERNS {
+                return Err(format!(
+                    "The keyword filter must not contain more than {} patterns.",
+                    KEYWORD_FILTER_MAX_PATTERNS
+                ));
+            }
+
+            if !guild_config
+                .keyword_filters
+                .iter()
+                .any(|existing_pattern| existing_pattern == pattern)
+            {
+                guild_config.keyword_filters.push(pattern.to_owned());
+            }
+        }
+        "remove" => {
+            let pattern =
+                get_string_option(subcommand_options, "pattern").ok_or("A pattern is required.")?;
+
+            let previous_length = guild_config.keyword_filters.len();
+            guild_config
+                .keyword_filters
+                .retain(|existing_pattern| existing_pattern != pattern);
+
+            if guild_config.keyword_filters.len() == previous_length {
+                return Err("The pattern is not part of the keyword filter.".to_owned());
+            }
+        }
+        "action" => {
+            guild_config.keyword_filter_action =
+                match get_string_option(subcommand_options, "action") {
+                    Some("block") => KeywordFilterAction::Block,
+                    Some("redact") => KeywordFilterAction::Redact,
+                    _ => return Err("Unknown keyword filter action.".to_owned()),
+                };
+        }
+        _ => return Err("Unknown subcommand.".to_owned()),
+    }
+
+    Ok(())
+}
+
+fn run_presets(
+    guild_config: &mut GuildConfig,
+    options: &[ResolvedOption<'_>],
+) -> Result<(), String> {
+    let (subcommand_name, subcommand_options) = match options.first() {
+        Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => (*name, subcommand_options.as_slice()),
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    let name = get_string_option(subcommand_options, "name")
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .ok_or("A name is required.")?;
+
+    match subcommand_name {
+        "add" => {
+            let divider_color = get_string_option(subcommand_options, "divider_color")
+                .map(|value| {
+                    RgbColor::parse(value).map(|color| color.to_hex()).ok_or(
+                        "The divider color is invalid. Use hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+                    )
+                })
+                .transpose()?;
+
+            let preset = JuxtaposePreset {
+                name: name.to_owned(),
+                left_label: get_string_option(subcommand_options, "left_label")
+                    .map(ToOwned::to_owned),
+                right_label: get_string_option(subcommand_options, "right_label")
+                    .map(ToOwned::to_owned),
+                is_vertical: get_boolean_option(subcommand_options, "vertical").unwrap_or(false),
+                divider_thickness: get_integer_option(subcommand_options, "divider_thickness")
+                    .map(|thickness| thickness.clamp(0, DividerStyle::MAX_THICKNESS as i64) as u32),
+                divider_color,
+            };
+
+            match guild_config
+                .juxtapose_presets
+                .iter_mut()
+                .find(|existing_preset| existing_preset.name.eq_ignore_ascii_case(name))
+            {
+                Some(existing_preset) => *existing_preset = preset,
+                None => {
+                    if guild_config.juxtapose_presets.len() >= JUXTAPOSE_PRESETS_MAX_COUNT {
+                        return Err(format!(
+                            "There must not be more than {} juxtapose presets.",
+                            JUXTAPOSE_PRESETS_MAX_COUNT
+                        ));
+                    }
+
+                    guild_config.juxtapose_presets.push(preset);
+                }
+            }
+        }
+        "remove" => {
+            let previous_length = guild_config.juxtapose_presets.len();
+            guild_config
+                .juxtapose_presets
+                .retain(|existing_preset| !existing_preset.name.eq_ignore_ascii_case(name));
+
+            if guild_config.juxtapose_presets.len() == previous_length {
+                return Err("There is no preset with that name.".to_owned());
+            }
+        }
+        _ => return Err("Unknown subcommand.".to_owned()),
+    }
+
+    Ok(())
+}
+
+fn run_aliases(
+    guild_config: &mut GuildConfig,
+    options: &[ResolvedOption<'_>],
+) -> Result<(), String> {
+    let (subcommand_name, subcommand_options) = match options.first() {
+        Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => (*name, subcommand_options.as_slice()),
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    let extension = normalize_alias_extension(
+        get_string_option(subcommand_options, "extension").ok_or("An extension is required.")?,
+    );
+
+    match subcommand_name {
+        "add" => {
+            let language = get_string_option(subcommand_options, "language")
+                .map(str::trim)
+                .ok_or("A language is required.")?;
+
+            validate_language_alias(extension.as_str(), language)?;
+
+            if !guild_config.language_aliases.contains_key(&extension)
+                && guild_config.language_aliases.len() >= LANGUAGE_ALIASES_MAX_COUNT
+            {
+                return Err(format!(
+                    "There must not be more than {} language aliases.",
+                    LANGUAGE_ALIASES_MAX_COUNT
+                ));
+            }
+
+            guild_config
+                .language_aliases
+                .insert(extension, language.to_owned());
+        }
+        "remove" => {
+            if guild_config.language_aliases.remove(&extension).is_none() {
+                return Err("There is no alias for that extension.".to_owned());
+            }
+        }
+        _ => return Err("Unknown subcommand.".to_owned()),
+    }
+
+    Ok(())
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer_ephemeral(&ctx.http).await {
+        println!("Failed to defer config interaction: {:?}", error);
+        return Ok(());
+    }
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or("This command can only be used in servers.")?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let mut guild_config = GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+        .await
+        .map_err(|_| "Failed to load the configuration.")?;
+
+    /* Apply Changes */
+
+    let options = interaction.data.options();
+
+    let is_changed = match options.first() {
+        Some(ResolvedOption { name: "show", .. }) => false,
+        Some(ResolvedOption {
+            name: "audit_channel",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.audit_channel_id =
+                get_channel_option(subcommand_options, "channel").map(|channel| channel.id);
+            true
+        }
+        Some(ResolvedOption {
+            name: "digest_channel",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.digest_channel_id =
+                get_channel_option(subcommand_options, "channel").map(|channel| channel.id);
+            true
+        }
+        Some(ResolvedOption {
+            name: "display_style",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.preview_display_style =
+                match get_string_option(subcommand_options, "style") {
+                    Some("code_block") => PreviewDisplayStyle::CodeBlock,
+                    Some("embed") => PreviewDisplayStyle::Embed,
+                    Some("thread") => PreviewDisplayStyle::Thread,
+                    _ => return Err("Unknown display style.".to_owned()),
+                };
+            true
+        }
+        Some(ResolvedOption {
+            name: "attachment_theme",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.attachment_theme = match get_string_option(subcommand_options, "theme") {
+                Some("dark") => Some(AttachmentTheme::Dark),
+                Some("light") => Some(AttachmentTheme::Light),
+                None => None,
+                _ => return Err("Unknown attachment theme.".to_owned()),
+            };
+            true
+        }
+        Some(ResolvedOption {
+            name: "whitespace_indicators",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.whitespace_indicators =
+                match get_string_option(subcommand_options, "files") {
+                    Some("off") => WhitespaceIndicators::Off,
+                    Some("diff_sensitive") => WhitespaceIndicators::DiffSensitive,
+                    Some("all") => WhitespaceIndicators::All,
+                    _ => return Err("Unknown whitespace indicator setting.".to_owned()),
+                };
+            true
+        }
+        Some(ResolvedOption {
+            name: "preview_source",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            let preview_source = get_string_option(subcommand_options, "source")
+                .and_then(PreviewSource::parse)
+                .ok_or("Unknown preview source.")?;
+            let is_enabled = get_boolean_option(subcommand_options, "enabled").unwrap_or(true);
+
+            guild_config
+                .disabled_preview_sources
+                .retain(|disabled_source| *disabled_source != preview_source);
+
+            if !is_enabled {
+                guild_config.disabled_preview_sources.push(preview_source);
+            }
+            true
+        }
+        Some(ResolvedOption {
+            name: "preview_channel",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            let channel_id = get_channel_option(subcommand_options, "channel")
+                .ok_or("A channel is required.")?
+                .id;
+            let is_enabled = get_boolean_option(subcommand_options, "enabled").unwrap_or(true);
+
+            guild_config
+                .disabled_channel_ids
+                .retain(|disabled_channel_id| *disabled_channel_id != channel_id);
+
+            if !is_enabled {
+                if guild_config.disabled_channel_ids.len() >= DISABLED_CHANNELS_MAX_COUNT {
+                    return Err(format!(
+                        "File previews must not be disabled in more than {} channels.",
+                        DISABLED_CHANNELS_MAX_COUNT
+                    ));
+                }
+
+                guild_config.disabled_channel_ids.push(channel_id);
+            }
+            true
+        }
+        Some(ResolvedOption {
+            name: "repository_metadata",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.show_repository_metadata =
+                get_boolean_option(subcommand_options, "enabled").unwrap_or(true);
+            true
+        }
+        Some(ResolvedOption {
+            name: "link_screening",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.link_screening =
+                get_boolean_option(subcommand_options, "enabled").unwrap_or(true);
+            true
+        }
+        Some(ResolvedOption {
+            name: "recycle_previews",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.recycle_previews =
+                get_boolean_option(subcommand_options, "enabled").unwrap_or(true);
+            true
+        }
+        Some(ResolvedOption {
+            name: "content_hash",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.show_content_hash =
+                get_boolean_option(subcommand_options, "enabled").unwrap_or(true);
+            true
+        }
+        Some(ResolvedOption {
+            name: "juxtapose_watermark",
+            value: ResolvedValue::SubCommand(subcommand_options),
+            ..
+        }) => {
+            guild_config.juxtapose_watermark = get_string_option(subcommand_options, "text")
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(ToOwned::to_owned);
+            true
+        }
+        Some(ResolvedOption {
+            name: "keyword_filter",
+            value: ResolvedValue::SubCommandGroup(subcommand_group_options),
+            ..
+        }) => {
+            run_keyword_filter(&mut guild_config, subcommand_group_options)?;
+            true
+        }
+        Some(ResolvedOption {
+            name: "presets",
+            value: ResolvedValue::SubCommandGroup(subcommand_group_options),
+            ..
+        }) => {
+            run_presets(&mut guild_config, subcommand_group_options)?;
+            true
+        }
+        Some(ResolvedOption {
+            name: "aliases",
+            value: ResolvedValue::SubCommandGroup(subcommand_group_options),
+            ..
+        }) => {
+            run_aliases(&mut guild_config, subcommand_group_options)?;
+            true
+        }
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    if is_changed {
+        guild_config
+            .redis_set(&mut redis_connection_manager, guild_id)
+            .await
+            .map_err(|_| "Failed to save the configuration.")?;
+
+        send_audit_log(
+            &ctx.http,
+            Some(guild_id),
+            &guild_config,
+            AuditLogEntry::ConfigChanged {
+                changed_by_id: interaction.user.id,
+                change: get_subcommand_path(&options).as_str(),
+            },
+        )
+        .await;
+    }
+
+    /* Reply */
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().add_embed(create_config_embed(&guild_config)),
+        )
+        .await
+        .map_err(|_| "Failed to send the configuration.")?;
+
+    Ok(())
+}
@@ -0,0 +1,410 @@
+use serenity::all::{ChannelType, CommandOptionType, CreateCommand, Permissions};
+
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::guild_config::{
+    JUXTAPOSE_PRESET_NAME_MAX_LENGTH, JUXTAPOSE_WATERMARK_MAX_LENGTH, LANGUAGE_ALIAS_MAX_LENGTH,
+};
+use crate::bot::i18n::{localized_command, localized_option, LocalizedChoices};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("config", "Configure the bot for this server.")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .add_option(localized_option(
+            CommandOptionType::SubCommand,
+            "show",
+            "Show the current configuration of this server.",
+        ))
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "audit_channel",
+                "Set the channel that receives audit log entries.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Channel,
+                    "channel",
+                    "The audit log channel. Disables audit logging if omitted.",
+                )
+                .channel_types(vec![ChannelType::Text])
+                .required(false),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "digest_channel",
+                "Set the channel that receives a weekly digest of previews and juxtaposes.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Channel,
+                    "channel",
+                    "The digest channel. Disables the digest if omitted.",
+                )
+                .channel_types(vec![ChannelType::Text])
+                .required(false),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "display_style",
+                "Set how file previews are displayed.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::String,
+                    "style",
+                    "A code block in the message, a rich embed, or a thread on the message.",
+                )
+                .add_localized_string_choice("Code Block", "code_block")
+                .add_localized_string_choice("Embed", "embed")
+                .add_localized_string_choice("Thread", "thread")
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "attachment_theme",
+                "Attach long file previews as highlighted HTML documents instead of plain text.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::String,
+                    "theme",
+                    "The color theme of the document. Attaches plain text if omitted.",
+                )
+                .add_localized_string_choice("Dark", "dark")
+                .add_localized_string_choice("Light", "light")
+                .required(false),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "whitespace_indicators",
+                "Show tabs and trailing whitespace in file previews, and mention CRLF line endings.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::String,
+                    "files",
+                    "The files whose previews show whitespace.",
+                )
+                .add_localized_string_choice("None", "off")
+                .add_localized_string_choice("Whitespace-Sensitive Files", "diff_sensitive")
+                .add_localized_string_choice("All Files", "all")
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "preview_source",
+                "Enable or disable file previews for a kind of link.",
+            )
+            .add_sub_option(
+                localized_option(CommandOptionType::String, "source", "The kind of link.")
+                    .add_localized_string_choice("GitHub Repository Files", "github")
+                    .add_localized_string_choice("GitHub Gists", "gist")
+                    .required(true),
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Boolean,
+                    "enabled",
+                    "Whether file previews are created for this kind of link.",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "preview_channel",
+                "Enable or disable automatic file previews in a channel.",
+            )
+            .add_sub_option(
+                localized_option(CommandOptionType::Channel, "channel", "The channel.")
+                    .channel_types(vec![
+                        ChannelType::Text,
+                        ChannelType::News,
+                        ChannelType::Voice,
+                        ChannelType::Stage,
+                        ChannelType::Forum,
+                    ])
+                    .required(true),
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Boolean,
+                    "enabled",
+                    "Whether links in this channel are previewed automatically.",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "repository_metadata",
+                "Show or hide the stars, language and license of repositories below file previews.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Boolean,
+                    "enabled",
+                    "Whether repository metadata is shown below previews of GitHub repository files.",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "link_screening",
+                "Warn above file previews of repositories that look like scams.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Boolean,
+                    "enabled",
+                    "Whether previews of blocklisted, new or suspiciously named files carry a warning.",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "recycle_previews",
+                "Refer to the existing preview when a link is posted again in the same channel.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Boolean,
+                    "enabled",
+                    "Whether links posted again are answered with a link to their recent preview.",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "content_hash",
+                "Show a hash of the previewed lines below file previews.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Boolean,
+                    "enabled",
+                    "Whether previews end with a BLAKE3 hash to tell later if the file has changed.",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "juxtapose_watermark",
+                "Set a text drawn in a corner of juxtapose previews, e.g. for attribution.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::String,
+                    "text",
+                    "The watermark text. Removes the watermark if omitted.",
+                )
+                .max_length(JUXTAPOSE_WATERMARK_MAX_LENGTH as u16)
+                .required(false),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommandGroup,
+                "keyword_filter",
+                "Manage patterns that block or redact file previews.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "add",
+                    "Add a case-insensitive regular expression to the keyword filter.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "pattern",
+                        "The regular expression.",
+                    )
+                    .max_length(200)
+                    .required(true),
+                ),
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "remove",
+                    "Remove a regular expression from the keyword filter.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "pattern",
+                        "The regular expression.",
+                    )
+                    .max_length(200)
+                    .required(true),
+                ),
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "action",
+                    "Set what happens to file previews matching the keyword filter.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "action",
+                        "Either block the whole preview or redact the matches.",
+                    )
+                    .add_localized_string_choice("Block", "block")
+                    .add_localized_string_choice("Redact", "redact")
+                    .required(true),
+                ),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommandGroup,
+                "presets",
+                "Manage presets of labels and layout for /juxtapose-preset.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "add",
+                    "Save a preset, replacing any preset with the same name.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "name",
+                        "The name of the preset.",
+                    )
+                    .max_length(JUXTAPOSE_PRESET_NAME_MAX_LENGTH as u16)
+                    .required(true),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "left_label",
+                        "The label on the left side (or top).",
+                    )
+                    .max_length(100)
+                    .required(false),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "right_label",
+                        "The label on the right side (or bottom).",
+                    )
+                    .max_length(100)
+                    .required(false),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::Boolean,
+                        "vertical",
+                        "Whether or not the juxtapose should be vertical instead of horizontal. Defaults to false.",
+                    )
+                    .required(false),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::Integer,
+                        "divider_thickness",
+                        "The thickness of the divider in pixels. Scales with the image size by default.",
+                    )
+                    .min_int_value(0)
+                    .max_int_value(DividerStyle::MAX_THICKNESS.into())
+                    .required(false),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "divider_color",
+                        "The color of the divider in hex, rgb() or hsl() notation. Defaults to white.",
+                    )
+                    .max_length(100)
+                    .required(false),
+                ),
+            )
+            .add_sub_option(
+                localized_option(CommandOptionType::SubCommand, "remove", "Delete a preset.")
+                    .add_sub_option(
+                        localized_option(
+                            CommandOptionType::String,
+                            "name",
+                            "The name of the preset.",
+                        )
+                        .max_length(JUXTAPOSE_PRESET_NAME_MAX_LENGTH as u16)
+                        .set_autocomplete(true)
+                        .required(true),
+                    ),
+            ),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommandGroup,
+                "aliases",
+                "Manage the languages that files are highlighted as by their extension.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "add",
+                    "Highlight files with an extension as another language, e.g. .zs as java.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "extension",
+                        "The file extension, e.g. zs.",
+                    )
+                    .max_length(LANGUAGE_ALIAS_MAX_LENGTH as u16 + 1)
+                    .required(true),
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "language",
+                        "The language or extension that Discord highlights, e.g. java.",
+                    )
+                    .max_length(LANGUAGE_ALIAS_MAX_LENGTH as u16)
+                    .required(true),
+                ),
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::SubCommand,
+                    "remove",
+                    "Remove the alias of a file extension.",
+                )
+                .add_sub_option(
+                    localized_option(
+                        CommandOptionType::String,
+                        "extension",
+                        "The file extension, e.g. zs.",
+                    )
+                    .max_length(LANGUAGE_ALIAS_MAX_LENGTH as u16 + 1)
+                    .required(true),
+                ),
+            ),
+        )
+}
@@ -0,0 +1,56 @@
+use serenity::all::{Colour, CommandInteraction, CreateEmbed, EditInteractionResponse};
+use serenity::prelude::*;
+
+use crate::bot::user_data::{redis_forget_user, ForgetUserSummary};
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::register;
+
+fn create_forget_user_embed(summary: &ForgetUserSummary) -> CreateEmbed<'static> {
+    CreateEmbed::new()
+        .title("Data Deleted")
+        .colour(Colour::DARK_GREEN)
+        .description(
+            "The juxtapose and album messages themselves remain on Discord. Delete them to revoke their links.",
+        )
+        .field("Juxtaposes", summary.juxtapose_count.to_string(), true)
+        .field("Albums", summary.album_count.to_string(), true)
+        .field(
+            "Deleted Entries",
+            summary.deleted_key_count.to_string(),
+            true,
+        )
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer_ephemeral(&ctx.http).await {
+        println!("Failed to defer forgetme interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Delete Data */
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let summary = redis_forget_user(&mut redis_connection_manager, interaction.user.id)
+        .await
+        .map_err(|_| "Failed to delete your data.")?;
+
+    /* Reply */
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(create_forget_user_embed(&summary)),
+        )
+        .await
+        .map_err(|_| "Failed to confirm the deletion of your data.")?;
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+use serenity::all::CreateCommand;
+
+use crate::bot::i18n::localized_command;
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("forgetme", "Delete all data the bot has stored about you.")
+}
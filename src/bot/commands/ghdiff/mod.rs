@@ -0,0 +1,62 @@
+use serenity::all::{CommandInteraction, EditInteractionResponse};
+use serenity::prelude::*;
+
+use crate::bot::commands::options::get_string_option;
+use crate::bot::file_preview::backpressure::SendPriority;
+use crate::bot::file_preview::github_compare::GitHubCompareFilePreview;
+use crate::bot::file_preview::{send_compare_preview, PreviewTarget};
+use crate::bot::github_api::parse_repository;
+
+mod structure;
+pub(crate) use structure::register;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let repository = get_string_option(&options, "repo").unwrap_or_default();
+    let path = get_string_option(&options, "path").unwrap_or_default();
+    let base = get_string_option(&options, "base").unwrap_or_default();
+    let head = get_string_option(&options, "head").unwrap_or_default();
+
+    let (owner, repository) = parse_repository(repository)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer ghdiff interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Compare */
+
+    let compare_preview =
+        GitHubCompareFilePreview::new(owner, repository, path, base.trim(), head.trim())
+            .await
+            .map_err(|error| format!("Failed to compare the file: {}", error))?;
+
+    /* Reply */
+
+    let reply = interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().content(compare_preview.get_compare_url().as_str()),
+        )
+        .await
+        .map_err(|_| "Failed to send the comparison.")?;
+
+    send_compare_preview(
+        ctx,
+        &PreviewTarget {
+            author_id: interaction.user.id,
+            channel_id: interaction.channel_id,
+            guild_id: interaction.guild_id,
+            reference_message: &reply,
+            priority: SendPriority::Urgent,
+        },
+        compare_preview,
+    )
+    .await
+    .map_err(|error| format!("Failed to preview the diff: {}", error))?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "ghdiff",
+        "Preview how a file in a GitHub repository changed between two references.",
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "repo",
+            "The repository in the format owner/repo.",
+        )
+        .max_length(140)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "path",
+            "The path of the file within the repository.",
+        )
+        .max_length(1000)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "base",
+            "The branch, tag or commit to compare against.",
+        )
+        .max_length(250)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "head",
+            "The branch, tag or commit containing the changes.",
+        )
+        .max_length(250)
+        .required(true),
+    )
+}
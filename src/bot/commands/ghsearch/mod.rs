@@ -0,0 +1,323 @@
+use std::error::Error;
+
+use redis::AsyncCommands;
+use reqwest::{header, Url};
+use serde::{Deserialize, Serialize};
+use serenity::all::{
+    Colour, CommandInteraction, ComponentInteraction, CreateActionRow, CreateEmbed,
+    CreateEmbedFooter, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    EditInteractionResponse, InteractionId, MessageBuilder,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::options::get_string_option;
+use crate::bot::custom_id::sign_custom_id;
+use crate::bot::file_preview::backpressure::SendPriority;
+use crate::bot::file_preview::{fetch_raw_content, send_file_preview_for_url, PreviewTarget};
+use crate::bot::github_api::{check_response_status, github_api_get, parse_repository};
+use crate::config::config;
+use crate::outbound::send_limited;
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::register;
+
+pub(crate) const SELECT_CUSTOM_ID_PREFIX: &str = "ghsearchPreview";
+
+const SEARCH_RESULT_LIMIT: usize = 5;
+
+/// Results are kept for as long as the select menu can reasonably be used.
+const SEARCH_RESULTS_TTL: u64 = 15 * 60;
+
+/// Upper bound for the number of lines previewed for a selected result.
+const PREVIEW_MAX_LINES: usize = 10;
+
+#[derive(Debug, Deserialize)]
+struct APICodeSearchResponse {
+    items: Vec<APICodeSearchItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APICodeSearchItem {
+    path: String,
+    html_url: String,
+    #[serde(default)]
+    text_matches: Vec<APICodeSearchTextMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APICodeSearchTextMatch {
+    fragment: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SearchResult {
+    path: String,
+    html_url: String,
+    fragment: Option<String>,
+}
+
+fn redis_search_results_key(interaction_id: InteractionId) -> String {
+    format!("ghsearch_results:{}", interaction_id)
+}
+
+fn truncate_chars(string: &str, max_length: usize) -> String {
+    if string.chars().count() > max_length {
+        let truncated_string: String = string.chars().take(max_length - 3).collect();
+        format!("{}...", truncated_string)
+    } else {
+        string.to_owned()
+    }
+}
+
+/// Returns the first two non-empty lines of the fragment.
+fn get_snippet(fragment: &str) -> String {
+    fragment
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(2)
+        .map(|line| truncate_chars(line.trim_end(), 100))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn search_code(
+    owner: &str,
+    repository: &str,
+    query: &str,
+) -> Result<Vec<SearchResult>, String> {
+    let response = send_limited(
+        github_api_get(&["search", "code"])
+            .header(header::ACCEPT, "application/vnd.github.text-match+json")
+            .query(&[
+                (
+                    "q",
+                    format!("{} repo:{}/{}", query, owner, repository).as_str(),
+                ),
+                ("per_page", SEARCH_RESULT_LIMIT.to_string().as_str()),
+            ]),
+    )
+    .await
+    .map_err(|_| "Failed to reach GitHub.")?;
+
+    check_response_status(
+        &response,
+        "The repository could not be found or the query is invalid.",
+    )?;
+
+    let search_response: APICodeSearchResponse = response
+        .json()
+        .await
+        .map_err(|_| "Failed to receive the search results from GitHub.")?;
+
+    Ok(search_response
+        .items
+        .into_iter()
+        .take(SEARCH_RESULT_LIMIT)
+        .map(|item| SearchResult {
+            path: item.path,
+            html_url: item.html_url,
+            fragment: item
+                .text_matches
+                .into_iter()
+                .next()
+                .map(|text_match| text_match.fragment),
+        })
+        .collect())
+}
+
+fn create_search_results_embed(
+    owner: &str,
+    repository: &str,
+    query: &str,
+    search_results: &[SearchResult],
+) -> CreateEmbed<'static> {
+    let mut description = MessageBuilder::new();
+
+    for search_result in search_results {
+        description
+            .push("[")
+            .push_safe(truncate_chars(search_result.path.as_str(), 100))
+            .push("](")
+            .push(search_result.html_url.as_str())
+            .push_line(")");
+
+        if let Some(fragment) = &search_result.fragment {
+            description.push_codeblock_safe(get_snippet(fragment), None);
+        }
+    }
+
+    CreateEmbed::new()
+        .title(format!("Code Search in {}/{}", owner, repository))
+        .colour(Colour::BLURPLE)
+        .description(description.build())
+        .footer(CreateEmbedFooter::new(truncate_chars(query, 200)))
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let query = get_string_option(&options, "query").unwrap_or_default();
+    let repository = get_string_option(&options, "repo").unwrap_or_default();
+
+    let (owner, repository) = parse_repository(repository)?;
+
+    // The code search API rejects unauthenticated requests.
+    if config().github_token.is_none() {
+        return Err("Code search is not available on this instance of the bot.".to_owned());
+    }
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer ghsearch interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Search */
+
+    let search_results = search_code(owner, repository, query).await?;
+
+    if search_results.is_empty() {
+        return Err("No results found.".to_owned());
+    }
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let _: () = redis_connection_manager
+        .set_ex(
+            redis_search_results_key(interaction.id),
+            serde_json::to_string(&search_results).expect("Failed to serialize search results."),
+            SEARCH_RESULTS_TTL,
+        )
+        .await
+        .map_err(|_| "Failed to store the search results.")?;
+
+    /* Reply */
+
+    let select_options: Vec<CreateSelectMenuOption> = search_results
+        .iter()
+        .enumerate()
+        .map(|(index, search_result)| {
+            CreateSelectMenuOption::new(
+                truncate_chars(search_result.path.as_str(), 100),
+                index.to_string(),
+            )
+        })
+        .collect();
+
+    let select_menu = CreateSelectMenu::new(
+        sign_custom_id(format!("{}:{}", SELECT_CUSTOM_ID_PREFIX, interaction.id).as_str()),
+        CreateSelectMenuKind::String {
+            options: select_options.into(),
+        },
+    )
+    .placeholder("Preview a result");
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .embed(create_search_results_embed(
+                    owner,
+                    repository,
+                    query,
+                    &search_results,
+                ))
+                .components(vec![CreateActionRow::SelectMenu(select_menu)]),
+        )
+        .await
+        .map_err(|_| "Failed to send the search results.")?;
+
+    Ok(())
+}
+
+/// Finds the lines of the fragment within the file, as the search API only returns the matching text.
+async fn get_result_preview_url(
+    search_result: &SearchResult,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let Some(fragment) = &search_result.fragment else {
+        return Ok(format!(
+            "{}#L1-L{}",
+            search_result.html_url, PREVIEW_MAX_LINES
+        ));
+    };
+
+    let raw_url = Url::parse(
+        search_result
+            .html_url
+            .replacen(
+                "https://github.com/",
+                "https://raw.githubusercontent.com/",
+                1,
+            )
+            .replacen("/blob/", "/", 1)
+            .as_str(),
+    )?;
+
+    let raw_content = fetch_raw_content(raw_url).await?;
+
+    let top_line_number = raw_content.find(fragment.as_str()).map_or(1, |position| {
+        raw_content[..position].matches('\n').count() + 1
+    });
+
+    let line_count = fragment.lines().count().clamp(1, PREVIEW_MAX_LINES);
+
+    Ok(format!(
+        "{}#L{}-L{}",
+        search_result.html_url,
+        top_line_number,
+        top_line_number + line_count - 1
+    ))
+}
+
+pub async fn handle_select_search_result(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+    value: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let search_interaction_id = custom_id
+        .split_once(':')
+        .and_then(|(_, interaction_id)| interaction_id.parse::<u64>().ok())
+        .map(InteractionId::new)
+        .ok_or("Failed to retrieve interaction ID from custom ID.")?;
+
+    let index = value.parse::<usize>()?;
+
+    interaction.defer(&ctx.http).await?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let search_results: Option<String> = redis_connection_manager
+        .get(redis_search_results_key(search_interaction_id))
+        .await?;
+
+    let Some(search_results) = search_results else {
+        return Ok(());
+    };
+
+    let search_results: Vec<SearchResult> = serde_json::from_str(search_results.as_str())?;
+    let search_result = search_results
+        .get(index)
+        .ok_or("Search result not found.")?;
+
+    send_file_preview_for_url(
+        ctx,
+        &PreviewTarget {
+            author_id: interaction.user.id,
+            channel_id: interaction.channel_id,
+            guild_id: interaction.guild_id,
+            reference_message: &interaction.message,
+            priority: SendPriority::Urgent,
+        },
+        get_result_preview_url(search_result).await?.as_str(),
+    )
+    .await
+}
@@ -0,0 +1,25 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("ghsearch", "Search code in a GitHub repository.")
+        .add_option(
+            localized_option(
+                CommandOptionType::String,
+                "query",
+                "The text to search for.",
+            )
+            .max_length(200)
+            .required(true),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::String,
+                "repo",
+                "The repository in the format owner/repo.",
+            )
+            .max_length(140)
+            .required(true),
+        )
+}
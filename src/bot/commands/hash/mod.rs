@@ -0,0 +1,121 @@
+use reqwest::Url;
+use serenity::all::{Colour, CommandInteraction, CreateEmbed, EditInteractionResponse};
+use serenity::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::bot::commands::options::{get_attachment_option, get_string_option};
+use crate::bot::file_preview::lfs::format_file_size;
+use crate::outbound::guarded_get;
+
+mod structure;
+pub(crate) use structure::register;
+
+/// Files are hashed while streaming, but downloads are still capped to bound the bandwidth per command.
+const HASH_MAX_SIZE: u64 = 128 * 1024 * 1024;
+
+struct Checksums {
+    sha256: String,
+    blake3: String,
+    size: u64,
+}
+
+/// Downloads the file in chunks, updating both hashers without buffering the whole file.
+async fn compute_checksums(url: Url) -> Result<Checksums, String> {
+    let mut response = guarded_get(url)
+        .map_err(|error| error.to_string())?
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|_| "Failed to download the file.")?;
+
+    if response
+        .content_length()
+        .is_some_and(|content_length| content_length > HASH_MAX_SIZE)
+    {
+        return Err(format!(
+            "The file must not be larger than {}.",
+            format_file_size(HASH_MAX_SIZE)
+        ));
+    }
+
+    let mut sha256_hasher = Sha256::new();
+    let mut blake3_hasher = blake3::Hasher::new();
+    let mut size = 0;
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|_| "Failed to download the file.")?
+    {
+        size += chunk.len() as u64;
+
+        if size > HASH_MAX_SIZE {
+            return Err(format!(
+                "The file must not be larger than {}.",
+                format_file_size(HASH_MAX_SIZE)
+            ));
+        }
+
+        sha256_hasher.update(&chunk);
+        blake3_hasher.update(&chunk);
+    }
+
+    Ok(Checksums {
+        sha256: format!("{:x}", sha256_hasher.finalize()),
+        blake3: blake3_hasher.finalize().to_hex().to_string(),
+        size,
+    })
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let (url, file_name) = match (
+        get_attachment_option(&options, "file"),
+        get_string_option(&options, "url"),
+    ) {
+        (Some(attachment), None) => (
+            Url::parse(attachment.url.as_str()).map_err(|_| "The attachment URL is malformed.")?,
+            attachment.filename.to_string(),
+        ),
+        (None, Some(url)) => {
+            let url = Url::parse(url.trim()).map_err(|_| "The URL is malformed.")?;
+            let file_name = url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|file_name| !file_name.is_empty())
+                .unwrap_or("file")
+                .to_owned();
+
+            (url, file_name)
+        }
+        _ => return Err("Either attach a file or specify a URL.".to_owned()),
+    };
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer hash interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Compute Checksums */
+
+    let checksums = compute_checksums(url).await?;
+
+    /* Reply */
+
+    let embed = CreateEmbed::new()
+        .colour(Colour::BLURPLE)
+        .title(file_name)
+        .description(format_file_size(checksums.size))
+        .field("SHA-256", format!("`{}`", checksums.sha256), false)
+        .field("BLAKE3", format!("`{}`", checksums.blake3), false);
+
+    interaction
+        .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+        .await
+        .map_err(|_| "Failed to send the checksums.")?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "hash",
+        "Compute the SHA-256 and BLAKE3 checksums of a file.",
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::Attachment,
+            "file",
+            "The file to compute the checksums of.",
+        )
+        .required(false),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "url",
+            "The URL of the file to compute the checksums of, if no file is attached.",
+        )
+        .max_length(2000)
+        .required(false),
+    )
+}
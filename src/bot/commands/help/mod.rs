@@ -0,0 +1,313 @@
+use std::error::Error;
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use serenity::all::{
+    Colour, CommandInteraction, ComponentInteraction, CreateActionRow, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::options::get_string_option;
+use crate::bot::commands::{juxtapose_images, preview_links, register_all, remove_preview};
+use crate::bot::custom_id::sign_custom_id;
+use crate::bot::i18n::translate;
+
+mod structure;
+pub(crate) use structure::register;
+
+pub const SELECT_CUSTOM_ID_PREFIX: &str = "helpTopic";
+
+const MESSAGE_COMMAND_TYPE: u64 = 3;
+const SUB_COMMAND_OPTION_TYPE: u64 = 1;
+const SUB_COMMAND_GROUP_OPTION_TYPE: u64 = 2;
+
+/// Embed field values are limited to 1024 characters.
+const FIELD_VALUE_MAX_LENGTH: usize = 1024;
+
+/// The commands as they are registered with Discord, which the help pages are generated from,
+/// so that they can't get out of sync with the commands themselves.
+static COMMAND_REGISTRY: Lazy<Vec<Value>> = Lazy::new(|| {
+    register_all()
+        .iter()
+        .filter_map(|command| serde_json::to_value(command).ok())
+        .collect()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HelpTopic {
+    Previews,
+    Juxtapose,
+    Tools,
+    Configuration,
+    Privacy,
+}
+
+impl HelpTopic {
+    const ALL: [Self; 5] = [
+        Self::Previews,
+        Self::Juxtapose,
+        Self::Tools,
+        Self::Configuration,
+        Self::Privacy,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Previews => "previews",
+            Self::Juxtapose => "juxtapose",
+            Self::Tools => "tools",
+            Self::Configuration => "configuration",
+            Self::Privacy => "privacy",
+        }
+    }
+
+    fn parse(topic: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|help_topic| help_topic.as_str() == topic)
+    }
+
+    /// Matches the name of the choice of the `topic` option, which shares its translations.
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Previews => "File Previews",
+            Self::Juxtapose => "Juxtapose",
+            Self::Tools => "Tools",
+            Self::Configuration => "Configuration",
+            Self::Privacy => "Privacy",
+        }
+    }
+
+    fn introduction(&self) -> &'static str {
+        match self {
+            Self::Previews => "Post a link to lines of a file on GitHub or in a gist, e.g. ending with `#L10-L20`, and the lines are previewed right below it. The buttons of a preview open the file, copy a permalink, show the blamed commit or render the lines as an image.",
+            Self::Juxtapose => "A juxtapose compares two images with an interactive slider, e.g. a screenshot before and after a change. Its link opens the viewer in the browser.",
+            Self::Tools => "Small utilities for images, colors and files.",
+            Self::Configuration => "Members with the Manage Server permission can change how and where previews are posted.",
+            Self::Privacy => "Messages are only read to find links to preview and aren't stored. Juxtaposes remember who created them, and servers store their configuration and usage statistics.",
+        }
+    }
+
+    /// Names of the commands listed on the page of the topic.
+    fn command_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Previews => &[
+                "permalink",
+                "browse",
+                "ghsearch",
+                "ghdiff",
+                preview_links::COMMAND_NAME,
+                remove_preview::COMMAND_NAME,
+            ],
+            Self::Juxtapose => &[
+                "juxtapose",
+                "juxtapose-from-message",
+                "juxtapose-preset",
+                juxtapose_images::COMMAND_NAME,
+                "album",
+            ],
+            Self::Tools => &["imagetool", "spritesheet", "palette", "color", "qr", "hash"],
+            Self::Configuration => &["config", "backfill", "stats"],
+            Self::Privacy => &["forgetme"],
+        }
+    }
+}
+
+fn find_registered_command(name: &str) -> Option<&'static Value> {
+    COMMAND_REGISTRY
+        .iter()
+        .find(|command| command.get("name").and_then(Value::as_str) == Some(name))
+}
+
+/// Picks the translation of a field of a command or option, e.g. `description`, falling back to English.
+fn get_localized_field<'a>(value: &'a Value, field: &str, locale: &str) -> Option<&'a str> {
+    value
+        .get(format!("{}_localizations", field).as_str())
+        .and_then(|localizations| localizations.get(locale))
+        .or_else(|| value.get(field))
+        .and_then(Value::as_str)
+}
+
+/// Names of the subcommands, prefixed with the name of their group if they are in one.
+fn get_subcommand_names(command: &Value) -> Vec<String> {
+    let Some(options) = command.get("options").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+
+    options
+        .iter()
+        .flat_map(|option| {
+            let name = option
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+
+            match option.get("type").and_then(Value::as_u64) {
+                Some(SUB_COMMAND_OPTION_TYPE) => vec![name.to_owned()],
+                Some(SUB_COMMAND_GROUP_OPTION_TYPE) => get_subcommand_names(option)
+                    .into_iter()
+                    .map(|subcommand_name| format!("{} {}", name, subcommand_name))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Creates the name and value of the embed field explaining a command.
+fn create_command_field(command: &Value, locale: &str) -> Option<(String, String)> {
+    let name = get_localized_field(command, "name", locale)?;
+
+    if command.get("type").and_then(Value::as_u64) == Some(MESSAGE_COMMAND_TYPE) {
+        return Some((
+            format!("Apps › {}", name),
+            "Right-click or long-press a message and open Apps to use this command.".to_owned(),
+        ));
+    }
+
+    let mut value = get_localized_field(command, "description", locale)?.to_owned();
+
+    for (index, subcommand_name) in get_subcommand_names(command).into_iter().enumerate() {
+        let separator = if index == 0 { "\n" } else { " " };
+        let subcommand = format!("{}`{}`", separator, subcommand_name);
+
+        if value.len() + subcommand.len() + " …".len() > FIELD_VALUE_MAX_LENGTH {
+            value.push_str(" …");
+            break;
+        }
+
+        value.push_str(subcommand.as_str());
+    }
+
+    Some((format!("/{}", name), value))
+}
+
+fn create_help_embed(topic: HelpTopic, locale: &str) -> CreateEmbed<'static> {
+    let fields = topic
+        .command_names()
+        .iter()
+        .filter_map(|command_name| find_registered_command(command_name))
+        .filter_map(|command| create_command_field(command, locale))
+        .map(|(name, value)| (name, value, false));
+
+    CreateEmbed::new()
+        .title(translate(locale, topic.title()).unwrap_or(topic.title()))
+        .colour(Colour::BLURPLE)
+        .description(topic.introduction())
+        .fields(fields)
+        .footer(CreateEmbedFooter::new(
+            "Pick another topic from the menu below.",
+        ))
+}
+
+fn create_help_components(topic: HelpTopic, locale: &str) -> Vec<CreateActionRow<'static>> {
+    let select_options: Vec<CreateSelectMenuOption> = HelpTopic::ALL
+        .into_iter()
+        .map(|help_topic| {
+            CreateSelectMenuOption::new(
+                translate(locale, help_topic.title()).unwrap_or(help_topic.title()),
+                help_topic.as_str(),
+            )
+            .default_selection(help_topic == topic)
+        })
+        .collect();
+
+    vec![CreateActionRow::SelectMenu(CreateSelectMenu::new(
+        sign_custom_id(SELECT_CUSTOM_ID_PREFIX),
+        CreateSelectMenuKind::String {
+            options: select_options.into(),
+        },
+    ))]
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let topic = get_string_option(&options, "topic")
+        .and_then(HelpTopic::parse)
+        .unwrap_or(HelpTopic::Previews);
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .embed(create_help_embed(topic, &interaction.locale))
+                    .components(create_help_components(topic, &interaction.locale))
+                    .ephemeral(true),
+            ),
+        )
+        .await
+        .map_err(|_| "Failed to send the help.")?;
+
+    Ok(())
+}
+
+/// Switches the help to the topic picked from the menu.
+pub async fn handle_select_topic(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    value: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let topic = HelpTopic::parse(value).ok_or("Unknown help topic.")?;
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(create_help_embed(topic, &interaction.locale))
+                    .components(create_help_components(topic, &interaction.locale)),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covers_all_public_commands() {
+        for help_topic in HelpTopic::ALL {
+            for command_name in help_topic.command_names() {
+                assert!(
+                    find_registered_command(command_name).is_some(),
+                    "{} isn't registered",
+                    command_name
+                );
+            }
+        }
+
+        for command in COMMAND_REGISTRY.iter() {
+            let command_name = command.get("name").and_then(Value::as_str).unwrap();
+
+            assert!(
+                matches!(command_name, "admin" | "help")
+                    || HelpTopic::ALL
+                        .iter()
+                        .any(|help_topic| help_topic.command_names().contains(&command_name)),
+                "{} isn't explained by any help topic",
+                command_name
+            );
+        }
+    }
+
+    #[test]
+    fn lists_subcommands_of_groups() {
+        let command = serde_json::json!({
+            "name": "config",
+            "options": [
+                { "type": 1, "name": "show" },
+                { "type": 2, "name": "presets", "options": [{ "type": 1, "name": "add" }] },
+                { "type": 3, "name": "channel" },
+            ],
+        });
+
+        assert_eq!(get_subcommand_names(&command), ["show", "presets add"]);
+    }
+}
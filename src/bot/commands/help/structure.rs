@@ -0,0 +1,18 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option, LocalizedChoices};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("help", "Learn what the bot can do and how to use it.").add_option(
+        localized_option(
+            CommandOptionType::String,
+            "topic",
+            "The topic to show first.",
+        )
+        .add_localized_string_choice("File Previews", "previews")
+        .add_localized_string_choice("Juxtapose", "juxtapose")
+        .add_localized_string_choice("Tools", "tools")
+        .add_localized_string_choice("Configuration", "configuration")
+        .add_localized_string_choice("Privacy", "privacy"),
+    )
+}
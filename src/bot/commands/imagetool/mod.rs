@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use once_cell::sync::Lazy;
+use serenity::all::{
+    Attachment, CommandInteraction, CreateAttachment, EditAttachments, EditInteractionResponse,
+    ResolvedOption, ResolvedValue,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::encode::{encode_image_as, encode_preview_image};
+use crate::bot::commands::juxtapose::{
+    decode_image, download_image_from_attachment, get_upload_limit, IMAGE_LIMITS, RENDER_QUEUE,
+};
+use crate::bot::commands::options::{get_attachment_option, get_integer_option, get_string_option};
+use crate::bot::commands::rate_limit::{format_cooldown_error, UserCooldown};
+
+mod structure;
+pub(crate) use structure::register;
+
+static IMAGETOOL_COOLDOWN: Lazy<UserCooldown> =
+    Lazy::new(|| UserCooldown::new(Duration::from_secs(10)));
+
+const IMAGE_MAX_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+enum ImageOperation {
+    Resize {
+        width: u32,
+        height: Option<u32>,
+    },
+    Crop {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    Rotate {
+        degrees: u32,
+    },
+    Flip {
+        is_vertical: bool,
+    },
+    Convert {
+        format: ImageFormat,
+    },
+}
+
+impl ImageOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Resize { .. } => "resized",
+            Self::Crop { .. } => "cropped",
+            Self::Rotate { .. } => "rotated",
+            Self::Flip { .. } => "flipped",
+            Self::Convert { .. } => "converted",
+        }
+    }
+
+    fn apply(&self, image: DynamicImage) -> Result<DynamicImage, String> {
+        match *self {
+            Self::Resize {
+                width,
+                height: Some(height),
+            } => Ok(image.resize_exact(width, height, FilterType::Lanczos3)),
+            Self::Resize {
+                width,
+                height: None,
+            } => {
+                let height = (image.height() as u64 * width as u64 / image.width() as u64)
+                    .clamp(1, IMAGE_LIMITS.max_image_height.unwrap_or(u32::MAX) as u64)
+                    as u32;
+
+                Ok(image.resize_exact(width, height, FilterType::Lanczos3))
+            }
+            Self::Crop {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                if x >= image.width() || y >= image.height() {
+                    return Err(format!(
+                        "The rectangle must start within the image, which is {}x{} pixels.",
+                        image.width(),
+                        image.height()
+                    ));
+                }
+
+                // Rectangles reaching past the edges are clamped to the image.
+                let width = width.min(image.width() - x);
+                let height = height.min(image.height() - y);
+
+                Ok(image.crop_imm(x, y, width, height))
+            }
+            Self::Rotate { degrees: 90 } => Ok(image.rotate90()),
+            Self::Rotate { degrees: 180 } => Ok(image.rotate180()),
+            Self::Rotate { degrees: 270 } => Ok(image.rotate270()),
+            Self::Rotate { .. } => Err("The rotation must be 90, 180 or 270 degrees.".to_owned()),
+            Self::Flip { is_vertical: false } => Ok(image.fliph()),
+            Self::Flip { is_vertical: true } => Ok(image.flipv()),
+            Self::Convert { .. } => Ok(image),
+        }
+    }
+}
+
+fn get_dimension_option(options: &[ResolvedOption<'_>], name: &str) -> Option<u32> {
+    get_integer_option(options, name).and_then(|value| u32::try_from(value).ok())
+}
+
+fn parse_subcommand<'a>(
+    options: &[ResolvedOption<'a>],
+) -> Result<(ImageOperation, &'a Attachment), String> {
+    let (name, options) = match options.first() {
+        Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) => (*name, options),
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    let attachment = get_attachment_option(options, "image").ok_or("No image attached.")?;
+
+    let operation = match name {
+        "resize" => ImageOperation::Resize {
+            width: get_dimension_option(options, "width").ok_or("No width specified.")?,
+            height: get_dimension_option(options, "height"),
+        },
+        "crop" => ImageOperation::Crop {
+            x: get_dimension_option(options, "x").ok_or("No x coordinate specified.")?,
+            y: get_dimension_option(options, "y").ok_or("No y coordinate specified.")?,
+            width: get_dimension_option(options, "width").ok_or("No width specified.")?,
+            height: get_dimension_option(options, "height").ok_or("No height specified.")?,
+        },
+        "rotate" => ImageOperation::Rotate {
+            degrees: get_dimension_option(options, "degrees").ok_or("No rotation specified.")?,
+        },
+        "flip" => ImageOperation::Flip {
+            is_vertical: get_string_option(options, "direction") == Some("vertical"),
+        },
+        "convert" => ImageOperation::Convert {
+            format: get_string_option(options, "format")
+                .and_then(ImageFormat::from_extension)
+                .filter(|format| format.can_write())
+                .ok_or("The format is not supported.")?,
+        },
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    Ok((operation, attachment))
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+    let (operation, attachment) = parse_subcommand(&options)?;
+
+    /* Check Cooldown */
+
+    IMAGETOOL_COOLDOWN
+        .check(interaction.user.id)
+        .map_err(format_cooldown_error)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer imagetool interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Limit Image Size and Dimensions */
+
+    if attachment.size as u64 > IMAGE_MAX_FILE_SIZE {
+        return Err("The image must not be bigger than 16 MB.".to_owned());
+    }
+
+    let image_width = attachment
+        .width
+        .ok_or("The attachment is not a supported image.")?
+        .get();
+    let image_height = attachment
+        .height
+        .ok_or("The attachment is not a supported image.")?
+        .get();
+
+    let max_image_width = IMAGE_LIMITS.max_image_width.unwrap_or(u32::MAX);
+    let max_image_height = IMAGE_LIMITS.max_image_height.unwrap_or(u32::MAX);
+
+    if image_width > max_image_width || image_height > max_image_height {
+        return Err(format!(
+            "The image must not be larger than {}x{} pixels.",
+            max_image_width, max_image_height
+        ));
+    }
+
+    /* Download Image */
+
+    let (image_bytes, image_format) =
+        download_image_from_attachment(attachment, image_width, image_height).await?;
+
+    /* Process Image */
+
+    let _render_permit = RENDER_QUEUE.enter(ctx, &interaction.token).await;
+
+    let processed_image = operation.apply(decode_image(&image_bytes, image_format)?)?;
+    drop(image_bytes);
+
+    let upload_limit = get_upload_limit(ctx, interaction.guild_id).await;
+
+    let processed_image_encoded = match operation {
+        ImageOperation::Convert { format } => {
+            let processed_image_encoded = encode_image_as(&processed_image, format)?;
+
+            if processed_image_encoded.data.len() > upload_limit {
+                return Err(
+                    "The converted image is too large to be uploaded in this server.".to_owned(),
+                );
+            }
+
+            processed_image_encoded
+        }
+        _ => encode_preview_image(&processed_image, upload_limit)?,
+    };
+
+    let file_stem = attachment
+        .filename
+        .rsplit_once('.')
+        .map_or(&*attachment.filename, |(file_stem, _)| file_stem);
+
+    /* Reply */
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().attachments(EditAttachments::new().add(
+                CreateAttachment::bytes(
+                    processed_image_encoded.data,
+                    format!(
+                        "{}_{}.{}",
+                        file_stem,
+                        operation.name(),
+                        processed_image_encoded.extension
+                    ),
+                ),
+            )),
+        )
+        .await
+        .map_err(|_| "Failed to upload the processed image to Discord.")?;
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption};
+
+use crate::bot::i18n::{localized_command, localized_option, LocalizedChoices};
+
+fn image_option() -> CreateCommandOption<'static> {
+    localized_option(
+        CommandOptionType::Attachment,
+        "image",
+        "The image to process.",
+    )
+    .required(true)
+}
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "imagetool",
+        "Resize, crop, rotate, flip or convert an image.",
+    )
+    .add_option(
+        localized_option(CommandOptionType::SubCommand, "resize", "Resize an image.")
+            .add_sub_option(image_option())
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Integer,
+                    "width",
+                    "The new width in pixels.",
+                )
+                .min_int_value(1)
+                .max_int_value(4096)
+                .required(true),
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::Integer,
+                    "height",
+                    "The new height in pixels. Preserves the aspect ratio if omitted.",
+                )
+                .min_int_value(1)
+                .max_int_value(4096)
+                .required(false),
+            ),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::SubCommand,
+            "crop",
+            "Crop an image to a rectangle.",
+        )
+        .add_sub_option(image_option())
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "x",
+                "The left edge of the rectangle in pixels.",
+            )
+            .min_int_value(0)
+            .required(true),
+        )
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "y",
+                "The top edge of the rectangle in pixels.",
+            )
+            .min_int_value(0)
+            .required(true),
+        )
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "width",
+                "The width of the rectangle in pixels.",
+            )
+            .min_int_value(1)
+            .required(true),
+        )
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "height",
+                "The height of the rectangle in pixels.",
+            )
+            .min_int_value(1)
+            .required(true),
+        ),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::SubCommand,
+            "rotate",
+            "Rotate an image clockwise.",
+        )
+        .add_sub_option(image_option())
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "degrees",
+                "The clockwise rotation.",
+            )
+            .add_int_choice("90°", 90)
+            .add_int_choice("180°", 180)
+            .add_int_choice("270°", 270)
+            .required(true),
+        ),
+    )
+    .add_option(
+        localized_option(CommandOptionType::SubCommand, "flip", "Mirror an image.")
+            .add_sub_option(image_option())
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::String,
+                    "direction",
+                    "The axis to mirror the image along.",
+                )
+                .add_localized_string_choice("Horizontal", "horizontal")
+                .add_localized_string_choice("Vertical", "vertical")
+                .required(true),
+            ),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::SubCommand,
+            "convert",
+            "Convert an image to another format.",
+        )
+        .add_sub_option(image_option())
+        .add_sub_option(
+            localized_option(CommandOptionType::String, "format", "The new format.")
+                .add_string_choice("PNG", "png")
+                .add_string_choice("JPEG", "jpeg")
+                .add_localized_string_choice("WebP (lossless)", "webp")
+                .add_string_choice("GIF", "gif")
+                .add_string_choice("BMP", "bmp")
+                .required(true),
+        ),
+    )
+}
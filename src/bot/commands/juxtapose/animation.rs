@@ -0,0 +1,73 @@
+use std::io::Cursor;
+
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, DynamicImage, ImageDecoder, ImageFormat};
+
+use super::{check_image_dimensions, decode_image, IMAGE_LIMITS};
+
+/// Decoding a frame requires decoding all frames before it, so only the first few can be selected.
+pub(crate) const MAX_FRAME_NUMBER: u32 = 100;
+
+fn apply_image_limits(decoder: &mut impl ImageDecoder) -> Result<(), String> {
+    check_image_dimensions(decoder.dimensions())?;
+
+    decoder
+        .set_limits(IMAGE_LIMITS.to_owned())
+        .map_err(|error| format!("Failed to decode image: {}", error))
+}
+
+fn decode_animation_frame<'a>(
+    decoder: impl AnimationDecoder<'a>,
+    frame_index: usize,
+) -> Result<DynamicImage, String> {
+    match decoder.into_frames().nth(frame_index) {
+        Some(Ok(frame)) => Ok(DynamicImage::ImageRgba8(frame.into_buffer())),
+        Some(Err(error)) => Err(format!("Failed to decode image: {}", error)),
+        None => Err(format!(
+            "The animated image doesn't have a frame with the number {}.",
+            frame_index + 1
+        )),
+    }
+}
+
+/// Decodes the frame with the given index of animated GIF, PNG and WebP images, which would otherwise
+/// be decoded differently depending on the format. Still images only consist of their first frame,
+/// which is used regardless of the index.
+pub(crate) fn decode_image_frame(
+    image_bytes: &[u8],
+    image_format: ImageFormat,
+    frame_index: usize,
+) -> Result<DynamicImage, String> {
+    let decode_error = |error: image::ImageError| format!("Failed to decode image: {}", error);
+
+    match image_format {
+        ImageFormat::Gif => {
+            let mut decoder = GifDecoder::new(Cursor::new(image_bytes)).map_err(decode_error)?;
+            apply_image_limits(&mut decoder)?;
+            decode_animation_frame(decoder, frame_index)
+        }
+        ImageFormat::Png => {
+            let mut decoder = PngDecoder::new(Cursor::new(image_bytes)).map_err(decode_error)?;
+
+            if decoder.is_apng().map_err(decode_error)? {
+                apply_image_limits(&mut decoder)?;
+                decode_animation_frame(decoder.apng().map_err(decode_error)?, frame_index)
+            } else {
+                decode_image(image_bytes, image_format)
+            }
+        }
+        ImageFormat::WebP => {
+            let mut decoder = WebPDecoder::new(Cursor::new(image_bytes)).map_err(decode_error)?;
+
+            if decoder.has_animation() {
+                apply_image_limits(&mut decoder)?;
+                decode_animation_frame(decoder, frame_index)
+            } else {
+                decode_image(image_bytes, image_format)
+            }
+        }
+        _ => decode_image(image_bytes, image_format),
+    }
+}
@@ -0,0 +1,107 @@
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+
+/// Quality of the lossy fallback encoding. The image crate can only encode lossless WebP, so JPEG is used instead.
+const LOSSY_QUALITY: u8 = 85;
+
+/// Maximum number of downscaling passes before giving up.
+const MAX_DOWNSCALE_PASSES: usize = 4;
+
+/// Images are never downscaled below this size of their longest side.
+const MIN_DIMENSION: u32 = 256;
+
+pub(crate) struct EncodedImage {
+    pub(crate) data: Vec<u8>,
+    pub(crate) extension: &'static str,
+}
+
+fn encode_png(image: &DynamicImage) -> Result<EncodedImage, String> {
+    let mut data = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+        .map_err(|error| format!("Failed to encode image: {}", error))?;
+
+    Ok(EncodedImage {
+        data,
+        extension: "png",
+    })
+}
+
+/// Encodes the image in the given format, dropping the alpha channel for formats that don't support it.
+pub(crate) fn encode_image_as(
+    image: &DynamicImage,
+    format: ImageFormat,
+) -> Result<EncodedImage, String> {
+    let image = match format {
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+        _ => DynamicImage::ImageRgba8(image.to_rgba8()),
+    };
+
+    let mut data = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), format)
+        .map_err(|error| format!("Failed to encode image: {}", error))?;
+
+    Ok(EncodedImage {
+        data,
+        extension: format.extensions_str().first().copied().unwrap_or("bin"),
+    })
+}
+
+fn encode_lossy(image: &DynamicImage) -> Result<EncodedImage, String> {
+    let mut data = Vec::new();
+    DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_with_encoder(JpegEncoder::new_with_quality(&mut data, LOSSY_QUALITY))
+        .map_err(|error| format!("Failed to encode image: {}", error))?;
+
+    Ok(EncodedImage {
+        data,
+        extension: "jpg",
+    })
+}
+
+/// Encodes the preview image so that it fits into the upload budget.
+/// Lossless PNG is preferred, followed by lossy encoding and then progressively downscaled lossy encodings.
+pub(crate) fn encode_preview_image(
+    image: &DynamicImage,
+    budget: usize,
+) -> Result<EncodedImage, String> {
+    let image_encoded = encode_png(image)?;
+
+    if image_encoded.data.len() <= budget {
+        return Ok(image_encoded);
+    }
+
+    let mut image_encoded = encode_lossy(image)?;
+    let mut downscaled_image = None;
+
+    for _ in 0..MAX_DOWNSCALE_PASSES {
+        if image_encoded.data.len() <= budget {
+            return Ok(image_encoded);
+        }
+
+        let current_image = downscaled_image.as_ref().unwrap_or(image);
+
+        // The size of the encoded image scales roughly with its pixel count.
+        let scale = ((budget as f64 / image_encoded.data.len() as f64).sqrt() * 0.9).min(0.9);
+        let width = (current_image.width() as f64 * scale) as u32;
+        let height = (current_image.height() as f64 * scale) as u32;
+
+        if width.max(height) < MIN_DIMENSION {
+            break;
+        }
+
+        let next_image = current_image.resize(width.max(1), height.max(1), FilterType::Triangle);
+        image_encoded = encode_lossy(&next_image)?;
+        downscaled_image = Some(next_image);
+    }
+
+    if image_encoded.data.len() <= budget {
+        Ok(image_encoded)
+    } else {
+        Err("The preview image is too large to be uploaded in this server.".to_owned())
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use redis::AsyncCommands;
+use serenity::all::{
+    ActionRowComponent, Attachment, ButtonStyle, ComponentInteraction, CreateActionRow,
+    CreateButton, CreateInputText, CreateInteractionResponse, CreateInteractionResponseFollowup,
+    CreateInteractionResponseMessage, CreateModal, EditInteractionResponse, InputTextStyle,
+    MessageId, ModalInteraction, UserId,
+};
+use serenity::prelude::*;
+use tokio::join;
+
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::commands::juxtapose::rerender::{
+    create_custom_id, get_message_sources, get_watermark, parse_custom_id, update_juxtapose_cache,
+};
+use crate::bot::commands::juxtapose::{
+    create_juxtapose_attachments, fetch_image_bytes, JuxtaposeSources, JUXTAPOSE_COOLDOWN,
+};
+use crate::bot::commands::rate_limit::format_cooldown_error;
+use crate::bot::custom_id::sign_custom_id;
+use crate::SerenityGlobalData;
+
+pub(crate) const LABEL_CUSTOM_ID_PREFIX: &str = "juxtaposeLabel";
+
+/// Suggestions are only needed until the author confirms them, which usually happens right away.
+const LABEL_SUGGESTIONS_TTL: i64 = 24 * 60 * 60;
+
+/// Labels of the juxtapose command are limited to this many characters.
+const LABEL_MAX_LENGTH: usize = 100;
+
+/// Metadata keywords that commonly name what an image shows, in order of preference.
+const LABEL_KEYWORDS: [&str; 3] = ["Title", "Description", "Comment"];
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Reads a label from the textual metadata of a PNG image, i.e. its `tEXt` and uncompressed `iTXt` chunks.
+fn read_png_text_label(image_bytes: &[u8]) -> Option<String> {
+    let mut chunks = image_bytes.strip_prefix(PNG_SIGNATURE)?;
+    let mut texts = Vec::new();
+
+    while let (Some(length), Some(chunk_type)) = (chunks.get(0..4), chunks.get(4..8)) {
+        let length = u32::from_be_bytes(length.try_into().ok()?) as usize;
+        let Some(data) = chunks.get(8..8 + length) else {
+            break;
+        };
+
+        match chunk_type {
+            b"tEXt" => {
+                if let Some((keyword, text)) = split_at_nul(data) {
+                    // tEXt chunks are Latin-1 encoded, which maps directly to the first code points of Unicode.
+                    texts.push((keyword, text.iter().map(|&byte| byte as char).collect()));
+                }
+            }
+            b"iTXt" => {
+                let fields = split_at_nul(data).and_then(|(keyword, rest)| {
+                    let (&compression_flag, rest) = rest.split_first()?;
+                    let (_, rest) = split_at_nul(rest.get(1..)?)?;
+                    let (_, text) = split_at_nul(rest)?;
+                    (compression_flag == 0).then_some((keyword, text))
+                });
+
+                if let Some((keyword, text)) = fields {
+                    texts.push((keyword, String::from_utf8_lossy(text).into_owned()));
+                }
+            }
+            b"IEND" => break,
+            _ => {}
+        }
+
+        chunks = chunks.get(12 + length..).unwrap_or_default();
+    }
+
+    LABEL_KEYWORDS.iter().find_map(|label_keyword| {
+        texts
+            .iter()
+            .filter(|(keyword, _)| *keyword == label_keyword.as_bytes())
+            .find_map(|(_, text)| sanitize_label(text))
+    })
+}
+
+fn split_at_nul(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let position = data.iter().position(|&byte| byte == 0)?;
+    Some((&data[..position], &data[position + 1..]))
+}
+
+/// Reduces metadata to a label, i.e. its first line, truncated to the length allowed for labels.
+fn sanitize_label(text: &str) -> Option<String> {
+    let label = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    Some(label.chars().take(LABEL_MAX_LENGTH).collect())
+}
+
+async fn suggest_label(attachment: &Attachment) -> Option<String> {
+    if attachment.content_type.as_deref() != Some("image/png") {
+        return None;
+    }
+
+    // Unlike the resized images of the media proxy, the original file still contains its metadata.
+    let image_url = reqwest::Url::parse(attachment.url.as_str()).ok()?;
+    let image_bytes = fetch_image_bytes(image_url).await.ok()?;
+
+    read_png_text_label(image_bytes.as_slice())
+}
+
+/// Labels suggested for a juxtapose created without labels, based on the metadata of its source images.
+#[derive(Default)]
+pub(crate) struct LabelSuggestions {
+    pub(crate) left_label: Option<String>,
+    pub(crate) right_label: Option<String>,
+}
+
+impl LabelSuggestions {
+    pub(crate) async fn from_sources(sources: &JuxtaposeSources<'_>) -> Self {
+        if sources.left_label.is_some() || sources.right_label.is_some() {
+            return Self::default();
+        }
+
+        let (left_label, right_label) = join!(
+            suggest_label(sources.left_image_attachment),
+            suggest_label(sources.right_image_attachment)
+        );
+
+        Self {
+            left_label,
+            right_label,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.left_label.is_none() && self.right_label.is_none()
+    }
+
+    fn redis_key(message_id: MessageId) -> String {
+        format!("juxtapose_label_suggestions:{}", message_id)
+    }
+
+    pub(crate) async fn redis_set(
+        &self,
+        connection: &mut redis::aio::ConnectionManager,
+        message_id: MessageId,
+    ) -> Result<(), redis::RedisError> {
+        let key = Self::redis_key(message_id);
+        let mut suggestions = Vec::new();
+
+        if let Some(left_label) = &self.left_label {
+            suggestions.push(("left_label", left_label.as_str()));
+        }
+
+        if let Some(right_label) = &self.right_label {
+            suggestions.push(("right_label", right_label.as_str()));
+        }
+
+        if suggestions.is_empty() {
+            return Ok(());
+        }
+
+        redis::pipe()
+            .atomic()
+            .hset_multiple(&key, &suggestions)
+            .ignore()
+            .expire(&key, LABEL_SUGGESTIONS_TTL)
+            .ignore()
+            .query_async(connection)
+            .await
+    }
+
+    async fn redis_get(
+        connection: &mut redis::aio::ConnectionManager,
+        message_id: MessageId,
+    ) -> Self {
+        let mut suggestions = connection
+            .hgetall::<String, HashMap<String, String>>(Self::redis_key(message_id))
+            .await
+            .unwrap_or_default();
+
+        Self {
+            left_label: suggestions.remove("left_label"),
+            right_label: suggestions.remove("right_label"),
+        }
+    }
+}
+
+/// Creates the button that lets the author confirm or edit the suggested labels before they are drawn.
+pub(crate) fn create_label_button(
+    author_id: UserId,
+    is_vertical: bool,
+    divider: &DividerStyle,
+    frame_index: usize,
+) -> CreateButton<'static> {
+    CreateButton::new(sign_custom_id(
+        create_custom_id(
+            LABEL_CUSTOM_ID_PREFIX,
+            author_id,
+            is_vertical,
+            divider,
+            frame_index,
+        )
+        .as_str(),
+    ))
+    .style(ButtonStyle::Secondary)
+    .emoji('🏷')
+    .label("Suggested Labels")
+}
+
+async fn respond_ephemeral(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    content: &str,
+) -> Result<(), serenity::Error> {
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await
+}
+
+fn create_label_input(
+    label: &'static str,
+    custom_id: &'static str,
+    value: Option<&str>,
+) -> CreateActionRow<'static> {
+    let mut input_text = CreateInputText::new(InputTextStyle::Short, label, custom_id)
+        .max_length(LABEL_MAX_LENGTH as u16)
+        .required(false);
+
+    if let Some(value) = value {
+        input_text = input_text.value(value.to_owned());
+    }
+
+    CreateActionRow::InputText(input_text)
+}
+
+/// Opens a form pre-filled with the suggested labels, or the current ones if they have been applied already.
+pub async fn handle_label_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (author_id, is_vertical, _, _) = parse_custom_id(custom_id)
+        .ok_or("Failed to retrieve author ID and layout from custom ID.")?;
+
+    if author_id != interaction.user.id.to_string() {
+        respond_ephemeral(
+            ctx,
+            interaction,
+            "Only the user who created the juxtapose can change its labels.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let suggestions =
+        LabelSuggestions::redis_get(&mut redis_connection_manager, interaction.message.id).await;
+
+    let attachments = &interaction.message.attachments;
+    let current_label = |index: usize| {
+        attachments
+            .get(index)
+            .and_then(|attachment| attachment.description.as_deref())
+    };
+
+    let left_label = current_label(1).or(suggestions.left_label.as_deref());
+    let right_label = current_label(2).or(suggestions.right_label.as_deref());
+
+    let (left_input_label, right_input_label) = if is_vertical {
+        ("Top Label", "Bottom Label")
+    } else {
+        ("Left Label", "Right Label")
+    };
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Modal(
+                CreateModal::new(sign_custom_id(custom_id), "Labels").components(vec![
+                    create_label_input(left_input_label, "left_label", left_label),
+                    create_label_input(right_input_label, "right_label", right_label),
+                ]),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Renders the juxtapose again with the labels entered into the form of [`handle_label_button`].
+pub async fn handle_label_modal(
+    ctx: &Context,
+    interaction: &ModalInteraction,
+    custom_id: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (author_id, is_vertical, divider, frame_index) = parse_custom_id(custom_id)
+        .ok_or("Failed to retrieve author ID and layout from custom ID.")?;
+
+    if author_id != interaction.user.id.to_string() {
+        return Err("Label form was submitted by someone other than the author.".into());
+    }
+
+    let message = interaction
+        .message
+        .as_deref()
+        .ok_or("Label form was not submitted from a juxtapose message.")?;
+
+    if let Err(remaining) = JUXTAPOSE_COOLDOWN.check(interaction.user.id) {
+        interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(format_cooldown_error(remaining))
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    interaction.defer(&ctx.http).await?;
+
+    let input_value = |custom_id: &str| {
+        interaction
+            .data
+            .components
+            .iter()
+            .flat_map(|action_row| action_row.components.iter())
+            .find_map(|component| match component {
+                ActionRowComponent::InputText(input_text) if input_text.custom_id == custom_id => {
+                    input_text.value.as_deref()
+                }
+                _ => None,
+            })
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let watermark = get_watermark(ctx, interaction.guild_id).await;
+
+    let original_sources = get_message_sources(
+        message,
+        is_vertical,
+        divider,
+        frame_index,
+        watermark.as_deref(),
+    )
+    .ok_or("Juxtapose message is missing its source images.")?;
+
+    let sources = JuxtaposeSources {
+        left_label: input_value("left_label"),
+        right_label: input_value("right_label"),
+        ..original_sources
+    };
+
+    let juxtapose_attachments =
+        match create_juxtapose_attachments(ctx, interaction.guild_id, &interaction.token, &sources)
+            .await
+        {
+            Ok(juxtapose_attachments) => juxtapose_attachments,
+            Err(error) => {
+                interaction
+                    .create_followup(
+                        &ctx.http,
+                        CreateInteractionResponseFollowup::new()
+                            .content(error)
+                            .ephemeral(true),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        };
+
+    let reply = interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().attachments(juxtapose_attachments),
+        )
+        .await?;
+
+    /* Update Cache */
+
+    update_juxtapose_cache(ctx, &reply, &sources).await?;
+
+    Ok(())
+}
@@ -1,32 +1,65 @@
-use std::env;
 use std::io::Cursor;
 use std::ops::Deref;
+use std::time::Duration;
 
-use base64::engine::general_purpose;
-use base64::Engine;
 use image::Limits;
-use image::{DynamicImage, GenericImage, GenericImageView, ImageFormat, Rgba};
-use imageproc::definitions::HasWhite;
+use image::{DynamicImage, GenericImage, GenericImageView, ImageFormat};
 use imageproc::drawing::Blend;
 use once_cell::sync::Lazy;
+use reqwest::StatusCode;
 use serenity::all::{
-    Attachment, CommandInteraction, CreateActionRow, CreateAttachment, CreateButton,
-    EditAttachments, EditInteractionResponse, ResolvedOption, ResolvedValue,
+    Attachment, ChannelId, CommandInteraction, CreateActionRow, CreateAttachment, CreateButton,
+    EditAttachments, EditInteractionResponse, GuildId, MessageId, PremiumTier, ResolvedOption,
 };
 use serenity::prelude::*;
-use tokio::try_join;
-
+use tokio::{join, try_join};
+
+use crate::bot::activity::{publish_activity, ActivityEvent};
+use crate::bot::audit_log::{send_audit_log, AuditLogEntry};
+use crate::bot::commands::color::parse::RgbColor;
+use crate::bot::commands::juxtapose::animation::{decode_image_frame, MAX_FRAME_NUMBER};
+use crate::bot::commands::juxtapose::encode::encode_preview_image;
+use crate::bot::commands::juxtapose::label::LabelSuggestions;
 use crate::bot::commands::juxtapose::preview::{
-    draw_horizontal_line_mut, draw_label, draw_vertical_line_mut, LabelPosition,
+    draw_horizontal_line_mut, draw_label, draw_vertical_line_mut, DividerStyle, LabelPosition,
+};
+use crate::bot::commands::juxtapose::result_cache::{
+    get_result_cache_key, redis_get_cached_result, redis_set_cached_result, ResultCacheOptions,
 };
-use crate::web::api_juxtapose_response::APIJuxtaposeResponse;
+use crate::bot::commands::options::{
+    get_attachment_option, get_boolean_option, get_integer_option, get_string_option,
+};
+use crate::bot::commands::rate_limit::{format_cooldown_error, JobQueue, UserCooldown};
+use crate::bot::guild_config::GuildConfig;
+use crate::bot::guild_stats::{redis_increment_guild_stats, GuildStatsCounter};
+use crate::bot::user_data::redis_record_user_juxtapose;
+use crate::config::runtime_config;
+use crate::outbound::check_outbound_url;
+use crate::retry::jittered_backoff;
+use crate::signing::{EncodedSignedPayload, SignedPayload};
+use crate::web::api_juxtapose_response::{APIJuxtaposeResponse, JuxtaposeOrientation};
+use crate::web::cache_warming::spawn_cache_warming;
 use crate::{SerenityGlobalData, BLAKE3_JUXTAPOSE_KEY, HTTP_CLIENT};
 
-mod preview;
+mod animation;
+pub(crate) mod encode;
+mod label;
+pub(crate) mod preview;
+mod rerender;
+mod result_cache;
 mod structure;
+mod swap;
+pub(crate) use label::{handle_label_button, handle_label_modal, LABEL_CUSTOM_ID_PREFIX};
 pub(crate) use structure::register;
+pub(crate) use swap::{handle_swap_button, SWAP_CUSTOM_ID_PREFIX};
+
+pub(crate) static JUXTAPOSE_COOLDOWN: Lazy<UserCooldown> =
+    Lazy::new(|| UserCooldown::new(Duration::from_secs(10)));
 
-static IMAGE_LIMITS: Lazy<Limits> = Lazy::new(|| {
+/// Decoding and rendering is memory-intensive, so only a few images are processed at once across all image commands.
+pub(crate) static RENDER_QUEUE: Lazy<JobQueue> = Lazy::new(|| JobQueue::new(2));
+
+pub(crate) static IMAGE_LIMITS: Lazy<Limits> = Lazy::new(|| {
     let mut image_limits = Limits::default();
     image_limits.max_image_width = Some(4096);
     image_limits.max_image_height = Some(4096);
@@ -35,20 +68,62 @@ static IMAGE_LIMITS: Lazy<Limits> = Lazy::new(|| {
     image_limits
 });
 
-static JUXTAPOSE_BASE_URL: Lazy<reqwest::Url> = Lazy::new(|| {
-    reqwest::Url::parse(
-        env::var("JUXTAPOSE_BASE_URL")
-            .as_deref()
-            .unwrap_or("http://localhost"),
-    )
-    .expect("Failed to parse JUXTAPOSE_BASE_URL.")
-});
+const UPLOAD_LIMIT_DEFAULT: usize = 25 * 1024 * 1024;
+
+const CDN_FETCH_MAX_ATTEMPTS: u32 = 4;
+const CDN_FETCH_BACKOFF_BASE: Duration = Duration::from_millis(250);
+
+/// Freshly uploaded attachments occasionally 404 or time out on the CDN for a short while.
+fn is_retryable_cdn_error(error: &reqwest::Error) -> bool {
+    error.is_timeout()
+        || error.is_connect()
+        || error.is_body()
+        || error.status().is_some_and(|status| {
+            status == StatusCode::NOT_FOUND
+                || status == StatusCode::REQUEST_TIMEOUT
+                || status == StatusCode::TOO_MANY_REQUESTS
+                || status.is_server_error()
+        })
+}
 
-async fn get_image_from_attachment(
+async fn fetch_image_bytes(image_url: reqwest::Url) -> Result<Vec<u8>, String> {
+    check_outbound_url(&image_url).map_err(|error| error.to_string())?;
+
+    let mut attempt = 0;
+
+    loop {
+        let result = async {
+            HTTP_CLIENT
+                .get(image_url.clone())
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(image_bytes) => return Ok(image_bytes.to_vec()),
+            Err(error)
+                if attempt + 1 < CDN_FETCH_MAX_ATTEMPTS && is_retryable_cdn_error(&error) =>
+            {
+                attempt += 1;
+                tokio::time::sleep(jittered_backoff(CDN_FETCH_BACKOFF_BASE, attempt)).await;
+            }
+            Err(error) if error.is_body() || error.is_decode() => {
+                return Err("Failed to receive image data from CDN.".to_owned())
+            }
+            Err(_) => return Err("Failed to fetch image from CDN.".to_owned()),
+        }
+    }
+}
+
+pub(crate) async fn download_image_from_attachment(
     attachment: &Attachment,
     image_width: u32,
     image_height: u32,
-) -> Result<(Blend<DynamicImage>, CreateAttachment), String> {
+) -> Result<(Vec<u8>, ImageFormat), String> {
     let image_mime = attachment
         .content_type
         .clone()
@@ -70,160 +145,81 @@ async fn get_image_from_attachment(
     )
     .map_err(|_| "Failed to parse attachment URL.")?;
 
-    let image_bytes = HTTP_CLIENT
-        .get(image_url)
-        .send()
-        .await
-        .map_err(|_| "Failed to fetch image from CDN.")?
-        .bytes()
-        .await
-        .map_err(|_| "Failed to receive image data from CDN.")?;
-
-    let mut image_reader = image::ImageReader::new(Cursor::new(&image_bytes));
-    image_reader.set_format(image_format);
-    image_reader.limits(IMAGE_LIMITS.to_owned());
-
-    let image = image_reader
-        .decode()
-        .map_err(|error| format!("Failed to decode image: {}", error))?;
+    let image_bytes = fetch_image_bytes(image_url).await?;
 
-    Ok((
-        Blend(image),
-        CreateAttachment::bytes(image_bytes.to_vec(), attachment.filename.to_owned()),
-    ))
+    Ok((image_bytes, image_format))
 }
 
-pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
-    let left_image_attachment = interaction
-        .data
-        .options()
-        .first()
-        .and_then(|option| match option {
-            ResolvedOption {
-                value: ResolvedValue::Attachment(attachment),
-                ..
-            } => Some(*attachment),
-            _ => None,
-        })
-        .unwrap();
-
-    let right_image_attachment = interaction
-        .data
-        .options()
-        .get(1)
-        .and_then(|option| match option {
-            ResolvedOption {
-                value: ResolvedValue::Attachment(attachment),
-                ..
-            } => Some(*attachment),
-            _ => None,
-        })
-        .unwrap();
-
-    let left_label = interaction
-        .data
-        .options()
-        .get(2)
-        .and_then(|option| match option {
-            ResolvedOption {
-                value: ResolvedValue::String(string),
-                ..
-            } => Some((*string).to_owned()),
-            _ => None,
-        });
-
-    let right_label = interaction
-        .data
-        .options()
-        .get(3)
-        .and_then(|option| match option {
-            ResolvedOption {
-                value: ResolvedValue::String(string),
-                ..
-            } => Some((*string).to_owned()),
-            _ => None,
-        });
-
-    let is_vertical = interaction
-        .data
-        .options()
-        .get(4)
-        .and_then(|option| match option {
-            ResolvedOption {
-                value: ResolvedValue::Boolean(boolean),
-                ..
-            } => Some(*boolean),
-            _ => None,
-        })
-        .unwrap_or(false);
-
-    /* Defer Interaction */
-
-    if let Err(error) = interaction.defer(&ctx.http).await {
-        println!("Failed to defer juxtapose interaction: {:?}", error);
-        return Ok(());
-    }
-
-    /* Limit Image Size and Dimensions */
+/// Rejects oversized images with a clear message before decoding them.
+fn check_image_dimensions((image_width, image_height): (u32, u32)) -> Result<(), String> {
+    let max_image_width = IMAGE_LIMITS.max_image_width.unwrap_or(u32::MAX);
+    let max_image_height = IMAGE_LIMITS.max_image_height.unwrap_or(u32::MAX);
 
-    if left_image_attachment.size > 16 * 1024 * 1024
-        || right_image_attachment.size > 16 * 1024 * 1024
-    {
-        return Err("The images must not be bigger than 16 MB.".to_owned());
+    if image_width > max_image_width || image_height > max_image_height {
+        return Err(format!(
+            "The image is {}×{} pixels, but must not be larger than {}×{} pixels.",
+            image_width, image_height, max_image_width, max_image_height
+        ));
     }
 
-    let left_image_width = left_image_attachment
-        .width
-        .ok_or("The left (top) attachment is not a supported image.")?;
-    let left_image_height = left_image_attachment
-        .height
-        .ok_or("The left (top) attachment is not a supported image.")?;
-    let right_image_width = right_image_attachment
-        .width
-        .ok_or("The right (bottom) attachment is not a supported image.")?;
-    let right_image_height = right_image_attachment
-        .height
-        .ok_or("The right (bottom) attachment is not a supported image.")?;
+    Ok(())
+}
 
-    let mut preview_image_width = left_image_width.min(right_image_width).get();
-    let mut preview_image_height = left_image_height.min(right_image_height).get();
+pub(crate) fn decode_image(
+    image_bytes: &[u8],
+    image_format: ImageFormat,
+) -> Result<DynamicImage, String> {
+    // The dimensions declared by Discord can disagree with the actual image, e.g. for mislabeled files,
+    // so they are read from the header instead.
+    check_image_dimensions(
+        image::ImageReader::with_format(Cursor::new(image_bytes), image_format)
+            .into_dimensions()
+            .map_err(|error| format!("Failed to read image dimensions: {}", error))?,
+    )?;
 
-    const PREVIEW_IMAGE_MAX_SIZE: u32 = 4096;
-    let preview_image_max_dimension = preview_image_width.max(preview_image_height);
+    let mut image_reader = image::ImageReader::with_format(Cursor::new(image_bytes), image_format);
+    image_reader.limits(IMAGE_LIMITS.to_owned());
 
-    if preview_image_max_dimension > PREVIEW_IMAGE_MAX_SIZE {
-        let scale = PREVIEW_IMAGE_MAX_SIZE as f32 / preview_image_max_dimension as f32;
+    image_reader
+        .decode()
+        .map_err(|error| format!("Failed to decode image: {}", error))
+}
 
-        preview_image_width = (preview_image_width as f32 * scale) as u32;
-        preview_image_height = (preview_image_height as f32 * scale) as u32;
+fn render_preview_image(
+    left_image: DynamicImage,
+    right_image: DynamicImage,
+    left_label: Option<&str>,
+    right_label: Option<&str>,
+    is_vertical: bool,
+    divider: &DividerStyle,
+    watermark: Option<&str>,
+) -> Result<DynamicImage, String> {
+    // The images are requested at the size of the preview, but the geometry is derived from the decoded images,
+    // as they can differ from the dimensions declared by Discord (e.g. for animated or mislabeled files).
+    let preview_image_width = left_image.width().min(right_image.width());
+    let preview_image_height = left_image.height().min(right_image.height());
+
+    if preview_image_width == 0 || preview_image_height == 0 {
+        return Err("The images must not be empty.".to_owned());
     }
 
-    /* Download and Process Images */
+    let crop_to_preview = |image: DynamicImage| {
+        if image.dimensions() == (preview_image_width, preview_image_height) {
+            image
+        } else {
+            image.crop_imm(0, 0, preview_image_width, preview_image_height)
+        }
+    };
 
-    let (
-        (mut left_image, mut left_image_create_attachment),
-        (mut right_image, mut right_image_create_attachment),
-    ) = try_join!(
-        get_image_from_attachment(
-            left_image_attachment,
-            preview_image_width,
-            preview_image_height
-        ),
-        get_image_from_attachment(
-            right_image_attachment,
-            preview_image_width,
-            preview_image_height
-        )
-    )?;
+    let mut left_image = Blend(crop_to_preview(left_image));
+    let mut right_image = Blend(crop_to_preview(right_image));
 
     let preview_image_min_dimension = preview_image_width.min(preview_image_height);
 
     let label_scale = (preview_image_min_dimension as f32) / 24.0;
     let label_margin = (preview_image_min_dimension as i32) / 64;
 
-    if let Some(ref left_label) = left_label {
-        left_image_create_attachment = left_image_create_attachment.description(left_label);
-
+    if let Some(left_label) = left_label {
         draw_label(
             &mut left_image,
             if is_vertical {
@@ -237,9 +233,7 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         );
     }
 
-    if let Some(ref right_label) = right_label {
-        right_image_create_attachment = right_image_create_attachment.description(right_label);
-
+    if let Some(right_label) = right_label {
         draw_label(
             &mut right_image,
             if is_vertical {
@@ -269,99 +263,562 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         .map_err(|_| "Failed to overlay left (top) image onto right (bottom) image.")?;
 
     if is_vertical {
-        let horizontal_line_center = preview_image_height / 2;
-        let horizontal_line_extent = (preview_image_height / 1000).max(1);
         draw_horizontal_line_mut(
             &mut right_image.0,
-            (horizontal_line_center - horizontal_line_extent)
-                ..(horizontal_line_center + horizontal_line_extent),
-            Rgba::white(),
+            divider.get_line(preview_image_height),
+            divider.color.to_rgba(),
         );
     } else {
-        let vertical_line_center = preview_image_width / 2;
-        let vertical_line_extent = (preview_image_width / 1000).max(1);
         draw_vertical_line_mut(
             &mut right_image.0,
-            (vertical_line_center - vertical_line_extent)
-                ..(vertical_line_center + vertical_line_extent),
-            Rgba::white(),
+            divider.get_line(preview_image_width),
+            divider.color.to_rgba(),
         );
     }
 
-    let mut final_image_encoded = Vec::new();
-    right_image
-        .0
-        .write_to(&mut Cursor::new(&mut final_image_encoded), ImageFormat::Png)
-        .map_err(|error| format!("Failed to encode image: {}", error))?;
+    // The top right corner is the only one never covered by a label.
+    if let Some(watermark) = watermark {
+        draw_label(
+            &mut right_image,
+            LabelPosition::TopRight,
+            label_scale / 2.0,
+            watermark,
+            label_margin / 2,
+        );
+    }
+
+    Ok(right_image.0)
+}
+
+/// Creates the signed URL of the interactive viewer for a juxtapose message.
+/// Also returns the encoded data, which is used as the key of the API cache.
+pub(crate) fn create_juxtapose_url(
+    message_id: MessageId,
+    channel_id: ChannelId,
+    is_vertical: bool,
+    divider: &DividerStyle,
+) -> (reqwest::Url, String) {
+    let EncodedSignedPayload {
+        data: juxtapose_url_data,
+        mac: juxtapose_url_mac,
+    } = SignedPayload::new(message_id, channel_id).encode(&BLAKE3_JUXTAPOSE_KEY);
+
+    let mut juxtapose_url = runtime_config().juxtapose_base_url.clone();
+    juxtapose_url.query_pairs_mut().extend_pairs(&[
+        ("d", juxtapose_url_data.as_str()),
+        ("m", juxtapose_url_mac.as_str()),
+        ("o", if is_vertical { "v" } else { "h" }),
+    ]);
+
+    // The viewer falls back to its own divider for parameters that are left out.
+    if let Some(thickness) = divider.thickness {
+        juxtapose_url
+            .query_pairs_mut()
+            .append_pair("dt", thickness.to_string().as_str());
+    }
+
+    if divider.color != DividerStyle::default().color {
+        juxtapose_url
+            .query_pairs_mut()
+            .append_pair("dc", &divider.color.to_hex()[1..]);
+    }
+
+    (juxtapose_url, juxtapose_url_data)
+}
+
+/// Upload limit of a single message, which depends on the boost tier of the guild.
+pub(crate) async fn get_upload_limit(ctx: &Context, guild_id: Option<GuildId>) -> usize {
+    let Some(guild_id) = guild_id else {
+        return UPLOAD_LIMIT_DEFAULT;
+    };
+
+    let cached_premium_tier = ctx.cache.guild(guild_id).map(|guild| guild.premium_tier);
+    let premium_tier = match cached_premium_tier {
+        Some(premium_tier) => Some(premium_tier),
+        None => ctx
+            .http
+            .get_guild(guild_id)
+            .await
+            .ok()
+            .map(|guild| guild.premium_tier),
+    };
+
+    match premium_tier {
+        Some(PremiumTier::Tier2) => 50 * 1024 * 1024,
+        Some(PremiumTier::Tier3) => 100 * 1024 * 1024,
+        _ => UPLOAD_LIMIT_DEFAULT,
+    }
+}
+
+/// Source images of a juxtapose and their labels, in the order they are shown.
+pub(crate) struct JuxtaposeSources<'a> {
+    pub(crate) left_image_attachment: &'a Attachment,
+    pub(crate) right_image_attachment: &'a Attachment,
+    pub(crate) left_label: Option<&'a str>,
+    pub(crate) right_label: Option<&'a str>,
+    pub(crate) is_vertical: bool,
+    pub(crate) divider: DividerStyle,
+    /// The frame of animated images to show, starting at 0.
+    pub(crate) frame_index: usize,
+    /// Attribution drawn in a corner of the preview, configured per guild.
+    pub(crate) watermark: Option<&'a str>,
+    /// Overrides the alt text of the preview, which is generated from the labels and orientation otherwise.
+    pub(crate) alt_text: Option<&'a str>,
+}
+
+impl JuxtaposeSources<'_> {
+    /// Describes the preview for screen readers, e.g. `Slider comparison: left 'Vanilla', right 'SEUS PTGI', vertical split`.
+    pub(crate) fn describe_preview(&self) -> String {
+        let (left_side, right_side, split) = if self.is_vertical {
+            ("top", "bottom", "horizontal")
+        } else {
+            ("left", "right", "vertical")
+        };
+
+        let describe_side = |side: &str, label: Option<&str>| match label {
+            Some(label) => format!("{} '{}'", side, label),
+            None => format!("{} unlabeled", side),
+        };
+
+        format!(
+            "Slider comparison: {}, {}, {} split",
+            describe_side(left_side, self.left_label),
+            describe_side(right_side, self.right_label),
+            split
+        )
+    }
+}
+
+/// Downloads the source images and renders the preview, returning the attachments of the juxtapose message:
+/// the preview image, followed by the left (top) and right (bottom) source images.
+pub(crate) async fn create_juxtapose_attachments(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    interaction_token: &str,
+    sources: &JuxtaposeSources<'_>,
+) -> Result<EditAttachments<'static>, String> {
+    /* Limit Image Size and Dimensions */
+
+    if sources.left_image_attachment.size > 16 * 1024 * 1024
+        || sources.right_image_attachment.size > 16 * 1024 * 1024
+    {
+        return Err("The images must not be bigger than 16 MB.".to_owned());
+    }
+
+    let left_image_width = sources
+        .left_image_attachment
+        .width
+        .ok_or("The left (top) attachment is not a supported image.")?;
+    let left_image_height = sources
+        .left_image_attachment
+        .height
+        .ok_or("The left (top) attachment is not a supported image.")?;
+    let right_image_width = sources
+        .right_image_attachment
+        .width
+        .ok_or("The right (bottom) attachment is not a supported image.")?;
+    let right_image_height = sources
+        .right_image_attachment
+        .height
+        .ok_or("The right (bottom) attachment is not a supported image.")?;
+
+    let mut preview_image_width = left_image_width.min(right_image_width).get();
+    let mut preview_image_height = left_image_height.min(right_image_height).get();
+
+    const PREVIEW_IMAGE_MAX_SIZE: u32 = 4096;
+    let preview_image_max_dimension = preview_image_width.max(preview_image_height);
+
+    if preview_image_max_dimension > PREVIEW_IMAGE_MAX_SIZE {
+        let scale = PREVIEW_IMAGE_MAX_SIZE as f32 / preview_image_max_dimension as f32;
+
+        preview_image_width = (preview_image_width as f32 * scale) as u32;
+        preview_image_height = (preview_image_height as f32 * scale) as u32;
+    }
+
+    /* Download Images */
+
+    let ((left_image_bytes, left_image_format), (right_image_bytes, right_image_format)) = try_join!(
+        download_image_from_attachment(
+            sources.left_image_attachment,
+            preview_image_width,
+            preview_image_height
+        ),
+        download_image_from_attachment(
+            sources.right_image_attachment,
+            preview_image_width,
+            preview_image_height
+        )
+    )?;
+
+    let upload_limit = get_upload_limit(ctx, guild_id).await;
+
+    let preview_image_budget = upload_limit
+        .checked_sub(left_image_bytes.len() + right_image_bytes.len())
+        .filter(|budget| *budget > 0)
+        .ok_or("The images are too large to be uploaded in this server.")?;
+
+    /* Process Images */
+
+    let _render_permit = RENDER_QUEUE.enter(ctx, interaction_token).await;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let result_cache_key = get_result_cache_key(
+        left_image_bytes.as_slice(),
+        right_image_bytes.as_slice(),
+        &ResultCacheOptions {
+            left_label: sources.left_label,
+            right_label: sources.right_label,
+            is_vertical: sources.is_vertical,
+            divider: &sources.divider,
+            frame_index: sources.frame_index,
+            watermark: sources.watermark,
+            budget: preview_image_budget,
+        },
+    );
+
+    let final_image_encoded =
+        match redis_get_cached_result(&mut redis_connection_manager, result_cache_key.as_str())
+            .await
+        {
+            Some(final_image_encoded) => final_image_encoded,
+            None => {
+                let final_image = render_preview_image(
+                    decode_image_frame(
+                        left_image_bytes.as_slice(),
+                        left_image_format,
+                        sources.frame_index,
+                    )?,
+                    decode_image_frame(
+                        right_image_bytes.as_slice(),
+                        right_image_format,
+                        sources.frame_index,
+                    )?,
+                    sources.left_label,
+                    sources.right_label,
+                    sources.is_vertical,
+                    &sources.divider,
+                    sources.watermark,
+                )?;
+
+                let final_image_encoded = encode_preview_image(&final_image, preview_image_budget)?;
+
+                redis_set_cached_result(
+                    &mut redis_connection_manager,
+                    result_cache_key.as_str(),
+                    &final_image_encoded,
+                )
+                .await;
+
+                final_image_encoded
+            }
+        };
+
+    let final_image_file_name = format!("preview.{}", final_image_encoded.extension);
+
+    let mut left_image_create_attachment = CreateAttachment::bytes(
+        left_image_bytes,
+        sources.left_image_attachment.filename.to_owned(),
+    );
+
+    if let Some(left_label) = sources.left_label {
+        left_image_create_attachment =
+            left_image_create_attachment.description(left_label.to_owned());
+    }
+
+    let mut right_image_create_attachment = CreateAttachment::bytes(
+        right_image_bytes,
+        sources.right_image_attachment.filename.to_owned(),
+    );
+
+    if let Some(right_label) = sources.right_label {
+        right_image_create_attachment =
+            right_image_create_attachment.description(right_label.to_owned());
+    }
+
+    let preview_image_description = sources
+        .alt_text
+        .map_or_else(|| sources.describe_preview(), ToOwned::to_owned);
+
+    Ok(EditAttachments::new()
+        .add(
+            CreateAttachment::bytes(final_image_encoded.data, final_image_file_name)
+                .description(preview_image_description),
+        )
+        .add(left_image_create_attachment)
+        .add(right_image_create_attachment))
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+    let juxtapose_options = get_juxtapose_options(&options)?;
+
+    create_juxtapose(ctx, interaction, juxtapose_options).await
+}
+
+/// Reads the options of `/juxtapose` by name, since Discord only sends the optional ones that were given.
+fn get_juxtapose_options<'a>(
+    options: &[ResolvedOption<'a>],
+) -> Result<JuxtaposeOptions<'a>, String> {
+    let divider = DividerStyle {
+        thickness: get_integer_option(options, "divider_thickness")
+            .map(|thickness| thickness.clamp(0, DividerStyle::MAX_THICKNESS as i64) as u32),
+        color: match get_string_option(options, "divider_color") {
+            Some(value) => RgbColor::parse(value).ok_or(
+                "The divider color is invalid. Use hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+            )?,
+            None => DividerStyle::default().color,
+        },
+    };
+
+    Ok(JuxtaposeOptions {
+        left_image_attachment: get_attachment_option(options, "left_image")
+            .ok_or("The left image is required.")?,
+        right_image_attachment: get_attachment_option(options, "right_image")
+            .ok_or("The right image is required.")?,
+        left_label: get_string_option(options, "left_label").map(ToOwned::to_owned),
+        right_label: get_string_option(options, "right_label").map(ToOwned::to_owned),
+        is_vertical: get_boolean_option(options, "vertical").unwrap_or(false),
+        divider,
+        frame_index: get_integer_option(options, "frame").map_or(0, |frame| {
+            frame.clamp(1, MAX_FRAME_NUMBER as i64) as usize - 1
+        }),
+        alt_text: get_string_option(options, "alt_text"),
+    })
+}
+
+/// Options of a juxtapose requested by a command, before the settings of the guild are applied.
+pub(crate) struct JuxtaposeOptions<'a> {
+    pub(crate) left_image_attachment: &'a Attachment,
+    pub(crate) right_image_attachment: &'a Attachment,
+    pub(crate) left_label: Option<String>,
+    pub(crate) right_label: Option<String>,
+    pub(crate) is_vertical: bool,
+    pub(crate) divider: DividerStyle,
+    pub(crate) frame_index: usize,
+    pub(crate) alt_text: Option<&'a str>,
+}
+
+/// Renders the juxtapose and replies to the command with it, its viewer link and the buttons for changing it.
+pub(crate) async fn create_juxtapose(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    juxtapose_options: JuxtaposeOptions<'_>,
+) -> Result<(), String> {
+    let JuxtaposeOptions {
+        left_image_attachment,
+        right_image_attachment,
+        left_label,
+        right_label,
+        is_vertical,
+        divider,
+        frame_index,
+        alt_text,
+    } = juxtapose_options;
+
+    /* Check Cooldown */
+
+    JUXTAPOSE_COOLDOWN
+        .check(interaction.user.id)
+        .map_err(format_cooldown_error)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer juxtapose interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Render Juxtapose */
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let guild_config = match interaction.guild_id {
+        Some(guild_id) => GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+            .await
+            .unwrap_or_default(),
+        None => GuildConfig::default(),
+    };
+
+    let sources = JuxtaposeSources {
+        left_image_attachment,
+        right_image_attachment,
+        left_label: left_label.as_deref(),
+        right_label: right_label.as_deref(),
+        is_vertical,
+        divider,
+        frame_index,
+        watermark: guild_config.juxtapose_watermark.as_deref(),
+        alt_text,
+    };
+
+    // Suggestions are read from the original files while the preview is rendered.
+    let (juxtapose_attachments, label_suggestions) = join!(
+        create_juxtapose_attachments(ctx, interaction.guild_id, &interaction.token, &sources),
+        LabelSuggestions::from_sources(&sources)
+    );
+    let juxtapose_attachments = juxtapose_attachments?;
 
     /* Reply */
 
     let reply = interaction
         .edit_response(
             &ctx.http,
-            EditInteractionResponse::new().attachments(
-                EditAttachments::new()
-                    .add(CreateAttachment::bytes(final_image_encoded, "preview.png"))
-                    .add(left_image_create_attachment)
-                    .add(right_image_create_attachment),
-            ),
+            EditInteractionResponse::new().attachments(juxtapose_attachments),
         )
         .await
         .map_err(|_| "Failed to upload images to Discord. Perhaps they are too large?")?;
 
     /* Encode Data */
 
-    let data = [
-        reply.id.get().to_le_bytes(),
-        interaction.channel_id.get().to_le_bytes(),
-    ]
-    .concat();
-
-    let mut mac = [0u8; 16];
-
-    blake3::Hasher::new_keyed(&BLAKE3_JUXTAPOSE_KEY)
-        .update(data.as_slice())
-        .finalize_xof()
-        .fill(&mut mac);
-
-    let juxtapose_url_data = general_purpose::URL_SAFE_NO_PAD.encode(data.as_slice());
-    let juxtapose_url_mac = general_purpose::URL_SAFE_NO_PAD.encode(mac.as_slice());
-
-    let mut juxtapose_url = JUXTAPOSE_BASE_URL.clone();
-    juxtapose_url.query_pairs_mut().extend_pairs(&[
-        ("d", juxtapose_url_data.as_str()),
-        ("m", juxtapose_url_mac.as_str()),
-        ("o", if is_vertical { "v" } else { "h" }),
-    ]);
+    let (juxtapose_url, juxtapose_url_data) =
+        create_juxtapose_url(reply.id, interaction.channel_id, is_vertical, &divider);
+
+    let mut buttons = vec![
+        CreateButton::new_link(juxtapose_url.as_str())
+            .emoji('🔗')
+            .label("Open"),
+        swap::create_swap_button(interaction.user.id, is_vertical, &divider, frame_index),
+    ];
+
+    if !label_suggestions.is_empty() {
+        if let Err(error) = label_suggestions
+            .redis_set(&mut redis_connection_manager, reply.id)
+            .await
+        {
+            println!("Error while storing label suggestions: {:?}", error);
+        } else {
+            buttons.push(label::create_label_button(
+                interaction.user.id,
+                is_vertical,
+                &divider,
+                frame_index,
+            ));
+        }
+    }
 
     interaction
         .edit_response(
             &ctx.http,
-            EditInteractionResponse::new().components(&[CreateActionRow::buttons(&[
-                CreateButton::new_link(juxtapose_url.as_str())
-                    .emoji('🔗')
-                    .label("Open"),
-            ])]),
+            EditInteractionResponse::new().components(&[CreateActionRow::buttons(&buttons)]),
         )
         .await
         .map_err(|_| "Failed to add button containing the juxtapose URL.")?;
 
-    let mut redis_connection_manager = ctx
-        .data::<SerenityGlobalData>()
-        .redis_connection_manager
-        .clone();
-
     let juxtapose_cache_data = APIJuxtaposeResponse {
         left_image_url: left_image_attachment.url.to_string(),
         right_image_url: right_image_attachment.url.to_string(),
         left_image_label: left_label,
         right_image_label: right_label,
+        preview_image_url: reply
+            .attachments
+            .first()
+            .map(|attachment| attachment.url.to_string()),
+        orientation: Some(JuxtaposeOrientation::from_is_vertical(is_vertical)),
+        divider_position: Some(0.5),
     };
 
-    juxtapose_cache_data
-        .redis_cache_set(&mut redis_connection_manager, juxtapose_url_data.as_str())
+    if let Err(error) = redis_record_user_juxtapose(
+        &mut redis_connection_manager,
+        interaction.user.id,
+        juxtapose_url_data.as_str(),
+    )
+    .await
+    {
+        println!("Error while recording juxtapose of user: {:?}", error);
+    }
+
+    spawn_cache_warming(
+        redis_connection_manager.clone(),
+        juxtapose_url_data,
+        juxtapose_cache_data,
+    );
+
+    publish_activity(ActivityEvent::JuxtaposeCreated {
+        guild_id: interaction.guild_id,
+        channel_id: interaction.channel_id,
+        message_id: reply.id,
+    });
+
+    if let Some(guild_id) = interaction.guild_id {
+        if let Err(error) = redis_increment_guild_stats(
+            &mut redis_connection_manager,
+            guild_id,
+            GuildStatsCounter::Juxtaposes,
+        )
         .await
-        .unwrap();
+        {
+            println!("Error while incrementing guild stats: {:?}", error);
+        }
+
+        send_audit_log(
+            &ctx.http,
+            Some(guild_id),
+            &guild_config,
+            AuditLogEntry::JuxtaposeCreated {
+                author_id: interaction.user.id,
+                channel_id: interaction.channel_id,
+                message_id: reply.id,
+            },
+        )
+        .await;
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serenity::all::CommandData;
+
+    use super::*;
+
+    fn create_attachment(id: u64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id.to_string(),
+            "filename": format!("{}.png", id),
+            "size": 1024,
+            "url": format!("https://cdn.discordapp.com/attachments/1/{}/{}.png", id, id),
+            "proxy_url": format!("https://media.discordapp.net/attachments/1/{}/{}.png", id, id),
+            "content_type": "image/png",
+        })
+    }
+
+    #[test]
+    fn resolves_options_by_name_without_labels() {
+        let command_data: CommandData = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "name": "juxtapose",
+            "type": 1,
+            "options": [
+                { "type": 11, "name": "left_image", "value": "10" },
+                { "type": 11, "name": "right_image", "value": "11" },
+                { "type": 5, "name": "vertical", "value": true },
+                { "type": 3, "name": "alt_text", "value": "A cat before and after its bath." },
+            ],
+            "resolved": {
+                "attachments": { "10": create_attachment(10), "11": create_attachment(11) },
+            },
+        }))
+        .unwrap();
+
+        let options = command_data.options();
+        let juxtapose_options = get_juxtapose_options(&options).unwrap();
+
+        assert_eq!(juxtapose_options.left_image_attachment.id.get(), 10);
+        assert_eq!(juxtapose_options.right_image_attachment.id.get(), 11);
+        assert_eq!(juxtapose_options.left_label, None);
+        assert_eq!(juxtapose_options.right_label, None);
+        assert!(juxtapose_options.is_vertical);
+        assert_eq!(
+            juxtapose_options.alt_text,
+            Some("A cat before and after its bath.")
+        );
+    }
+}
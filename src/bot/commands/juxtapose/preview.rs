@@ -9,11 +9,49 @@ use imageproc::{
 };
 use once_cell::sync::Lazy;
 
+use crate::bot::commands::color::parse::RgbColor;
+
 static LABEL_FONT: Lazy<FontRef> = Lazy::new(|| {
     let font_data = include_bytes!("../../../../assets/font/RobotoSlab-Regular.ttf");
     FontRef::try_from_slice(font_data).unwrap()
 });
 
+/// Appearance of the line between both images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DividerStyle {
+    /// Thickness in pixels of the preview image. Scales with the size of the preview if unset.
+    pub(crate) thickness: Option<u32>,
+    pub(crate) color: RgbColor,
+}
+
+impl Default for DividerStyle {
+    fn default() -> Self {
+        Self {
+            thickness: None,
+            color: RgbColor {
+                red: 255,
+                green: 255,
+                blue: 255,
+            },
+        }
+    }
+}
+
+impl DividerStyle {
+    pub(crate) const MAX_THICKNESS: u32 = 32;
+
+    /// Range of the pixels covered by the divider, centered within the given length of the preview image.
+    pub(super) fn get_line(&self, length: u32) -> Range<u32> {
+        let thickness = self
+            .thickness
+            .unwrap_or(2 * (length / 1000).max(1))
+            .min(length);
+
+        let start = (length - thickness) / 2;
+        start..start + thickness
+    }
+}
+
 pub(super) fn draw_vertical_line_mut(image: &mut DynamicImage, line: Range<u32>, color: Rgba<u8>) {
     for y in 0..image.height() {
         for x in line.clone() {
@@ -34,13 +72,14 @@ pub(super) fn draw_horizontal_line_mut(
     }
 }
 
-pub(super) enum LabelPosition {
+pub(crate) enum LabelPosition {
     TopLeft,
+    TopRight,
     BottomLeft,
     BottomRight,
 }
 
-pub(super) fn draw_label(
+pub(crate) fn draw_label(
     canvas: &mut Blend<DynamicImage>,
     position: LabelPosition,
     scale: f32,
@@ -52,6 +91,9 @@ pub(super) fn draw_label(
 
     let background_position = match position {
         LabelPosition::TopLeft => Rect::at(0, 0),
+        LabelPosition::TopRight => {
+            Rect::at(canvas.0.width() as i32 - label_width as i32 - 2 * margin, 0)
+        }
         LabelPosition::BottomLeft => Rect::at(
             0,
             canvas.0.height() as i32 - label_height as i32 - 2 * margin,
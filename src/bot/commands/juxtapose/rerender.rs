@@ -0,0 +1,171 @@
+use std::error::Error;
+
+use serenity::all::{GuildId, Message, UserId};
+use serenity::prelude::*;
+
+use crate::bot::commands::color::parse::RgbColor;
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::commands::juxtapose::JuxtaposeSources;
+use crate::bot::guild_config::GuildConfig;
+use crate::signing::SignedPayload;
+use crate::web::api_juxtapose_response::{APIJuxtaposeResponse, JuxtaposeOrientation};
+use crate::web::cache_warming::spawn_cache_warming;
+use crate::{SerenityGlobalData, BLAKE3_JUXTAPOSE_KEY};
+
+/// Encodes the author, orientation, divider and frame of a juxtapose into the custom ID of a button on its message,
+/// so that the juxtapose can be rendered again without parsing the message.
+pub(super) fn create_custom_id(
+    prefix: &str,
+    author_id: UserId,
+    is_vertical: bool,
+    divider: &DividerStyle,
+    frame_index: usize,
+) -> String {
+    format!(
+        "{}:{}:{}:{}:{}:{}",
+        prefix,
+        author_id,
+        if is_vertical { "v" } else { "h" },
+        divider
+            .thickness
+            .map(|thickness| thickness.to_string())
+            .unwrap_or_default(),
+        divider.color.to_hex(),
+        frame_index
+    )
+}
+
+/// Parses the author ID, orientation, divider and frame encoded by [`create_custom_id`].
+pub(super) fn parse_custom_id(custom_id: &str) -> Option<(&str, bool, DividerStyle, usize)> {
+    let mut arguments = custom_id.split(':').skip(1);
+
+    let author_id = arguments.next()?;
+    let is_vertical = arguments.next()? == "v";
+
+    let divider = match (arguments.next(), arguments.next()) {
+        (Some(thickness), Some(color)) => DividerStyle {
+            thickness: (!thickness.is_empty())
+                .then(|| thickness.parse())
+                .transpose()
+                .ok()?,
+            color: RgbColor::parse(color)?,
+        },
+        // Buttons created before the divider could be customized.
+        _ => DividerStyle::default(),
+    };
+
+    // Buttons created before animated images were supported use the first frame.
+    let frame_index = match arguments.next() {
+        Some(frame_index) => frame_index.parse().ok()?,
+        None => 0,
+    };
+
+    Some((author_id, is_vertical, divider, frame_index))
+}
+
+pub(super) async fn get_watermark(ctx: &Context, guild_id: Option<GuildId>) -> Option<String> {
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    match guild_id {
+        Some(guild_id) => {
+            GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+                .await
+                .unwrap_or_default()
+                .juxtapose_watermark
+        }
+        None => None,
+    }
+}
+
+/// Reconstructs the sources of the juxtapose on a message from its attachments,
+/// whose descriptions hold the labels. Alt text that differs from the generated one
+/// was written by the author and is kept.
+pub(super) fn get_message_sources<'a>(
+    message: &'a Message,
+    is_vertical: bool,
+    divider: DividerStyle,
+    frame_index: usize,
+    watermark: Option<&'a str>,
+) -> Option<JuxtaposeSources<'a>> {
+    let left_image_attachment = message.attachments.get(1)?;
+    let right_image_attachment = message.attachments.get(2)?;
+
+    let mut sources = JuxtaposeSources {
+        left_image_attachment,
+        right_image_attachment,
+        left_label: left_image_attachment.description.as_deref(),
+        right_label: right_image_attachment.description.as_deref(),
+        is_vertical,
+        divider,
+        frame_index,
+        watermark,
+        alt_text: None,
+    };
+
+    sources.alt_text = message
+        .attachments
+        .first()
+        .and_then(|attachment| attachment.description.as_deref())
+        .filter(|description| *description != sources.describe_preview());
+
+    Some(sources)
+}
+
+/// Replaces the cached image URLs and labels of a juxtapose after its message has been edited.
+/// The message stays the same, so the viewer URL does too.
+pub(super) async fn update_juxtapose_cache(
+    ctx: &Context,
+    reply: &Message,
+    sources: &JuxtaposeSources<'_>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let juxtapose_url_data = SignedPayload::new(reply.id, reply.channel_id)
+        .encode(&BLAKE3_JUXTAPOSE_KEY)
+        .data;
+
+    let mut juxtapose_cache_data = APIJuxtaposeResponse {
+        left_image_url: reply
+            .attachments
+            .get(1)
+            .ok_or("Edited juxtapose is missing its left (top) image.")?
+            .url
+            .to_string(),
+        right_image_url: reply
+            .attachments
+            .get(2)
+            .ok_or("Edited juxtapose is missing its right (bottom) image.")?
+            .url
+            .to_string(),
+        left_image_label: sources.left_label.map(ToOwned::to_owned),
+        right_image_label: sources.right_label.map(ToOwned::to_owned),
+        preview_image_url: reply
+            .attachments
+            .first()
+            .map(|attachment| attachment.url.to_string()),
+        orientation: None,
+        divider_position: None,
+    };
+
+    // Keep the layout the viewer may have stored since the juxtapose was created.
+    juxtapose_cache_data
+        .redis_get_layout(&mut redis_connection_manager, juxtapose_url_data.as_str())
+        .await;
+    juxtapose_cache_data
+        .orientation
+        .get_or_insert(JuxtaposeOrientation::from_is_vertical(sources.is_vertical));
+
+    spawn_cache_warming(
+        redis_connection_manager,
+        juxtapose_url_data,
+        juxtapose_cache_data,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use redis::AsyncCommands;
+
+use super::encode::EncodedImage;
+use super::preview::DividerStyle;
+
+/// Generated previews are only cached briefly, e.g. for retries after a failed first attempt.
+const RESULT_CACHE_TTL_SECONDS: i64 = 15 * 60;
+
+/// Previews bigger than this are not worth keeping in Redis.
+const RESULT_CACHE_MAX_SIZE: usize = 8 * 1024 * 1024;
+
+pub(super) struct ResultCacheOptions<'a> {
+    pub(super) left_label: Option<&'a str>,
+    pub(super) right_label: Option<&'a str>,
+    pub(super) is_vertical: bool,
+    pub(super) divider: &'a DividerStyle,
+    pub(super) frame_index: usize,
+    pub(super) watermark: Option<&'a str>,
+    pub(super) budget: usize,
+}
+
+fn update_length_prefixed(hasher: &mut blake3::Hasher, data: &[u8]) {
+    hasher.update(&(data.len() as u64).to_le_bytes());
+    hasher.update(data);
+}
+
+/// Derives the cache key from the contents of both source images and every option affecting the preview.
+/// The juxtapose URL is not cached, as it references the reply message and has to be created anew.
+pub(super) fn get_result_cache_key(
+    left_image_bytes: &[u8],
+    right_image_bytes: &[u8],
+    options: &ResultCacheOptions,
+) -> String {
+    let mut hasher = blake3::Hasher::new();
+
+    update_length_prefixed(&mut hasher, left_image_bytes);
+    update_length_prefixed(&mut hasher, right_image_bytes);
+
+    for label in [options.left_label, options.right_label, options.watermark] {
+        match label {
+            Some(label) => {
+                hasher.update(&[1]);
+                update_length_prefixed(&mut hasher, label.as_bytes());
+            }
+            None => {
+                hasher.update(&[0]);
+            }
+        }
+    }
+
+    hasher.update(&[options.is_vertical as u8]);
+
+    match options.divider.thickness {
+        Some(thickness) => {
+            hasher.update(&[1]);
+            hasher.update(&thickness.to_le_bytes());
+        }
+        None => {
+            hasher.update(&[0]);
+        }
+    }
+
+    hasher.update(&options.divider.color.to_u32().to_le_bytes());
+    hasher.update(&(options.frame_index as u64).to_le_bytes());
+    hasher.update(&(options.budget as u64).to_le_bytes());
+
+    format!("juxtapose_result:{}", hasher.finalize().to_hex())
+}
+
+pub(super) async fn redis_get_cached_result(
+    connection: &mut redis::aio::ConnectionManager,
+    key: &str,
+) -> Option<EncodedImage> {
+    let mut cached_result = connection
+        .hgetall::<&str, HashMap<String, Vec<u8>>>(key)
+        .await
+        .ok()?;
+
+    let extension = match cached_result.get("extension")?.as_slice() {
+        b"png" => "png",
+        b"jpg" => "jpg",
+        _ => return None,
+    };
+
+    Some(EncodedImage {
+        data: cached_result.remove("data")?,
+        extension,
+    })
+}
+
+pub(super) async fn redis_set_cached_result(
+    connection: &mut redis::aio::ConnectionManager,
+    key: &str,
+    image_encoded: &EncodedImage,
+) {
+    if image_encoded.data.len() > RESULT_CACHE_MAX_SIZE {
+        return;
+    }
+
+    let result: Result<(), redis::RedisError> = redis::pipe()
+        .hset(key, "data", image_encoded.data.as_slice())
+        .ignore()
+        .hset(key, "extension", image_encoded.extension)
+        .ignore()
+        .expire(key, RESULT_CACHE_TTL_SECONDS)
+        .ignore()
+        .query_async(connection)
+        .await;
+
+    if let Err(error) = result {
+        println!("Error while caching juxtapose result: {:?}", error);
+    }
+}
@@ -1,10 +1,13 @@
-use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption};
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use super::animation::MAX_FRAME_NUMBER;
+use super::preview::DividerStyle;
+use crate::bot::i18n::{localized_command, localized_option};
 
 pub(crate) fn register() -> CreateCommand<'static> {
-    CreateCommand::new("juxtapose")
-        .description("Create a juxtapose by uploading two images.")
+    localized_command("juxtapose", "Create a juxtapose by uploading two images.")
         .add_option(
-            CreateCommandOption::new(
+            localized_option(
                 CommandOptionType::Attachment,
                 "left_image",
                 "The image on the left side (or top).",
@@ -12,7 +15,7 @@ pub(crate) fn register() -> CreateCommand<'static> {
             .required(true),
         )
         .add_option(
-            CreateCommandOption::new(
+            localized_option(
                 CommandOptionType::Attachment,
                 "right_image",
                 "The image on the right side (or bottom).",
@@ -20,7 +23,7 @@ pub(crate) fn register() -> CreateCommand<'static> {
             .required(true),
         )
         .add_option(
-            CreateCommandOption::new(
+            localized_option(
                 CommandOptionType::String,
                 "left_label",
                 "The label on the left side (or top).",
@@ -29,7 +32,7 @@ pub(crate) fn register() -> CreateCommand<'static> {
             .required(false),
         )
         .add_option(
-            CreateCommandOption::new(
+            localized_option(
                 CommandOptionType::String,
                 "right_label",
                 "The label on the right side (or bottom).",
@@ -38,11 +41,49 @@ pub(crate) fn register() -> CreateCommand<'static> {
             .required(false),
         )
         .add_option(
-            CreateCommandOption::new(
+            localized_option(
                 CommandOptionType::Boolean,
                 "vertical",
                 "Whether or not the juxtapose should be vertical instead of horizontal. Defaults to false.",
             )
             .required(false),
         )
+        .add_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "divider_thickness",
+                "The thickness of the divider in pixels. Scales with the image size by default.",
+            )
+            .min_int_value(0)
+            .max_int_value(DividerStyle::MAX_THICKNESS.into())
+            .required(false),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::String,
+                "divider_color",
+                "The color of the divider in hex, rgb() or hsl() notation. Defaults to white.",
+            )
+            .max_length(100)
+            .required(false),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::String,
+                "alt_text",
+                "Describes the preview for screen readers. Generated from the labels by default.",
+            )
+            .max_length(1024)
+            .required(false),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "frame",
+                "The frame of animated images to compare, starting at 1. Defaults to the first frame.",
+            )
+            .min_int_value(1)
+            .max_int_value(MAX_FRAME_NUMBER.into())
+            .required(false),
+        )
 }
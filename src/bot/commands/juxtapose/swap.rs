@@ -0,0 +1,137 @@
+use std::error::Error;
+
+use serenity::all::{
+    ButtonStyle, ComponentInteraction, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, EditInteractionResponse,
+    UserId,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::commands::juxtapose::rerender::{
+    create_custom_id, get_message_sources, get_watermark, parse_custom_id, update_juxtapose_cache,
+};
+use crate::bot::commands::juxtapose::{
+    create_juxtapose_attachments, JuxtaposeSources, JUXTAPOSE_COOLDOWN,
+};
+use crate::bot::commands::rate_limit::format_cooldown_error;
+use crate::bot::custom_id::sign_custom_id;
+
+pub(crate) const SWAP_CUSTOM_ID_PREFIX: &str = "juxtaposeSwap";
+
+/// Creates the button that exchanges the sides of a juxtapose. Only the author can use it.
+pub(crate) fn create_swap_button(
+    author_id: UserId,
+    is_vertical: bool,
+    divider: &DividerStyle,
+    frame_index: usize,
+) -> CreateButton<'static> {
+    CreateButton::new(sign_custom_id(
+        create_custom_id(
+            SWAP_CUSTOM_ID_PREFIX,
+            author_id,
+            is_vertical,
+            divider,
+            frame_index,
+        )
+        .as_str(),
+    ))
+    .style(ButtonStyle::Secondary)
+    .emoji('🔄')
+    .label("Swap")
+}
+
+async fn respond_ephemeral(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    content: &str,
+) -> Result<(), serenity::Error> {
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await
+}
+
+/// Renders the juxtapose again with the source images (and their labels) exchanged,
+/// re-using the images attached to the message instead of requiring them to be uploaded again.
+pub async fn handle_swap_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (author_id, is_vertical, divider, frame_index) = parse_custom_id(custom_id)
+        .ok_or("Failed to retrieve author ID and layout from custom ID.")?;
+
+    if author_id != interaction.user.id.to_string() {
+        respond_ephemeral(
+            ctx,
+            interaction,
+            "Only the user who created the juxtapose can swap its images.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if let Err(remaining) = JUXTAPOSE_COOLDOWN.check(interaction.user.id) {
+        respond_ephemeral(ctx, interaction, &format_cooldown_error(remaining)).await?;
+        return Ok(());
+    }
+
+    interaction.defer(&ctx.http).await?;
+
+    let watermark = get_watermark(ctx, interaction.guild_id).await;
+
+    let original_sources = get_message_sources(
+        &interaction.message,
+        is_vertical,
+        divider,
+        frame_index,
+        watermark.as_deref(),
+    )
+    .ok_or("Juxtapose message is missing its source images.")?;
+
+    let sources = JuxtaposeSources {
+        left_image_attachment: original_sources.right_image_attachment,
+        right_image_attachment: original_sources.left_image_attachment,
+        left_label: original_sources.right_label,
+        right_label: original_sources.left_label,
+        ..original_sources
+    };
+
+    let juxtapose_attachments =
+        match create_juxtapose_attachments(ctx, interaction.guild_id, &interaction.token, &sources)
+            .await
+        {
+            Ok(juxtapose_attachments) => juxtapose_attachments,
+            Err(error) => {
+                interaction
+                    .create_followup(
+                        &ctx.http,
+                        CreateInteractionResponseFollowup::new()
+                            .content(error)
+                            .ephemeral(true),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        };
+
+    let reply = interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().attachments(juxtapose_attachments),
+        )
+        .await?;
+
+    /* Update Cache */
+
+    update_juxtapose_cache(ctx, &reply, &sources).await?;
+
+    Ok(())
+}
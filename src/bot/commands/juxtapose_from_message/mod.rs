@@ -0,0 +1,62 @@
+use serenity::all::{CommandInteraction, CreateActionRow, CreateButton, EditInteractionResponse};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::create_juxtapose_url;
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::commands::message_link::resolve_juxtapose_message_link;
+use crate::bot::commands::options::{get_boolean_option, get_string_option};
+
+mod structure;
+pub(crate) use structure::register;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let message_link = get_string_option(&options, "message_link").unwrap_or_default();
+    let is_vertical = get_boolean_option(&options, "vertical").unwrap_or(false);
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!(
+            "Failed to defer juxtapose from message interaction: {:?}",
+            error
+        );
+        return Ok(());
+    }
+
+    /* Resolve Message */
+
+    let (Some(guild_id), Some(member)) = (interaction.guild_id, interaction.member.as_deref())
+    else {
+        return Err("This command can only be used in servers.".to_owned());
+    };
+
+    let (channel_id, message_id) =
+        resolve_juxtapose_message_link(ctx, guild_id, member, message_link).await?;
+
+    /* Reply */
+
+    let (juxtapose_url, _) = create_juxtapose_url(
+        message_id,
+        channel_id,
+        is_vertical,
+        &DividerStyle::default(),
+    );
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .content(message_link)
+                .components(&[CreateActionRow::buttons(&[CreateButton::new_link(
+                    juxtapose_url.as_str(),
+                )
+                .emoji('🔗')
+                .label("Open")])]),
+        )
+        .await
+        .map_err(|_| "Failed to add button containing the juxtapose URL.")?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "juxtapose-from-message",
+        "Recreate the interactive link of a previous juxtapose.",
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "message_link",
+            "The link to the message containing the juxtapose.",
+        )
+        .max_length(200)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::Boolean,
+            "vertical",
+            "Whether or not the juxtapose is vertical instead of horizontal. Defaults to false.",
+        )
+        .required(false),
+    )
+}
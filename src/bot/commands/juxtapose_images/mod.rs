@@ -0,0 +1,116 @@
+use serenity::all::{Attachment, CommandInteraction, ResolvedTarget};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::commands::juxtapose::{create_juxtapose, JuxtaposeOptions};
+
+mod structure;
+pub(crate) use structure::{register, COMMAND_NAME};
+
+/// Picks the images of the first list of attachments that contains any. Forwarded messages have no attachments
+/// of their own, but carry those of the original message in their message snapshot.
+fn find_image_attachments<'a>(
+    attachment_lists: impl IntoIterator<Item = &'a [Attachment]>,
+) -> Vec<&'a Attachment> {
+    attachment_lists
+        .into_iter()
+        .map(|attachments| {
+            attachments
+                .iter()
+                .filter(|attachment| attachment.width.is_some() && attachment.height.is_some())
+                .collect::<Vec<_>>()
+        })
+        .find(|image_attachments| !image_attachments.is_empty())
+        .unwrap_or_default()
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let Some(ResolvedTarget::Message(target_message)) = interaction.data.target() else {
+        return Err("The command must be used on a message.".to_owned());
+    };
+
+    let image_attachments = find_image_attachments(
+        std::iter::once(&target_message.attachments[..]).chain(
+            target_message
+                .message_snapshots
+                .iter()
+                .map(|message_snapshot| &message_snapshot.attachments[..]),
+        ),
+    );
+
+    let [left_image_attachment, right_image_attachment, ..] = image_attachments[..] else {
+        return Err("The message must contain at least two images.".to_owned());
+    };
+
+    create_juxtapose(
+        ctx,
+        interaction,
+        JuxtaposeOptions {
+            left_image_attachment,
+            right_image_attachment,
+            left_label: None,
+            right_label: None,
+            is_vertical: false,
+            divider: DividerStyle::default(),
+            frame_index: 0,
+            alt_text: None,
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_attachment(id: u64, dimensions: Option<(u32, u32)>) -> Attachment {
+        let mut attachment = serde_json::json!({
+            "id": id.to_string(),
+            "filename": format!("{}.png", id),
+            "size": 1024,
+            "url": format!("https://cdn.discordapp.com/attachments/1/{}/{}.png", id, id),
+            "proxy_url": format!("https://media.discordapp.net/attachments/1/{}/{}.png", id, id),
+            "content_type": "image/png",
+        });
+
+        if let Some((width, height)) = dimensions {
+            attachment["width"] = width.into();
+            attachment["height"] = height.into();
+        }
+
+        serde_json::from_value(attachment).unwrap()
+    }
+
+    #[test]
+    fn resolves_images_of_forwarded_messages() {
+        let own_attachments: Vec<Attachment> = Vec::new();
+        let snapshot_attachments = vec![
+            create_attachment(1, Some((640, 480))),
+            create_attachment(2, None),
+            create_attachment(3, Some((640, 480))),
+        ];
+
+        let image_attachments =
+            find_image_attachments([own_attachments.as_slice(), snapshot_attachments.as_slice()]);
+
+        assert_eq!(
+            image_attachments
+                .iter()
+                .map(|attachment| attachment.id.get())
+                .collect::<Vec<_>>(),
+            [1, 3]
+        );
+    }
+
+    #[test]
+    fn prefers_own_images() {
+        let own_attachments = vec![create_attachment(1, Some((640, 480)))];
+        let snapshot_attachments = vec![create_attachment(2, Some((640, 480)))];
+
+        let image_attachments =
+            find_image_attachments([own_attachments.as_slice(), snapshot_attachments.as_slice()]);
+
+        assert_eq!(image_attachments.len(), 1);
+        assert_eq!(image_attachments[0].id.get(), 1);
+    }
+}
@@ -0,0 +1,9 @@
+use serenity::all::{CommandType, CreateCommand};
+
+use crate::bot::i18n::localized_message_command;
+
+pub(crate) const COMMAND_NAME: &str = "Juxtapose Images";
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_message_command(COMMAND_NAME).kind(CommandType::Message)
+}
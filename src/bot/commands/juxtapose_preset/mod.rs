@@ -0,0 +1,92 @@
+use serenity::all::{CommandInteraction, CreateAutocompleteResponse, CreateInteractionResponse};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::{create_juxtapose, JuxtaposeOptions};
+use crate::bot::commands::options::{get_attachment_option, get_string_option};
+use crate::bot::guild_config::GuildConfig;
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::register;
+
+/// Suggests the presets of the guild whose names contain the text typed so far.
+/// Also used by `/config presets remove`.
+pub async fn autocomplete(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), serenity::Error> {
+    let query = interaction
+        .data
+        .autocomplete()
+        .map(|option| option.value.to_lowercase())
+        .unwrap_or_default();
+
+    let guild_config = match interaction.guild_id {
+        Some(guild_id) => {
+            let mut redis_connection_manager = ctx
+                .data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone();
+
+            GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+                .await
+                .unwrap_or_default()
+        }
+        None => GuildConfig::default(),
+    };
+
+    let response = guild_config
+        .juxtapose_presets
+        .iter()
+        .filter(|preset| preset.name.to_lowercase().contains(query.as_str()))
+        .fold(CreateAutocompleteResponse::new(), |response, preset| {
+            response.add_string_choice(preset.name.clone(), preset.name.clone())
+        });
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+        .await
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let guild_id = interaction
+        .guild_id
+        .ok_or("This command can only be used in servers.")?;
+
+    let options = interaction.data.options();
+
+    let preset_name = get_string_option(&options, "preset").ok_or("A preset is required.")?;
+    let left_image_attachment =
+        get_attachment_option(&options, "left_image").ok_or("The left (top) image is required.")?;
+    let right_image_attachment = get_attachment_option(&options, "right_image")
+        .ok_or("The right (bottom) image is required.")?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let guild_config = GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+        .await
+        .map_err(|_| "Failed to load the presets of this server.")?;
+
+    let preset = guild_config.find_juxtapose_preset(preset_name).ok_or(
+        "This server has no preset with that name. Presets are managed with /config presets.",
+    )?;
+
+    create_juxtapose(
+        ctx,
+        interaction,
+        JuxtaposeOptions {
+            left_image_attachment,
+            right_image_attachment,
+            left_label: preset.left_label.clone(),
+            right_label: preset.right_label.clone(),
+            is_vertical: preset.is_vertical,
+            divider: preset.divider(),
+            frame_index: 0,
+            alt_text: None,
+        },
+    )
+    .await
+}
@@ -0,0 +1,37 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::guild_config::JUXTAPOSE_PRESET_NAME_MAX_LENGTH;
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "juxtapose-preset",
+        "Create a juxtapose using labels and layout saved by this server.",
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "preset",
+            "The name of the preset.",
+        )
+        .max_length(JUXTAPOSE_PRESET_NAME_MAX_LENGTH as u16)
+        .set_autocomplete(true)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::Attachment,
+            "left_image",
+            "The image on the left side (or top).",
+        )
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::Attachment,
+            "right_image",
+            "The image on the right side (or bottom).",
+        )
+        .required(true),
+    )
+}
@@ -0,0 +1,98 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serenity::all::{
+    ChannelId, ChannelType, GuildChannel, GuildId, Member, MessageId, Permissions,
+};
+use serenity::prelude::*;
+
+static MESSAGE_LINK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^https://(?:(?:ptb|canary)\.)?discord(?:app)?\.com/channels/(\d+)/(\d+)/(\d+)$")
+        .unwrap()
+});
+
+/// Checks that the member can read the messages of the channel, using the parent channel for threads,
+/// which inherit its permissions. Private threads additionally require the Manage Threads permission,
+/// as membership of the thread isn't known.
+fn can_read_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel: &GuildChannel,
+    member: &Member,
+) -> bool {
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return false;
+    };
+
+    let permission_channel = match channel.thread_metadata {
+        Some(_) => match channel
+            .parent_id
+            .and_then(|parent_id| guild.channels.get(&parent_id))
+        {
+            Some(parent_channel) => parent_channel,
+            None => return false,
+        },
+        None => channel,
+    };
+
+    let mut required_permissions = Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY;
+
+    if channel.kind == ChannelType::PrivateThread {
+        required_permissions |= Permissions::MANAGE_THREADS;
+    }
+
+    guild
+        .user_permissions_in(permission_channel, member)
+        .contains(required_permissions)
+}
+
+/// Resolves a user-provided link to a juxtapose message of this bot within the given guild,
+/// in a channel whose messages the member can read.
+pub(crate) async fn resolve_juxtapose_message_link(
+    ctx: &Context,
+    guild_id: GuildId,
+    member: &Member,
+    message_link: &str,
+) -> Result<(ChannelId, MessageId), String> {
+    let message_link_captures = MESSAGE_LINK_REGEX
+        .captures(message_link.trim())
+        .ok_or("The message link is malformed.")?;
+
+    let channel_id = message_link_captures[2]
+        .parse::<u64>()
+        .map(ChannelId::new)
+        .map_err(|_| "The message link is malformed.")?;
+
+    let message_id = message_link_captures[3]
+        .parse::<u64>()
+        .map(MessageId::new)
+        .map_err(|_| "The message link is malformed.")?;
+
+    // The link itself is user-provided, so verify that the channel actually belongs to this guild.
+    let channel = ctx
+        .http
+        .get_channel(channel_id)
+        .await
+        .ok()
+        .and_then(|channel| channel.guild())
+        .filter(|channel| channel.guild_id == guild_id)
+        .ok_or("The message must be in this server.")?;
+
+    // The juxtapose URL grants access to the images, so it is only handed out to members who can see them.
+    if !can_read_channel(ctx, guild_id, &channel, member) {
+        return Err("You can't read the messages of that channel.".to_owned());
+    }
+
+    let juxtapose_message = ctx
+        .http
+        .get_message(channel_id, message_id)
+        .await
+        .map_err(|_| "The message could not be found.")?;
+
+    if juxtapose_message.author.id != ctx.cache.current_user().id
+        || juxtapose_message.attachments.len() < 3
+    {
+        return Err("The message is not a juxtapose.".to_owned());
+    }
+
+    Ok((channel_id, message_id))
+}
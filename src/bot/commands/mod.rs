@@ -1 +1,58 @@
+use serenity::all::CreateCommand;
+
+pub(crate) mod admin;
+pub(crate) mod album;
+pub(crate) mod backfill;
+pub(crate) mod browse;
+pub(crate) mod color;
+pub(crate) mod config;
+pub(crate) mod forgetme;
+pub(crate) mod ghdiff;
+pub(crate) mod ghsearch;
+pub(crate) mod hash;
+pub(crate) mod help;
+pub(crate) mod imagetool;
 pub(crate) mod juxtapose;
+pub(crate) mod juxtapose_from_message;
+pub(crate) mod juxtapose_images;
+pub(crate) mod juxtapose_preset;
+pub(crate) mod message_link;
+pub(crate) mod options;
+pub(crate) mod palette;
+pub(crate) mod permalink;
+pub(crate) mod preview_links;
+pub(crate) mod qr;
+pub(crate) mod rate_limit;
+pub(crate) mod remove_preview;
+pub(crate) mod spritesheet;
+pub(crate) mod stats;
+pub(crate) mod watchdog;
+
+/// All commands of the bot, as registered with Discord.
+pub(crate) fn register_all() -> Vec<CreateCommand<'static>> {
+    vec![
+        juxtapose::register(),
+        juxtapose_from_message::register(),
+        juxtapose_images::register(),
+        juxtapose_preset::register(),
+        config::register(),
+        remove_preview::register(),
+        preview_links::register(),
+        stats::register(),
+        admin::register(),
+        forgetme::register(),
+        permalink::register(),
+        ghsearch::register(),
+        browse::register(),
+        ghdiff::register(),
+        backfill::register(),
+        hash::register(),
+        qr::register(),
+        color::register(),
+        palette::register(),
+        imagetool::register(),
+        spritesheet::register(),
+        album::register(),
+        help::register(),
+    ]
+}
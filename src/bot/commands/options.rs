@@ -0,0 +1,62 @@
+use serenity::all::{Attachment, PartialChannel, ResolvedOption, ResolvedValue};
+
+pub(crate) fn get_string_option<'a>(options: &[ResolvedOption<'a>], name: &str) -> Option<&'a str> {
+    options.iter().find_map(|option| match option {
+        ResolvedOption {
+            name: option_name,
+            value: ResolvedValue::String(string),
+            ..
+        } if *option_name == name => Some(*string),
+        _ => None,
+    })
+}
+
+pub(crate) fn get_channel_option<'a>(
+    options: &[ResolvedOption<'a>],
+    name: &str,
+) -> Option<&'a PartialChannel> {
+    options.iter().find_map(|option| match option {
+        ResolvedOption {
+            name: option_name,
+            value: ResolvedValue::Channel(channel),
+            ..
+        } if *option_name == name => Some(*channel),
+        _ => None,
+    })
+}
+
+pub(crate) fn get_boolean_option(options: &[ResolvedOption<'_>], name: &str) -> Option<bool> {
+    options.iter().find_map(|option| match option {
+        ResolvedOption {
+            name: option_name,
+            value: ResolvedValue::Boolean(boolean),
+            ..
+        } if *option_name == name => Some(*boolean),
+        _ => None,
+    })
+}
+
+pub(crate) fn get_integer_option(options: &[ResolvedOption<'_>], name: &str) -> Option<i64> {
+    options.iter().find_map(|option| match option {
+        ResolvedOption {
+            name: option_name,
+            value: ResolvedValue::Integer(integer),
+            ..
+        } if *option_name == name => Some(*integer),
+        _ => None,
+    })
+}
+
+pub(crate) fn get_attachment_option<'a>(
+    options: &[ResolvedOption<'a>],
+    name: &str,
+) -> Option<&'a Attachment> {
+    options.iter().find_map(|option| match option {
+        ResolvedOption {
+            name: option_name,
+            value: ResolvedValue::Attachment(attachment),
+            ..
+        } if *option_name == name => Some(*attachment),
+        _ => None,
+    })
+}
@@ -0,0 +1,103 @@
+use image::{DynamicImage, GenericImage, RgbaImage};
+use serenity::all::{
+    CommandInteraction, CreateAttachment, EditAttachments, EditInteractionResponse,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::color::parse::RgbColor;
+use crate::bot::commands::color::render_swatch;
+use crate::bot::commands::juxtapose::encode::encode_preview_image;
+use crate::bot::commands::juxtapose::get_upload_limit;
+use crate::bot::commands::options::get_string_option;
+
+mod structure;
+pub(crate) use structure::register;
+
+pub(crate) const PALETTE_MAX_COLORS: usize = 8;
+
+const STRIPE_WIDTH: u32 = 160;
+const STRIPE_HEIGHT: u32 = 200;
+
+/// Places a labeled swatch for each color side by side.
+fn render_palette(colors: &[RgbColor]) -> Result<DynamicImage, String> {
+    let mut palette = DynamicImage::ImageRgba8(RgbaImage::new(
+        STRIPE_WIDTH * colors.len() as u32,
+        STRIPE_HEIGHT,
+    ));
+
+    for (index, color) in colors.iter().enumerate() {
+        let stripe = render_swatch(
+            *color,
+            STRIPE_WIDTH,
+            STRIPE_HEIGHT,
+            Some(color.to_hex().as_str()),
+        );
+
+        palette
+            .copy_from(&stripe, STRIPE_WIDTH * index as u32, 0)
+            .map_err(|_| "Failed to render the palette.")?;
+    }
+
+    Ok(palette)
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let colors = (1..=PALETTE_MAX_COLORS)
+        .filter_map(|index| get_string_option(&options, format!("color_{}", index).as_str()))
+        .map(|value| {
+            RgbColor::parse(value).ok_or_else(|| {
+                format!(
+                    "The color {} is invalid. Use hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+                    value
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if colors.is_empty() {
+        return Err("No colors specified.".to_owned());
+    }
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer palette interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Render Palette */
+
+    let palette = render_palette(&colors)?;
+    let upload_limit = get_upload_limit(ctx, interaction.guild_id).await;
+    let palette_encoded = encode_preview_image(&palette, upload_limit)?;
+
+    let palette_description = colors
+        .iter()
+        .map(|color| color.to_hex())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    /* Reply */
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .content(format!("`{}`", palette_description))
+                .attachments(
+                    EditAttachments::new().add(
+                        CreateAttachment::bytes(
+                            palette_encoded.data,
+                            format!("palette.{}", palette_encoded.extension),
+                        )
+                        .description(palette_description),
+                    ),
+                ),
+        )
+        .await
+        .map_err(|_| "Failed to send the palette.")?;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+use super::PALETTE_MAX_COLORS;
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    let mut command = localized_command("palette", "Show a labeled strip of up to 8 colors.");
+
+    for index in 1..=PALETTE_MAX_COLORS {
+        command = command.add_option(
+            localized_option(
+                CommandOptionType::String,
+                format!("color_{}", index),
+                "A color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+            )
+            .max_length(100)
+            .required(index <= 2),
+        );
+    }
+
+    command
+}
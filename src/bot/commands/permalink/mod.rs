@@ -0,0 +1,116 @@
+use reqwest::Url;
+use serenity::all::{CommandInteraction, EditInteractionResponse};
+use serenity::prelude::*;
+
+use crate::bot::commands::options::get_string_option;
+use crate::bot::file_preview::backpressure::SendPriority;
+use crate::bot::file_preview::{send_file_preview_for_url, PreviewTarget};
+use crate::bot::github_api::{parse_repository, resolve_commit_sha};
+
+mod structure;
+pub(crate) use structure::register;
+
+/// Parses either a single line number or a range in the format `a-b`, returning the URL fragment.
+pub(crate) fn get_line_fragment(lines: &str) -> Result<String, String> {
+    let parse_line_number = |line_number: &str| {
+        line_number
+            .trim()
+            .trim_start_matches('L')
+            .parse::<u32>()
+            .ok()
+            .filter(|line_number| *line_number > 0)
+            .ok_or_else(|| "The lines must be a line number or a range like 10-20.".to_owned())
+    };
+
+    match lines.split_once('-') {
+        Some((top_line_number, bottom_line_number)) => {
+            let top_line_number = parse_line_number(top_line_number)?;
+            let bottom_line_number = parse_line_number(bottom_line_number)?;
+
+            Ok(format!(
+                "L{}-L{}",
+                top_line_number.min(bottom_line_number),
+                top_line_number.max(bottom_line_number)
+            ))
+        }
+        None => Ok(format!("L{}", parse_line_number(lines)?)),
+    }
+}
+
+/// Builds a link to the lines of a file at a specific commit.
+pub(crate) fn build_permalink(
+    owner: &str,
+    repository: &str,
+    commit_sha: &str,
+    path: &str,
+    line_fragment: &str,
+) -> Url {
+    let mut permalink = Url::parse("https://github.com/").unwrap();
+
+    permalink
+        .path_segments_mut()
+        .unwrap()
+        .extend(&[owner, repository, "blob", commit_sha])
+        .extend(path.trim_matches('/').split('/'));
+
+    permalink.set_fragment(Some(line_fragment));
+
+    permalink
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let repository = get_string_option(&options, "repo").unwrap_or_default();
+    let path = get_string_option(&options, "path").unwrap_or_default();
+    let lines = get_string_option(&options, "lines").unwrap_or_default();
+    let reference = get_string_option(&options, "ref").unwrap_or("HEAD");
+
+    let (owner, repository) = parse_repository(repository)?;
+    let line_fragment = get_line_fragment(lines)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer permalink interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Build Permalink */
+
+    let commit_sha = resolve_commit_sha(owner, repository, reference.trim()).await?;
+
+    let permalink = build_permalink(
+        owner,
+        repository,
+        commit_sha.as_str(),
+        path,
+        line_fragment.as_str(),
+    );
+
+    /* Reply */
+
+    let reply = interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().content(permalink.as_str()),
+        )
+        .await
+        .map_err(|_| "Failed to send the permalink.")?;
+
+    send_file_preview_for_url(
+        ctx,
+        &PreviewTarget {
+            author_id: interaction.user.id,
+            channel_id: interaction.channel_id,
+            guild_id: interaction.guild_id,
+            reference_message: &reply,
+            priority: SendPriority::Urgent,
+        },
+        permalink.as_str(),
+    )
+    .await
+    .map_err(|error| format!("Failed to preview the file: {}", error))?;
+
+    Ok(())
+}
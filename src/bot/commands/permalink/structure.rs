@@ -0,0 +1,46 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "permalink",
+        "Create a permanent link to lines of a file on GitHub and preview them.",
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "repo",
+            "The repository in the format owner/repo.",
+        )
+        .max_length(140)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "path",
+            "The path of the file within the repository.",
+        )
+        .max_length(1000)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "lines",
+            "A single line or a range of lines, e.g. 10-20.",
+        )
+        .max_length(20)
+        .required(true),
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::String,
+            "ref",
+            "A branch, tag or commit. Defaults to the default branch.",
+        )
+        .max_length(250)
+        .required(false),
+    )
+}
@@ -0,0 +1,55 @@
+use serenity::all::{CommandInteraction, EditInteractionResponse, ResolvedTarget};
+use serenity::prelude::*;
+
+use crate::bot::file_preview::backpressure::SendPriority;
+use crate::bot::file_preview::{send_message_file_previews, PreviewTarget};
+
+mod structure;
+pub(crate) use structure::{register, COMMAND_NAME};
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let Some(ResolvedTarget::Message(target_message)) = interaction.data.target() else {
+        return Err("The command must be used on a message.".to_owned());
+    };
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer_ephemeral(&ctx.http).await {
+        println!("Failed to defer preview links interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Preview Links */
+
+    // The previews reply to the target message, but belong to the user who requested them.
+    let file_preview_count = send_message_file_previews(
+        ctx,
+        &PreviewTarget {
+            author_id: interaction.user.id,
+            channel_id: interaction.channel_id,
+            guild_id: interaction.guild_id,
+            reference_message: target_message,
+            priority: SendPriority::Urgent,
+        },
+        target_message,
+    )
+    .await
+    .map_err(|error| format!("Failed to preview the links: {}", error))?;
+
+    if file_preview_count == 0 {
+        return Err("The message doesn't contain any links that can be previewed.".to_owned());
+    }
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().content(match file_preview_count {
+                1 => "Posted 1 preview.".to_owned(),
+                _ => format!("Posted {} previews.", file_preview_count),
+            }),
+        )
+        .await
+        .map_err(|_| "Failed to confirm the previews.")?;
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use once_cell::sync::Lazy;
+use qrcode::{Color, EcLevel, QrCode};
+use serenity::all::{
+    CommandInteraction, CreateAttachment, EditAttachments, EditInteractionResponse,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::encode::encode_preview_image;
+use crate::bot::commands::juxtapose::{
+    decode_image, download_image_from_attachment, get_upload_limit,
+};
+use crate::bot::commands::options::{get_attachment_option, get_integer_option, get_string_option};
+use crate::bot::commands::rate_limit::{format_cooldown_error, UserCooldown};
+
+mod structure;
+pub(crate) use structure::register;
+
+static QR_COOLDOWN: Lazy<UserCooldown> = Lazy::new(|| UserCooldown::new(Duration::from_secs(5)));
+
+const QR_SIZE_DEFAULT: u32 = 512;
+
+/// Number of light modules around the code, as required by the specification.
+const QUIET_ZONE_MODULES: u32 = 4;
+
+/// Fraction of the code width covered by the logo, which high error correction can still recover from.
+const LOGO_SCALE: f32 = 0.2;
+
+const LOGO_MAX_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+const LIGHT_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const DARK_COLOR: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+fn parse_error_correction_level(level: &str) -> Option<EcLevel> {
+    match level {
+        "low" => Some(EcLevel::L),
+        "medium" => Some(EcLevel::M),
+        "quartile" => Some(EcLevel::Q),
+        "high" => Some(EcLevel::H),
+        _ => None,
+    }
+}
+
+/// Draws the modules of the code with a quiet zone, scaled to integer multiples to keep the edges sharp.
+fn render_qr_code(code: &QrCode, size: u32) -> RgbaImage {
+    let code_width = code.width() as u32;
+    let total_modules = code_width + 2 * QUIET_ZONE_MODULES;
+    let module_size = (size / total_modules).max(1);
+    let colors = code.to_colors();
+
+    ImageBuffer::from_fn(
+        total_modules * module_size,
+        total_modules * module_size,
+        |x, y| {
+            let module_x = (x / module_size).checked_sub(QUIET_ZONE_MODULES);
+            let module_y = (y / module_size).checked_sub(QUIET_ZONE_MODULES);
+
+            match (module_x, module_y) {
+                (Some(module_x), Some(module_y))
+                    if module_x < code_width
+                        && module_y < code_width
+                        && colors[(module_y * code_width + module_x) as usize] == Color::Dark =>
+                {
+                    DARK_COLOR
+                }
+                _ => LIGHT_COLOR,
+            }
+        },
+    )
+}
+
+/// Places the logo on a light background in the center of the code.
+fn overlay_logo(qr_image: &mut RgbaImage, logo: DynamicImage) {
+    let logo_max_size = ((qr_image.width() as f32 * LOGO_SCALE) as u32).max(1);
+    let logo = logo
+        .resize(logo_max_size, logo_max_size, FilterType::Lanczos3)
+        .to_rgba8();
+
+    let padding = (logo_max_size / 10).max(1);
+    let background = ImageBuffer::from_pixel(
+        logo.width() + 2 * padding,
+        logo.height() + 2 * padding,
+        LIGHT_COLOR,
+    );
+
+    let background_x = (qr_image.width() - background.width()) / 2;
+    let background_y = (qr_image.height() - background.height()) / 2;
+
+    image::imageops::overlay(
+        qr_image,
+        &background,
+        background_x as i64,
+        background_y as i64,
+    );
+    image::imageops::overlay(
+        qr_image,
+        &logo,
+        (background_x + padding) as i64,
+        (background_y + padding) as i64,
+    );
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let text = get_string_option(&options, "text").ok_or("No text specified.")?;
+    let size = get_integer_option(&options, "size")
+        .and_then(|size| u32::try_from(size).ok())
+        .unwrap_or(QR_SIZE_DEFAULT);
+    let logo_attachment = get_attachment_option(&options, "logo");
+
+    let error_correction_level = match get_string_option(&options, "error_correction") {
+        Some(level) => {
+            parse_error_correction_level(level).ok_or("Invalid error correction level.")?
+        }
+        None if logo_attachment.is_some() => EcLevel::H,
+        None => EcLevel::M,
+    };
+
+    if logo_attachment.is_some() && error_correction_level < EcLevel::Q {
+        return Err(
+            "A logo requires at least quartile error correction to keep the code readable."
+                .to_owned(),
+        );
+    }
+
+    let code = QrCode::with_error_correction_level(text.as_bytes(), error_correction_level)
+        .map_err(|_| "The text is too long to be encoded with this error correction level.")?;
+
+    /* Check Cooldown */
+
+    QR_COOLDOWN
+        .check(interaction.user.id)
+        .map_err(format_cooldown_error)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer qr interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Render Image */
+
+    let mut qr_image = render_qr_code(&code, size);
+
+    if let Some(logo_attachment) = logo_attachment {
+        if logo_attachment.size as u64 > LOGO_MAX_FILE_SIZE {
+            return Err("The logo must not be bigger than 16 MB.".to_owned());
+        }
+
+        let logo_width = logo_attachment
+            .width
+            .ok_or("The logo is not a supported image.")?;
+        let logo_height = logo_attachment
+            .height
+            .ok_or("The logo is not a supported image.")?;
+
+        // Let the CDN downscale the logo, as it only covers a fraction of the code.
+        let logo_max_size = ((qr_image.width() as f32 * LOGO_SCALE) as u32).max(1);
+        let logo_scale =
+            (logo_max_size as f32 / logo_width.get().max(logo_height.get()) as f32).min(1.0);
+
+        let (logo_bytes, logo_format) = download_image_from_attachment(
+            logo_attachment,
+            ((logo_width.get() as f32 * logo_scale) as u32).max(1),
+            ((logo_height.get() as f32 * logo_scale) as u32).max(1),
+        )
+        .await?;
+
+        overlay_logo(&mut qr_image, decode_image(&logo_bytes, logo_format)?);
+    }
+
+    let upload_limit = get_upload_limit(ctx, interaction.guild_id).await;
+    let qr_image_encoded = encode_preview_image(&DynamicImage::ImageRgba8(qr_image), upload_limit)?;
+
+    /* Reply */
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().attachments(
+                EditAttachments::new().add(
+                    CreateAttachment::bytes(
+                        qr_image_encoded.data,
+                        format!("qr.{}", qr_image_encoded.extension),
+                    )
+                    .description(text.to_owned()),
+                ),
+            ),
+        )
+        .await
+        .map_err(|_| "Failed to upload the QR code to Discord.")?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option, LocalizedChoices};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("qr", "Generate a QR code.")
+        .add_option(
+            localized_option(
+                CommandOptionType::String,
+                "text",
+                "The text or URL to encode.",
+            )
+            .max_length(1000)
+            .required(true),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "size",
+                "The approximate width and height of the image in pixels.",
+            )
+            .min_int_value(128)
+            .max_int_value(2048)
+            .required(false),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::String,
+                "error_correction",
+                "How much of the code can be damaged or covered while remaining readable.",
+            )
+            .add_localized_string_choice("Low (7%)", "low")
+            .add_localized_string_choice("Medium (15%)", "medium")
+            .add_localized_string_choice("Quartile (25%)", "quartile")
+            .add_localized_string_choice("High (30%)", "high")
+            .required(false),
+        )
+        .add_option(
+            localized_option(
+                CommandOptionType::Attachment,
+                "logo",
+                "An image to place in the center of the code.",
+            )
+            .required(false),
+        )
+}
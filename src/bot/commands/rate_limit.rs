@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use serenity::all::{EditInteractionResponse, UserId};
+use serenity::prelude::*;
+use tokio::sync::{watch, Semaphore, SemaphorePermit};
+
+/// Describes why an action was denied, phrased for the user who triggered it.
+/// Returned as the error of a command before deferring, which the watchdog sends as an ephemeral response.
+pub(crate) fn format_cooldown_error(remaining: Duration) -> String {
+    let available_unix_ts = (SystemTime::now() + remaining)
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        + 1;
+
+    format!(
+        "You're using this command too often. You can use it again <t:{}:R>.",
+        available_unix_ts
+    )
+}
+
+/// Limits how often each user can perform an action.
+pub(crate) struct UserCooldown {
+    duration: Duration,
+    last_used: Mutex<HashMap<UserId, Instant>>,
+}
+
+impl UserCooldown {
+    pub(crate) fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            last_used: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Starts the cooldown if the user isn't on cooldown, otherwise returns the remaining time.
+    pub(crate) fn check(&self, user_id: UserId) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut last_used = self.last_used.lock().unwrap();
+
+        last_used.retain(|_, used_at| now.duration_since(*used_at) < self.duration);
+
+        match last_used.get(&user_id) {
+            Some(used_at) => Err(self.duration - now.duration_since(*used_at)),
+            None => {
+                last_used.insert(user_id, now);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Marks a job as no longer waiting when dropped, including when the waiting command is cancelled.
+struct QueueTicket<'a> {
+    started_count: &'a watch::Sender<usize>,
+}
+
+impl Drop for QueueTicket<'_> {
+    fn drop(&mut self) {
+        self.started_count
+            .send_modify(|started_count| *started_count += 1);
+    }
+}
+
+/// First-in, first-out queue that limits how many jobs run concurrently.
+pub(crate) struct JobQueue {
+    semaphore: Semaphore,
+    next_ticket: AtomicUsize,
+    started_count: watch::Sender<usize>,
+}
+
+impl JobQueue {
+    pub(crate) fn new(concurrency: usize) -> Self {
+        Self {
+            semaphore: Semaphore::new(concurrency),
+            next_ticket: AtomicUsize::new(0),
+            started_count: watch::channel(0).0,
+        }
+    }
+
+    /// Waits for a free slot, editing the deferred response of the interaction whenever the queue position changes.
+    /// Takes the token of the interaction, so that both commands and components can wait in the queue.
+    pub(crate) async fn enter(
+        &self,
+        ctx: &Context,
+        interaction_token: &str,
+    ) -> SemaphorePermit<'_> {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::SeqCst);
+        let mut started_count = self.started_count.subscribe();
+
+        let queue_ticket = QueueTicket {
+            started_count: &self.started_count,
+        };
+
+        let acquire = self.semaphore.acquire();
+        tokio::pin!(acquire);
+
+        let mut shown_position = None;
+
+        let permit = loop {
+            let position = ticket.saturating_sub(*started_count.borrow_and_update()) + 1;
+
+            tokio::select! {
+                biased;
+                permit = &mut acquire => break permit.expect("Job queue semaphore was closed."),
+                // Only show the position once the job actually has to wait, and whenever it changes.
+                _ = std::future::ready(()), if shown_position != Some(position) => {
+                    shown_position = Some(position);
+
+                    EditInteractionResponse::new()
+                        .content(format!("⏳ Waiting in queue, position {}...", position))
+                        .execute(&ctx.http, interaction_token)
+                        .await
+                        .ok();
+                }
+                _ = started_count.changed() => {}
+            }
+        };
+
+        drop(queue_ticket);
+
+        if shown_position.is_some() {
+            EditInteractionResponse::new()
+                .content("")
+                .execute(&ctx.http, interaction_token)
+                .await
+                .ok();
+        }
+
+        permit
+    }
+}
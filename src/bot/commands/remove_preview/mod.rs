@@ -0,0 +1,92 @@
+use serenity::all::{CommandInteraction, EditInteractionResponse, Permissions, ResolvedTarget};
+use serenity::prelude::*;
+
+use crate::bot::audit_log::{send_audit_log, AuditLogEntry};
+use crate::bot::file_preview::is_file_preview_message;
+use crate::bot::guild_config::GuildConfig;
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::{register, COMMAND_NAME};
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let Some(ResolvedTarget::Message(target_message)) = interaction.data.target() else {
+        return Err("The command must be used on a message.".to_owned());
+    };
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer_ephemeral(&ctx.http).await {
+        println!("Failed to defer remove preview interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Check Permissions */
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or("This command can only be used in servers.")?;
+
+    let has_permission = interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_MESSAGES));
+
+    if !has_permission {
+        return Err("You need the Manage Messages permission to remove previews.".to_owned());
+    }
+
+    if target_message.author.id != ctx.cache.current_user().id
+        || !is_file_preview_message(target_message)
+    {
+        return Err("The message is not a preview of this bot.".to_owned());
+    }
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let guild_config = GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+        .await
+        .unwrap_or_default();
+
+    // Entries of the audit log document moderation, including the removal of previews.
+    if guild_config.audit_channel_id == Some(target_message.channel_id) {
+        return Err("Messages in the audit log channel can't be removed.".to_owned());
+    }
+
+    /* Delete Preview */
+
+    ctx.http
+        .delete_message(
+            target_message.channel_id,
+            target_message.id,
+            Some(format!("Preview removed by {}", interaction.user.id).as_str()),
+        )
+        .await
+        .map_err(|_| "Failed to remove the preview.")?;
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().content("The preview has been removed."),
+        )
+        .await
+        .map_err(|_| "Failed to confirm the removal of the preview.")?;
+
+    send_audit_log(
+        &ctx.http,
+        Some(guild_id),
+        &guild_config,
+        AuditLogEntry::FilePreviewDeleted {
+            deleted_by_id: interaction.user.id,
+            channel_id: target_message.channel_id,
+            is_moderation: true,
+        },
+    )
+    .await;
+
+    Ok(())
+}
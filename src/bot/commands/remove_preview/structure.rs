@@ -0,0 +1,11 @@
+use serenity::all::{CommandType, CreateCommand, Permissions};
+
+use crate::bot::i18n::localized_message_command;
+
+pub(crate) const COMMAND_NAME: &str = "Remove Bot Preview";
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_message_command(COMMAND_NAME)
+        .kind(CommandType::Message)
+        .default_member_permissions(Permissions::MANAGE_MESSAGES)
+}
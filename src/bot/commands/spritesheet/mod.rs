@@ -0,0 +1,220 @@
+use std::io::{Cursor, Write};
+use std::time::Duration;
+
+use image::{DynamicImage, ImageFormat};
+use once_cell::sync::Lazy;
+use serenity::all::{
+    CommandInteraction, CreateAttachment, EditAttachments, EditInteractionResponse, ResolvedOption,
+    ResolvedValue,
+};
+use serenity::prelude::*;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::bot::commands::juxtapose::encode::{encode_image_as, EncodedImage};
+use crate::bot::commands::juxtapose::{
+    decode_image, download_image_from_attachment, get_upload_limit, IMAGE_LIMITS, RENDER_QUEUE,
+};
+use crate::bot::commands::options::{get_attachment_option, get_integer_option};
+use crate::bot::commands::rate_limit::{format_cooldown_error, UserCooldown};
+
+mod structure;
+pub(crate) use structure::register;
+
+static SPRITESHEET_COOLDOWN: Lazy<UserCooldown> =
+    Lazy::new(|| UserCooldown::new(Duration::from_secs(10)));
+
+const IMAGE_MAX_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Maximum number of columns and rows.
+pub(crate) const GRID_MAX_SIZE: u32 = 64;
+
+/// Discord allows this many attachments per message. More tiles are sent as a single archive.
+const ATTACHMENTS_MAX_COUNT: usize = 10;
+
+struct Tile {
+    file_name: String,
+    encoded: EncodedImage,
+}
+
+fn split_image(
+    image: &DynamicImage,
+    file_stem: &str,
+    columns: u32,
+    rows: u32,
+) -> Result<Vec<Tile>, String> {
+    if image.width() % columns != 0 || image.height() % rows != 0 {
+        return Err(format!(
+            "The image is {}x{} pixels, which can't be split into {} columns and {} rows of equal size.",
+            image.width(),
+            image.height(),
+            columns,
+            rows
+        ));
+    }
+
+    let tile_width = image.width() / columns;
+    let tile_height = image.height() / rows;
+
+    (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (row, column)))
+        .map(|(row, column)| {
+            let tile = image.crop_imm(
+                column * tile_width,
+                row * tile_height,
+                tile_width,
+                tile_height,
+            );
+
+            Ok(Tile {
+                file_name: format!("{}_{}_{}.png", file_stem, row + 1, column + 1),
+                encoded: encode_image_as(&tile, ImageFormat::Png)?,
+            })
+        })
+        .collect()
+}
+
+/// Bundles the tiles into an uncompressed archive, as the PNG data is already compressed.
+fn create_tile_archive(tiles: Vec<Tile>) -> Result<Vec<u8>, String> {
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    let file_options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    for tile in tiles {
+        archive
+            .start_file(tile.file_name, file_options)
+            .and_then(|_| archive.write_all(&tile.encoded.data).map_err(Into::into))
+            .map_err(|_| "Failed to create the archive.")?;
+    }
+
+    archive
+        .finish()
+        .map(Cursor::into_inner)
+        .map_err(|_| "Failed to create the archive.".to_owned())
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    let options = interaction.data.options();
+
+    let Some(ResolvedOption {
+        name: "split",
+        value: ResolvedValue::SubCommand(options),
+        ..
+    }) = options.first()
+    else {
+        return Err("Unknown subcommand.".to_owned());
+    };
+
+    let attachment = get_attachment_option(options, "image").ok_or("No image attached.")?;
+    let columns = get_integer_option(options, "columns")
+        .and_then(|columns| u32::try_from(columns).ok())
+        .filter(|columns| (1..=GRID_MAX_SIZE).contains(columns))
+        .ok_or("Invalid number of columns.")?;
+    let rows = get_integer_option(options, "rows")
+        .and_then(|rows| u32::try_from(rows).ok())
+        .filter(|rows| (1..=GRID_MAX_SIZE).contains(rows))
+        .ok_or("Invalid number of rows.")?;
+
+    /* Check Cooldown */
+
+    SPRITESHEET_COOLDOWN
+        .check(interaction.user.id)
+        .map_err(format_cooldown_error)?;
+
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer(&ctx.http).await {
+        println!("Failed to defer spritesheet interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Limit Image Size and Dimensions */
+
+    if attachment.size as u64 > IMAGE_MAX_FILE_SIZE {
+        return Err("The image must not be bigger than 16 MB.".to_owned());
+    }
+
+    let image_width = attachment
+        .width
+        .ok_or("The attachment is not a supported image.")?
+        .get();
+    let image_height = attachment
+        .height
+        .ok_or("The attachment is not a supported image.")?
+        .get();
+
+    let max_image_width = IMAGE_LIMITS.max_image_width.unwrap_or(u32::MAX);
+    let max_image_height = IMAGE_LIMITS.max_image_height.unwrap_or(u32::MAX);
+
+    if image_width > max_image_width || image_height > max_image_height {
+        return Err(format!(
+            "The image must not be larger than {}x{} pixels.",
+            max_image_width, max_image_height
+        ));
+    }
+
+    /* Download Image */
+
+    let (image_bytes, image_format) =
+        download_image_from_attachment(attachment, image_width, image_height).await?;
+
+    /* Split Image */
+
+    let _render_permit = RENDER_QUEUE.enter(ctx, &interaction.token).await;
+
+    let file_stem = attachment
+        .filename
+        .rsplit_once('.')
+        .map_or(&*attachment.filename, |(file_stem, _)| file_stem);
+
+    let tiles = split_image(
+        &decode_image(&image_bytes, image_format)?,
+        file_stem,
+        columns,
+        rows,
+    )?;
+    drop(image_bytes);
+
+    let upload_limit = get_upload_limit(ctx, interaction.guild_id).await;
+
+    let edit_attachments = if tiles.len() <= ATTACHMENTS_MAX_COUNT {
+        if tiles
+            .iter()
+            .map(|tile| tile.encoded.data.len())
+            .sum::<usize>()
+            > upload_limit
+        {
+            return Err("The tiles are too large to be uploaded in this server.".to_owned());
+        }
+
+        tiles
+            .into_iter()
+            .fold(EditAttachments::new(), |edit_attachments, tile| {
+                edit_attachments.add(CreateAttachment::bytes(tile.encoded.data, tile.file_name))
+            })
+    } else {
+        let archive = create_tile_archive(tiles)?;
+
+        if archive.len() > upload_limit {
+            return Err("The tiles are too large to be uploaded in this server.".to_owned());
+        }
+
+        EditAttachments::new().add(CreateAttachment::bytes(
+            archive,
+            format!("{}_tiles.zip", file_stem),
+        ))
+    };
+
+    /* Reply */
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .content(format!("Split into {} columns and {} rows.", columns, rows))
+                .attachments(edit_attachments),
+        )
+        .await
+        .map_err(|_| "Failed to upload the tiles to Discord.")?;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+use super::GRID_MAX_SIZE;
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command(
+        "spritesheet",
+        "Work with sprite sheets and texture atlases.",
+    )
+    .add_option(
+        localized_option(
+            CommandOptionType::SubCommand,
+            "split",
+            "Split an image into a grid of equally sized tiles.",
+        )
+        .add_sub_option(
+            localized_option(CommandOptionType::Attachment, "image", "The sprite sheet.")
+                .required(true),
+        )
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "columns",
+                "The number of tiles per row.",
+            )
+            .min_int_value(1)
+            .max_int_value(GRID_MAX_SIZE.into())
+            .required(true),
+        )
+        .add_sub_option(
+            localized_option(
+                CommandOptionType::Integer,
+                "rows",
+                "The number of tiles per column.",
+            )
+            .min_int_value(1)
+            .max_int_value(GRID_MAX_SIZE.into())
+            .required(true),
+        ),
+    )
+}
@@ -0,0 +1,199 @@
+use reqwest::Url;
+use serenity::all::{
+    Colour, CommandInteraction, CreateEmbed, EditInteractionResponse, MessageBuilder,
+    ResolvedOption, ResolvedValue,
+};
+use serenity::prelude::*;
+
+use crate::bot::commands::juxtapose::create_juxtapose_url;
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::commands::options::get_string_option;
+use crate::bot::guild_config::PreviewSource;
+use crate::bot::guild_stats::{get_last_days, redis_get_preview_stats, PREVIEW_STATS_DAYS};
+use crate::web::view_counter::redis_get_view_count;
+use crate::SerenityGlobalData;
+
+mod structure;
+pub(crate) use structure::register;
+
+/// Number of repositories and files listed by `/stats previews`.
+const PREVIEW_LEADERBOARD_SIZE: usize = 10;
+
+/// Embed descriptions are limited to 4096 characters, keep some headroom for the headings.
+const PREVIEW_LEADERBOARD_MAX_LENGTH: usize = 3900;
+
+fn get_preview_source_label(source: &str) -> &str {
+    match PreviewSource::parse(source) {
+        Some(PreviewSource::GitHubRepositoryFile) => "GitHub Repository Files",
+        Some(PreviewSource::Gist) => "GitHub Gists",
+        None => source,
+    }
+}
+
+fn truncate_chars(string: &str, max_length: usize) -> String {
+    if string.chars().count() > max_length {
+        let truncated_string: String = string.chars().take(max_length - 3).collect();
+        format!("{}...", truncated_string)
+    } else {
+        string.to_owned()
+    }
+}
+
+/// Links to a repository given as `owner/repository`, or to a file given as `owner/repository/path`
+/// on the default branch.
+fn get_leaderboard_url(member: &str) -> Option<Url> {
+    let mut segments = member.splitn(3, '/');
+    let (owner, repository) = (segments.next()?, segments.next()?);
+
+    let mut url = Url::parse("https://github.com/").ok()?;
+
+    {
+        let mut path_segments = url.path_segments_mut().ok()?;
+        path_segments.pop_if_empty().extend([owner, repository]);
+
+        if let Some(path) = segments.next() {
+            path_segments
+                .extend(["blob", "HEAD"])
+                .extend(path.split('/'));
+        }
+    }
+
+    Some(url)
+}
+
+/// Appends a numbered list of repositories or files with their preview counts, linking to them on GitHub.
+pub(crate) fn push_leaderboard(description: &mut String, heading: &str, counts: &[(String, u64)]) {
+    description.push_str(format!("**{}**\n", heading).as_str());
+
+    if counts.is_empty() {
+        description.push_str("No files of repositories have been previewed yet.\n");
+    }
+
+    for (rank, (member, count)) in counts.iter().enumerate() {
+        let mut line = MessageBuilder::new();
+        line.push(format!("{}. ", rank + 1));
+
+        match get_leaderboard_url(member) {
+            Some(url) => line
+                .push("[")
+                .push_safe(truncate_chars(member, 80))
+                .push("](")
+                .push(url.as_str())
+                .push(")"),
+            None => line.push_safe(truncate_chars(member, 80)),
+        };
+
+        let line = line.push_line(format!(" – {}", count)).build();
+
+        if description.len() + line.len() > PREVIEW_LEADERBOARD_MAX_LENGTH {
+            break;
+        }
+
+        description.push_str(line.as_str());
+    }
+}
+
+async fn run_previews(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<CreateEmbed<'static>, String> {
+    let guild_id = interaction
+        .guild_id
+        .ok_or("This command can only be used in servers.")?;
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let preview_stats = redis_get_preview_stats(
+        &mut redis_connection_manager,
+        guild_id,
+        get_last_days(PREVIEW_STATS_DAYS),
+        PREVIEW_LEADERBOARD_SIZE,
+    )
+    .await
+    .map_err(|_| "Failed to load the statistics.")?;
+
+    let mut description = String::new();
+    push_leaderboard(
+        &mut description,
+        "Top Repositories",
+        &preview_stats.repositories,
+    );
+    description.push('\n');
+    push_leaderboard(&mut description, "Top Files", &preview_stats.files);
+
+    let fields = preview_stats.sources.iter().map(|(source, count)| {
+        (
+            get_preview_source_label(source).to_owned(),
+            count.to_string(),
+            true,
+        )
+    });
+
+    Ok(CreateEmbed::new()
+        .title(format!(
+            "Preview Statistics of the Last {} Days",
+            PREVIEW_STATS_DAYS
+        ))
+        .colour(Colour::BLURPLE)
+        .description(description)
+        .fields(fields))
+}
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), String> {
+    /* Defer Interaction */
+
+    if let Err(error) = interaction.defer_ephemeral(&ctx.http).await {
+        println!("Failed to defer stats interaction: {:?}", error);
+        return Ok(());
+    }
+
+    /* Run Subcommand */
+
+    let options = interaction.data.options();
+
+    let embed = match options.first() {
+        Some(ResolvedOption {
+            name: "juxtapose",
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) => run_juxtapose(ctx, interaction, options).await?,
+        Some(ResolvedOption {
+            name: "previews",
+            value: ResolvedValue::SubCommand(_),
+            ..
+        }) => run_previews(ctx, interaction).await?,
+        _ => return Err("Unknown subcommand.".to_owned()),
+    };
+
+    /* Reply */
+
+    interaction
+        .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+        .await
+        .map_err(|_| "Failed to send the statistics.")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_leaderboard_entries() {
+        assert_eq!(
+            get_leaderboard_url("Kneemund/previewBOT").unwrap().as_str(),
+            "https://github.com/Kneemund/previewBOT"
+        );
+        assert_eq!(
+            get_leaderboard_url("Kneemund/previewBOT/src/main file.rs")
+                .unwrap()
+                .as_str(),
+            "https://github.com/Kneemund/previewBOT/blob/HEAD/src/main%20file.rs"
+        );
+        assert!(get_leaderboard_url("gist").is_none());
+    }
+}
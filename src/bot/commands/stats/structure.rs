@@ -0,0 +1,28 @@
+use serenity::all::{CommandOptionType, CreateCommand};
+
+use crate::bot::i18n::{localized_command, localized_option};
+
+pub(crate) fn register() -> CreateCommand<'static> {
+    localized_command("stats", "Show usage statistics of the bot.")
+        .add_option(
+            localized_option(
+                CommandOptionType::SubCommand,
+                "juxtapose",
+                "Show how often a juxtapose has been opened.",
+            )
+            .add_sub_option(
+                localized_option(
+                    CommandOptionType::String,
+                    "message_link",
+                    "The link to the message containing the juxtapose.",
+                )
+                .max_length(200)
+                .required(true),
+            ),
+        )
+        .add_option(localized_option(
+            CommandOptionType::SubCommand,
+            "previews",
+            "Show the most previewed repositories and files of this server in the last 30 days.",
+        ))
+}
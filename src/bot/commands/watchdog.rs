@@ -0,0 +1,92 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use serenity::all::{
+    Colour, CommandInteraction, CreateActionRow, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseMessage, EditAttachments, EditInteractionResponse,
+};
+use serenity::prelude::*;
+
+use crate::bot::activity::{publish_activity, ActivityEvent};
+
+/// Deferred interactions can be edited for 15 minutes, leave some headroom for the final response.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(14 * 60);
+
+async fn send_command_error(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    error: String,
+) -> Result<(), serenity::Error> {
+    let embed = CreateEmbed::new()
+        .title("Error")
+        .colour(Colour::RED)
+        .description(error);
+
+    // Remove attachments and components of partial responses, so they don't appear to be valid.
+    let edit_result = interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .add_embed(embed.clone())
+                .attachments(EditAttachments::new())
+                .components(Vec::<CreateActionRow>::new()),
+        )
+        .await;
+
+    // Editing fails if the command never responded, e.g. when failing before deferring.
+    if edit_result.is_err() {
+        interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .add_embed(embed)
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Runs a command and guarantees a terminal response to the interaction,
+/// replacing it with an error embed if the command fails or exceeds [`COMMAND_TIMEOUT`].
+pub(crate) async fn run_with_watchdog(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    command: impl Future<Output = Result<(), String>>,
+) {
+    let start_time = Instant::now();
+
+    let error = match tokio::time::timeout(COMMAND_TIMEOUT, command).await {
+        Ok(Ok(())) => {
+            println!(
+                "Command \"{}\" succeeded after {:?}.",
+                interaction.data.name,
+                start_time.elapsed()
+            );
+            return;
+        }
+        Ok(Err(error)) => error,
+        Err(_) => "The command took too long to complete. Please try again later.".to_owned(),
+    };
+
+    println!(
+        "Command \"{}\" failed after {:?}: {}",
+        interaction.data.name,
+        start_time.elapsed(),
+        error
+    );
+
+    publish_activity(ActivityEvent::CommandFailed {
+        guild_id: interaction.guild_id,
+        channel_id: interaction.channel_id,
+        command: interaction.data.name.to_string(),
+        error: error.clone(),
+    });
+
+    if let Err(error) = send_command_error(ctx, interaction, error).await {
+        println!("Failed to send command error response: {:?}", error);
+    }
+}
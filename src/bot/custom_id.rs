@@ -0,0 +1,37 @@
+use base64::{engine::general_purpose, Engine};
+
+use crate::BLAKE3_CUSTOM_ID_KEY;
+
+/// Length of the truncated MAC appended to custom IDs. Discord limits custom IDs to 100 characters.
+const CUSTOM_ID_MAC_LENGTH: usize = 8;
+
+fn calculate_mac(custom_id: &str) -> [u8; CUSTOM_ID_MAC_LENGTH] {
+    let mut mac = [0u8; CUSTOM_ID_MAC_LENGTH];
+
+    blake3::Hasher::new_keyed(&BLAKE3_CUSTOM_ID_KEY)
+        .update(custom_id.as_bytes())
+        .finalize_xof()
+        .fill(&mut mac);
+
+    mac
+}
+
+/// Appends a MAC to the custom ID of a component, so that components crafted by others
+/// (e.g. other bots re-using the custom ID in messages they control) are rejected.
+pub(crate) fn sign_custom_id(custom_id: &str) -> String {
+    format!(
+        "{}:{}",
+        custom_id,
+        general_purpose::URL_SAFE_NO_PAD.encode(calculate_mac(custom_id))
+    )
+}
+
+/// Returns the original custom ID if the MAC appended by [`sign_custom_id`] is valid.
+pub(crate) fn verify_custom_id(signed_custom_id: &str) -> Option<&str> {
+    let (custom_id, encoded_mac) = signed_custom_id.rsplit_once(':')?;
+
+    let mac = general_purpose::URL_SAFE_NO_PAD.decode(encoded_mac).ok()?;
+
+    constant_time_eq::constant_time_eq(mac.as_slice(), &calculate_mac(custom_id))
+        .then_some(custom_id)
+}
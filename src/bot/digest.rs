@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serenity::all::{Colour, CreateEmbed, CreateEmbedFooter, CreateMessage, GuildId};
+use serenity::prelude::*;
+
+use crate::bot::blocklist::is_guild_blocked;
+use crate::bot::commands::stats::push_leaderboard;
+use crate::bot::guild_config::GuildConfig;
+use crate::bot::guild_stats::{
+    get_unix_day, redis_get_guild_stats_of_days, redis_get_preview_stats, GuildStats, PreviewStats,
+    SECONDS_PER_DAY,
+};
+use crate::SerenityGlobalData;
+
+/// Digests are posted on the first poll after the week has ended, i.e. shortly after midnight UTC on Monday.
+const DIGEST_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Number of repositories and files listed in the digest.
+const DIGEST_LEADERBOARD_SIZE: usize = 5;
+
+/// Marks the digest of a guild for a week as posted, so that each is posted once even if several instances share Redis.
+/// Expires after the following digest has been posted.
+const DIGEST_SENT_TTL: u64 = 8 * SECONDS_PER_DAY;
+
+static IS_DIGEST_WORKER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Weeks since the UNIX epoch, starting on Monday. The epoch was a Thursday.
+fn get_unix_week(day: u64) -> u64 {
+    (day + 3) / 7
+}
+
+/// The days of the week before the given one.
+fn get_days_of_previous_week(week: u64) -> RangeInclusive<u64> {
+    let week_start_day = (week * 7).saturating_sub(3);
+    week_start_day.saturating_sub(7)..=week_start_day.saturating_sub(1)
+}
+
+fn redis_digest_sent_key(guild_id: GuildId, week: u64) -> String {
+    format!("digest_sent:{}:{}", guild_id, week)
+}
+
+/// Claims the digest of the guild for the week, returning whether it hasn't been claimed before.
+async fn redis_claim_digest(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+    week: u64,
+) -> Result<bool, redis::RedisError> {
+    let result: Option<String> = redis::cmd("SET")
+        .arg(redis_digest_sent_key(guild_id, week))
+        .arg(1)
+        .arg("NX")
+        .arg("EX")
+        .arg(DIGEST_SENT_TTL)
+        .query_async(connection)
+        .await?;
+
+    Ok(result.is_some())
+}
+
+fn create_digest_embed(
+    days: &RangeInclusive<u64>,
+    guild_stats: &GuildStats,
+    preview_stats: &PreviewStats,
+) -> CreateEmbed<'static> {
+    let mut description = format!(
+        "Activity from <t:{}:D> to <t:{}:D>.\n\n",
+        *days.start() * SECONDS_PER_DAY,
+        *days.end() * SECONDS_PER_DAY
+    );
+
+    push_leaderboard(
+        &mut description,
+        "Top Repositories",
+        &preview_stats.repositories,
+    );
+    description.push('\n');
+    push_leaderboard(&mut description, "Top Files", &preview_stats.files);
+
+    CreateEmbed::new()
+        .title("Weekly Digest")
+        .colour(Colour::BLURPLE)
+        .description(description)
+        .field("File Previews", guild_stats.file_previews.to_string(), true)
+        .field("Juxtaposes", guild_stats.juxtaposes.to_string(), true)
+        .footer(CreateEmbedFooter::new(
+            "Change or disable the digest channel with /config digest_channel.",
+        ))
+}
+
+/// Posts the digest of the previous week to the digest channel of the guild, unless nothing happened.
+async fn send_digest(
+    ctx: &Context,
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+    week: u64,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let guild_config = GuildConfig::redis_get(connection, guild_id).await?;
+
+    let Some(digest_channel_id) = guild_config.digest_channel_id else {
+        return Ok(());
+    };
+
+    let days = get_days_of_previous_week(week);
+    let guild_stats = redis_get_guild_stats_of_days(connection, guild_id, days.clone()).await?;
+
+    if guild_stats.file_previews == 0 && guild_stats.juxtaposes == 0 {
+        return Ok(());
+    }
+
+    let preview_stats =
+        redis_get_preview_stats(connection, guild_id, days.clone(), DIGEST_LEADERBOARD_SIZE)
+            .await?;
+
+    digest_channel_id
+        .send_message(
+            &ctx.http,
+            CreateMessage::new().embed(create_digest_embed(&days, &guild_stats, &preview_stats)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Starts posting the weekly digests in the background, once per process.
+pub(crate) fn spawn_digest_worker(ctx: Context) {
+    if IS_DIGEST_WORKER_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut redis_connection_manager = ctx
+            .data::<SerenityGlobalData>()
+            .redis_connection_manager
+            .clone();
+
+        let mut interval = tokio::time::interval(DIGEST_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let week = get_unix_week(get_unix_day());
+
+            let guild_ids = match GuildConfig::redis_get_digest_guild_ids(
+                &mut redis_connection_manager,
+            )
+            .await
+            {
+                Ok(guild_ids) => guild_ids,
+                Err(error) => {
+                    println!("Error while loading digest guilds: {:?}", error);
+                    continue;
+                }
+            };
+
+            for guild_id in guild_ids {
+                if is_guild_blocked(guild_id) {
+                    continue;
+                }
+
+                match redis_claim_digest(&mut redis_connection_manager, guild_id, week).await {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(error) => {
+                        println!(
+                            "Error while claiming digest of guild {}: {:?}",
+                            guild_id, error
+                        );
+                        continue;
+                    }
+                }
+
+                if let Err(error) =
+                    send_digest(&ctx, &mut redis_connection_manager, guild_id, week).await
+                {
+                    println!(
+                        "Error while sending digest of guild {}: {:?}",
+                        guild_id, error
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covers_previous_week_from_monday() {
+        // 2024-01-08 was a Monday.
+        let monday = 19730;
+        let week = get_unix_week(monday);
+
+        assert_eq!(get_unix_week(monday - 1), week - 1);
+        assert_eq!(get_unix_week(monday + 6), week);
+        assert_eq!(get_days_of_previous_week(week), monday - 7..=monday - 1);
+    }
+}
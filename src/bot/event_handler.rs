@@ -1,76 +1,306 @@
 use std::env;
 
 use serenity::all::{
-    Colour, Command, ComponentInteractionDataKind, CreateEmbed, EditInteractionResponse,
-    Interaction, Message, Ready,
+    ActivityData, Command, ComponentInteractionDataKind, Guild, GuildId, Interaction, Message,
+    Ready, UnavailableGuild, UserId,
 };
 use serenity::async_trait;
 use serenity::prelude::*;
 
-pub struct Handler;
-
+use super::activity::{publish_activity, ActivityEvent};
+use super::backfill::spawn_join_backfill;
+use super::blocklist::{is_blocked, is_guild_blocked, spawn_blocklist_refresh};
+use super::bot_lists::spawn_bot_list_posting;
+use super::commands::watchdog::run_with_watchdog;
 use super::commands::*;
+use super::custom_id::verify_custom_id;
+use super::digest::spawn_digest_worker;
 use super::file_preview::check_file_preview;
-use super::file_preview::handle_delete_file_preview_button;
+use super::file_preview::retry_queue::spawn_preview_retry_worker;
+use super::file_preview::{
+    handle_blamed_commit_button, handle_copy_permalink_button, handle_delete_file_preview_button,
+    handle_render_image_button, verify_legacy_delete_custom_id, BLAMED_COMMIT_CUSTOM_ID_PREFIX,
+    DELETE_CUSTOM_ID_PREFIX, PERMALINK_CUSTOM_ID_PREFIX, RENDER_IMAGE_CUSTOM_ID_PREFIX,
+};
+use super::guild_lifecycle::{
+    handle_guild_join, handle_guild_leave, handle_setup_button,
+    SETUP_DISPLAY_STYLE_CUSTOM_ID_PREFIX, SETUP_SHOW_CONFIG_CUSTOM_ID_PREFIX,
+};
+use crate::config::runtime_config;
+use crate::web::cache_janitor::spawn_cache_janitor;
+use crate::web::cache_warming::spawn_reconciliation;
+use crate::SerenityGlobalData;
+
+pub struct Handler;
+
+/// The user that caused the interaction and the guild it happened in. Commands of the owners are
+/// exempt from the blocklist, so that `/admin` keeps working in blocked guilds.
+fn get_interaction_origin(interaction: &Interaction) -> Option<(UserId, Option<GuildId>)> {
+    match interaction {
+        Interaction::Command(command_interaction)
+            if command_interaction.data.name.as_str() == "admin" =>
+        {
+            None
+        }
+        Interaction::Command(command_interaction)
+        | Interaction::Autocomplete(command_interaction) => {
+            Some((command_interaction.user.id, command_interaction.guild_id))
+        }
+        Interaction::Component(component_interaction) => Some((
+            component_interaction.user.id,
+            component_interaction.guild_id,
+        )),
+        Interaction::Modal(modal_interaction) => {
+            Some((modal_interaction.user.id, modal_interaction.guild_id))
+        }
+        _ => None,
+    }
+}
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn message(&self, ctx: Context, msg: Message) {
-        if msg.author.bot() {
+        if msg.author.bot() || is_blocked(msg.author.id, msg.guild_id) {
             return;
         }
 
         if let Err(error) = check_file_preview(&ctx, &msg).await {
             println!("Error while checking file preview: {:?}", error);
+
+            publish_activity(ActivityEvent::FilePreviewFailed {
+                guild_id: msg.guild_id,
+                channel_id: msg.channel_id,
+                error: error.to_string(),
+            });
+        }
+    }
+
+    async fn guild_create(&self, ctx: Context, guild: Guild, is_new: Option<bool>) {
+        if is_new == Some(true) && !is_guild_blocked(guild.id) {
+            if let Err(error) = handle_guild_join(&ctx, &guild).await {
+                println!("Error while setting up guild {}: {:?}", guild.id, error);
+            }
+
+            spawn_join_backfill(&ctx, &guild);
+        }
+    }
+
+    async fn guild_delete(&self, ctx: Context, incomplete: UnavailableGuild, _full: Option<Guild>) {
+        if let Err(error) = handle_guild_leave(&ctx, &incomplete).await {
+            println!(
+                "Error while cleaning up guild {}: {:?}",
+                incomplete.id, error
+            );
         }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Some((user_id, guild_id)) = get_interaction_origin(&interaction) {
+            if is_blocked(user_id, guild_id) {
+                println!("Ignored interaction of blocked user {}.", user_id);
+                return;
+            }
+        }
+
         match interaction {
-            Interaction::Component(component_interaction) =>
-            {
-                #[allow(clippy::single_match)]
-                match component_interaction.data.kind {
+            Interaction::Component(component_interaction) => {
+                let Some(custom_id) = verify_custom_id(&component_interaction.data.custom_id)
+                    .or_else(|| {
+                        verify_legacy_delete_custom_id(&component_interaction.data.custom_id)
+                    })
+                else {
+                    println!(
+                        "Rejected component interaction with invalid custom ID: {}",
+                        component_interaction.data.custom_id
+                    );
+                    return;
+                };
+
+                if custom_id.starts_with(browse::CUSTOM_ID_PREFIX) {
+                    if let Err(error) =
+                        browse::handle_browse_component(&ctx, &component_interaction, custom_id)
+                            .await
+                    {
+                        println!("Error while handling browser component: {:?}", error);
+                    }
+                    return;
+                }
+
+                match &component_interaction.data.kind {
                     ComponentInteractionDataKind::Button => {
-                        if component_interaction
-                            .data
-                            .custom_id
-                            .starts_with("deleteFilePreview")
+                        if custom_id.starts_with(juxtapose::SWAP_CUSTOM_ID_PREFIX) {
+                            if let Err(error) = juxtapose::handle_swap_button(
+                                &ctx,
+                                &component_interaction,
+                                custom_id,
+                            )
+                            .await
+                            {
+                                println!("Error while handling juxtapose swap button: {:?}", error);
+                            }
+                        } else if custom_id.starts_with(juxtapose::LABEL_CUSTOM_ID_PREFIX) {
+                            if let Err(error) = juxtapose::handle_label_button(
+                                &ctx,
+                                &component_interaction,
+                                custom_id,
+                            )
+                            .await
+                            {
+                                println!(
+                                    "Error while handling juxtapose label button: {:?}",
+                                    error
+                                );
+                            }
+                        } else if custom_id.starts_with(DELETE_CUSTOM_ID_PREFIX) {
+                            if let Err(error) = handle_delete_file_preview_button(
+                                &ctx,
+                                &component_interaction,
+                                custom_id,
+                            )
+                            .await
+                            {
+                                println!(
+                                    "Error while handling delete file preview button: {:?}",
+                                    error
+                                );
+                            }
+                        } else if custom_id.starts_with(PERMALINK_CUSTOM_ID_PREFIX) {
+                            if let Err(error) = handle_copy_permalink_button(
+                                &ctx,
+                                &component_interaction,
+                                custom_id,
+                            )
+                            .await
+                            {
+                                println!("Error while handling permalink button: {:?}", error);
+                            }
+                        } else if custom_id.starts_with(BLAMED_COMMIT_CUSTOM_ID_PREFIX) {
+                            if let Err(error) =
+                                handle_blamed_commit_button(&ctx, &component_interaction).await
+                            {
+                                println!("Error while handling blamed commit button: {:?}", error);
+                            }
+                        } else if custom_id.starts_with(SETUP_DISPLAY_STYLE_CUSTOM_ID_PREFIX)
+                            || custom_id.starts_with(SETUP_SHOW_CONFIG_CUSTOM_ID_PREFIX)
                         {
                             if let Err(error) =
-                                handle_delete_file_preview_button(&ctx, &component_interaction)
-                                    .await
+                                handle_setup_button(&ctx, &component_interaction, custom_id).await
+                            {
+                                println!("Error while handling setup button: {:?}", error);
+                            }
+                        } else if custom_id.starts_with(RENDER_IMAGE_CUSTOM_ID_PREFIX) {
+                            if let Err(error) =
+                                handle_render_image_button(&ctx, &component_interaction).await
+                            {
+                                println!("Error while handling render button: {:?}", error);
+                            }
+                        }
+                    }
+                    ComponentInteractionDataKind::StringSelect { values } => {
+                        let value = values.first().map(ToString::to_string).unwrap_or_default();
+
+                        if custom_id.starts_with(ghsearch::SELECT_CUSTOM_ID_PREFIX) {
+                            if let Err(error) = ghsearch::handle_select_search_result(
+                                &ctx,
+                                &component_interaction,
+                                custom_id,
+                                value.as_str(),
+                            )
+                            .await
                             {
                                 println!(
-                                    "Error while handling delete file preview button: {:?}",
+                                    "Error while handling code search result selection: {:?}",
                                     error
                                 );
                             }
+                        } else if custom_id.starts_with(help::SELECT_CUSTOM_ID_PREFIX) {
+                            if let Err(error) = help::handle_select_topic(
+                                &ctx,
+                                &component_interaction,
+                                value.as_str(),
+                            )
+                            .await
+                            {
+                                println!("Error while handling help topic selection: {:?}", error);
+                            }
                         }
                     }
                     _ => {}
                 }
             }
+            Interaction::Modal(modal_interaction) => {
+                let Some(custom_id) = verify_custom_id(&modal_interaction.data.custom_id) else {
+                    println!(
+                        "Rejected modal interaction with invalid custom ID: {}",
+                        modal_interaction.data.custom_id
+                    );
+                    return;
+                };
+
+                if custom_id.starts_with(browse::CUSTOM_ID_PREFIX) {
+                    if let Err(error) =
+                        browse::handle_browse_modal(&ctx, &modal_interaction, custom_id).await
+                    {
+                        println!("Error while handling browser modal: {:?}", error);
+                    }
+                } else if custom_id.starts_with(juxtapose::LABEL_CUSTOM_ID_PREFIX) {
+                    if let Err(error) =
+                        juxtapose::handle_label_modal(&ctx, &modal_interaction, custom_id).await
+                    {
+                        println!("Error while handling juxtapose label modal: {:?}", error);
+                    }
+                }
+            }
+            Interaction::Autocomplete(command_interaction) => {
+                // The preset name is the only option with autocompletion.
+                if let "juxtapose-preset" | "config" = command_interaction.data.name.as_str() {
+                    if let Err(error) =
+                        juxtapose_preset::autocomplete(&ctx, &command_interaction).await
+                    {
+                        println!("Error while autocompleting juxtapose preset: {:?}", error);
+                    }
+                }
+            }
             Interaction::Command(command_interaction) => {
-                #[allow(clippy::single_match)]
-                match command_interaction.data.name.as_str() {
-                    "juxtapose" => {
-                        if let Err(error) = juxtapose::run(&ctx, &command_interaction).await {
-                            let _ = command_interaction
-                                .edit_response(
-                                    &ctx.http,
-                                    EditInteractionResponse::new().add_embed(
-                                        CreateEmbed::new()
-                                            .title("Error")
-                                            .colour(Colour::RED)
-                                            .description(error),
-                                    ),
-                                )
-                                .await;
+                run_with_watchdog(&ctx, &command_interaction, async {
+                    match command_interaction.data.name.as_str() {
+                        "juxtapose" => juxtapose::run(&ctx, &command_interaction).await,
+                        "juxtapose-from-message" => {
+                            juxtapose_from_message::run(&ctx, &command_interaction).await
                         }
+                        "juxtapose-preset" => {
+                            juxtapose_preset::run(&ctx, &command_interaction).await
+                        }
+                        "config" => config::run(&ctx, &command_interaction).await,
+                        "stats" => stats::run(&ctx, &command_interaction).await,
+                        "admin" => admin::run(&ctx, &command_interaction).await,
+                        "forgetme" => forgetme::run(&ctx, &command_interaction).await,
+                        "permalink" => permalink::run(&ctx, &command_interaction).await,
+                        "ghsearch" => ghsearch::run(&ctx, &command_interaction).await,
+                        "browse" => browse::run(&ctx, &command_interaction).await,
+                        "ghdiff" => ghdiff::run(&ctx, &command_interaction).await,
+                        "backfill" => backfill::run(&ctx, &command_interaction).await,
+                        "hash" => hash::run(&ctx, &command_interaction).await,
+                        "qr" => qr::run(&ctx, &command_interaction).await,
+                        "color" => color::run(&ctx, &command_interaction).await,
+                        "palette" => palette::run(&ctx, &command_interaction).await,
+                        "imagetool" => imagetool::run(&ctx, &command_interaction).await,
+                        "spritesheet" => spritesheet::run(&ctx, &command_interaction).await,
+                        "album" => album::run(&ctx, &command_interaction).await,
+                        "help" => help::run(&ctx, &command_interaction).await,
+                        remove_preview::COMMAND_NAME => {
+                            remove_preview::run(&ctx, &command_interaction).await
+                        }
+                        preview_links::COMMAND_NAME => {
+                            preview_links::run(&ctx, &command_interaction).await
+                        }
+                        juxtapose_images::COMMAND_NAME => {
+                            juxtapose_images::run(&ctx, &command_interaction).await
+                        }
+                        _ => Ok(()),
                     }
-                    _ => {}
-                }
+                })
+                .await;
             }
             _ => {}
         }
@@ -79,12 +309,43 @@ impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
 
+        if let Some(presence_text) = runtime_config().presence_text.as_deref() {
+            ctx.set_activity(Some(ActivityData::custom(presence_text)));
+        }
+
+        spawn_reconciliation(
+            ctx.http.clone(),
+            ready.user.id,
+            ctx.data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone(),
+        );
+
+        spawn_cache_janitor(
+            ctx.http.clone(),
+            ctx.data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone(),
+        );
+
+        spawn_bot_list_posting(ctx.cache.clone(), ready.user.id);
+
+        spawn_blocklist_refresh(
+            ctx.data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone(),
+        );
+
+        spawn_preview_retry_worker(ctx.clone());
+
+        spawn_digest_worker(ctx.clone());
+
         let reload_commands = env::args().any(|argument| argument == "--reload-commands");
 
         if reload_commands {
             println!("Reloading commands...");
 
-            Command::set_global_commands(&ctx.http, &[juxtapose::register()])
+            Command::set_global_commands(&ctx.http, &register_all())
                 .await
                 .expect("Failed to register global commands.");
         }
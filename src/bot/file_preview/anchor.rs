@@ -0,0 +1,567 @@
+use std::error::Error;
+
+use once_cell::sync::Lazy;
+use percent_encoding::percent_decode_str;
+use regex::{Regex, RegexBuilder};
+use reqwest::Url;
+use serde::Deserialize;
+
+static LINE_NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"L(\d+)").unwrap());
+
+/// `L10`, `L10-L20` (GitHub), `L10-20` (GitLab) and `L10C5-L10C20` (GitHub column selection).
+static LINE_RANGE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^L(\d+)(?:C(\d+))?(?:-L?(\d+)(?:C(\d+))?)?$").unwrap());
+
+/// `lines-10` and `lines-10:20` (Bitbucket).
+static BITBUCKET_LINE_RANGE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^lines-(\d+)(?::(\d+))?$").unwrap());
+
+static CELL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^cell-(\d+)$").unwrap());
+
+/// `page=10`, the open parameter of PDF viewers.
+static PAGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^page=(\d+)$").unwrap());
+
+/// Symbols are previewed up to the end of their block, but not beyond this many lines.
+const SYMBOL_MAX_LINES: usize = 50;
+
+/// The part of a file that a preview URL points to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Anchor {
+    /// Both line numbers are inclusive.
+    Lines {
+        top_line_number: u32,
+        bottom_line_number: u32,
+    },
+    /// A part of a single line, both column numbers are inclusive.
+    Columns {
+        line_number: u32,
+        start_column: u32,
+        end_column: u32,
+    },
+    /// The definition of a function, type or variable.
+    Symbol(String),
+    /// A cell of a Jupyter notebook, starting at 1.
+    Cell(u32),
+    /// A value within a JSON document, see RFC 6901.
+    JsonPointer(String),
+    /// A page of a PDF file, starting at 1. Previewed as an image instead of selecting lines.
+    Page(u32),
+}
+
+/// The lines of a file selected by an anchor, and the number of the first line.
+pub(super) struct AnchorSelection {
+    pub(super) first_line_number: usize,
+    pub(super) lines: Vec<String>,
+    /// Explains how the selection differs from what the anchor points to, shown below the preview.
+    pub(super) notices: Vec<String>,
+}
+
+impl AnchorSelection {
+    /// The first 16 hex digits of the BLAKE3 hash of the selected lines, each ending with LF,
+    /// which matches e.g. `sed -n '10,20p' main.rs | b3sum` for files with LF line endings.
+    pub(super) fn get_content_hash(&self) -> String {
+        let mut hasher = blake3::Hasher::new();
+
+        for line in &self.lines {
+            hasher.update(line.as_bytes());
+            hasher.update(b"\n");
+        }
+
+        hasher.finalize().to_hex()[..16].to_owned()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NotebookCell {
+    source: NotebookCellSource,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NotebookCellSource {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct Notebook {
+    cells: Vec<NotebookCell>,
+}
+
+fn parse_line_number(string: &str) -> Option<u32> {
+    string
+        .parse::<u32>()
+        .ok()
+        .filter(|line_number| *line_number > 0)
+}
+
+impl Anchor {
+    fn lines(first_line_number: u32, second_line_number: u32) -> Self {
+        Self::Lines {
+            top_line_number: first_line_number.min(second_line_number),
+            bottom_line_number: first_line_number.max(second_line_number),
+        }
+    }
+
+    /// Parses the lines selected by `L<number>` markers anywhere in the fragment, e.g. `#file-main-rs-L10-L20`.
+    pub(super) fn parse_line_markers(url: &Url) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let line_numbers: Vec<u32> = LINE_NUMBER_REGEX
+            .captures_iter(url.fragment().ok_or("The specified URL is malformed.")?)
+            .filter_map(|match_captures| parse_line_number(&match_captures[1]))
+            .collect();
+
+        match (line_numbers.iter().min(), line_numbers.iter().max()) {
+            (Some(top_line_number), Some(bottom_line_number)) => {
+                Ok(Self::lines(*top_line_number, *bottom_line_number))
+            }
+            _ => Err("At least one line number is required.".into()),
+        }
+    }
+
+    /// Parses a fragment consisting of a single selector,
+    /// i.e. a line or column range in the format of GitHub, GitLab or Bitbucket,
+    /// `cell-<number>`, `page=<number>`, `symbol-<name>` or a JSON pointer starting with a slash.
+    pub(super) fn parse_fragment(url: &Url) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let fragment = percent_decode_str(url.fragment().ok_or("The specified URL is malformed.")?)
+            .decode_utf8()
+            .map_err(|_| "The specified URL is malformed.")?;
+
+        if let Some(captures) = LINE_RANGE_REGEX.captures(&fragment) {
+            let top_line_number =
+                parse_line_number(&captures[1]).ok_or("The line number is invalid.")?;
+            let bottom_line_number = match captures.get(3) {
+                Some(bottom_line_number) => parse_line_number(bottom_line_number.as_str())
+                    .ok_or("The line number is invalid.")?,
+                None => top_line_number,
+            };
+
+            return Ok(match (captures.get(2), captures.get(4)) {
+                (Some(start_column), Some(end_column)) if top_line_number == bottom_line_number => {
+                    let start_column = parse_line_number(start_column.as_str())
+                        .ok_or("The column number is invalid.")?;
+                    let end_column = parse_line_number(end_column.as_str())
+                        .ok_or("The column number is invalid.")?;
+
+                    Self::Columns {
+                        line_number: top_line_number,
+                        start_column: start_column.min(end_column),
+                        end_column: start_column.max(end_column),
+                    }
+                }
+                _ => Self::lines(top_line_number, bottom_line_number),
+            });
+        }
+
+        if let Some(captures) = BITBUCKET_LINE_RANGE_REGEX.captures(&fragment) {
+            let top_line_number =
+                parse_line_number(&captures[1]).ok_or("The line number is invalid.")?;
+            let bottom_line_number = match captures.get(2) {
+                Some(bottom_line_number) => parse_line_number(bottom_line_number.as_str())
+                    .ok_or("The line number is invalid.")?,
+                None => top_line_number,
+            };
+
+            return Ok(Self::lines(top_line_number, bottom_line_number));
+        }
+
+        if let Some(captures) = CELL_REGEX.captures(&fragment) {
+            return Ok(Self::Cell(
+                parse_line_number(&captures[1]).ok_or("The cell number is invalid.")?,
+            ));
+        }
+
+        if let Some(captures) = PAGE_REGEX.captures(&fragment) {
+            return Ok(Self::Page(
+                parse_line_number(&captures[1]).ok_or("The page number is invalid.")?,
+            ));
+        }
+
+        if let Some(symbol) = fragment.strip_prefix("symbol-") {
+            if !symbol.is_empty() {
+                return Ok(Self::Symbol(symbol.to_owned()));
+            }
+        }
+
+        if fragment.starts_with('/') {
+            return Ok(Self::JsonPointer(fragment.into_owned()));
+        }
+
+        Err("The URL doesn't point to any lines of the file.".into())
+    }
+
+    /// Selects the lines of the file that the anchor points to.
+    /// Line ranges are truncated to at most `max_line_count` lines.
+    pub(super) fn select(
+        &self,
+        raw_content: &str,
+        max_line_count: usize,
+    ) -> Result<AnchorSelection, Box<dyn Error + Send + Sync>> {
+        let selection = match self {
+            Self::Lines {
+                top_line_number,
+                bottom_line_number,
+            } => Self::select_lines(
+                raw_content,
+                *top_line_number as usize,
+                *bottom_line_number as usize,
+                max_line_count,
+            ),
+            Self::Columns {
+                line_number,
+                start_column,
+                end_column,
+            } => AnchorSelection {
+                first_line_number: *line_number as usize,
+                lines: raw_content
+                    .lines()
+                    .nth(*line_number as usize - 1)
+                    .map(|line| {
+                        line.chars()
+                            .skip(*start_column as usize - 1)
+                            .take((end_column - start_column + 1) as usize)
+                            .collect()
+                    })
+                    .into_iter()
+                    .collect(),
+                notices: Vec::new(),
+            },
+            Self::Symbol(symbol) => Self::select_symbol(raw_content, symbol)?,
+            Self::Cell(cell_number) => {
+                let notebook: Notebook = serde_json::from_str(raw_content)
+                    .map_err(|_| "The file is not a valid notebook.")?;
+
+                let cell = notebook
+                    .cells
+                    .into_iter()
+                    .nth(*cell_number as usize - 1)
+                    .ok_or("The notebook doesn't contain this cell.")?;
+
+                let source = match cell.source {
+                    NotebookCellSource::Lines(lines) => lines.concat(),
+                    NotebookCellSource::Text(text) => text,
+                };
+
+                AnchorSelection {
+                    first_line_number: 1,
+                    lines: source.lines().map(str::to_owned).collect(),
+                    notices: Vec::new(),
+                }
+            }
+            Self::JsonPointer(pointer) => {
+                let document: serde_json::Value = serde_json::from_str(raw_content)
+                    .map_err(|_| "The file is not a valid JSON document.")?;
+
+                let value = document
+                    .pointer(pointer)
+                    .ok_or("The JSON document doesn't contain this value.")?;
+
+                AnchorSelection {
+                    first_line_number: 1,
+                    lines: serde_json::to_string_pretty(value)?
+                        .lines()
+                        .map(str::to_owned)
+                        .collect(),
+                    notices: Vec::new(),
+                }
+            }
+            Self::Page(_) => return Err("Only pages of PDF files can be previewed.".into()),
+        };
+
+        if selection.lines.is_empty() {
+            return Err("No content selected.".into());
+        }
+
+        Ok(selection)
+    }
+
+    /// Selects the lines of the range that exist in the file, truncating the range to its first `max_line_count` lines.
+    /// Ranges ending past the end of the file, e.g. after the file changed on a branch, are clamped to it.
+    /// If the range starts past the end of the file, its length is kept and the tail of the file is selected.
+    fn select_lines(
+        raw_content: &str,
+        top_line_number: usize,
+        bottom_line_number: usize,
+        max_line_count: usize,
+    ) -> AnchorSelection {
+        let line_count = raw_content.lines().count();
+        let mut notices = Vec::new();
+
+        let max_line_count = max_line_count.max(1);
+        let span = bottom_line_number - top_line_number + 1;
+
+        let bottom_line_number = if span > max_line_count {
+            notices.push(format!(
+                "The linked range of {} lines was truncated to its first {}.",
+                span, max_line_count
+            ));
+
+            top_line_number + max_line_count - 1
+        } else {
+            bottom_line_number
+        };
+
+        let (first_line_number, last_line_number) = if bottom_line_number <= line_count {
+            (top_line_number, bottom_line_number)
+        } else {
+            if line_count > 0 {
+                notices.push(format!(
+                    "The file ends at line {}, before the end of the linked range.",
+                    line_count
+                ));
+            }
+
+            if top_line_number <= line_count {
+                (top_line_number, line_count)
+            } else {
+                let span = bottom_line_number - top_line_number + 1;
+                (line_count.saturating_sub(span) + 1, line_count)
+            }
+        };
+
+        AnchorSelection {
+            first_line_number,
+            lines: raw_content
+                .lines()
+                .skip(first_line_number - 1)
+                .take((last_line_number + 1).saturating_sub(first_line_number))
+                .map(str::to_owned)
+                .collect(),
+            notices,
+        }
+    }
+
+    /// Finds the first definition of the symbol, and selects it up to the end of its indented block.
+    fn select_symbol(
+        raw_content: &str,
+        symbol: &str,
+    ) -> Result<AnchorSelection, Box<dyn Error + Send + Sync>> {
+        let definition_regex = RegexBuilder::new(&format!(
+            r"^\s*(?:(?:pub(?:\([^)]*\))?|export|default|async|static|public|private|protected)\s+)*(?:fn|def|class|struct|enum|trait|interface|function|func|type|const|let|var|impl|mod|module)\s+{}\b",
+            regex::escape(symbol)
+        ))
+        .build()?;
+
+        let lines: Vec<&str> = raw_content.lines().collect();
+
+        let definition_index = lines
+            .iter()
+            .position(|line| definition_regex.is_match(line))
+            .ok_or("The symbol wasn't found in the file.")?;
+
+        let indentation = |line: &str| line.len() - line.trim_start().len();
+        let definition_indentation = indentation(lines[definition_index]);
+
+        let block_length = lines[definition_index + 1..]
+            .iter()
+            .position(|line| !line.trim().is_empty() && indentation(line) <= definition_indentation)
+            .map_or(lines.len() - definition_index, |index| {
+                let closing_line = lines[definition_index + 1 + index].trim_start();
+
+                // Include closing brackets and keywords at the indentation of the definition.
+                if closing_line.starts_with(['}', ')', ']']) || closing_line.starts_with("end") {
+                    index + 2
+                } else {
+                    index + 1
+                }
+            });
+
+        Ok(AnchorSelection {
+            first_line_number: definition_index + 1,
+            lines: lines[definition_index..]
+                .iter()
+                .take(block_length.min(SYMBOL_MAX_LINES))
+                .map(|line| (*line).to_owned())
+                .collect(),
+            notices: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    fn get_url(fragment: &str) -> Url {
+        let mut url =
+            Url::parse("https://github.com/octocat/hello-world/blob/main/README.md").unwrap();
+        url.set_fragment(Some(fragment));
+
+        url
+    }
+
+    /// Fragments close to the supported formats, which reach deeper into the parser than arbitrary strings.
+    fn fragment() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "\\PC*",
+            "L[0-9]{0,12}(C[0-9]{0,12})?(-L?[0-9]{0,12}(C[0-9]{0,12})?)?",
+            "lines-[0-9]{0,12}(:[0-9]{0,12})?",
+            "cell-[0-9]{0,12}",
+            "page=[0-9]{0,12}",
+            "symbol-\\PC{0,16}",
+            "/\\PC{0,16}",
+            "(%[0-9A-Fa-f]{0,2}|\\PC){0,16}",
+        ]
+    }
+
+    fn assert_valid_anchor(anchor: &Anchor) -> Result<(), TestCaseError> {
+        match anchor {
+            Anchor::Lines {
+                top_line_number,
+                bottom_line_number,
+            } => {
+                prop_assert!(*top_line_number >= 1);
+                prop_assert!(top_line_number <= bottom_line_number);
+            }
+            Anchor::Columns {
+                line_number,
+                start_column,
+                end_column,
+            } => {
+                prop_assert!(*line_number >= 1);
+                prop_assert!(*start_column >= 1);
+                prop_assert!(start_column <= end_column);
+            }
+            Anchor::Cell(cell_number) => prop_assert!(*cell_number >= 1),
+            Anchor::Page(page_number) => prop_assert!(*page_number >= 1),
+            Anchor::Symbol(symbol) => prop_assert!(!symbol.is_empty()),
+            Anchor::JsonPointer(pointer) => prop_assert!(pointer.starts_with('/')),
+        }
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn parses_fragments_into_valid_anchors(fragment in fragment(), raw_content in "\\PC{0,256}") {
+            if let Ok(anchor) = Anchor::parse_fragment(&get_url(fragment.as_str())) {
+                assert_valid_anchor(&anchor)?;
+                let _ = anchor.select(raw_content.as_str(), 100);
+            }
+        }
+
+        #[test]
+        fn parses_line_markers_into_valid_anchors(fragment in fragment(), raw_content in "\\PC{0,256}") {
+            if let Ok(anchor) = Anchor::parse_line_markers(&get_url(fragment.as_str())) {
+                assert_valid_anchor(&anchor)?;
+                let _ = anchor.select(raw_content.as_str(), 100);
+            }
+        }
+
+        #[test]
+        fn parses_line_ranges(
+            first_line_number in 1..=u32::MAX,
+            second_line_number in 1..=u32::MAX,
+            separator in prop::sample::select(vec!["-L", "-"]),
+        ) {
+            let fragment = format!("L{}{}{}", first_line_number, separator, second_line_number);
+
+            prop_assert_eq!(
+                Anchor::parse_fragment(&get_url(fragment.as_str())).unwrap(),
+                Anchor::Lines {
+                    top_line_number: first_line_number.min(second_line_number),
+                    bottom_line_number: first_line_number.max(second_line_number),
+                }
+            );
+        }
+
+        #[test]
+        fn selects_lines_within_the_file(
+            line_count in 1..64usize,
+            top_line_number in 1..64u32,
+            bottom_line_number in 1..64u32,
+        ) {
+            let raw_content: String = (1..=line_count).map(|line_number| format!("{}\n", line_number)).collect();
+            let anchor = Anchor::lines(top_line_number, bottom_line_number);
+            let (top_line_number, bottom_line_number) = (
+                top_line_number.min(bottom_line_number) as usize,
+                top_line_number.max(bottom_line_number) as usize,
+            );
+
+            let selection = anchor.select(raw_content.as_str(), 100).unwrap();
+
+            prop_assert!(!selection.lines.is_empty());
+            prop_assert!(selection.lines.len() <= bottom_line_number - top_line_number + 1);
+            prop_assert_eq!(selection.notices.is_empty(), bottom_line_number <= line_count);
+
+            if top_line_number <= line_count {
+                prop_assert_eq!(selection.first_line_number, top_line_number);
+            } else {
+                prop_assert_eq!(selection.first_line_number + selection.lines.len() - 1, line_count);
+            }
+
+            for (index, line) in selection.lines.iter().enumerate() {
+                prop_assert_eq!(line.clone(), (selection.first_line_number + index).to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_zero_and_overflowing_line_numbers() {
+        for fragment in [
+            "L0",
+            "L0-L10",
+            "L10-L0",
+            "L4294967296",
+            "lines-0",
+            "cell-0",
+            "page=0",
+            "L1C0-L1C5",
+        ] {
+            assert!(
+                Anchor::parse_fragment(&get_url(fragment)).is_err(),
+                "{}",
+                fragment
+            );
+        }
+    }
+
+    #[test]
+    fn hashes_selected_lines() {
+        let selection = Anchor::lines(2, 3).select("1\n2\n3\n4", 100).unwrap();
+
+        assert_eq!(
+            selection.get_content_hash(),
+            blake3::hash(b"2\n3\n").to_hex()[..16]
+        );
+    }
+
+    #[test]
+    fn selects_the_tail_of_files_shorter_than_the_range() {
+        let selection = Anchor::lines(10, 12).select("1\n2\n3\n4\n5", 100).unwrap();
+
+        assert_eq!(selection.first_line_number, 3);
+        assert_eq!(selection.lines, ["3", "4", "5"]);
+        assert_eq!(selection.notices.len(), 1);
+
+        assert!(Anchor::lines(1, 2).select("", 100).is_err());
+    }
+
+    #[test]
+    fn truncates_huge_ranges() {
+        let raw_content: String = (1..=1000)
+            .map(|line_number| format!("{}\n", line_number))
+            .collect();
+
+        let selection = Anchor::lines(1, 100_000).select(&raw_content, 50).unwrap();
+
+        assert_eq!(selection.first_line_number, 1);
+        assert_eq!(selection.lines.len(), 50);
+        assert_eq!(selection.notices.len(), 1);
+
+        let selection = Anchor::lines(500, 10).select(&raw_content, 50).unwrap();
+
+        assert_eq!(selection.first_line_number, 10);
+        assert_eq!(selection.lines.first().map(String::as_str), Some("10"));
+        assert_eq!(selection.lines.len(), 50);
+    }
+
+    #[test]
+    fn rejects_urls_without_fragment() {
+        let url = Url::parse("https://github.com/octocat/hello-world/blob/main/README.md").unwrap();
+
+        assert!(Anchor::parse_fragment(&url).is_err());
+        assert!(Anchor::parse_line_markers(&url).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Holds back deferrable calls to the Discord API while the bot is close to its rate limits.
+//!
+//! The remaining budget isn't read from the `X-RateLimit-*` headers, which serenity consumes in its own rate limiter
+//! without exposing them to callers. Instead, calls are counted against soft limits below the documented limits of
+//! Discord, and the 429 responses that do get through pause deferrable calls.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serenity::all::ChannelId;
+
+/// Discord allows 50 requests per second across all routes, deferrable calls leave headroom for urgent ones.
+const GLOBAL_WINDOW: Duration = Duration::from_secs(1);
+const GLOBAL_SOFT_LIMIT: usize = 40;
+
+/// Discord allows 5 messages per 5 seconds in a channel.
+const CHANNEL_WINDOW: Duration = Duration::from_secs(5);
+const CHANNEL_SOFT_LIMIT: usize = 4;
+
+/// Deferrable calls are paused for this long after Discord responded with 429 Too Many Requests.
+const RATE_LIMITED_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Deferrable calls are made anyway after waiting this long, leaving the rest to the rate limiter of serenity.
+const MAX_DEFER_TIME: Duration = Duration::from_secs(30);
+
+/// Whether a call to the Discord API may be held back while the bot is close to its rate limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SendPriority {
+    /// Someone is waiting for the response, e.g. after using a command.
+    Urgent,
+    /// Previews of links in messages, which nobody explicitly asked for.
+    Deferrable,
+}
+
+#[derive(Default)]
+struct BackpressureState {
+    global_call_times: VecDeque<Instant>,
+    channel_call_times: HashMap<ChannelId, VecDeque<Instant>>,
+    rate_limited_until: Option<Instant>,
+}
+
+static BACKPRESSURE_STATE: Lazy<Mutex<BackpressureState>> =
+    Lazy::new(|| Mutex::new(BackpressureState::default()));
+
+static DEFERRED_CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+static MAX_DEFERRED_CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+static TOTAL_DEFERRED_CALL_COUNT: AtomicU64 = AtomicU64::new(0);
+static FORCED_CALL_COUNT: AtomicU64 = AtomicU64::new(0);
+static RATE_LIMITED_RESPONSE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+fn prune_call_times(call_times: &mut VecDeque<Instant>, window: Duration, now: Instant) {
+    while call_times
+        .front()
+        .is_some_and(|call_time| now.duration_since(*call_time) >= window)
+    {
+        call_times.pop_front();
+    }
+}
+
+/// Time until the oldest call leaves the window, if the window is full.
+fn get_window_wait_time(
+    call_times: &VecDeque<Instant>,
+    window: Duration,
+    limit: usize,
+    now: Instant,
+) -> Option<Duration> {
+    if call_times.len() < limit {
+        return None;
+    }
+
+    call_times
+        .front()
+        .map(|call_time| window.saturating_sub(now.duration_since(*call_time)))
+}
+
+/// Counts a call towards the limits, or returns how long a deferrable call should wait before trying again.
+fn reserve_call(channel_id: ChannelId, priority: SendPriority, now: Instant) -> Option<Duration> {
+    let mut state = BACKPRESSURE_STATE.lock().unwrap();
+
+    prune_call_times(&mut state.global_call_times, GLOBAL_WINDOW, now);
+    state.channel_call_times.retain(|_, call_times| {
+        prune_call_times(call_times, CHANNEL_WINDOW, now);
+        !call_times.is_empty()
+    });
+
+    if priority == SendPriority::Deferrable {
+        let rate_limited_wait_time = state
+            .rate_limited_until
+            .filter(|rate_limited_until| *rate_limited_until > now)
+            .map(|rate_limited_until| rate_limited_until - now);
+
+        let wait_time = [
+            rate_limited_wait_time,
+            get_window_wait_time(
+                &state.global_call_times,
+                GLOBAL_WINDOW,
+                GLOBAL_SOFT_LIMIT,
+                now,
+            ),
+            state
+                .channel_call_times
+                .get(&channel_id)
+                .and_then(|call_times| {
+                    get_window_wait_time(call_times, CHANNEL_WINDOW, CHANNEL_SOFT_LIMIT, now)
+                }),
+        ]
+        .into_iter()
+        .flatten()
+        .max();
+
+        if wait_time.is_some() {
+            return wait_time;
+        }
+    }
+
+    state.global_call_times.push_back(now);
+    state
+        .channel_call_times
+        .entry(channel_id)
+        .or_default()
+        .push_back(now);
+
+    None
+}
+
+/// Leaves the queue when dropped, including when the waiting task is cancelled.
+struct DeferredSlot;
+
+impl Drop for DeferredSlot {
+    fn drop(&mut self) {
+        DEFERRED_CALL_COUNT.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+async fn defer_call(channel_id: ChannelId, mut wait_time: Duration) {
+    let deadline = Instant::now() + MAX_DEFER_TIME;
+
+    TOTAL_DEFERRED_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
+    let queue_depth = DEFERRED_CALL_COUNT.fetch_add(1, Ordering::Relaxed) + 1;
+    let _deferred_slot = DeferredSlot;
+
+    MAX_DEFERRED_CALL_COUNT.fetch_max(queue_depth, Ordering::Relaxed);
+
+    loop {
+        let now = Instant::now();
+
+        if now >= deadline {
+            FORCED_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
+            reserve_call(channel_id, SendPriority::Urgent, now);
+            return;
+        }
+
+        tokio::time::sleep(wait_time.min(deadline - now)).await;
+
+        match reserve_call(channel_id, SendPriority::Deferrable, Instant::now()) {
+            Some(next_wait_time) => wait_time = next_wait_time,
+            None => return,
+        }
+    }
+}
+
+/// Makes a call to the Discord API in the channel, holding it back first if it is deferrable and the bot
+/// is close to the rate limits of Discord. Responses with 429 Too Many Requests pause all deferrable calls.
+pub(crate) async fn call_with_backpressure<T>(
+    channel_id: ChannelId,
+    priority: SendPriority,
+    call: impl Future<Output = serenity::Result<T>>,
+) -> serenity::Result<T> {
+    if let Some(wait_time) = reserve_call(channel_id, priority, Instant::now()) {
+        defer_call(channel_id, wait_time).await;
+    }
+
+    let result = call.await;
+
+    if let Err(serenity::Error::Http(error)) = &result {
+        if error.status_code().map(|status_code| status_code.as_u16()) == Some(429) {
+            RATE_LIMITED_RESPONSE_COUNT.fetch_add(1, Ordering::Relaxed);
+            BACKPRESSURE_STATE.lock().unwrap().rate_limited_until =
+                Some(Instant::now() + RATE_LIMITED_COOLDOWN);
+        }
+    }
+
+    result
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BackpressureMetrics {
+    calls_last_second: usize,
+    deferred_calls: usize,
+    max_deferred_calls: usize,
+    total_deferred_calls: u64,
+    /// Deferred calls that were made anyway after waiting for [`MAX_DEFER_TIME`].
+    forced_calls: u64,
+    rate_limited_responses: u64,
+    is_rate_limited: bool,
+}
+
+pub(crate) fn get_backpressure_metrics() -> BackpressureMetrics {
+    let now = Instant::now();
+    let mut state = BACKPRESSURE_STATE.lock().unwrap();
+
+    prune_call_times(&mut state.global_call_times, GLOBAL_WINDOW, now);
+
+    BackpressureMetrics {
+        calls_last_second: state.global_call_times.len(),
+        deferred_calls: DEFERRED_CALL_COUNT.load(Ordering::Relaxed),
+        max_deferred_calls: MAX_DEFERRED_CALL_COUNT.load(Ordering::Relaxed),
+        total_deferred_calls: TOTAL_DEFERRED_CALL_COUNT.load(Ordering::Relaxed),
+        forced_calls: FORCED_CALL_COUNT.load(Ordering::Relaxed),
+        rate_limited_responses: RATE_LIMITED_RESPONSE_COUNT.load(Ordering::Relaxed),
+        is_rate_limited: state
+            .rate_limited_until
+            .is_some_and(|rate_limited_until| rate_limited_until > now),
+    }
+}
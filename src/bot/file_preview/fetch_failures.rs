@@ -0,0 +1,52 @@
+use std::error::Error;
+
+use redis::AsyncCommands;
+use reqwest::Url;
+
+use super::retry_queue::is_transient_error;
+use super::source::PreviewSourceHandler;
+use super::FilePreview;
+
+/// Failures are remembered briefly, so that a broken link posted again or a message edited repeatedly
+/// doesn't reach upstream every time, while files pushed after being linked are picked up soon.
+const FETCH_FAILURE_TTL: u64 = 5 * 60;
+
+fn redis_fetch_failure_key(url: &Url) -> String {
+    format!(
+        "fetch_failure:{}",
+        blake3::hash(url.as_str().as_bytes()).to_hex()
+    )
+}
+
+/// Fetches the file that a URL points to, unless that failed recently, in which case the reason is returned again.
+pub(super) async fn fetch_remembering_failures(
+    connection: &mut redis::aio::ConnectionManager,
+    handler: &dyn PreviewSourceHandler,
+    url: Url,
+) -> Result<Box<dyn FilePreview>, Box<dyn Error + Send + Sync>> {
+    let redis_key = redis_fetch_failure_key(&url);
+
+    if let Some(reason) = connection
+        .get::<_, Option<String>>(redis_key.as_str())
+        .await
+        .ok()
+        .flatten()
+    {
+        return Err(reason.into());
+    }
+
+    let result = handler.fetch(url).await;
+
+    // Transient failures aren't remembered, as the preview is retried later.
+    if let Some(error) = result
+        .as_ref()
+        .err()
+        .filter(|error| !is_transient_error(&**error))
+    {
+        let _: Result<(), _> = connection
+            .set_ex(redis_key, error.to_string(), FETCH_FAILURE_TTL)
+            .await;
+    }
+
+    result
+}
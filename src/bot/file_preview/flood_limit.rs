@@ -0,0 +1,83 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serenity::all::{ChannelId, UserId};
+
+/// Previews are counted over a sliding window, so that links spread over multiple messages can't flood a channel.
+const FLOOD_WINDOW: Duration = Duration::from_secs(60);
+
+/// Number of previews per channel and author within the window.
+const FLOOD_MAX_PREVIEWS: usize = 6;
+
+/// Number of previews for the links in a single message.
+const MESSAGE_MAX_PREVIEWS: usize = 3;
+
+#[derive(Default)]
+struct FloodState {
+    preview_times: VecDeque<Instant>,
+    /// At most one notice about skipped links is posted per window, as the notices would flood the channel otherwise.
+    last_notice_time: Option<Instant>,
+}
+
+static FLOOD_STATES: Lazy<Mutex<HashMap<(ChannelId, UserId), FloodState>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(super) struct FloodLimitOutcome {
+    /// Number of links that may be previewed.
+    pub(super) allowed_count: usize,
+    /// Number of links beyond the limits that a notice should be posted for, zero if no notice should be posted.
+    pub(super) notice_skipped_count: usize,
+}
+
+/// Reserves previews for the links of a message, counting them towards the limit of the channel and author.
+pub(super) fn reserve_previews(
+    channel_id: ChannelId,
+    author_id: UserId,
+    link_count: usize,
+) -> FloodLimitOutcome {
+    let now = Instant::now();
+    let mut flood_states = FLOOD_STATES.lock().unwrap();
+
+    flood_states.retain(|_, flood_state| {
+        while flood_state
+            .preview_times
+            .front()
+            .is_some_and(|preview_time| now.duration_since(*preview_time) >= FLOOD_WINDOW)
+        {
+            flood_state.preview_times.pop_front();
+        }
+
+        !flood_state.preview_times.is_empty()
+            || flood_state
+                .last_notice_time
+                .is_some_and(|notice_time| now.duration_since(notice_time) < FLOOD_WINDOW)
+    });
+
+    let flood_state = flood_states.entry((channel_id, author_id)).or_default();
+
+    let allowed_count = link_count
+        .min(MESSAGE_MAX_PREVIEWS)
+        .min(FLOOD_MAX_PREVIEWS.saturating_sub(flood_state.preview_times.len()));
+
+    flood_state
+        .preview_times
+        .extend(std::iter::repeat(now).take(allowed_count));
+
+    let skipped_count = link_count - allowed_count;
+
+    let is_notice_allowed = skipped_count > 0
+        && !flood_state
+            .last_notice_time
+            .is_some_and(|notice_time| now.duration_since(notice_time) < FLOOD_WINDOW);
+
+    if is_notice_allowed {
+        flood_state.last_notice_time = Some(now);
+    }
+
+    FloodLimitOutcome {
+        allowed_count,
+        notice_skipped_count: if is_notice_allowed { skipped_count } else { 0 },
+    }
+}
@@ -2,14 +2,17 @@ use std::error::Error;
 use std::path::PathBuf;
 
 use once_cell::sync::Lazy;
+use percent_encoding::percent_decode_str;
 use regex::Regex;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use serenity::all::MessageBuilder;
 
-use crate::HTTP_CLIENT;
+use crate::bot::guild_config::PreviewSource;
+use crate::outbound::{guarded_get, send_limited};
 
-use super::{fetch_raw_content, truncate_string, FilePreview};
+use super::retry_queue::request_failed_error;
+use super::{fetch_raw_content, truncate_string, FilePreview, FilePreviewMetadata};
 
 #[derive(Debug, Deserialize, Serialize)]
 struct APIGistMetadata {
@@ -24,10 +27,62 @@ struct APIGistMetadata {
 
 static FILE_NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"file-([^L]+)").unwrap());
 
+/// Builds the fragment that GitHub links a file of a gist with, e.g. `file-main-rs` for `main.rs`.
+fn get_file_fragment(file_name: &str) -> String {
+    let file_name: String = file_name
+        .to_lowercase()
+        .chars()
+        .map(|character| {
+            if character.is_alphanumeric() {
+                character
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    format!("file-{}", file_name)
+}
+
+/// Rewrites links to raw files of a gist to the file on gist.github.com, keeping the revision and line numbers,
+/// e.g. `gist.githubusercontent.com/octocat/6cad3268/raw/<revision>/main.rs#L10`
+/// to `gist.github.com/octocat/6cad3268/<revision>#file-main-rs-L10`.
+pub(super) fn to_canonical_gist_url(url: &Url) -> Option<Url> {
+    let path_segments: Vec<&str> = url.path_segments()?.collect();
+
+    let (path, file_name) = match (url.host_str()?, path_segments.as_slice()) {
+        ("gist.github.com", _) => return Some(url.clone()),
+        ("gist.githubusercontent.com", [owner, id, "raw", file_name]) => {
+            (format!("{}/{}", owner, id), file_name)
+        }
+        ("gist.githubusercontent.com", [owner, id, "raw", revision, file_name]) => {
+            (format!("{}/{}/{}", owner, id, revision), file_name)
+        }
+        _ => return None,
+    };
+
+    let file_name = percent_decode_str(file_name).decode_utf8().ok()?;
+
+    // The segments are still percent-encoded, which setting the path keeps as is.
+    let mut canonical_url = Url::parse("https://gist.github.com/").unwrap();
+    canonical_url.set_path(path.as_str());
+    canonical_url.set_fragment(Some(
+        format!(
+            "{}-{}",
+            get_file_fragment(file_name.as_ref()),
+            url.fragment()?
+        )
+        .as_str(),
+    ));
+
+    Some(canonical_url)
+}
+
 #[derive(Debug)]
 pub struct GistFilePreview {
     message_url: Url,
     metadata_content: String,
+    metadata: FilePreviewMetadata,
     file_extension: Option<String>,
     raw_content: String,
 }
@@ -48,8 +103,9 @@ impl GistFilePreview {
             .collect()
     }
 
-    pub async fn new(message_url: Url) -> Result<Self, Box<dyn Error + Send + Sync>> {
-        let selected_file_name_fragment = Self::normalize_file_name(
+    /// Extracts the normalized name of the selected file from the URL fragment, e.g. `mainrs` from `#file-main-rs-L10`.
+    fn parse_file_name_fragment(message_url: &Url) -> Result<String, Box<dyn Error + Send + Sync>> {
+        Ok(Self::normalize_file_name(
             &FILE_NAME_REGEX
                 .captures(
                     message_url
@@ -57,16 +113,23 @@ impl GistFilePreview {
                         .ok_or("The specified URL is malformed.")?,
                 )
                 .ok_or("File name not found.")?[1],
-        );
+        ))
+    }
+
+    pub async fn new(message_url: Url) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let selected_file_name_fragment = Self::parse_file_name_fragment(&message_url)?;
 
         let mut metadata_url = message_url.clone();
         metadata_url.set_fragment(None);
         metadata_url.set_path((metadata_url.path().to_owned() + ".json").as_str());
 
-        let response = HTTP_CLIENT.get(metadata_url).send().await?;
+        let response = send_limited(guarded_get(metadata_url)?).await?;
 
         if !response.status().is_success() {
-            return Err("API request failed.".into());
+            return Err(request_failed_error(
+                response.status(),
+                "API request failed.",
+            ));
         }
 
         let metadata: APIGistMetadata = response.json().await?;
@@ -81,14 +144,36 @@ impl GistFilePreview {
             .extension()
             .map(|extension| extension.to_string_lossy().into_owned());
 
-        let mut raw_url = message_url.clone();
-        raw_url.set_fragment(None);
+        // Links to a revision of the gist have its SHA as the third segment, which goes after `raw` instead.
+        let (gist_path, revision) = match message_url
+            .path_segments()
+            .ok_or("The specified URL is malformed.")?
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [owner, id] => (format!("{}/{}", owner, id), None),
+            [owner, id, revision] => (format!("{}/{}", owner, id), Some(revision.to_string())),
+            _ => return Err("The specified URL is malformed.".into()),
+        };
+
+        let mut raw_url = Url::parse("https://gist.github.com/").unwrap();
+        raw_url.set_path(gist_path.as_str());
         raw_url
             .path_segments_mut()
-            .unwrap()
+            .map_err(|_| "The specified URL is malformed.")?
             .push("raw")
+            .extend(revision)
             .push(selected_file_name);
 
+        let preview_metadata = FilePreviewMetadata {
+            source: metadata.owner.clone(),
+            file_path: selected_file_name.to_owned(),
+            owner_avatar_url: Some(format!(
+                "https://github.com/{}.png?size=128",
+                metadata.owner
+            )),
+        };
+
         let mut metadata_content_builder = MessageBuilder::new()
             .push_bold_line_safe(metadata.owner.as_str())
             .push_line_safe(selected_file_name.as_str());
@@ -103,6 +188,7 @@ impl GistFilePreview {
         Ok(Self {
             message_url,
             metadata_content: metadata_content_builder.build(),
+            metadata: preview_metadata,
             file_extension,
             raw_content,
         })
@@ -110,6 +196,10 @@ impl GistFilePreview {
 }
 
 impl FilePreview for GistFilePreview {
+    fn get_source(&self) -> PreviewSource {
+        PreviewSource::Gist
+    }
+
     fn get_message_url(&self) -> &Url {
         &self.message_url
     }
@@ -118,6 +208,10 @@ impl FilePreview for GistFilePreview {
         self.metadata_content.as_str()
     }
 
+    fn get_metadata(&self) -> &FilePreviewMetadata {
+        &self.metadata
+    }
+
     fn get_file_extension(&self) -> Option<&str> {
         self.file_extension.as_deref()
     }
@@ -126,3 +220,92 @@ impl FilePreview for GistFilePreview {
         self.raw_content.as_str()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn normalization_is_idempotent(file_name in "\\PC*") {
+            let normalized_file_name = GistFilePreview::normalize_file_name(file_name.as_str());
+
+            prop_assert_eq!(
+                GistFilePreview::normalize_file_name(normalized_file_name.as_str()),
+                normalized_file_name.clone()
+            );
+            prop_assert!(normalized_file_name
+                .chars()
+                .all(|character| character.is_alphanumeric() && !character.is_ascii_uppercase()));
+        }
+
+        #[test]
+        fn never_panics_on_arbitrary_fragments(fragment in "\\PC*") {
+            let mut url = Url::parse("https://gist.github.com/octocat/6cad326836d38bd3a7ae").unwrap();
+            url.set_fragment(Some(fragment.as_str()));
+
+            let _ = GistFilePreview::parse_file_name_fragment(&url);
+        }
+
+        #[test]
+        fn matches_fragments_of_file_names(
+            file_name in "[A-Za-z0-9._-]{1,32}",
+            line_number in 1..10_000u32,
+        ) {
+            let normalized_file_name = GistFilePreview::normalize_file_name(file_name.as_str());
+            prop_assume!(!normalized_file_name.is_empty());
+
+            let mut url = Url::parse("https://gist.github.com/octocat/6cad326836d38bd3a7ae").unwrap();
+            url.set_fragment(Some(
+                format!("{}-L{}", get_file_fragment(file_name.as_str()), line_number).as_str(),
+            ));
+
+            prop_assert_eq!(
+                GistFilePreview::parse_file_name_fragment(&url).unwrap(),
+                normalized_file_name
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalizes_raw_urls() {
+        let revision = "3a4b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a5b";
+
+        for (url, canonical_url) in [
+            (
+                "https://gist.githubusercontent.com/octocat/6cad326836d38bd3a7ae/raw/main.rs#L10-L20".to_owned(),
+                "https://gist.github.com/octocat/6cad326836d38bd3a7ae#file-main-rs-L10-L20".to_owned(),
+            ),
+            (
+                format!("https://gist.githubusercontent.com/octocat/6cad326836d38bd3a7ae/raw/{}/My%20Notes.md#L3", revision),
+                format!("https://gist.github.com/octocat/6cad326836d38bd3a7ae/{}#file-my-notes-md-L3", revision),
+            ),
+        ] {
+            assert_eq!(
+                to_canonical_gist_url(&Url::parse(url.as_str()).unwrap())
+                    .unwrap()
+                    .as_str(),
+                canonical_url
+            );
+        }
+
+        let url = Url::parse(
+            "https://gist.githubusercontent.com/octocat/6cad326836d38bd3a7ae/raw/main.rs",
+        )
+        .unwrap();
+        assert!(to_canonical_gist_url(&url).is_none());
+    }
+
+    #[test]
+    fn rejects_urls_without_file_name() {
+        for url in [
+            "https://gist.github.com/octocat/6cad326836d38bd3a7ae",
+            "https://gist.github.com/octocat/6cad326836d38bd3a7ae#L10",
+            "https://gist.github.com/octocat/6cad326836d38bd3a7ae#file-L10",
+        ] {
+            assert!(GistFilePreview::parse_file_name_fragment(&Url::parse(url).unwrap()).is_err());
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+
+use reqwest::Url;
+use serde::Deserialize;
+use serenity::all::MessageBuilder;
+
+use crate::bot::github_api::{check_response_status, github_api_get};
+use crate::bot::guild_config::PreviewSource;
+use crate::outbound::send_limited;
+
+use super::{FilePreview, FilePreviewMetadata};
+
+#[derive(Debug, Deserialize)]
+struct APICompareResponse {
+    html_url: String,
+    #[serde(default)]
+    files: Vec<APICompareFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APICompareFile {
+    filename: String,
+    previous_filename: Option<String>,
+    status: String,
+    patch: Option<String>,
+}
+
+/// The unified diff of a single file between two references.
+pub(crate) struct GitHubCompareFilePreview {
+    message_url: Url,
+    metadata_content: String,
+    metadata: FilePreviewMetadata,
+    raw_content: String,
+}
+
+impl GitHubCompareFilePreview {
+    pub(crate) async fn new(
+        owner: &str,
+        repository: &str,
+        path: &str,
+        base: &str,
+        head: &str,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let path = path.trim_matches('/');
+
+        let response = send_limited(github_api_get(&[
+            "repos",
+            owner,
+            repository,
+            "compare",
+            format!("{}...{}", base, head).as_str(),
+        ]))
+        .await
+        .map_err(|_| "Failed to reach GitHub.")?;
+
+        check_response_status(
+            &response,
+            "The repository or one of the references could not be found.",
+        )?;
+
+        let compare_response: APICompareResponse = response
+            .json()
+            .await
+            .map_err(|_| "Failed to receive the comparison from GitHub.")?;
+
+        let compare_file = compare_response
+            .files
+            .into_iter()
+            .find(|file| file.filename == path || file.previous_filename.as_deref() == Some(path))
+            .ok_or("The file is identical in both references.")?;
+
+        let patch = compare_file.patch.ok_or(
+            "The diff of the file is not available, e.g. because it is binary or too large.",
+        )?;
+
+        let previous_filename = compare_file
+            .previous_filename
+            .as_deref()
+            .unwrap_or(compare_file.filename.as_str());
+
+        let raw_content = format!(
+            "--- a/{}\n+++ b/{}\n{}",
+            previous_filename, compare_file.filename, patch
+        );
+
+        let metadata_content = MessageBuilder::new()
+            .push_bold_safe(owner.to_owned())
+            .push("/")
+            .push_bold_safe(repository.to_owned())
+            .push(" (")
+            .push_safe(base)
+            .push("...")
+            .push_safe(head)
+            .push_line(")")
+            .push_safe(compare_file.filename.as_str())
+            .push(" (")
+            .push_safe(compare_file.status.as_str())
+            .push_line(")")
+            .build();
+
+        let metadata = FilePreviewMetadata {
+            source: format!("{}/{} ({}...{})", owner, repository, base, head),
+            file_path: compare_file.filename,
+            owner_avatar_url: Some(format!("https://github.com/{}.png?size=128", owner)),
+        };
+
+        Ok(Self {
+            message_url: Url::parse(compare_response.html_url.as_str())?,
+            metadata_content,
+            metadata,
+            raw_content,
+        })
+    }
+
+    pub(crate) fn get_compare_url(&self) -> &Url {
+        &self.message_url
+    }
+}
+
+impl FilePreview for GitHubCompareFilePreview {
+    fn get_source(&self) -> PreviewSource {
+        PreviewSource::GitHubRepositoryFile
+    }
+
+    fn get_message_url(&self) -> &Url {
+        &self.message_url
+    }
+
+    fn get_metadata_content(&self) -> &str {
+        self.metadata_content.as_str()
+    }
+
+    fn get_metadata(&self) -> &FilePreviewMetadata {
+        &self.metadata
+    }
+
+    /// Code blocks highlighted as diffs show additions and deletions in color.
+    fn get_file_extension(&self) -> Option<&str> {
+        Some("diff")
+    }
+
+    fn get_raw_content(&self) -> &str {
+        self.raw_content.as_str()
+    }
+}
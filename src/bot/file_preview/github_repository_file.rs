@@ -4,30 +4,227 @@ use std::path::PathBuf;
 use percent_encoding::percent_decode_str;
 use reqwest::Url;
 use serenity::all::MessageBuilder;
+use tokio::join;
 
-use super::{fetch_raw_content, FilePreview};
+use crate::bot::github_api::{get_blamed_commit_sha, resolve_commit_sha};
+use crate::bot::guild_config::PreviewSource;
+use crate::config::{runtime_config, PreviewFileLimits};
+
+use super::anchor::Anchor;
+use super::pdf::{fetch_raw_pdf, is_pdf_file};
+use super::{fetch_limited_raw_content, fetch_raw_content, FilePreview, FilePreviewMetadata};
 
 pub struct GitHubRepositoryFilePreview {
     message_url: Url,
+    location: GitHubRepositoryFileLocation,
     metadata_content: String,
+    metadata: FilePreviewMetadata,
     file_extension: Option<String>,
     raw_content: String,
+    raw_pdf: Option<Vec<u8>>,
+    /// The commit that the branch of the URL pointed to when the file was fetched.
+    commit_sha: Option<String>,
+}
+
+/// Whether the content that a reference points to can change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReferenceKind {
+    /// A full SHA-1 or SHA-256 commit hash.
+    Commit,
+    /// Branches, tags and abbreviated commit hashes, which can't be told apart by their name alone.
+    Branch,
+}
+
+impl ReferenceKind {
+    fn of(reference: &str) -> Self {
+        if matches!(reference.len(), 40 | 64) && reference.chars().all(|c| c.is_ascii_hexdigit()) {
+            Self::Commit
+        } else {
+            Self::Branch
+        }
+    }
+
+    fn file_limits(self) -> PreviewFileLimits {
+        match self {
+            Self::Commit => runtime_config().commit_file_limits,
+            Self::Branch => runtime_config().branch_file_limits,
+        }
+    }
 }
 
 fn get_short_reference(reference: &str) -> &str {
-    if reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit()) {
-        &reference[..7]
-    } else {
-        reference
+    match ReferenceKind::of(reference) {
+        ReferenceKind::Commit => &reference[..7],
+        ReferenceKind::Branch => reference,
     }
 }
 
-impl GitHubRepositoryFilePreview {
-    pub async fn new(message_url: Url) -> Result<Self, Box<dyn Error + Send + Sync>> {
-        let path_segments: Vec<&str> = message_url.path_segments().unwrap().collect();
+/// Rejects files with more lines than allowed for the kind of reference they were linked on.
+fn check_line_limit(
+    raw_content: &str,
+    reference_kind: ReferenceKind,
+    max_lines: Option<u32>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let Some(max_lines) = max_lines else {
+        return Ok(());
+    };
+
+    if raw_content.lines().count() <= max_lines as usize {
+        return Ok(());
+    }
+
+    Err(match reference_kind {
+        ReferenceKind::Commit => format!(
+            "The file has more than {} lines, which is the limit for previews.",
+            max_lines
+        ),
+        ReferenceKind::Branch => format!(
+            "Files on branches are only previewed up to {} lines, link a specific commit instead.",
+            max_lines
+        ),
+    }
+    .into())
+}
+
+/// Replaces the reference of a GitHub repository file URL with a commit SHA, keeping the path and fragment.
+pub(super) fn pin_to_commit(message_url: &Url, commit_sha: &str) -> Option<Url> {
+    let mut path_segments: Vec<&str> = message_url.path_segments()?.collect();
+
+    match path_segments.as_mut_slice() {
+        [_, _, "blob" | "blame", reference, path @ ..] if !path.is_empty() => {
+            *reference = commit_sha;
+        }
+        _ => return None,
+    }
+
+    // The segments are still percent-encoded, which setting the path keeps as is.
+    let mut permalink = message_url.clone();
+    permalink.set_path(format!("/{}", path_segments.join("/")).as_str());
+
+    Some(permalink)
+}
+
+/// Rewrites links to raw files and to the web editor on github.dev to the file on github.com,
+/// e.g. `raw.githubusercontent.com/owner/repo/main/README.md` to `github.com/owner/repo/blob/main/README.md`.
+/// Raw links using the full name of a branch or tag, e.g. `refs/heads/main`, are shortened to the name.
+pub(super) fn to_canonical_url(url: &Url) -> Option<Url> {
+    let path_segments: Vec<&str> = url.path_segments()?.collect();
+
+    let canonical_path = match (url.host_str()?, path_segments.as_slice()) {
+        ("github.com", _) => return Some(url.clone()),
+        ("github.dev", _) => path_segments.join("/"),
+        (
+            "raw.githubusercontent.com",
+            [author, repository, "refs", "heads" | "tags", reference, path @ ..]
+            | [author, repository, reference, path @ ..],
+        ) if !path.is_empty() => {
+            format!(
+                "{}/{}/blob/{}/{}",
+                author,
+                repository,
+                reference,
+                path.join("/")
+            )
+        }
+        _ => return None,
+    };
+
+    // The segments are still percent-encoded, which setting the path keeps as is.
+    let mut canonical_url = Url::parse("https://github.com/").unwrap();
+    canonical_url.set_path(canonical_path.as_str());
+    canonical_url.set_fragment(url.fragment());
+
+    Some(canonical_url)
+}
+
+/// Whether the URL points to the blame view of a file, e.g. `/owner/repo/blame/main/src/main.rs#L12`.
+pub(super) fn is_blame_url(message_url: &Url) -> bool {
+    message_url
+        .path_segments()
+        .is_some_and(|mut path_segments| path_segments.nth(2) == Some("blame"))
+}
+
+/// Finds the line with the given content that is closest to the line number,
+/// as lines move when the surrounding code changes.
+fn find_closest_line(raw_content: &str, line: &str, line_number: u32) -> Option<u32> {
+    raw_content
+        .lines()
+        .zip(1..)
+        .filter(|(other_line, _)| *other_line == line)
+        .map(|(_, other_line_number)| other_line_number)
+        .min_by_key(|other_line_number| other_line_number.abs_diff(line_number))
+}
+
+/// Resolves the URL of the file at the commit that last changed the linked line of a blame URL.
+/// The line is linked where it was in that commit, unless the file has been renamed since.
+pub(super) async fn resolve_blamed_commit_url(
+    message_url: &Url,
+) -> Result<Url, Box<dyn Error + Send + Sync>> {
+    let location = GitHubRepositoryFileLocation::parse(message_url)?;
+
+    let line_number = match Anchor::parse_fragment(message_url)? {
+        Anchor::Lines {
+            top_line_number, ..
+        } => top_line_number,
+        Anchor::Columns { line_number, .. } => line_number,
+        _ => return Err("The preview doesn't link a line.".into()),
+    };
+
+    let (blamed_commit_sha, raw_content) = join!(
+        get_blamed_commit_sha(
+            location.author.as_str(),
+            location.repository.as_str(),
+            location.reference.as_str(),
+            location.path.as_str(),
+            line_number,
+        ),
+        fetch_raw_content(location.raw_url()),
+    );
+
+    let blamed_location = GitHubRepositoryFileLocation {
+        reference: blamed_commit_sha?,
+        ..location
+    };
+
+    let line = raw_content?
+        .lines()
+        .nth((line_number as usize).saturating_sub(1))
+        .map(ToOwned::to_owned)
+        .ok_or("The linked line doesn't exist in the file.")?;
+
+    let blamed_raw_content = fetch_raw_content(blamed_location.raw_url())
+        .await
+        .map_err(|_| "The file has been renamed or moved since the line was last changed.")?;
+
+    let blamed_line_number =
+        find_closest_line(&blamed_raw_content, &line, line_number).unwrap_or(line_number);
+
+    let mut blamed_url = blamed_location.blob_url();
+    blamed_url.set_fragment(Some(format!("L{}", blamed_line_number).as_str()));
+
+    Ok(blamed_url)
+}
+
+/// The file that a GitHub repository URL points to.
+#[derive(Debug, PartialEq, Eq)]
+struct GitHubRepositoryFileLocation {
+    author: String,
+    repository: String,
+    reference: String,
+    path: String,
+}
+
+impl GitHubRepositoryFileLocation {
+    fn parse(message_url: &Url) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let path_segments: Vec<&str> = message_url
+            .path_segments()
+            .ok_or("Malformed GitHub repository URL.")?
+            .collect();
 
         let (author, repository, reference, urlencoded_path) = match path_segments.as_slice() {
-            [author, repository, "blob" | "blame", reference, urlencoded_path @ ..] => {
+            [author, repository, "blob" | "blame", reference, urlencoded_path @ ..]
+                if !urlencoded_path.is_empty() =>
+            {
                 (author, repository, reference, urlencoded_path.join("/"))
             }
             _ => return Err("Malformed GitHub repository URL.".into()),
@@ -37,23 +234,73 @@ impl GitHubRepositoryFilePreview {
             .decode_utf8()
             .map_err(|_| "Failed to decode GitHub URL file path.")?;
 
+        Ok(Self {
+            author: (*author).to_owned(),
+            repository: (*repository).to_owned(),
+            reference: (*reference).to_owned(),
+            path: path.into_owned(),
+        })
+    }
+
+    fn raw_url(&self) -> Url {
+        let mut raw_url = Url::parse("https://raw.githubusercontent.com/").unwrap();
+        raw_url.path_segments_mut().unwrap().extend(&[
+            self.author.as_str(),
+            self.repository.as_str(),
+            self.reference.as_str(),
+            self.path.as_str(),
+        ]);
+
+        raw_url
+    }
+
+    fn blob_url(&self) -> Url {
+        let mut blob_url = Url::parse("https://github.com/").unwrap();
+        blob_url
+            .path_segments_mut()
+            .unwrap()
+            .extend(&[
+                self.author.as_str(),
+                self.repository.as_str(),
+                "blob",
+                self.reference.as_str(),
+            ])
+            .extend(self.path.split('/'));
+
+        blob_url
+    }
+}
+
+impl GitHubRepositoryFilePreview {
+    pub async fn new(message_url: Url) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let location = GitHubRepositoryFileLocation::parse(&message_url)?;
+        let GitHubRepositoryFileLocation {
+            author,
+            repository,
+            reference,
+            path,
+        } = &location;
+
         let metadata_content = MessageBuilder::new()
-            .push_bold_safe(author.to_owned())
+            .push_bold_safe(author.as_str())
             .push("/")
-            .push_bold_safe(repository.to_owned())
+            .push_bold_safe(repository.as_str())
             .push(" (on ")
             .push_safe(get_short_reference(reference))
             .push_line(")")
-            .push_line_safe(path.as_ref())
+            .push_line_safe(path.as_str())
             .build();
 
-        let mut raw_url = Url::parse("https://raw.githubusercontent.com/").unwrap();
-        raw_url.path_segments_mut().unwrap().extend(&[
-            author,
-            repository,
-            reference,
-            path.as_ref(),
-        ]);
+        let metadata = FilePreviewMetadata {
+            source: format!(
+                "{}/{} (on {})",
+                author,
+                repository,
+                get_short_reference(reference)
+            ),
+            file_path: path.to_owned(),
+            owner_avatar_url: Some(format!("https://github.com/{}.png?size=128", author)),
+        };
 
         let file_name = message_url
             .path_segments()
@@ -64,18 +311,58 @@ impl GitHubRepositoryFilePreview {
             .extension()
             .map(|extension| extension.to_string_lossy().into_owned());
 
-        let raw_content = fetch_raw_content(raw_url).await?;
+        let reference_kind = ReferenceKind::of(reference);
+
+        let fetch_content = async {
+            // PDF files aren't text, their pages are rendered instead.
+            if is_pdf_file(file_name) {
+                return Ok((
+                    String::new(),
+                    Some(fetch_raw_pdf(location.raw_url()).await?),
+                ));
+            }
+
+            // Branches may be limited more strictly, as their content can change after the preview was posted.
+            let file_limits = reference_kind.file_limits();
+
+            let raw_content =
+                fetch_limited_raw_content(location.raw_url(), file_limits.max_size).await?;
+            check_line_limit(&raw_content, reference_kind, file_limits.max_lines)?;
+
+            Ok::<_, Box<dyn Error + Send + Sync>>((raw_content, None))
+        };
+
+        // Without a commit, e.g. if GitHub is rate limiting the bot, the preview just can't be linked permanently.
+        let resolve_commit = async {
+            match reference_kind {
+                ReferenceKind::Branch => {
+                    resolve_commit_sha(author, repository, reference).await.ok()
+                }
+                ReferenceKind::Commit => None,
+            }
+        };
+
+        let (content, commit_sha) = join!(fetch_content, resolve_commit);
+        let (raw_content, raw_pdf) = content?;
 
         Ok(Self {
             message_url,
+            location,
             metadata_content,
+            metadata,
             file_extension,
             raw_content,
+            raw_pdf,
+            commit_sha,
         })
     }
 }
 
 impl FilePreview for GitHubRepositoryFilePreview {
+    fn get_source(&self) -> PreviewSource {
+        PreviewSource::GitHubRepositoryFile
+    }
+
     fn get_message_url(&self) -> &Url {
         &self.message_url
     }
@@ -84,6 +371,10 @@ impl FilePreview for GitHubRepositoryFilePreview {
         self.metadata_content.as_str()
     }
 
+    fn get_metadata(&self) -> &FilePreviewMetadata {
+        &self.metadata
+    }
+
     fn get_file_extension(&self) -> Option<&str> {
         self.file_extension.as_deref()
     }
@@ -91,4 +382,196 @@ impl FilePreview for GitHubRepositoryFilePreview {
     fn get_raw_content(&self) -> &str {
         self.raw_content.as_str()
     }
+
+    fn get_raw_pdf(&self) -> Option<&[u8]> {
+        self.raw_pdf.as_deref()
+    }
+
+    fn get_commit_sha(&self) -> Option<&str> {
+        self.commit_sha.as_deref()
+    }
+
+    fn get_repository(&self) -> Option<(&str, &str)> {
+        Some((
+            self.location.author.as_str(),
+            self.location.repository.as_str(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    /// A path segment as it appears in a URL, which may be percent-encoded.
+    fn path_segment() -> impl Strategy<Value = String> {
+        "[A-Za-z0-9._~%-]{1,16}"
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics_on_arbitrary_paths(path in "\\PC*") {
+            if let Ok(url) = Url::parse(format!("https://github.com/{}", path).as_str()) {
+                if let Ok(location) = GitHubRepositoryFileLocation::parse(&url) {
+                    let _ = location.raw_url();
+                }
+            }
+        }
+
+        #[test]
+        fn never_panics_on_arbitrary_urls(url in "[a-z]{1,8}:\\PC*") {
+            if let Ok(url) = Url::parse(url.as_str()) {
+                let _ = GitHubRepositoryFileLocation::parse(&url);
+            }
+        }
+
+        #[test]
+        fn parses_file_urls(
+            author in "[A-Za-z0-9-]{1,16}",
+            repository in "[A-Za-z0-9._-]{1,16}",
+            reference in "[A-Za-z0-9._-]{1,16}",
+            path_segments in prop::collection::vec("[A-Za-z0-9._ -]{1,16}", 1..4),
+            kind in prop::sample::select(vec!["blob", "blame"]),
+        ) {
+            prop_assume!(![".", ".."].contains(&repository.as_str()));
+            prop_assume!(![".", ".."].contains(&reference.as_str()));
+            prop_assume!(path_segments.iter().all(|segment| ![".", ".."].contains(&segment.as_str())));
+
+            let mut url = Url::parse("https://github.com/").unwrap();
+            url.path_segments_mut()
+                .unwrap()
+                .extend([author.as_str(), repository.as_str(), kind, reference.as_str()])
+                .extend(&path_segments);
+
+            let location = GitHubRepositoryFileLocation::parse(&url).unwrap();
+
+            prop_assert_eq!(
+                &location,
+                &GitHubRepositoryFileLocation {
+                    author,
+                    repository,
+                    reference,
+                    path: path_segments.join("/"),
+                }
+            );
+            prop_assert_eq!(location.raw_url().host_str(), Some("raw.githubusercontent.com"));
+        }
+
+        #[test]
+        fn rejects_urls_without_file_path(
+            segments in prop::collection::vec(path_segment(), 0..4),
+        ) {
+            let mut url = Url::parse("https://github.com/").unwrap();
+            url.path_segments_mut().unwrap().extend(&segments);
+
+            prop_assert!(GitHubRepositoryFileLocation::parse(&url).is_err());
+        }
+
+        #[test]
+        fn shortens_only_commit_hashes(reference in "\\PC{0,48}") {
+            let short_reference = get_short_reference(reference.as_str());
+
+            prop_assert!(reference.starts_with(short_reference));
+            prop_assert!(short_reference == reference || short_reference.len() == 7);
+        }
+    }
+
+    #[test]
+    fn detects_commit_references() {
+        for reference in [
+            "0123456789abcdef0123456789abcdef01234567",
+            "0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF",
+        ] {
+            assert_eq!(ReferenceKind::of(reference), ReferenceKind::Commit);
+        }
+
+        for reference in [
+            "main",
+            "v1.0.0",
+            "0123456",
+            "0123456789abcdef0123456789abcdef0123456g",
+        ] {
+            assert_eq!(ReferenceKind::of(reference), ReferenceKind::Branch);
+        }
+    }
+
+    #[test]
+    fn limits_lines_of_files() {
+        let raw_content = "1\n2\n3\n";
+
+        assert!(check_line_limit(raw_content, ReferenceKind::Branch, None).is_ok());
+        assert!(check_line_limit(raw_content, ReferenceKind::Branch, Some(3)).is_ok());
+        assert!(check_line_limit(raw_content, ReferenceKind::Branch, Some(2)).is_err());
+        assert!(check_line_limit(raw_content, ReferenceKind::Commit, Some(2)).is_err());
+    }
+
+    #[test]
+    fn pins_urls_to_commits() {
+        let commit_sha = "0123456789abcdef0123456789abcdef01234567";
+        let url =
+            Url::parse("https://github.com/octocat/hello-world/blob/main/src/a%20b.rs#L10-L20")
+                .unwrap();
+
+        assert_eq!(
+            pin_to_commit(&url, commit_sha).unwrap().as_str(),
+            "https://github.com/octocat/hello-world/blob/0123456789abcdef0123456789abcdef01234567/src/a%20b.rs#L10-L20"
+        );
+
+        let url = Url::parse("https://github.com/octocat/hello-world/tree/main").unwrap();
+        assert!(pin_to_commit(&url, commit_sha).is_none());
+    }
+
+    #[test]
+    fn canonicalizes_raw_and_editor_urls() {
+        for (url, canonical_url) in [
+            (
+                "https://raw.githubusercontent.com/octocat/hello-world/main/src/main.rs#L3",
+                "https://github.com/octocat/hello-world/blob/main/src/main.rs#L3",
+            ),
+            (
+                "https://raw.githubusercontent.com/octocat/hello-world/refs/heads/main/README.md?token=secret#L1-L2",
+                "https://github.com/octocat/hello-world/blob/main/README.md#L1-L2",
+            ),
+            (
+                "https://github.dev/octocat/hello-world/blob/main/my%20file.rs#L7",
+                "https://github.com/octocat/hello-world/blob/main/my%20file.rs#L7",
+            ),
+        ] {
+            assert_eq!(
+                to_canonical_url(&Url::parse(url).unwrap()).unwrap().as_str(),
+                canonical_url
+            );
+        }
+
+        let url = Url::parse("https://raw.githubusercontent.com/octocat/hello-world/main").unwrap();
+        assert!(to_canonical_url(&url).is_none());
+    }
+
+    #[test]
+    fn finds_closest_matching_line() {
+        let raw_content = "}\nfn a() {\n}\n\nfn b() {\n}\n";
+
+        assert_eq!(find_closest_line(raw_content, "}", 5), Some(6));
+        assert_eq!(find_closest_line(raw_content, "}", 2), Some(1));
+        assert_eq!(find_closest_line(raw_content, "fn c() {", 1), None);
+    }
+
+    #[test]
+    fn detects_blame_urls() {
+        let blame_url =
+            Url::parse("https://github.com/octocat/hello-world/blame/main/README.md#L3");
+        let blob_url = Url::parse("https://github.com/octocat/hello-world/blob/main/README.md#L3");
+
+        assert!(is_blame_url(&blame_url.unwrap()));
+        assert!(!is_blame_url(&blob_url.unwrap()));
+    }
+
+    #[test]
+    fn rejects_urls_that_cannot_be_a_base() {
+        for url in ["mailto:octocat@github.com", "data:text/plain,blob"] {
+            assert!(GitHubRepositoryFileLocation::parse(&Url::parse(url).unwrap()).is_err());
+        }
+    }
 }
@@ -0,0 +1,260 @@
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use redis::AsyncCommands;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Color, Theme, ThemeSet};
+use syntect::html::{
+    start_highlighted_html_snippet, styled_line_to_highlighted_html, IncludeBackground,
+};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+use crate::bot::guild_config::AttachmentTheme;
+
+/// Larger attachments are posted as plain text, as highlighting them would take too long.
+const HIGHLIGHT_MAX_SIZE: usize = 524_288;
+
+/// Highlighting gives up after this long, e.g. on pathological input for the regular expressions of a syntax.
+const HIGHLIGHT_TIME_BUDGET: Duration = Duration::from_secs(2);
+
+/// Highlighted attachments are cached, as popular files tend to be linked repeatedly within a short time.
+const HIGHLIGHTED_HTML_TTL: u64 = 24 * 60 * 60;
+
+/// Larger documents aren't cached, to bound the memory used in Redis.
+const HIGHLIGHTED_HTML_CACHE_MAX_SIZE: usize = 1_048_576;
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+fn get_theme_name(theme: AttachmentTheme) -> &'static str {
+    match theme {
+        AttachmentTheme::Dark => "base16-ocean.dark",
+        AttachmentTheme::Light => "InspiredGitHub",
+    }
+}
+
+fn get_syntax_and_theme(
+    language: &str,
+    theme: AttachmentTheme,
+) -> Option<(&'static SyntaxReference, &'static Theme)> {
+    let syntax = SYNTAX_SET
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+
+    Some((syntax, THEME_SET.themes.get(get_theme_name(theme))?))
+}
+
+fn get_rgb(color: Color) -> [u8; 3] {
+    [color.r, color.g, color.b]
+}
+
+/// Lines split into regions of text with the same color, for drawing them.
+pub(super) struct HighlightedLines {
+    pub(super) background: [u8; 3],
+    pub(super) foreground: [u8; 3],
+    pub(super) lines: Vec<Vec<([u8; 3], String)>>,
+}
+
+/// Highlights the lines for drawing them, or returns `None` if it doesn't finish within the deadline.
+pub(super) fn highlight_lines(
+    lines: &[String],
+    language: &str,
+    theme: AttachmentTheme,
+    deadline: Instant,
+) -> Option<HighlightedLines> {
+    let (syntax, theme) = get_syntax_and_theme(language, theme)?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut highlighted_lines = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        if Instant::now() >= deadline {
+            return None;
+        }
+
+        // The default syntaxes expect lines to end with a newline.
+        let line = format!("{}\n", line);
+
+        highlighted_lines.push(
+            highlighter
+                .highlight_line(line.as_str(), &SYNTAX_SET)
+                .ok()?
+                .into_iter()
+                .map(|(style, text)| {
+                    (
+                        get_rgb(style.foreground),
+                        text.trim_end_matches('\n').to_owned(),
+                    )
+                })
+                .filter(|(_, text)| !text.is_empty())
+                .collect(),
+        );
+    }
+
+    Some(HighlightedLines {
+        background: get_rgb(theme.settings.background?),
+        foreground: get_rgb(theme.settings.foreground?),
+        lines: highlighted_lines,
+    })
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn redis_highlighted_html_key(
+    title: &str,
+    content: &str,
+    language: &str,
+    theme: AttachmentTheme,
+) -> String {
+    let mut hasher = blake3::Hasher::new();
+
+    for part in [title, language, get_theme_name(theme), content] {
+        hasher.update(&(part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+
+    format!("highlighted_html:{}", hasher.finalize().to_hex())
+}
+
+/// Renders the content as a standalone HTML document with inline styles,
+/// or returns `None` if it doesn't finish within the deadline.
+fn highlight_to_html(
+    title: &str,
+    content: &str,
+    language: &str,
+    theme: AttachmentTheme,
+    deadline: Instant,
+) -> Option<String> {
+    let (syntax, theme) = get_syntax_and_theme(language, theme)?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let (snippet_start, _) = start_highlighted_html_snippet(theme);
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body style=\"margin:0\">\n{}",
+        escape_html(title),
+        snippet_start
+    );
+
+    for line in LinesWithEndings::from(content) {
+        if Instant::now() >= deadline {
+            return None;
+        }
+
+        let regions = highlighter.highlight_line(line, &SYNTAX_SET).ok()?;
+        html.push_str(
+            styled_line_to_highlighted_html(&regions, IncludeBackground::No)
+                .ok()?
+                .as_str(),
+        );
+    }
+
+    html.push_str("</pre>\n</body>\n</html>\n");
+
+    Some(html)
+}
+
+/// Highlights the content of an attachment, which Discord only shows as plain text.
+/// Returns `None` if the content is too large or highlighting exceeds its time budget.
+pub(super) async fn get_highlighted_html(
+    connection: &mut redis::aio::ConnectionManager,
+    title: &str,
+    content: &str,
+    language: Option<&str>,
+    theme: AttachmentTheme,
+) -> Option<String> {
+    if content.len() > HIGHLIGHT_MAX_SIZE {
+        return None;
+    }
+
+    let language = language.unwrap_or("txt");
+    let redis_key = redis_highlighted_html_key(title, content, language, theme);
+
+    if let Some(html) = connection
+        .get::<_, Option<String>>(redis_key.as_str())
+        .await
+        .ok()
+        .flatten()
+    {
+        return Some(html);
+    }
+
+    let deadline = Instant::now() + HIGHLIGHT_TIME_BUDGET;
+    let (title, content, language) = (title.to_owned(), content.to_owned(), language.to_owned());
+
+    let html = tokio::task::spawn_blocking(move || {
+        highlight_to_html(&title, &content, &language, theme, deadline)
+    })
+    .await
+    .ok()
+    .flatten()?;
+
+    if html.len() <= HIGHLIGHTED_HTML_CACHE_MAX_SIZE {
+        let _: Result<(), _> = connection
+            .set_ex(redis_key, html.as_str(), HIGHLIGHTED_HTML_TTL)
+            .await;
+    }
+
+    Some(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_into_html_document() {
+        let html = highlight_to_html(
+            "<main.rs>",
+            "fn main() {}\n",
+            "rs",
+            AttachmentTheme::Dark,
+            Instant::now() + HIGHLIGHT_TIME_BUDGET,
+        )
+        .unwrap();
+
+        assert!(html.contains("<title>&lt;main.rs&gt;</title>"));
+        assert!(html.contains("<span style=\""));
+        assert!(html.ends_with("</pre>\n</body>\n</html>\n"));
+    }
+
+    #[test]
+    fn highlights_lines_into_regions() {
+        let highlighted_lines = highlight_lines(
+            &["let x = 1;".to_owned(), String::new()],
+            "rs",
+            AttachmentTheme::Light,
+            Instant::now() + HIGHLIGHT_TIME_BUDGET,
+        )
+        .unwrap();
+
+        assert_eq!(highlighted_lines.lines.len(), 2);
+        assert!(highlighted_lines.lines[0].len() > 1);
+        assert!(highlighted_lines.lines[1].is_empty());
+        assert_eq!(
+            highlighted_lines.lines[0]
+                .iter()
+                .map(|(_, text)| text.as_str())
+                .collect::<String>(),
+            "let x = 1;"
+        );
+    }
+
+    #[test]
+    fn gives_up_after_deadline() {
+        let html = highlight_to_html(
+            "main.rs",
+            "fn main() {}\n",
+            "rs",
+            AttachmentTheme::Light,
+            Instant::now(),
+        );
+
+        assert!(html.is_none());
+    }
+}
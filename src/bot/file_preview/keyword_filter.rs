@@ -0,0 +1,90 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::bot::guild_config::{GuildConfig, KeywordFilterAction};
+
+/// Upper bound for the compiled size of a single guild-provided pattern.
+const KEYWORD_FILTER_SIZE_LIMIT: usize = 64 * 1024;
+
+pub(crate) const KEYWORD_FILTER_MAX_PATTERNS: usize = 25;
+
+pub(crate) fn compile_keyword_filter(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(KEYWORD_FILTER_SIZE_LIMIT)
+        .build()
+}
+
+pub(super) enum KeywordFilterOutcome<'a> {
+    Passed,
+    Blocked(Vec<&'a str>),
+    Redacted(Vec<String>, Vec<&'a str>),
+}
+
+/// Applies the keyword filter of the guild to the raw lines of a preview, before line numbers or other formatting are added.
+/// Patterns are matched against each line on its own, so that `^` and `$` anchor to the start and end of a line.
+pub(super) fn apply_keyword_filter<'a>(
+    guild_config: &'a GuildConfig,
+    lines: &[String],
+) -> KeywordFilterOutcome<'a> {
+    let matched_filters: Vec<(&str, Regex)> = guild_config
+        .keyword_filters
+        .iter()
+        .filter_map(|pattern| {
+            compile_keyword_filter(pattern)
+                .ok()
+                .filter(|regex| lines.iter().any(|line| regex.is_match(line)))
+                .map(|regex| (pattern.as_str(), regex))
+        })
+        .collect();
+
+    if matched_filters.is_empty() {
+        return KeywordFilterOutcome::Passed;
+    }
+
+    let matched_patterns = matched_filters
+        .iter()
+        .map(|(pattern, _)| *pattern)
+        .collect();
+
+    match guild_config.keyword_filter_action {
+        KeywordFilterAction::Block => KeywordFilterOutcome::Blocked(matched_patterns),
+        KeywordFilterAction::Redact => KeywordFilterOutcome::Redacted(
+            lines
+                .iter()
+                .map(|line| {
+                    matched_filters
+                        .iter()
+                        .fold(line.to_owned(), |line, (_, regex)| {
+                            regex.replace_all(&line, "[redacted]").into_owned()
+                        })
+                })
+                .collect(),
+            matched_patterns,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_anchored_patterns_per_line() {
+        let guild_config = GuildConfig {
+            keyword_filters: vec!["^token = .*$".to_owned(), r"\d{4}".to_owned()],
+            keyword_filter_action: KeywordFilterAction::Redact,
+            ..GuildConfig::default()
+        };
+
+        let lines = ["let id = 1;".to_owned(), "token = abc".to_owned()];
+
+        let KeywordFilterOutcome::Redacted(redacted_lines, matched_patterns) =
+            apply_keyword_filter(&guild_config, &lines)
+        else {
+            panic!("anchored pattern didn't match");
+        };
+
+        assert_eq!(redacted_lines, ["let id = 1;", "[redacted]"]);
+        assert_eq!(matched_patterns, ["^token = .*$"]);
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use reqwest::{header, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::config::config;
+use crate::outbound::{guarded_get, send_limited};
+use crate::HTTP_CLIENT;
+
+use super::retry_queue::request_failed_error;
+
+/// LFS objects are only downloaded for previews up to this size, larger ones are only described.
+const LFS_PREVIEW_MAX_SIZE: u64 = 1_048_576;
+
+/// Pointer files are at most a few hundred bytes, see the Git LFS specification.
+const LFS_POINTER_MAX_LENGTH: usize = 1024;
+
+/// The content of a file in a Git repository that is stored using Git LFS.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct LfsPointer {
+    pub(crate) oid: String,
+    pub(crate) size: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct APILfsBatchRequest<'a> {
+    operation: &'static str,
+    transfers: [&'static str; 1],
+    objects: [&'a LfsPointer; 1],
+}
+
+#[derive(Debug, Deserialize)]
+struct APILfsBatchResponse {
+    objects: Vec<APILfsObject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APILfsObject {
+    actions: Option<APILfsActions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APILfsActions {
+    download: APILfsAction,
+}
+
+#[derive(Debug, Deserialize)]
+struct APILfsAction {
+    href: String,
+    #[serde(default)]
+    header: HashMap<String, String>,
+}
+
+/// Parses the content of an LFS pointer file, returning `None` for regular files.
+pub(crate) fn parse_lfs_pointer(content: &str) -> Option<LfsPointer> {
+    if content.len() > LFS_POINTER_MAX_LENGTH
+        || !content.starts_with("version https://git-lfs.github.com/spec/v1\n")
+    {
+        return None;
+    }
+
+    let mut oid = None;
+    let mut size = None;
+
+    for line in content.lines() {
+        match line.split_once(' ') {
+            Some(("oid", value)) => oid = value.strip_prefix("sha256:").map(str::to_owned),
+            Some(("size", value)) => size = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some(LfsPointer {
+        oid: oid?,
+        size: size?,
+    })
+}
+
+pub(crate) fn format_file_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if size < 1024 {
+        return format!("{} bytes", size);
+    }
+
+    let mut value = size as f64 / 1024.0;
+    let mut unit_index = 0;
+
+    while value >= 1024.0 && unit_index + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit_index += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit_index])
+}
+
+/// Describes an LFS object that can't be previewed, using the extension of the file name as its type.
+fn describe_lfs_object(raw_url: &Url, lfs_pointer: &LfsPointer, reason: &str) -> String {
+    let file_type = raw_url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|file_name| file_name.rsplit_once('.'))
+        .map_or_else(
+            || "file".to_owned(),
+            |(_, extension)| format!("{} file", extension.to_ascii_uppercase()),
+        );
+
+    format!(
+        "The {} is stored using Git LFS ({}) and {}.",
+        file_type,
+        format_file_size(lfs_pointer.size),
+        reason
+    )
+}
+
+/// Requests the download URL of the object from the LFS batch API of the repository.
+async fn request_lfs_download(
+    owner: &str,
+    repository: &str,
+    github_token: &str,
+    lfs_pointer: &LfsPointer,
+) -> Result<APILfsAction, Box<dyn Error + Send + Sync>> {
+    let mut batch_url = Url::parse("https://github.com/").unwrap();
+    batch_url
+        .path_segments_mut()
+        .unwrap()
+        .extend(&[owner, format!("{}.git", repository).as_str()])
+        .extend(&["info", "lfs", "objects", "batch"]);
+
+    let response = send_limited(
+        HTTP_CLIENT
+            .post(batch_url)
+            .header(header::ACCEPT, "application/vnd.git-lfs+json")
+            .header(header::CONTENT_TYPE, "application/vnd.git-lfs+json")
+            .basic_auth("x-access-token", Some(github_token))
+            .json(&APILfsBatchRequest {
+                operation: "download",
+                transfers: ["basic"],
+                objects: [lfs_pointer],
+            }),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        return Err(request_failed_error(
+            response.status(),
+            "LFS API request failed.",
+        ));
+    }
+
+    let batch_response: APILfsBatchResponse = response.json().await?;
+
+    Ok(batch_response
+        .objects
+        .into_iter()
+        .next()
+        .and_then(|object| object.actions)
+        .ok_or("LFS object not found.")?
+        .download)
+}
+
+/// Resolves the content of an LFS object that a raw GitHub URL returned the pointer of.
+/// Only text files up to [`LFS_PREVIEW_MAX_SIZE`] are downloaded, and only if `GITHUB_TOKEN` is set.
+/// Otherwise, the error describes the size and type of the actual file.
+pub(crate) async fn resolve_lfs_pointer(
+    raw_url: &Url,
+    lfs_pointer: LfsPointer,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let repository = match raw_url.path_segments() {
+        Some(mut segments) if raw_url.host_str() == Some("raw.githubusercontent.com") => {
+            segments.next().zip(segments.next())
+        }
+        _ => None,
+    };
+
+    let (Some((owner, repository)), Some(github_token)) =
+        (repository, config().github_token.as_deref())
+    else {
+        return Err(describe_lfs_object(raw_url, &lfs_pointer, "can't be previewed").into());
+    };
+
+    if lfs_pointer.size > LFS_PREVIEW_MAX_SIZE {
+        return Err(
+            describe_lfs_object(raw_url, &lfs_pointer, "is too large to be previewed").into(),
+        );
+    }
+
+    let download = request_lfs_download(owner, repository, github_token, &lfs_pointer).await?;
+
+    let mut request = guarded_get(Url::parse(download.href.as_str())?)?;
+
+    for (name, value) in &download.header {
+        request = request.header(name.as_str(), value.as_str());
+    }
+
+    let response = send_limited(request).await?;
+
+    if !response.status().is_success() {
+        return Err(request_failed_error(
+            response.status(),
+            "LFS object download failed.",
+        ));
+    }
+
+    let content = response.bytes().await?;
+
+    if content.len() as u64 > LFS_PREVIEW_MAX_SIZE {
+        return Err(
+            describe_lfs_object(raw_url, &lfs_pointer, "is too large to be previewed").into(),
+        );
+    }
+
+    String::from_utf8(content.to_vec())
+        .map_err(|_| describe_lfs_object(raw_url, &lfs_pointer, "is not a text file").into())
+}
@@ -0,0 +1,103 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Lines wrapped into more rows than this are cut off, as they are rarely code worth reading, e.g. minified files.
+const MAX_WRAPPED_ROWS: usize = 4;
+
+/// Ends the last row of a line that was cut off.
+const TRUNCATION_MARKER: char = '…';
+
+/// Columns taken up by a character in the monospace font of Discord, where wide characters such as CJK take two.
+fn get_char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+fn get_line_width(line: &str) -> usize {
+    line.chars().map(get_char_width).sum()
+}
+
+/// Splits a line into rows that are at most `max_width` columns wide, preferring to break after whitespace
+/// in the second half of a row. Lines that would need more than [`MAX_WRAPPED_ROWS`] rows are cut off.
+pub(super) fn wrap_line(line: &str, max_width: usize) -> Vec<String> {
+    let max_width = max_width.max(2);
+    let mut rows: Vec<String> = Vec::new();
+    let mut remaining = line;
+
+    while get_line_width(remaining) > max_width {
+        if rows.len() + 1 == MAX_WRAPPED_ROWS {
+            let mut row = String::new();
+            let mut row_width = 0;
+
+            for c in remaining.chars() {
+                if row_width + get_char_width(c) > max_width - 1 {
+                    break;
+                }
+
+                row.push(c);
+                row_width += get_char_width(c);
+            }
+
+            row.push(TRUNCATION_MARKER);
+            rows.push(row);
+
+            return rows;
+        }
+
+        let mut break_index = 0;
+        let mut whitespace_break_index = None;
+        let mut row_width = 0;
+
+        for (index, c) in remaining.char_indices() {
+            if row_width + get_char_width(c) > max_width {
+                break;
+            }
+
+            row_width += get_char_width(c);
+            break_index = index + c.len_utf8();
+
+            if c.is_whitespace() && row_width > max_width / 2 {
+                whitespace_break_index = Some(break_index);
+            }
+        }
+
+        let (row, rest) = remaining.split_at(whitespace_break_index.unwrap_or(break_index));
+        rows.push(row.trim_end().to_owned());
+        remaining = rest;
+    }
+
+    rows.push(remaining.to_owned());
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_short_lines() {
+        assert_eq!(wrap_line("let x = 1;", 20), vec!["let x = 1;"]);
+        assert_eq!(wrap_line("", 20), vec![""]);
+    }
+
+    #[test]
+    fn wraps_after_whitespace() {
+        assert_eq!(
+            wrap_line("let value = compute(first, second);", 20),
+            vec!["let value =", "compute(first,", "second);"]
+        );
+    }
+
+    #[test]
+    fn counts_wide_characters_twice() {
+        assert_eq!(get_line_width("日本語"), 6);
+        assert_eq!(wrap_line("日本語日本語", 6), vec!["日本語", "日本語"]);
+    }
+
+    #[test]
+    fn cuts_off_long_lines() {
+        let rows = wrap_line("x".repeat(100).as_str(), 10);
+
+        assert_eq!(rows.len(), MAX_WRAPPED_ROWS);
+        assert!(rows.iter().all(|row| get_line_width(row) <= 10));
+        assert!(rows.last().unwrap().ends_with(TRUNCATION_MARKER));
+    }
+}
@@ -1,102 +1,203 @@
 use std::error::Error;
 use std::fmt::Write;
+use std::time::Instant;
 
-use once_cell::sync::Lazy;
-use regex::Regex;
 use reqwest::Url;
 use serenity::all::{
-    ButtonStyle, ComponentInteraction, CreateActionRow, CreateAllowedMentions, CreateAttachment,
-    CreateButton, CreateMessage, EditAttachments, EditMessage, Message, MessageBuilder,
-    MessageReference,
+    ActionRowComponent, AutoArchiveDuration, Button, ButtonKind, ButtonStyle, ChannelId,
+    ComponentInteraction, CreateActionRow, CreateAllowedMentions, CreateAttachment, CreateButton,
+    CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+    CreateMessage, CreateThread, EditAttachments, EditMessage, GuildId, Message, MessageBuilder,
+    MessageId, MessageReference, UserId,
 };
 use serenity::futures::future::join_all;
 use serenity::prelude::*;
 
-use crate::HTTP_CLIENT;
+use crate::bot::activity::{publish_activity, ActivityEvent};
+use crate::bot::audit_log::{send_audit_log, AuditLogEntry};
+use crate::bot::custom_id::{sign_custom_id, verify_custom_id};
+use crate::bot::github_api::get_repository_metadata;
+use crate::bot::guild_config::{AttachmentTheme, GuildConfig, PreviewSource, WhitespaceIndicators};
+use crate::bot::guild_stats::{
+    redis_increment_guild_stats, redis_record_guild_preview, GuildStatsCounter,
+};
+use crate::config::{config, runtime_config, PREVIEW_MAX_FILE_SIZE};
+use crate::feature_flags::{is_feature_enabled, FeatureFlag};
+use crate::outbound::get_following_redirects;
+use crate::SerenityGlobalData;
 
-use self::gist::GistFilePreview;
-use self::github_repository_file::GitHubRepositoryFilePreview;
+use self::anchor::{Anchor, AnchorSelection};
+use self::backpressure::{call_with_backpressure, SendPriority};
+use self::fetch_failures::fetch_remembering_failures;
+use self::flood_limit::{reserve_previews, FloodLimitOutcome};
+use self::github_compare::GitHubCompareFilePreview;
+use self::github_repository_file::{is_blame_url, pin_to_commit, resolve_blamed_commit_url};
+use self::highlight::get_highlighted_html;
+use self::keyword_filter::{apply_keyword_filter, KeywordFilterOutcome};
+use self::lfs::{parse_lfs_pointer, resolve_lfs_pointer};
+use self::line_wrap::wrap_line;
+use self::pdf::{render_pdf_page, RenderedPdfPage};
+use self::permissions::{get_preview_permissions, get_thread_parent_id};
+use self::processing_limit::acquire_processing_permit;
+use self::recycling::{recycle_preview, redis_set_indexed_preview};
+use self::render::{select_renderer, RenderContext};
+use self::retry_queue::{is_transient_error, redis_enqueue_preview_retry, request_failed_error};
+use self::scan_metrics::{record_scan_time, record_skipped_message};
+use self::screening::screen_repository_file;
+use self::snippet_image::{render_snippet_image, SNIPPET_IMAGE_MAX_LINES};
+use self::source::{PreviewSourceHandler, PREVIEW_SOURCE_REGISTRY};
+use self::whitespace::{is_diff_sensitive_file, normalize_line_endings, visualize_whitespace};
 
+mod anchor;
+pub(crate) mod backpressure;
+mod fetch_failures;
+mod flood_limit;
 mod gist;
+pub(crate) mod github_compare;
 mod github_repository_file;
+mod highlight;
+pub(crate) mod keyword_filter;
+pub(crate) mod lfs;
+mod line_wrap;
+mod pdf;
+mod permissions;
+pub(crate) mod processing_limit;
+mod recycling;
+mod render;
+pub(crate) mod retry_queue;
+pub(crate) mod scan_metrics;
+mod screening;
+mod snippet_image;
+mod source;
+mod whitespace;
 
-static GITHUB_REPOSITORY_FILE_URL_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r"https://github\.com(?:/[^/\s]+){2}/(?:blob|blame)(?:/[^/\s]+)+#(?:[^/\s]*L[^/\s]*)+",
-    )
-    .unwrap()
-});
+/// Hosts that raw file content may be served from, including redirects, e.g. for renamed repositories.
+const RAW_CONTENT_HOSTS: [&str; 2] = ["github.com", "githubusercontent.com"];
+
+pub const DELETE_CUSTOM_ID_PREFIX: &str = "deleteFilePreview";
+pub const PERMALINK_CUSTOM_ID_PREFIX: &str = "copyPermalink";
+pub const BLAMED_COMMIT_CUSTOM_ID_PREFIX: &str = "blamedCommit";
+pub const RENDER_IMAGE_CUSTOM_ID_PREFIX: &str = "renderImage";
 
-static GIST_URL_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"https://gist\.github\.com(?:/[^/\s]+){2}#file\-[^\s]+").unwrap());
+/// Length of the shortest URL matched by any preview source.
+const MIN_PREVIEW_URL_LENGTH: usize = 30;
 
-static GITHUB_LINE_NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"L(\d+)").unwrap());
+/// Structured metadata for renderers that don't display the metadata content as is.
+struct FilePreviewMetadata {
+    /// The origin of the file, e.g. the repository and reference.
+    source: String,
+    file_path: String,
+    owner_avatar_url: Option<String>,
+}
 
 trait FilePreview: Sync + Send {
+    fn get_source(&self) -> PreviewSource;
     fn get_message_url(&self) -> &Url;
     fn get_metadata_content(&self) -> &str;
+    fn get_metadata(&self) -> &FilePreviewMetadata;
     fn get_file_extension(&self) -> Option<&str>;
     fn get_raw_content(&self) -> &str;
+
+    /// The content of PDF files, which are previewed by rendering one of their pages instead.
+    fn get_raw_pdf(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// The commit that a file linked on a branch was fetched from, for linking to it permanently.
+    fn get_commit_sha(&self) -> Option<&str> {
+        None
+    }
+
+    /// The owner and name of the GitHub repository that the file belongs to, if any.
+    fn get_repository(&self) -> Option<(&str, &str)> {
+        None
+    }
 }
 
 impl dyn FilePreview {
-    fn get_file_extension_with_alias(&self) -> Option<&str> {
-        self.get_file_extension().map(|extension| match extension {
-            "vsh" => "glsl",
-            "fsh" => "glsl",
-            "gsh" => "glsl",
-            "csh" => "glsl",
-            "vert" => "glsl",
-            "frag" => "glsl",
-            "inc" => "glsl",
-            _ => extension,
+    /// Applies the aliases of the guild, which take precedence over the configured ones.
+    fn get_file_extension_with_alias(&self, guild_config: &GuildConfig) -> Option<String> {
+        self.get_file_extension().map(|extension| {
+            let lowercase_extension = extension.to_ascii_lowercase();
+
+            guild_config
+                .language_aliases
+                .get(lowercase_extension.as_str())
+                .or_else(|| {
+                    runtime_config()
+                        .language_aliases
+                        .get(lowercase_extension.as_str())
+                })
+                .map_or(extension, String::as_str)
+                .to_owned()
         })
     }
 }
 
-#[derive(Debug)]
-enum PreviewUrlType {
-    GitHubRepositoryFile,
-    Gist,
-}
-
-#[derive(Debug)]
 struct PreviewUrlMatch<'a> {
     url_string: &'a str,
-    url_type: PreviewUrlType,
+    handler: &'static dyn PreviewSourceHandler,
     position: usize,
 }
 
 impl PreviewUrlMatch<'_> {
     fn get_url(&self) -> Result<Url, Box<dyn Error + Send + Sync>> {
-        Url::parse(self.url_string).map_err(|_| "The specified URL is malformed.".into())
+        let url = Url::parse(self.url_string).map_err(|_| "The specified URL is malformed.")?;
+
+        self.handler.canonicalize(url)
     }
 
-    async fn get_file_preview(self) -> Result<Box<dyn FilePreview>, Box<dyn Error + Send + Sync>> {
-        match self.url_type {
-            PreviewUrlType::GitHubRepositoryFile => Ok(Box::new(
-                GitHubRepositoryFilePreview::new(self.get_url()?).await?,
-            )),
-            PreviewUrlType::Gist => Ok(Box::new(GistFilePreview::new(self.get_url()?).await?)),
-        }
+    async fn get_file_preview(
+        self,
+        mut connection: redis::aio::ConnectionManager,
+    ) -> Result<(Box<dyn FilePreview>, Anchor), Box<dyn Error + Send + Sync>> {
+        let url = self.get_url()?;
+        let anchor = self.handler.parse(&url)?;
+
+        Ok((
+            fetch_remembering_failures(&mut connection, self.handler, url).await?,
+            anchor,
+        ))
     }
 }
 
-async fn fetch_raw_content(url: Url) -> Result<String, Box<dyn Error + Send + Sync>> {
-    let response = HTTP_CLIENT.get(url).send().await?;
+pub(crate) async fn fetch_raw_content(url: Url) -> Result<String, Box<dyn Error + Send + Sync>> {
+    fetch_limited_raw_content(url, PREVIEW_MAX_FILE_SIZE).await
+}
+
+/// Fetches the content of a file, rejecting files larger than `max_size` bytes.
+async fn fetch_limited_raw_content(
+    url: Url,
+    max_size: u64,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let response = get_following_redirects(url.clone(), &RAW_CONTENT_HOSTS).await?;
 
     if !response.status().is_success() {
-        return Err("API request failed.".into());
+        return Err(request_failed_error(
+            response.status(),
+            "API request failed.",
+        ));
     }
 
     if response
         .content_length()
-        .is_some_and(|file_size| file_size > 4_194_304)
+        .is_some_and(|file_size| file_size > max_size)
     {
         return Err("File size is too large.".into());
     }
 
-    Ok(response.text().await?)
+    let raw_content = response.text().await?;
+
+    // The content length is missing from compressed responses.
+    if raw_content.len() as u64 > max_size {
+        return Err("File size is too large.".into());
+    }
+
+    if let Some(lfs_pointer) = parse_lfs_pointer(raw_content.as_str()) {
+        return resolve_lfs_pointer(&url, lfs_pointer).await;
+    }
+
+    Ok(raw_content)
 }
 
 fn truncate_string(string: String, max_length: usize) -> String {
@@ -126,44 +227,192 @@ fn expand_tabs(input: &str, tab_size: usize) -> String {
     result
 }
 
+/// Where a file preview is posted, and on whose behalf.
+pub(crate) struct PreviewTarget<'a> {
+    pub(crate) author_id: UserId,
+    pub(crate) channel_id: ChannelId,
+    pub(crate) guild_id: Option<GuildId>,
+    /// The message containing the link, which the preview replies to.
+    pub(crate) reference_message: &'a Message,
+    /// Previews of links posted in messages are held back while the bot is close to the rate limits of Discord.
+    pub(crate) priority: SendPriority,
+}
+
+impl<'a> From<&'a Message> for PreviewTarget<'a> {
+    fn from(msg: &'a Message) -> Self {
+        Self {
+            author_id: msg.author.id,
+            channel_id: msg.channel_id,
+            guild_id: msg.guild_id,
+            reference_message: msg,
+            priority: SendPriority::Deferrable,
+        }
+    }
+}
+
+/// The repository metadata shown below a preview and the warning of link screening shown above it,
+/// depending on the guild config.
+async fn get_repository_annotations(
+    ctx: &Context,
+    guild_config: &GuildConfig,
+    file_preview: &dyn FilePreview,
+) -> (Option<String>, Option<String>) {
+    let Some((owner, repository)) = file_preview.get_repository() else {
+        return (None, None);
+    };
+
+    let repository_metadata =
+        if guild_config.show_repository_metadata || guild_config.link_screening {
+            let mut redis_connection_manager = ctx
+                .data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone();
+
+            // The preview is still useful without the metadata, e.g. while GitHub is rate limiting the bot.
+            get_repository_metadata(&mut redis_connection_manager, owner, repository)
+                .await
+                .ok()
+        } else {
+            None
+        };
+
+    let footer_line = repository_metadata
+        .as_ref()
+        .filter(|_| guild_config.show_repository_metadata)
+        .map(|repository_metadata| repository_metadata.format_footer());
+
+    let banner = if guild_config.link_screening {
+        screen_repository_file(
+            owner,
+            repository,
+            file_preview.get_metadata().file_path.as_str(),
+            repository_metadata.as_ref(),
+        )
+    } else {
+        None
+    };
+
+    (footer_line, banner)
+}
+
+/// Posts the preview of the anchored lines or PDF page, with an optional notice at the end of the footer.
 async fn send_file_preview(
     ctx: &Context,
-    msg: &Message,
+    target: &PreviewTarget<'_>,
+    guild_config: &GuildConfig,
     file_preview: Box<dyn FilePreview>,
+    anchor: Anchor,
+    notice: Option<&str>,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let line_numbers: Vec<u32> = GITHUB_LINE_NUMBER_REGEX
-        .captures_iter(
-            file_preview
-                .get_message_url()
-                .fragment()
-                .ok_or("The specified URL is malformed.")?,
+    if let Anchor::Page(page_number) = anchor {
+        let mut redis_connection_manager = ctx
+            .data::<SerenityGlobalData>()
+            .redis_connection_manager
+            .clone();
+
+        if !is_feature_enabled(
+            &mut redis_connection_manager,
+            FeatureFlag::PdfPages,
+            target.guild_id,
         )
-        .filter_map(|match_captures| match_captures[1].parse::<u32>().ok())
-        .collect();
+        .await
+        {
+            return Err("Previews of PDF pages are currently disabled.".into());
+        }
 
-    let top_line_number = *line_numbers
-        .iter()
-        .min()
-        .ok_or("At least one line number is required.")?;
+        return send_pdf_page_preview(
+            ctx,
+            target,
+            guild_config,
+            file_preview.as_ref(),
+            page_number,
+            notice,
+        )
+        .await;
+    }
 
-    let bottom_line_number = *line_numbers
-        .iter()
-        .max()
-        .ok_or("At least one line number is required.")?;
+    let (raw_content, line_ending) = normalize_line_endings(file_preview.get_raw_content());
 
-    let selected_content_lines: Vec<String> = file_preview
-        .get_raw_content()
-        .lines()
-        .skip(top_line_number as usize - 1)
-        .take((bottom_line_number - top_line_number + 1) as usize)
-        .map(|line| expand_tabs(line, 4))
-        .collect();
+    let selection = anchor.select(
+        raw_content.as_ref(),
+        runtime_config().preview_max_lines as usize,
+    )?;
+
+    let content_hash = guild_config
+        .show_content_hash
+        .then(|| selection.get_content_hash());
 
-    if selected_content_lines.is_empty() {
-        return Err("No content selected.".into());
+    let AnchorSelection {
+        first_line_number,
+        lines,
+        notices,
+    } = selection;
+
+    let Some(lines) =
+        filter_preview_lines(ctx, target, guild_config, file_preview.as_ref(), lines).await
+    else {
+        return Ok(());
+    };
+
+    let (repository_footer_line, banner) =
+        get_repository_annotations(ctx, guild_config, file_preview.as_ref()).await;
+
+    let mut footer_lines = notices;
+
+    if let Some(repository_footer_line) = repository_footer_line {
+        footer_lines.insert(0, repository_footer_line);
     }
 
-    let line_number_length = (top_line_number as usize + selected_content_lines.len() - 1)
+    if let Some(content_hash) = content_hash {
+        footer_lines.push(format!("BLAKE3 `{}`", content_hash));
+    }
+
+    if let Some(notice) = notice {
+        footer_lines.push(notice.to_owned());
+    }
+
+    let shows_whitespace = match guild_config.whitespace_indicators {
+        WhitespaceIndicators::Off => false,
+        WhitespaceIndicators::DiffSensitive => {
+            is_diff_sensitive_file(file_preview.get_metadata().file_path.as_str())
+        }
+        WhitespaceIndicators::All => true,
+    };
+
+    let selected_content_lines: Vec<String> = if shows_whitespace {
+        let mut trailing_whitespace_count = 0;
+
+        let selected_content_lines = lines
+            .iter()
+            .map(|line| {
+                let (line, has_trailing_whitespace) = visualize_whitespace(line, 4);
+                trailing_whitespace_count += usize::from(has_trailing_whitespace);
+                line
+            })
+            .collect();
+
+        match trailing_whitespace_count {
+            0 => {}
+            1 => footer_lines.push("1 line has trailing whitespace.".to_owned()),
+            _ => footer_lines.push(format!(
+                "{} lines have trailing whitespace.",
+                trailing_whitespace_count
+            )),
+        }
+
+        if let Some(line_ending) = line_ending {
+            footer_lines.push(format!(
+                "The file uses {} line endings, which are shown as LF.",
+                line_ending
+            ));
+        }
+
+        selected_content_lines
+    } else {
+        lines.iter().map(|line| expand_tabs(line, 4)).collect()
+    };
+
+    let line_number_length = (first_line_number + selected_content_lines.len() - 1)
         .to_string()
         .len()
         .max(1);
@@ -174,145 +423,758 @@ async fn send_file_preview(
         .sum::<usize>()
         + selected_content_lines.len() * (line_number_length + 4);
 
+    // Long lines are wrapped so that the mobile client doesn't have to scroll horizontally,
+    // with continuation rows marked in place of the line number.
+    let max_row_width = runtime_config()
+        .preview_max_line_width
+        .map(|max_line_width| (max_line_width as usize).saturating_sub(line_number_length + 3));
+
+    let mut row_count = 0;
+
     let file_content = selected_content_lines.iter().enumerate().fold(
         String::with_capacity(file_content_capacity),
         |mut output, (index, line)| {
-            let _ = writeln!(
-                output,
-                "{:width$} | {}",
-                top_line_number + index as u32,
-                line,
-                width = line_number_length
-            );
+            let rows = match max_row_width {
+                Some(max_row_width) => wrap_line(line, max_row_width),
+                None => vec![line.clone()],
+            };
+
+            for (row_index, row) in rows.iter().enumerate() {
+                let _ = if row_index == 0 {
+                    writeln!(
+                        output,
+                        "{:width$} | {}",
+                        first_line_number + index,
+                        row,
+                        width = line_number_length
+                    )
+                } else {
+                    writeln!(
+                        output,
+                        "{:>width$} | {}",
+                        "↪",
+                        row,
+                        width = line_number_length
+                    )
+                };
+            }
+
+            row_count += rows.len();
 
             output
         },
     );
 
+    post_file_preview(
+        ctx,
+        target,
+        guild_config,
+        file_preview.as_ref(),
+        file_content,
+        row_count,
+        banner.as_deref(),
+        &footer_lines,
+    )
+    .await
+}
+
+/// Applies the keyword filter of the guild to the selected lines and reports matches to the audit log.
+/// Returns `None` if the preview is blocked.
+async fn filter_preview_lines(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    guild_config: &GuildConfig,
+    file_preview: &dyn FilePreview,
+    lines: Vec<String>,
+) -> Option<Vec<String>> {
+    let (filtered_lines, matched_patterns) = match apply_keyword_filter(guild_config, &lines) {
+        KeywordFilterOutcome::Passed => return Some(lines),
+        KeywordFilterOutcome::Blocked(matched_patterns) => (None, matched_patterns),
+        KeywordFilterOutcome::Redacted(redacted_lines, matched_patterns) => {
+            (Some(redacted_lines), matched_patterns)
+        }
+    };
+
+    send_audit_log(
+        &ctx.http,
+        target.guild_id,
+        guild_config,
+        AuditLogEntry::FilePreviewFiltered {
+            author_id: target.author_id,
+            channel_id: target.channel_id,
+            url: file_preview.get_message_url().as_str(),
+            is_blocked: filtered_lines.is_none(),
+            matched_patterns: &matched_patterns,
+        },
+    )
+    .await;
+
+    filtered_lines
+}
+
+/// Renders and posts the formatted content of a file preview.
+async fn post_file_preview(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    guild_config: &GuildConfig,
+    file_preview: &dyn FilePreview,
+    mut file_content: String,
+    mut line_count: usize,
+    banner: Option<&str>,
+    footer_lines: &[String],
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let permissions = get_preview_permissions(ctx, target.guild_id, target.channel_id);
+
+    if !permissions.can_send_messages {
+        return Ok(());
+    }
+
+    let language = file_preview.get_file_extension_with_alias(guild_config);
+
+    // Without attachments or threads, drop trailing lines until the content fits into the message itself.
+    let renderer = loop {
+        let render_context = RenderContext {
+            file_preview,
+            file_content: file_content.as_str(),
+            line_count,
+            language: language.as_deref(),
+            highlighted_html: None,
+            banner,
+            footer_lines,
+        };
+
+        match select_renderer(
+            guild_config.preview_display_style,
+            &render_context,
+            &permissions,
+        ) {
+            Some(renderer) => break renderer,
+            None if line_count > 1 => {
+                let trimmed_length = file_content.trim_end_matches('\n').len();
+                file_content.truncate(
+                    file_content[..trimmed_length]
+                        .rfind('\n')
+                        .map_or(0, |index| index + 1),
+                );
+                line_count -= 1;
+            }
+            None => return Ok(()),
+        }
+    };
+
+    let highlighted_html = match guild_config.attachment_theme {
+        Some(attachment_theme) if renderer.attaches_content() => {
+            let mut redis_connection_manager = ctx
+                .data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone();
+
+            get_highlighted_html(
+                &mut redis_connection_manager,
+                file_preview.get_metadata().file_path.as_str(),
+                file_content.as_str(),
+                language.as_deref(),
+                attachment_theme,
+            )
+            .await
+        }
+        _ => None,
+    };
+
+    let render_context = RenderContext {
+        file_preview,
+        file_content: file_content.as_str(),
+        line_count,
+        language: language.as_deref(),
+        highlighted_html: highlighted_html.as_deref(),
+        banner,
+        footer_lines,
+    };
+
+    let thread = renderer.render_thread(&render_context);
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let shows_render_button = is_feature_enabled(
+        &mut redis_connection_manager,
+        FeatureFlag::RenderImage,
+        target.guild_id,
+    )
+    .await;
+
+    let mut message = renderer
+        .render(&render_context)
+        .allowed_mentions(CreateAllowedMentions::new().replied_user(false))
+        .components(create_preview_components(
+            file_preview,
+            target.author_id,
+            shows_render_button,
+        ));
+
+    if permissions.can_read_message_history {
+        message = message.reference_message(MessageReference::from(target.reference_message));
+    }
+
+    let mut reply = call_with_backpressure(
+        target.channel_id,
+        target.priority,
+        target.channel_id.send_message(&ctx.http, message),
+    )
+    .await?;
+
+    // Discord doesn't recognize some file extensions, fall back to a plain text attachment.
+    if reply
+        .attachments
+        .first()
+        .map(|a| a.content_type.is_none())
+        .unwrap_or(false)
+    {
+        call_with_backpressure(
+            target.channel_id,
+            target.priority,
+            reply.edit(
+                &ctx,
+                EditMessage::new().attachments(EditAttachments::new().add(
+                    CreateAttachment::bytes(file_content.into_bytes(), "preview.txt"),
+                )),
+            ),
+        )
+        .await?;
+    }
+
+    if let Some((thread_name, thread_messages)) = thread {
+        let thread = call_with_backpressure(
+            target.channel_id,
+            target.priority,
+            target.channel_id.create_thread_from_message(
+                &ctx.http,
+                reply.id,
+                CreateThread::new(thread_name).auto_archive_duration(AutoArchiveDuration::OneHour),
+            ),
+        )
+        .await?;
+
+        for thread_message in thread_messages {
+            call_with_backpressure(
+                thread.id,
+                target.priority,
+                thread.id.send_message(&ctx.http, thread_message),
+            )
+            .await?;
+        }
+    }
+
+    record_posted_preview(ctx, target, guild_config, file_preview, reply.id).await;
+
+    Ok(())
+}
+
+fn create_preview_components(
+    file_preview: &dyn FilePreview,
+    author_id: UserId,
+    shows_render_button: bool,
+) -> Vec<CreateActionRow<'_>> {
     let open_button = CreateButton::new_link(file_preview.get_message_url().as_str())
         .emoji('🔗')
         .label("Open")
         .to_owned();
 
-    let delete_button = CreateButton::new(format!("deleteFilePreview:{}", msg.author.id))
-        .style(ButtonStyle::Secondary)
-        .emoji('🗑')
-        .to_owned();
+    let delete_button = CreateButton::new(sign_custom_id(
+        format!("{}:{}", DELETE_CUSTOM_ID_PREFIX, author_id).as_str(),
+    ))
+    .style(ButtonStyle::Secondary)
+    .emoji('🗑')
+    .to_owned();
+
+    let mut buttons = vec![open_button];
 
-    if file_content.len() + file_preview.get_metadata_content().len() > 1900
-        || selected_content_lines.len() > 6
+    if let Some(commit_sha) = file_preview.get_commit_sha() {
+        buttons.push(
+            CreateButton::new(sign_custom_id(
+                format!("{}:{}", PERMALINK_CUSTOM_ID_PREFIX, commit_sha).as_str(),
+            ))
+            .style(ButtonStyle::Secondary)
+            .emoji('📌')
+            .label("Permalink"),
+        );
+    }
+
+    if is_blame_url(file_preview.get_message_url()) && config().github_token.is_some() {
+        buttons.push(
+            CreateButton::new(sign_custom_id(BLAMED_COMMIT_CUSTOM_ID_PREFIX))
+                .style(ButtonStyle::Secondary)
+                .emoji('🕰')
+                .label("Blamed Commit"),
+        );
+    }
+
+    // Pages of PDF files are images already, and diffs of compared files can't be fetched again from their URL.
+    if shows_render_button
+        && file_preview.get_raw_pdf().is_none()
+        && find_preview_urls(
+            file_preview.get_message_url().as_str(),
+            0,
+            &PreviewSource::ALL,
+        )
+        .next()
+        .is_some()
     {
-        let mut reply = msg
-            .channel_id
-            .send_message(
-                &ctx.http,
-                CreateMessage::new()
-                    .content(file_preview.get_metadata_content())
-                    .add_file(CreateAttachment::bytes(
-                        file_content.clone().into_bytes(),
-                        format!(
-                            "preview.{}",
-                            file_preview
-                                .get_file_extension_with_alias()
-                                .unwrap_or("txt")
-                        ),
-                    ))
-                    .reference_message(msg)
-                    .allowed_mentions(CreateAllowedMentions::new().replied_user(false))
-                    .components(&[CreateActionRow::buttons(&[open_button, delete_button])]),
-            )
-            .await?;
+        buttons.push(
+            CreateButton::new(sign_custom_id(RENDER_IMAGE_CUSTOM_ID_PREFIX))
+                .style(ButtonStyle::Secondary)
+                .emoji('🖼')
+                .label("Render"),
+        );
+    }
+
+    buttons.push(delete_button);
+
+    vec![CreateActionRow::buttons(buttons)]
+}
 
-        if reply
-            .attachments
-            .first()
-            .map(|a| a.content_type.is_none())
-            .unwrap_or(false)
+/// Counts, publishes and logs a preview after it has been posted.
+async fn record_posted_preview(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    guild_config: &GuildConfig,
+    file_preview: &dyn FilePreview,
+    message_id: MessageId,
+) {
+    if let Some(guild_id) = target.guild_id {
+        let mut redis_connection_manager = ctx
+            .data::<SerenityGlobalData>()
+            .redis_connection_manager
+            .clone();
+
+        if let Err(error) = redis_increment_guild_stats(
+            &mut redis_connection_manager,
+            guild_id,
+            GuildStatsCounter::FilePreviews,
+        )
+        .await
         {
-            reply
-                .edit(
-                    &ctx,
-                    EditMessage::new().attachments(EditAttachments::new().add(
-                        CreateAttachment::bytes(file_content.into_bytes(), "preview.txt"),
-                    )),
-                )
-                .await?;
+            println!("Error while incrementing guild stats: {:?}", error);
         }
-    } else {
-        msg.channel_id
-            .send_message(
-                &ctx.http,
-                CreateMessage::new()
-                    .content(
-                        MessageBuilder::new()
-                            .push(file_preview.get_metadata_content())
-                            .push_codeblock_safe(
-                                file_content.as_str(),
-                                file_preview.get_file_extension_with_alias(),
-                            )
-                            .build(),
-                    )
-                    .reference_message(MessageReference::from(msg))
-                    .allowed_mentions(CreateAllowedMentions::new().replied_user(false))
-                    .components(&[CreateActionRow::buttons(&[open_button, delete_button])]),
+
+        if let Err(error) = redis_record_guild_preview(
+            &mut redis_connection_manager,
+            guild_id,
+            file_preview.get_source(),
+            file_preview.get_repository(),
+            file_preview.get_metadata().file_path.as_str(),
+        )
+        .await
+        {
+            println!("Error while recording guild preview stats: {:?}", error);
+        }
+
+        if guild_config.recycle_previews {
+            if let Err(error) = redis_set_indexed_preview(
+                &mut redis_connection_manager,
+                target.channel_id,
+                file_preview.get_message_url().as_str(),
+                message_id,
             )
-            .await?;
+            .await
+            {
+                println!("Error while indexing file preview: {:?}", error);
+            }
+        }
+    }
+
+    publish_activity(ActivityEvent::FilePreviewCreated {
+        guild_id: target.guild_id,
+        channel_id: target.channel_id,
+        message_id,
+        url: file_preview.get_message_url().to_string(),
+    });
+
+    send_audit_log(
+        &ctx.http,
+        target.guild_id,
+        guild_config,
+        AuditLogEntry::FilePreviewPosted {
+            author_id: target.author_id,
+            channel_id: target.channel_id,
+            message_id,
+            url: file_preview.get_message_url().as_str(),
+        },
+    )
+    .await;
+}
+
+/// Posts a page of a PDF file as an image. The keyword filter only applies to text content, so it isn't used here.
+async fn send_pdf_page_preview(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    guild_config: &GuildConfig,
+    file_preview: &dyn FilePreview,
+    page_number: u32,
+    notice: Option<&str>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let raw_pdf = file_preview
+        .get_raw_pdf()
+        .ok_or("Only pages of PDF files can be previewed.")?;
+
+    let permissions = get_preview_permissions(ctx, target.guild_id, target.channel_id);
+
+    if !permissions.can_send_messages || !permissions.can_attach_files {
+        return Ok(());
     }
 
+    let RenderedPdfPage {
+        png_data,
+        page_count,
+    } = render_pdf_page(raw_pdf, page_number)?;
+
+    let (repository_footer_line, banner) =
+        get_repository_annotations(ctx, guild_config, file_preview).await;
+
+    let mut message_builder = MessageBuilder::new();
+
+    if let Some(banner) = banner {
+        message_builder = message_builder.push_line(banner);
+    }
+
+    message_builder = message_builder.push(file_preview.get_metadata_content());
+
+    let page_footer_line = format!("Page {} of {}", page_number, page_count);
+
+    for footer_line in repository_footer_line
+        .iter()
+        .map(String::as_str)
+        .chain([page_footer_line.as_str()])
+        .chain(notice)
+    {
+        message_builder = message_builder.push("-# ").push_line_safe(footer_line);
+    }
+
+    let mut message = CreateMessage::new()
+        .content(message_builder.build())
+        .add_file(CreateAttachment::bytes(
+            png_data,
+            format!("page-{}.png", page_number),
+        ))
+        .allowed_mentions(CreateAllowedMentions::new().replied_user(false))
+        .components(create_preview_components(
+            file_preview,
+            target.author_id,
+            false,
+        ));
+
+    if permissions.can_read_message_history {
+        message = message.reference_message(MessageReference::from(target.reference_message));
+    }
+
+    let reply = call_with_backpressure(
+        target.channel_id,
+        target.priority,
+        target.channel_id.send_message(&ctx.http, message),
+    )
+    .await?;
+
+    record_posted_preview(ctx, target, guild_config, file_preview, reply.id).await;
+
     Ok(())
 }
 
+/// Finds all preview URLs of the given sources in the content,
+/// with positions shifted by the offset of the content within the message.
+fn find_preview_urls<'a>(
+    content: &'a str,
+    offset: usize,
+    sources: &'a [PreviewSource],
+) -> impl Iterator<Item = PreviewUrlMatch<'a>> {
+    PREVIEW_SOURCE_REGISTRY
+        .matching_handlers(content)
+        .filter(move |(handler, _)| sources.contains(&handler.source()))
+        .flat_map(move |(handler, regex)| {
+            regex
+                .find_iter(content)
+                .map(move |url_match| PreviewUrlMatch {
+                    url_string: url_match.as_str(),
+                    handler,
+                    position: offset + url_match.start(),
+                })
+        })
+}
+
+async fn get_guild_config(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+) -> Result<GuildConfig, Box<dyn Error + Send + Sync>> {
+    match guild_id {
+        Some(guild_id) => {
+            let mut redis_connection_manager = ctx
+                .data::<SerenityGlobalData>()
+                .redis_connection_manager
+                .clone();
+
+            Ok(GuildConfig::redis_get(&mut redis_connection_manager, guild_id).await?)
+        }
+        None => Ok(GuildConfig::default()),
+    }
+}
+
+/// Checks whether automatic previews are disabled in the channel of the target, or in the channel that its thread belongs to,
+/// e.g. the forum of a forum post.
+fn is_preview_channel_disabled(
+    ctx: &Context,
+    guild_config: &GuildConfig,
+    target: &PreviewTarget<'_>,
+) -> bool {
+    let is_disabled = |channel_id| guild_config.disabled_channel_ids.contains(&channel_id);
+
+    is_disabled(target.channel_id)
+        || get_thread_parent_id(ctx, target.guild_id, target.channel_id).is_some_and(is_disabled)
+}
+
+/// Posts the preview of a single URL that wasn't taken from a message, e.g. one built by a command.
+pub(crate) async fn send_file_preview_for_url(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    url: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let guild_config = get_guild_config(ctx, target.guild_id).await?;
+
+    let url_match = find_preview_urls(url, 0, &PreviewSource::ALL)
+        .next()
+        .ok_or("The URL can't be previewed.")?;
+
+    if guild_config
+        .disabled_preview_sources
+        .contains(&url_match.handler.source())
+    {
+        return Err("Previews of this kind of link are disabled on this server.".into());
+    }
+
+    let redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let (file_preview, anchor) = url_match.get_file_preview(redis_connection_manager).await?;
+
+    send_file_preview(ctx, target, &guild_config, file_preview, anchor, None).await
+}
+
+/// Posts the diff of a file between two references, without line numbers as the patch carries its own hunk headers.
+pub(crate) async fn send_compare_preview(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    compare_preview: GitHubCompareFilePreview,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let guild_config = get_guild_config(ctx, target.guild_id).await?;
+
+    let lines = compare_preview
+        .get_raw_content()
+        .lines()
+        .map(ToOwned::to_owned)
+        .collect();
+
+    let Some(lines) =
+        filter_preview_lines(ctx, target, &guild_config, &compare_preview, lines).await
+    else {
+        return Ok(());
+    };
+
+    let file_content = expand_tabs(lines.join("\n").as_str(), 4);
+    let line_count = lines.len();
+
+    post_file_preview(
+        ctx,
+        target,
+        &guild_config,
+        &compare_preview,
+        file_content,
+        line_count,
+        None,
+        &[],
+    )
+    .await
+}
+
 pub async fn check_file_preview(
     ctx: &Context,
     msg: &Message,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let mut url_matches: Vec<PreviewUrlMatch> = GITHUB_REPOSITORY_FILE_URL_REGEX
-        .find_iter(&msg.content)
-        .map(|url_match| PreviewUrlMatch {
-            url_string: url_match.as_str(),
-            url_type: PreviewUrlType::GitHubRepositoryFile,
-            position: url_match.start(),
-        })
-        .chain(
-            GIST_URL_REGEX
-                .find_iter(&msg.content)
-                .map(|url_match| PreviewUrlMatch {
-                    url_string: url_match.as_str(),
-                    url_type: PreviewUrlType::Gist,
-                    position: url_match.start(),
-                }),
+    send_message_file_previews(ctx, &PreviewTarget::from(msg), msg)
+        .await
+        .map(|_| ())
+}
+
+/// Posts previews for the links in the message, returning the number of previewed links.
+pub(crate) async fn send_message_file_previews(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    msg: &Message,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    // Forwarded messages carry their content in message snapshots instead.
+    let contents = || {
+        std::iter::once(msg.content.as_str()).chain(
+            msg.message_snapshots
+                .iter()
+                .map(|message_snapshot| message_snapshot.content.as_str()),
         )
-        .collect();
+    };
+
+    // Cheap checks first, most messages don't contain any links and shouldn't cost more than a substring search.
+    if contents().all(|content| content.len() < MIN_PREVIEW_URL_LENGTH || !content.contains("http"))
+        || !get_preview_permissions(ctx, target.guild_id, target.channel_id).can_send_messages
+    {
+        record_skipped_message();
+        return Ok(0);
+    }
+
+    let guild_config = get_guild_config(ctx, target.guild_id).await?;
+
+    if is_preview_channel_disabled(ctx, &guild_config, target) {
+        record_skipped_message();
+        return Ok(0);
+    }
+
+    let enabled_preview_sources = guild_config.enabled_preview_sources();
+    let scan_start_time = Instant::now();
+
+    let mut content_offset = 0;
+    let mut url_matches: Vec<PreviewUrlMatch> = Vec::new();
+
+    for content in contents() {
+        url_matches.extend(find_preview_urls(
+            content,
+            content_offset,
+            &enabled_preview_sources,
+        ));
+        content_offset += content.len();
+    }
+
+    record_scan_time(scan_start_time.elapsed());
 
     if url_matches.is_empty() {
-        return Ok(());
+        return Ok(0);
     }
 
     url_matches.sort_unstable_by_key(|element| element.position);
 
+    let Some(_processing_permit) = acquire_processing_permit().await else {
+        println!(
+            "Dropped file previews of message {}, as the queue is full.",
+            msg.id
+        );
+        return Ok(0);
+    };
+
+    let FloodLimitOutcome {
+        allowed_count,
+        notice_skipped_count,
+    } = reserve_previews(target.channel_id, target.author_id, url_matches.len());
+
+    let mut recycled_count = 0;
+    let mut previewed_url_matches = Vec::with_capacity(allowed_count);
+
+    for url_match in url_matches.into_iter().take(allowed_count) {
+        // Links that were previewed recently in the same channel refer to the existing preview instead.
+        if guild_config.recycle_previews {
+            if let Ok(url) = url_match.get_url() {
+                if recycle_preview(ctx, target, url.as_str()).await? {
+                    recycled_count += 1;
+                    continue;
+                }
+            }
+        }
+
+        previewed_url_matches.push(url_match);
+    }
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let url_strings: Vec<&str> = previewed_url_matches
+        .iter()
+        .map(|element| element.url_string)
+        .collect();
+
     let file_previews = join_all(
-        url_matches
+        previewed_url_matches
             .into_iter()
-            .take(3)
-            .map(|element| element.get_file_preview())
+            .map(|element| element.get_file_preview(redis_connection_manager.clone()))
             .collect::<Vec<_>>(),
     )
     .await;
 
-    for file_preview in file_previews {
-        send_file_preview(ctx, msg, file_preview?).await?;
+    let file_preview_count = recycled_count + file_previews.len();
+
+    for (file_preview, url_string) in file_previews.into_iter().zip(url_strings) {
+        let (file_preview, anchor) = match file_preview {
+            Ok(file_preview) => file_preview,
+            // Transient failures are retried in the background instead of dropping the preview.
+            Err(error)
+                if is_transient_error(&*error)
+                    && is_feature_enabled(
+                        &mut redis_connection_manager,
+                        FeatureFlag::PreviewRetries,
+                        target.guild_id,
+                    )
+                    .await =>
+            {
+                redis_enqueue_preview_retry(&mut redis_connection_manager, target, url_string)
+                    .await?;
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+
+        send_file_preview(ctx, target, &guild_config, file_preview, anchor, None).await?;
+    }
+
+    if notice_skipped_count > 0 {
+        send_flood_notice(ctx, target, notice_skipped_count).await?;
+    }
+
+    Ok(file_preview_count)
+}
+
+/// Collapses the links skipped due to the flood limits into a single notice.
+async fn send_flood_notice(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    skipped_count: usize,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut message = CreateMessage::new()
+        .content(match skipped_count {
+            1 => "1 more link was not previewed to avoid flooding the channel.".to_owned(),
+            _ => format!(
+                "{} more links were not previewed to avoid flooding the channel.",
+                skipped_count
+            ),
+        })
+        .allowed_mentions(CreateAllowedMentions::new().replied_user(false));
+
+    if get_preview_permissions(ctx, target.guild_id, target.channel_id).can_read_message_history {
+        message = message.reference_message(MessageReference::from(target.reference_message));
     }
 
+    call_with_backpressure(
+        target.channel_id,
+        target.priority,
+        target.channel_id.send_message(&ctx.http, message),
+    )
+    .await?;
+
     Ok(())
 }
 
 pub async fn handle_delete_file_preview_button(
     ctx: &Context,
     interaction: &ComponentInteraction,
+    custom_id: &str,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let author_id = interaction
-        .data
-        .custom_id
+    let author_id = custom_id
         .split_once(':')
         .ok_or("Failed to retrieve author ID from custom ID.")?
         .1;
@@ -324,5 +1186,233 @@ pub async fn handle_delete_file_preview_button(
     }
 
     interaction.delete_response(&ctx.http).await?;
+
+    if let Some(guild_id) = interaction.guild_id {
+        let mut redis_connection_manager = ctx
+            .data::<SerenityGlobalData>()
+            .redis_connection_manager
+            .clone();
+
+        let guild_config = GuildConfig::redis_get(&mut redis_connection_manager, guild_id).await?;
+
+        send_audit_log(
+            &ctx.http,
+            Some(guild_id),
+            &guild_config,
+            AuditLogEntry::FilePreviewDeleted {
+                deleted_by_id: interaction.user.id,
+                channel_id: interaction.channel_id,
+                is_moderation: false,
+            },
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+/// Previews posted before custom IDs were signed carry an unsigned `deleteFilePreview:<author ID>` custom ID, which is
+/// still accepted. Only the author can delete a preview with it, so a crafted custom ID gains nothing.
+pub(crate) fn verify_legacy_delete_custom_id(custom_id: &str) -> Option<&str> {
+    custom_id
+        .strip_prefix(DELETE_CUSTOM_ID_PREFIX)?
+        .strip_prefix(':')?
+        .parse::<u64>()
+        .ok()
+        .map(|_| custom_id)
+}
+
+/// Whether the message is a file preview, i.e. carries the delete button that previews are posted with,
+/// unlike other messages of the bot such as audit log entries or juxtaposes.
+pub(crate) fn is_file_preview_message(message: &Message) -> bool {
+    message
+        .components
+        .iter()
+        .flat_map(|action_row| action_row.components.iter())
+        .any(|component| match component {
+            ActionRowComponent::Button(Button {
+                data: ButtonKind::NonLink { custom_id, .. },
+                ..
+            }) => verify_custom_id(custom_id)
+                .or_else(|| verify_legacy_delete_custom_id(custom_id))
+                .is_some_and(|custom_id| custom_id.starts_with(DELETE_CUSTOM_ID_PREFIX)),
+            _ => false,
+        })
+}
+
+/// The URL of the previewed file, taken from the "Open" button of a preview.
+fn get_previewed_url(message: &Message) -> Option<Url> {
+    message
+        .components
+        .iter()
+        .flat_map(|action_row| action_row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::Button(Button {
+                data: ButtonKind::Link { url },
+                ..
+            }) => Url::parse(url).ok(),
+            _ => None,
+        })
+}
+
+/// Replies with the link to the previewed file at the commit that its branch pointed to when the preview was posted,
+/// which keeps pointing to the same content after the branch moves on.
+pub async fn handle_copy_permalink_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let commit_sha = custom_id
+        .split_once(':')
+        .ok_or("Failed to retrieve commit SHA from custom ID.")?
+        .1;
+
+    let message_url = get_previewed_url(&interaction.message)
+        .ok_or("File preview is missing the link to the file.")?;
+
+    let permalink = pin_to_commit(&message_url, commit_sha)
+        .ok_or("File preview doesn't link to a file in a GitHub repository.")?;
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(permalink.to_string())
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Previews the file at the commit that last changed the linked line of a blame URL.
+pub async fn handle_blamed_commit_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let message_url = get_previewed_url(&interaction.message)
+        .ok_or("File preview is missing the link to the file.")?;
+
+    interaction.defer(&ctx.http).await?;
+
+    let result = match resolve_blamed_commit_url(&message_url).await {
+        Ok(blamed_commit_url) => {
+            send_file_preview_for_url(
+                ctx,
+                &PreviewTarget {
+                    author_id: interaction.user.id,
+                    channel_id: interaction.channel_id,
+                    guild_id: interaction.guild_id,
+                    reference_message: &interaction.message,
+                    priority: SendPriority::Urgent,
+                },
+                blamed_commit_url.as_str(),
+            )
+            .await
+        }
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = result {
+        interaction
+            .create_followup(
+                &ctx.http,
+                CreateInteractionResponseFollowup::new()
+                    .content(error.to_string())
+                    .ephemeral(true),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Renders the lines that a preview URL points to as a highlighted image, in the attachment theme of the guild.
+async fn render_preview_image(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    message_url: &Url,
+) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    // Buttons of previews posted before the feature was turned off keep showing up.
+    if !is_feature_enabled(
+        &mut redis_connection_manager,
+        FeatureFlag::RenderImage,
+        guild_id,
+    )
+    .await
+    {
+        return Err("Rendering previews as images is currently disabled.".into());
+    }
+
+    let guild_config = get_guild_config(ctx, guild_id).await?;
+
+    let url_match = find_preview_urls(message_url.as_str(), 0, &PreviewSource::ALL)
+        .next()
+        .ok_or("The URL can't be previewed.")?;
+
+    let (file_preview, anchor) = url_match.get_file_preview(redis_connection_manager).await?;
+
+    if let Anchor::Page(_) = anchor {
+        return Err("Pages of PDF files are previewed as images already.".into());
+    }
+
+    let (raw_content, _) = normalize_line_endings(file_preview.get_raw_content());
+
+    let AnchorSelection {
+        first_line_number,
+        lines,
+        ..
+    } = anchor.select(raw_content.as_ref(), SNIPPET_IMAGE_MAX_LINES)?;
+
+    let lines = match apply_keyword_filter(&guild_config, &lines) {
+        KeywordFilterOutcome::Passed => lines,
+        KeywordFilterOutcome::Blocked(_) => {
+            return Err("The file matches the keyword filter of this server.".into())
+        }
+        KeywordFilterOutcome::Redacted(redacted_lines, _) => redacted_lines,
+    };
+
+    let lines: Vec<String> = lines.iter().map(|line| expand_tabs(line, 4)).collect();
+    let language = file_preview
+        .get_file_extension_with_alias(&guild_config)
+        .unwrap_or_else(|| "txt".to_owned());
+    let theme = guild_config
+        .attachment_theme
+        .unwrap_or(AttachmentTheme::Dark);
+
+    tokio::task::spawn_blocking(move || {
+        render_snippet_image(&lines, first_line_number, language.as_str(), theme)
+    })
+    .await?
+    .ok_or_else(|| "Rendering the file took too long.".into())
+}
+
+/// Replies with an image of the previewed lines, for sharing them on platforms without code formatting.
+pub async fn handle_render_image_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let message_url = get_previewed_url(&interaction.message)
+        .ok_or("File preview is missing the link to the file.")?;
+
+    interaction.defer_ephemeral(&ctx.http).await?;
+
+    let followup = match render_preview_image(ctx, interaction.guild_id, &message_url).await {
+        Ok(png_data) => CreateInteractionResponseFollowup::new()
+            .add_file(CreateAttachment::bytes(png_data, "snippet.png")),
+        Err(error) => CreateInteractionResponseFollowup::new().content(error.to_string()),
+    };
+
+    interaction
+        .create_followup(&ctx.http, followup.ephemeral(true))
+        .await?;
+
     Ok(())
 }
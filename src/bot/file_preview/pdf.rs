@@ -0,0 +1,114 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use hayro::{render, InterpreterSettings, Pdf, RenderSettings};
+use reqwest::Url;
+
+use crate::outbound::get_following_redirects;
+
+use super::lfs::{format_file_size, parse_lfs_pointer};
+use super::retry_queue::request_failed_error;
+use super::RAW_CONTENT_HOSTS;
+
+/// PDF files are only downloaded for previews up to this size.
+const PDF_MAX_SIZE: u64 = 8_388_608;
+
+/// Pages further into a document are less likely to be linked on purpose, and take longer to find.
+const PDF_MAX_PAGE_NUMBER: u32 = 500;
+
+/// Length of the longer side of rendered pages in pixels, which keeps text readable in the Discord client.
+const PAGE_MAX_SIDE_LENGTH: f32 = 1600.0;
+
+/// Scale used for regularly sized pages, i.e. 144 DPI.
+const PAGE_SCALE: f32 = 2.0;
+
+pub(super) fn is_pdf_file(file_name: &str) -> bool {
+    file_name
+        .rsplit_once('.')
+        .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case("pdf"))
+}
+
+/// Fetches the content of a PDF file, which unlike text files may not be decoded as UTF-8.
+pub(super) async fn fetch_raw_pdf(url: Url) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let response = get_following_redirects(url, &RAW_CONTENT_HOSTS).await?;
+
+    if !response.status().is_success() {
+        return Err(request_failed_error(
+            response.status(),
+            "API request failed.",
+        ));
+    }
+
+    if response
+        .content_length()
+        .is_some_and(|file_size| file_size > PDF_MAX_SIZE)
+    {
+        return Err("The PDF file is too large to be previewed.".into());
+    }
+
+    let raw_content = response.bytes().await?;
+
+    if raw_content.len() as u64 > PDF_MAX_SIZE {
+        return Err("The PDF file is too large to be previewed.".into());
+    }
+
+    if let Some(lfs_pointer) = std::str::from_utf8(&raw_content)
+        .ok()
+        .and_then(parse_lfs_pointer)
+    {
+        return Err(format!(
+            "The PDF file is stored using Git LFS ({}) and can't be previewed.",
+            format_file_size(lfs_pointer.size)
+        )
+        .into());
+    }
+
+    Ok(raw_content.to_vec())
+}
+
+/// A page of a PDF file, rendered to a PNG image.
+pub(super) struct RenderedPdfPage {
+    pub(super) png_data: Vec<u8>,
+    pub(super) page_count: usize,
+}
+
+/// Renders the page with the given number, starting at 1. Pages larger than usual are scaled down,
+/// so that neither side exceeds [`PAGE_MAX_SIDE_LENGTH`].
+pub(super) fn render_pdf_page(
+    raw_content: &[u8],
+    page_number: u32,
+) -> Result<RenderedPdfPage, Box<dyn Error + Send + Sync>> {
+    if page_number > PDF_MAX_PAGE_NUMBER {
+        return Err(format!(
+            "Only the first {} pages of a PDF file can be previewed.",
+            PDF_MAX_PAGE_NUMBER
+        )
+        .into());
+    }
+
+    let pdf =
+        Pdf::new(Arc::new(raw_content.to_vec())).map_err(|_| "The PDF file can't be read.")?;
+    let pages = pdf.pages();
+
+    let page = pages
+        .get(page_number as usize - 1)
+        .ok_or_else(|| format!("The PDF file only has {} pages.", pages.len()))?;
+
+    let (width, height) = page.render_dimensions();
+    let scale = (PAGE_MAX_SIDE_LENGTH / width.max(height).max(1.0)).min(PAGE_SCALE);
+
+    let pixmap = render(
+        page,
+        &InterpreterSettings::default(),
+        &RenderSettings {
+            x_scale: scale,
+            y_scale: scale,
+            ..Default::default()
+        },
+    );
+
+    Ok(RenderedPdfPage {
+        png_data: pixmap.take_png(),
+        page_count: pages.len(),
+    })
+}
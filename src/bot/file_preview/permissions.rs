@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serenity::all::{ChannelId, ChannelType, GuildChannel, GuildId, Permissions, UserId};
+use serenity::prelude::*;
+
+/// Permission overwrites rarely change, but cached entries expire so that changes apply without a restart.
+const PERMISSION_CACHE_TTL: Duration = Duration::from_secs(60);
+
+const PERMISSION_CACHE_MAX_ENTRIES: usize = 10_000;
+
+static PERMISSION_CACHE: Lazy<
+    Mutex<HashMap<ChannelId, (Instant, Permissions, PreviewChannelKind)>>,
+> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The kind of channel a preview is posted in, as far as it matters for what the bot may do there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PreviewChannelKind {
+    /// Text and announcement channels, in which threads can be created on messages.
+    Text,
+    /// The text chat of voice and stage channels, which doesn't support threads.
+    VoiceText,
+    /// Threads, including forum posts, which inherit the permissions of their parent channel.
+    Thread,
+    /// Private threads that the bot isn't a member of, in which it can't send messages despite the permissions
+    /// of the parent channel.
+    UnjoinedPrivateThread,
+}
+
+impl PreviewChannelKind {
+    fn from_channel_type(channel_type: ChannelType) -> Self {
+        match channel_type {
+            ChannelType::Voice | ChannelType::Stage => Self::VoiceText,
+            ChannelType::PublicThread | ChannelType::PrivateThread | ChannelType::NewsThread => {
+                Self::Thread
+            }
+            _ => Self::Text,
+        }
+    }
+
+    /// The cache only knows the thread member of the bot if it joined the thread, or created it.
+    fn from_thread(thread: &GuildChannel, current_user_id: UserId) -> Self {
+        let is_member = thread.member.is_some() || thread.owner_id == Some(current_user_id);
+
+        if thread.kind == ChannelType::PrivateThread && !is_member {
+            Self::UnjoinedPrivateThread
+        } else {
+            Self::Thread
+        }
+    }
+}
+
+/// What the bot may do in the channel of a file preview.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct PreviewPermissions {
+    pub(super) can_send_messages: bool,
+    pub(super) can_embed_links: bool,
+    pub(super) can_attach_files: bool,
+    /// Replies to messages require reading the message history.
+    pub(super) can_read_message_history: bool,
+    /// Threads can be created on messages, except within threads and the text chat of voice and stage channels.
+    pub(super) can_create_threads: bool,
+}
+
+impl PreviewPermissions {
+    const ALL: Self = Self {
+        can_send_messages: true,
+        can_embed_links: true,
+        can_attach_files: true,
+        can_read_message_history: true,
+        can_create_threads: true,
+    };
+
+    fn from_permissions(permissions: Permissions, channel_kind: PreviewChannelKind) -> Self {
+        let send_permission = match channel_kind {
+            PreviewChannelKind::Thread | PreviewChannelKind::UnjoinedPrivateThread => {
+                Permissions::SEND_MESSAGES_IN_THREADS
+            }
+            PreviewChannelKind::Text | PreviewChannelKind::VoiceText => Permissions::SEND_MESSAGES,
+        };
+
+        Self {
+            can_send_messages: channel_kind != PreviewChannelKind::UnjoinedPrivateThread
+                && permissions.contains(send_permission | Permissions::VIEW_CHANNEL),
+            can_embed_links: permissions.contains(Permissions::EMBED_LINKS),
+            can_attach_files: permissions.contains(Permissions::ATTACH_FILES),
+            can_read_message_history: permissions.contains(Permissions::READ_MESSAGE_HISTORY),
+            can_create_threads: channel_kind == PreviewChannelKind::Text
+                && permissions.contains(
+                    Permissions::CREATE_PUBLIC_THREADS | Permissions::SEND_MESSAGES_IN_THREADS,
+                ),
+        }
+    }
+}
+
+/// Computes the permissions of the bot in the channel from the cache, using the parent channel for threads.
+fn compute_permissions(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Option<(Permissions, PreviewChannelKind)> {
+    let current_user_id = ctx.cache.current_user().id;
+    let guild = ctx.cache.guild(guild_id)?;
+    let member = guild.members.get(&current_user_id)?;
+
+    if let Some(channel) = guild.channels.get(&channel_id) {
+        return Some((
+            guild.user_permissions_in(channel, member),
+            PreviewChannelKind::from_channel_type(channel.kind),
+        ));
+    }
+
+    let thread = guild
+        .threads
+        .iter()
+        .find(|thread| thread.id == channel_id)?;
+    let parent_channel = guild.channels.get(&thread.parent_id?)?;
+
+    Some((
+        guild.user_permissions_in(parent_channel, member),
+        PreviewChannelKind::from_thread(thread, current_user_id),
+    ))
+}
+
+/// Returns the channel that the thread belongs to, e.g. the forum of a forum post, if the channel is a cached thread.
+pub(super) fn get_thread_parent_id(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+) -> Option<ChannelId> {
+    ctx.cache
+        .guild(guild_id?)?
+        .threads
+        .iter()
+        .find(|thread| thread.id == channel_id)?
+        .parent_id
+}
+
+/// Returns the permissions of the bot in the channel. Assumes all permissions except threads in DMs and channels that aren't cached,
+/// in which case sending fails the same way it would without this check. Threads are only created where the channel is known to support them.
+pub(super) fn get_preview_permissions(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+) -> PreviewPermissions {
+    let Some(guild_id) = guild_id else {
+        return PreviewPermissions {
+            can_create_threads: false,
+            ..PreviewPermissions::ALL
+        };
+    };
+
+    {
+        let permission_cache = PERMISSION_CACHE.lock().unwrap();
+
+        if let Some((cached_at, permissions, channel_kind)) = permission_cache.get(&channel_id) {
+            if cached_at.elapsed() < PERMISSION_CACHE_TTL {
+                return PreviewPermissions::from_permissions(*permissions, *channel_kind);
+            }
+        }
+    }
+
+    let Some((permissions, channel_kind)) = compute_permissions(ctx, guild_id, channel_id) else {
+        return PreviewPermissions {
+            can_create_threads: false,
+            ..PreviewPermissions::ALL
+        };
+    };
+
+    let mut permission_cache = PERMISSION_CACHE.lock().unwrap();
+
+    if permission_cache.len() >= PERMISSION_CACHE_MAX_ENTRIES {
+        permission_cache.retain(|_, (cached_at, _, _)| cached_at.elapsed() < PERMISSION_CACHE_TTL);
+    }
+
+    permission_cache.insert(channel_id, (Instant::now(), permissions, channel_kind));
+
+    PreviewPermissions::from_permissions(permissions, channel_kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANNEL_PERMISSIONS: Permissions = Permissions::VIEW_CHANNEL
+        .union(Permissions::SEND_MESSAGES)
+        .union(Permissions::SEND_MESSAGES_IN_THREADS)
+        .union(Permissions::CREATE_PUBLIC_THREADS)
+        .union(Permissions::READ_MESSAGE_HISTORY);
+
+    fn get_channel_permissions(channel_type: ChannelType) -> PreviewPermissions {
+        PreviewPermissions::from_permissions(
+            CHANNEL_PERMISSIONS,
+            PreviewChannelKind::from_channel_type(channel_type),
+        )
+    }
+
+    #[test]
+    fn creates_threads_in_text_channels() {
+        for channel_type in [ChannelType::Text, ChannelType::News] {
+            let permissions = get_channel_permissions(channel_type);
+
+            assert!(permissions.can_send_messages);
+            assert!(permissions.can_create_threads);
+        }
+    }
+
+    #[test]
+    fn replies_without_threads_in_voice_and_stage_channels() {
+        for channel_type in [ChannelType::Voice, ChannelType::Stage] {
+            let permissions = get_channel_permissions(channel_type);
+
+            assert!(permissions.can_send_messages);
+            assert!(permissions.can_read_message_history);
+            assert!(!permissions.can_create_threads);
+        }
+    }
+
+    #[test]
+    fn replies_within_threads_and_forum_posts() {
+        // Forum posts are public threads whose parent is the forum channel.
+        for channel_type in [
+            ChannelType::PublicThread,
+            ChannelType::PrivateThread,
+            ChannelType::NewsThread,
+        ] {
+            let permissions = get_channel_permissions(channel_type);
+
+            assert!(permissions.can_send_messages);
+            assert!(!permissions.can_create_threads);
+        }
+
+        let permissions = PreviewPermissions::from_permissions(
+            CHANNEL_PERMISSIONS.difference(Permissions::SEND_MESSAGES_IN_THREADS),
+            PreviewChannelKind::Thread,
+        );
+
+        assert!(!permissions.can_send_messages);
+    }
+
+    #[test]
+    fn doesnt_reply_within_unjoined_private_threads() {
+        let permissions = PreviewPermissions::from_permissions(
+            CHANNEL_PERMISSIONS,
+            PreviewChannelKind::UnjoinedPrivateThread,
+        );
+
+        assert!(!permissions.can_send_messages);
+        assert!(!permissions.can_create_threads);
+    }
+}
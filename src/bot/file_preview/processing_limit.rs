@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Number of messages with preview URLs that are processed at the same time across all guilds.
+const MAX_CONCURRENT_MESSAGES: usize = 8;
+
+/// Messages beyond this queue depth are dropped, bounding the memory used during link spam.
+const MAX_QUEUED_MESSAGES: usize = 256;
+
+static PROCESSING_SEMAPHORE: Lazy<Semaphore> =
+    Lazy::new(|| Semaphore::new(MAX_CONCURRENT_MESSAGES));
+
+static QUEUED_MESSAGE_COUNT: AtomicUsize = AtomicUsize::new(0);
+static MAX_QUEUED_MESSAGE_COUNT: AtomicUsize = AtomicUsize::new(0);
+static DROPPED_MESSAGE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Leaves the queue when dropped, including when the waiting task is cancelled.
+struct QueueSlot;
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        QUEUED_MESSAGE_COUNT.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Waits until the message may be processed, or returns `None` if the queue is full.
+pub(super) async fn acquire_processing_permit() -> Option<SemaphorePermit<'static>> {
+    if let Ok(permit) = PROCESSING_SEMAPHORE.try_acquire() {
+        return Some(permit);
+    }
+
+    let queue_depth = QUEUED_MESSAGE_COUNT.fetch_add(1, Ordering::Relaxed) + 1;
+    let _queue_slot = QueueSlot;
+
+    if queue_depth > MAX_QUEUED_MESSAGES {
+        DROPPED_MESSAGE_COUNT.fetch_add(1, Ordering::Relaxed);
+        return None;
+    }
+
+    MAX_QUEUED_MESSAGE_COUNT.fetch_max(queue_depth, Ordering::Relaxed);
+
+    PROCESSING_SEMAPHORE.acquire().await.ok()
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ProcessingMetrics {
+    processing_messages: usize,
+    queued_messages: usize,
+    max_queued_messages: usize,
+    dropped_messages: u64,
+}
+
+pub(crate) fn get_processing_metrics() -> ProcessingMetrics {
+    ProcessingMetrics {
+        processing_messages: MAX_CONCURRENT_MESSAGES - PROCESSING_SEMAPHORE.available_permits(),
+        queued_messages: QUEUED_MESSAGE_COUNT.load(Ordering::Relaxed),
+        max_queued_messages: MAX_QUEUED_MESSAGE_COUNT.load(Ordering::Relaxed),
+        dropped_messages: DROPPED_MESSAGE_COUNT.load(Ordering::Relaxed),
+    }
+}
@@ -0,0 +1,138 @@
+use std::error::Error;
+
+use redis::AsyncCommands;
+use serenity::all::{
+    ChannelId, CreateAllowedMentions, CreateMessage, EditMessage, MessageId, MessageReference,
+    Timestamp,
+};
+use serenity::prelude::*;
+
+use crate::SerenityGlobalData;
+
+use super::backpressure::call_with_backpressure;
+use super::permissions::get_preview_permissions;
+use super::PreviewTarget;
+
+/// Previews are only recycled while they are likely still close to the bottom of the channel.
+const PREVIEW_INDEX_TTL: u64 = 24 * 60 * 60;
+
+const LAST_REFERENCED_PREFIX: &str = "-# Last referenced ";
+
+fn redis_preview_index_key(channel_id: ChannelId, url: &str) -> String {
+    format!(
+        "file_preview_index:{}:{}",
+        channel_id,
+        blake3::hash(url.as_bytes()).to_hex()
+    )
+}
+
+/// Remembers the preview of a URL, so that posting the URL again in the same channel can refer to it.
+pub(super) async fn redis_set_indexed_preview(
+    connection: &mut redis::aio::ConnectionManager,
+    channel_id: ChannelId,
+    url: &str,
+    message_id: MessageId,
+) -> Result<(), redis::RedisError> {
+    connection
+        .set_ex(
+            redis_preview_index_key(channel_id, url),
+            message_id.get(),
+            PREVIEW_INDEX_TTL,
+        )
+        .await
+}
+
+/// Replaces the "last referenced" line of a preview, which is always its last line.
+fn bump_last_referenced(content: &str) -> String {
+    let content = match content.rsplit_once('\n') {
+        Some((previous_content, last_line)) if last_line.starts_with(LAST_REFERENCED_PREFIX) => {
+            previous_content
+        }
+        _ => content.trim_end_matches('\n'),
+    };
+
+    format!(
+        "{}\n{}<t:{}:R>",
+        content,
+        LAST_REFERENCED_PREFIX,
+        Timestamp::now().unix_timestamp()
+    )
+}
+
+/// Instead of previewing a URL again, bumps the existing preview of the URL in the channel and replies with a link to it.
+/// Returns whether the preview was recycled, which fails if it has been deleted or can't fit another line.
+pub(super) async fn recycle_preview(
+    ctx: &Context,
+    target: &PreviewTarget<'_>,
+    url: &str,
+) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let redis_key = redis_preview_index_key(target.channel_id, url);
+
+    let Some(message_id) = redis_connection_manager
+        .get::<_, Option<u64>>(redis_key.as_str())
+        .await?
+        .filter(|message_id| *message_id != 0)
+        .map(MessageId::new)
+    else {
+        return Ok(false);
+    };
+
+    let Ok(mut preview_message) = ctx.http.get_message(target.channel_id, message_id).await else {
+        let _: Result<(), _> = redis_connection_manager.del(redis_key).await;
+        return Ok(false);
+    };
+
+    let content = bump_last_referenced(preview_message.content.as_str());
+
+    if content.chars().count() > 2000 {
+        return Ok(false);
+    }
+
+    call_with_backpressure(
+        target.channel_id,
+        target.priority,
+        preview_message.edit(&ctx, EditMessage::new().content(content)),
+    )
+    .await?;
+
+    let mut message = CreateMessage::new()
+        .content(format!(
+            "This link was previewed recently: {}",
+            preview_message.link()
+        ))
+        .allowed_mentions(CreateAllowedMentions::new().replied_user(false));
+
+    if get_preview_permissions(ctx, target.guild_id, target.channel_id).can_read_message_history {
+        message = message.reference_message(MessageReference::from(target.reference_message));
+    }
+
+    call_with_backpressure(
+        target.channel_id,
+        target.priority,
+        target.channel_id.send_message(&ctx.http, message),
+    )
+    .await?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_previous_last_referenced_line() {
+        let content =
+            bump_last_referenced("**octocat**/**hello-world**\n```rs\nfn main() {}\n```\n");
+        assert_eq!(content.matches(LAST_REFERENCED_PREFIX).count(), 1);
+        assert!(content.starts_with("**octocat**/**hello-world**\n```rs\nfn main() {}\n```\n-# "));
+
+        let content = bump_last_referenced(content.as_str());
+        assert_eq!(content.matches(LAST_REFERENCED_PREFIX).count(), 1);
+    }
+}
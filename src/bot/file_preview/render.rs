@@ -0,0 +1,317 @@
+use serenity::all::{
+    CreateAttachment, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateMessage,
+    MessageBuilder,
+};
+
+use crate::bot::guild_config::PreviewDisplayStyle;
+
+use super::permissions::PreviewPermissions;
+use super::FilePreview;
+
+/// Length of the code in a single message of a thread, leaving room for the code block delimiters.
+const THREAD_MESSAGE_MAX_LENGTH: usize = 1900;
+
+/// Limits how many messages a single preview can flood a thread with.
+const THREAD_MAX_MESSAGES: usize = 10;
+
+/// Discord rejects embeds whose title or author name is longer than 256 characters.
+const EMBED_TITLE_MAX_LENGTH: usize = 256;
+
+/// Shortens text for the title or author of an embed. Keeps the end if `keeps_end` is set,
+/// e.g. for file paths, whose end names the file.
+fn truncate_embed_title(text: &str, keeps_end: bool) -> String {
+    let length = text.chars().count();
+
+    if length <= EMBED_TITLE_MAX_LENGTH {
+        return text.to_owned();
+    }
+
+    if keeps_end {
+        let kept_text: String = text
+            .chars()
+            .skip(length - EMBED_TITLE_MAX_LENGTH + 1)
+            .collect();
+        format!("…{}", kept_text)
+    } else {
+        let kept_text: String = text.chars().take(EMBED_TITLE_MAX_LENGTH - 1).collect();
+        format!("{}…", kept_text)
+    }
+}
+
+pub(super) struct RenderContext<'a> {
+    pub(super) file_preview: &'a dyn FilePreview,
+    pub(super) file_content: &'a str,
+    pub(super) line_count: usize,
+    /// The language used for highlighting, i.e. the file extension with aliases applied.
+    pub(super) language: Option<&'a str>,
+    /// Attached instead of the content by renderers that attach it, if highlighting is enabled by the guild.
+    pub(super) highlighted_html: Option<&'a str>,
+    /// A warning shown above the preview, e.g. from link screening.
+    pub(super) banner: Option<&'a str>,
+    /// Shown below the content, e.g. repository metadata or if the selected lines differ from the linked ones.
+    pub(super) footer_lines: &'a [String],
+}
+
+impl RenderContext<'_> {
+    fn get_footer(&self) -> Option<String> {
+        (!self.footer_lines.is_empty()).then(|| self.footer_lines.join("\n"))
+    }
+
+    fn push_banner(&self, message_builder: MessageBuilder) -> MessageBuilder {
+        match self.banner {
+            Some(banner) => message_builder.push_line(banner),
+            None => message_builder,
+        }
+    }
+
+    /// Appends the footer lines as subtext.
+    fn push_footer(&self, mut message_builder: MessageBuilder) -> MessageBuilder {
+        for footer_line in self.footer_lines {
+            message_builder = message_builder
+                .push("-# ")
+                .push_line_safe(footer_line.as_str());
+        }
+
+        message_builder
+    }
+
+    /// The banner, metadata and footer, for renderers that show the content elsewhere.
+    fn get_message_content(&self) -> String {
+        self.push_footer(
+            self.push_banner(MessageBuilder::new())
+                .push(self.file_preview.get_metadata_content()),
+        )
+        .build()
+    }
+}
+
+pub(super) trait FilePreviewRenderer: Sync {
+    /// Whether the content fits into the message created by this renderer.
+    fn can_render(&self, render_context: &RenderContext) -> bool;
+    fn render<'a>(&self, render_context: &RenderContext<'a>) -> CreateMessage<'a>;
+
+    /// Whether the content is attached as a file, which can be highlighted ahead of rendering.
+    fn attaches_content(&self) -> bool {
+        false
+    }
+
+    /// The name and messages of a thread created on the rendered message, if any.
+    fn render_thread(
+        &self,
+        _render_context: &RenderContext,
+    ) -> Option<(String, Vec<CreateMessage<'static>>)> {
+        None
+    }
+}
+
+/// Renders the metadata followed by a code block in the message content.
+struct CodeBlockRenderer;
+
+impl FilePreviewRenderer for CodeBlockRenderer {
+    fn can_render(&self, render_context: &RenderContext) -> bool {
+        render_context.file_content.len()
+            + render_context.file_preview.get_metadata_content().len()
+            + render_context.banner.map_or(0, |banner| banner.len() + 1)
+            + render_context
+                .footer_lines
+                .iter()
+                .map(|footer_line| footer_line.len() + 4)
+                .sum::<usize>()
+            <= 1900
+            && render_context.line_count <= 6
+    }
+
+    fn render<'a>(&self, render_context: &RenderContext<'a>) -> CreateMessage<'a> {
+        let mut message_builder = render_context
+            .push_banner(MessageBuilder::new())
+            .push(render_context.file_preview.get_metadata_content())
+            .push_codeblock_safe(render_context.file_content, render_context.language);
+
+        if !render_context.footer_lines.is_empty() {
+            message_builder = render_context.push_footer(message_builder.push("\n"));
+        }
+
+        CreateMessage::new().content(message_builder.build())
+    }
+}
+
+/// Renders a rich embed with the code block in its description.
+struct EmbedRenderer;
+
+impl FilePreviewRenderer for EmbedRenderer {
+    fn can_render(&self, render_context: &RenderContext) -> bool {
+        render_context.file_content.len() <= 3900 && render_context.line_count <= 15
+    }
+
+    fn render<'a>(&self, render_context: &RenderContext<'a>) -> CreateMessage<'a> {
+        let metadata = render_context.file_preview.get_metadata();
+
+        let mut embed = CreateEmbed::new()
+            .author(CreateEmbedAuthor::new(truncate_embed_title(
+                metadata.source.as_str(),
+                false,
+            )))
+            .title(truncate_embed_title(metadata.file_path.as_str(), true))
+            .url(render_context.file_preview.get_message_url().as_str())
+            .description(
+                MessageBuilder::new()
+                    .push_codeblock_safe(render_context.file_content, render_context.language)
+                    .build(),
+            );
+
+        if let Some(owner_avatar_url) = &metadata.owner_avatar_url {
+            embed = embed.thumbnail(owner_avatar_url.as_str());
+        }
+
+        if let Some(footer) = render_context.get_footer() {
+            embed = embed.footer(CreateEmbedFooter::new(footer));
+        }
+
+        let message = CreateMessage::new().embed(embed);
+
+        match render_context.banner {
+            Some(banner) => message.content(banner),
+            None => message,
+        }
+    }
+}
+
+/// Renders the metadata in the message content and attaches the content as a file.
+/// Used as the fallback for content that doesn't fit into any other renderer.
+struct AttachmentRenderer;
+
+impl FilePreviewRenderer for AttachmentRenderer {
+    fn can_render(&self, _render_context: &RenderContext) -> bool {
+        true
+    }
+
+    fn render<'a>(&self, render_context: &RenderContext<'a>) -> CreateMessage<'a> {
+        let attachment = match render_context.highlighted_html {
+            Some(highlighted_html) => {
+                CreateAttachment::bytes(highlighted_html.as_bytes().to_vec(), "preview.html")
+            }
+            None => CreateAttachment::bytes(
+                render_context.file_content.as_bytes().to_vec(),
+                format!("preview.{}", render_context.language.unwrap_or("txt")),
+            ),
+        };
+
+        CreateMessage::new()
+            .content(render_context.get_message_content())
+            .add_file(attachment)
+    }
+
+    fn attaches_content(&self) -> bool {
+        true
+    }
+}
+
+/// Splits the lines into code blocks that fit into a message each.
+fn split_into_code_blocks(file_content: &str, language: Option<&str>) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    let mut chunk = String::new();
+
+    for line in file_content.lines() {
+        let line: String = line.chars().take(THREAD_MESSAGE_MAX_LENGTH - 1).collect();
+
+        if chunk.len() + line.len() + 1 > THREAD_MESSAGE_MAX_LENGTH {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+
+        chunk.push_str(line.as_str());
+        chunk.push('\n');
+    }
+
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+
+    chunks
+        .into_iter()
+        .map(|chunk| {
+            MessageBuilder::new()
+                .push_codeblock_safe(chunk, language)
+                .build()
+        })
+        .collect()
+}
+
+/// Renders the metadata in the message content, and posts the content in a thread created on it.
+/// Used for content too long for a single message where attachments aren't permitted, or if preferred by the guild.
+struct ThreadRenderer;
+
+impl FilePreviewRenderer for ThreadRenderer {
+    fn can_render(&self, render_context: &RenderContext) -> bool {
+        render_context.file_content.len() <= THREAD_MESSAGE_MAX_LENGTH * THREAD_MAX_MESSAGES / 2
+    }
+
+    fn render<'a>(&self, render_context: &RenderContext<'a>) -> CreateMessage<'a> {
+        CreateMessage::new().content(render_context.get_message_content())
+    }
+
+    fn render_thread(
+        &self,
+        render_context: &RenderContext,
+    ) -> Option<(String, Vec<CreateMessage<'static>>)> {
+        let thread_name: String = render_context
+            .file_preview
+            .get_metadata()
+            .file_path
+            .chars()
+            .take(100)
+            .collect();
+
+        let messages = split_into_code_blocks(render_context.file_content, render_context.language)
+            .into_iter()
+            .take(THREAD_MAX_MESSAGES)
+            .map(|chunk| CreateMessage::new().content(chunk))
+            .collect();
+
+        Some((thread_name, messages))
+    }
+}
+
+/// Chooses how the content is rendered, based on the display style of the guild, the size of the content
+/// and the permissions in the channel. Returns the preferred renderer if the content fits,
+/// falling back to attachments and threads if they are permitted.
+pub(super) fn select_renderer(
+    display_style: PreviewDisplayStyle,
+    render_context: &RenderContext,
+    permissions: &PreviewPermissions,
+) -> Option<&'static dyn FilePreviewRenderer> {
+    let preferred_renderer: &'static dyn FilePreviewRenderer = match display_style {
+        PreviewDisplayStyle::Embed if permissions.can_embed_links => &EmbedRenderer,
+        PreviewDisplayStyle::Thread if permissions.can_create_threads => &ThreadRenderer,
+        _ => &CodeBlockRenderer,
+    };
+
+    let fallback_renderers: [(&'static dyn FilePreviewRenderer, bool); 2] = [
+        (&AttachmentRenderer, permissions.can_attach_files),
+        (&ThreadRenderer, permissions.can_create_threads),
+    ];
+
+    std::iter::once((preferred_renderer, true))
+        .chain(fallback_renderers)
+        .find(|(renderer, is_permitted)| *is_permitted && renderer.can_render(render_context))
+        .map(|(renderer, _)| renderer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_long_embed_titles() {
+        let file_path = format!("{}/main.rs", "ä".repeat(300));
+
+        let title = truncate_embed_title(file_path.as_str(), true);
+        assert_eq!(title.chars().count(), EMBED_TITLE_MAX_LENGTH);
+        assert!(title.starts_with('…') && title.ends_with("/main.rs"));
+
+        let author = truncate_embed_title(file_path.as_str(), false);
+        assert_eq!(author.chars().count(), EMBED_TITLE_MAX_LENGTH);
+        assert!(author.ends_with('…'));
+
+        assert_eq!(truncate_embed_title("src/main.rs", true), "src/main.rs");
+    }
+}
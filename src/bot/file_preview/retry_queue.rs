@@ -0,0 +1,310 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use redis::AsyncCommands;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, GuildId, MessageId, UserId};
+use serenity::prelude::*;
+
+use crate::retry::jittered_backoff;
+use crate::SerenityGlobalData;
+
+use super::{
+    find_preview_urls, get_guild_config, is_preview_channel_disabled, send_file_preview,
+    PreviewTarget,
+};
+
+/// Previews that failed due to a transient error are retried at most this many times.
+const PREVIEW_RETRY_MAX_ATTEMPTS: u32 = 3;
+
+/// Retries wait at least this long, plus a jittered backoff with the same base.
+const PREVIEW_RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+
+const PREVIEW_RETRY_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Due retries processed per poll, spreading out the burst after an outage of an upstream.
+const PREVIEW_RETRY_BATCH_SIZE: isize = 10;
+
+/// Sorted set of the pending retries, scored by the UNIX timestamp at which they are due.
+const REDIS_PREVIEW_RETRY_QUEUE_KEY: &str = "preview_retry_queue";
+
+const DELAYED_PREVIEW_NOTICE: &str =
+    "This preview was delayed, as the file couldn't be fetched at first.";
+
+static IS_RETRY_WORKER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// A failure that is likely to go away on its own, e.g. a server error or rate limit of an upstream.
+#[derive(Debug)]
+pub(super) struct TransientError(&'static str);
+
+impl fmt::Display for TransientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl Error for TransientError {}
+
+/// Builds the error for an unsuccessful response, which is transient for server errors and rate limits.
+pub(super) fn request_failed_error(
+    status: StatusCode,
+    message: &'static str,
+) -> Box<dyn Error + Send + Sync> {
+    if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        Box::new(TransientError(message))
+    } else {
+        message.into()
+    }
+}
+
+/// Whether retrying later might succeed, i.e. the error or one of its sources is transient or a timeout.
+pub(super) fn is_transient_error(error: &(dyn Error + 'static)) -> bool {
+    let mut current_error = Some(error);
+
+    while let Some(error) = current_error {
+        if error.is::<TransientError>()
+            || error
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(reqwest::Error::is_timeout)
+        {
+            return true;
+        }
+
+        current_error = error.source();
+    }
+
+    false
+}
+
+#[derive(Serialize, Deserialize)]
+struct PreviewRetry {
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    author_id: UserId,
+    url: String,
+    /// Number of retries that already failed.
+    attempt: u32,
+}
+
+fn get_unix_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+async fn redis_push_preview_retry(
+    connection: &mut redis::aio::ConnectionManager,
+    retry: &PreviewRetry,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let delay =
+        PREVIEW_RETRY_BASE_DELAY + jittered_backoff(PREVIEW_RETRY_BASE_DELAY, retry.attempt);
+    let due_unix_ts = get_unix_ts() + delay.as_secs();
+
+    connection
+        .zadd::<_, _, _, ()>(
+            REDIS_PREVIEW_RETRY_QUEUE_KEY,
+            serde_json::to_string(retry)?,
+            due_unix_ts,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Queues the preview of a URL in the message of the target to be retried later.
+pub(super) async fn redis_enqueue_preview_retry(
+    connection: &mut redis::aio::ConnectionManager,
+    target: &PreviewTarget<'_>,
+    url: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    redis_push_preview_retry(
+        connection,
+        &PreviewRetry {
+            guild_id: target.guild_id,
+            channel_id: target.channel_id,
+            message_id: target.reference_message.id,
+            author_id: target.author_id,
+            url: url.to_owned(),
+            attempt: 0,
+        },
+    )
+    .await
+}
+
+/// Removes and returns the retries that are due.
+async fn redis_take_due_preview_retries(
+    connection: &mut redis::aio::ConnectionManager,
+) -> Result<Vec<PreviewRetry>, redis::RedisError> {
+    let members: Vec<String> = connection
+        .zrangebyscore_limit(
+            REDIS_PREVIEW_RETRY_QUEUE_KEY,
+            "-inf",
+            get_unix_ts(),
+            0,
+            PREVIEW_RETRY_BATCH_SIZE,
+        )
+        .await?;
+
+    let mut retries = Vec::with_capacity(members.len());
+
+    for member in members {
+        // Removing the retry claims it, so that it is processed once even if several instances share Redis.
+        let removed_count: usize = connection
+            .zrem(REDIS_PREVIEW_RETRY_QUEUE_KEY, member.as_str())
+            .await?;
+
+        if removed_count == 0 {
+            continue;
+        }
+
+        match serde_json::from_str(member.as_str()) {
+            Ok(retry) => retries.push(retry),
+            Err(error) => println!("Dropped malformed file preview retry: {:?}", error),
+        }
+    }
+
+    Ok(retries)
+}
+
+/// Fetches the file again and posts the preview with a note that it was delayed,
+/// or queues another retry if it failed due to a transient error again.
+async fn retry_preview(
+    ctx: &Context,
+    retry: PreviewRetry,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // The preview is dropped if the message has been deleted in the meantime.
+    let reference_message = ctx
+        .http
+        .get_message(retry.channel_id, retry.message_id)
+        .await?;
+
+    let contains_url = std::iter::once(reference_message.content.as_str())
+        .chain(
+            reference_message
+                .message_snapshots
+                .iter()
+                .map(|message_snapshot| message_snapshot.content.as_str()),
+        )
+        .any(|content| content.contains(retry.url.as_str()));
+
+    if !contains_url {
+        return Ok(());
+    }
+
+    // Messages fetched over HTTP don't carry the guild ID.
+    let target = PreviewTarget {
+        author_id: retry.author_id,
+        guild_id: retry.guild_id,
+        ..PreviewTarget::from(&reference_message)
+    };
+
+    let guild_config = get_guild_config(ctx, target.guild_id).await?;
+
+    if is_preview_channel_disabled(ctx, &guild_config, &target) {
+        return Ok(());
+    }
+
+    let enabled_preview_sources = guild_config.enabled_preview_sources();
+
+    let Some(url_match) = find_preview_urls(retry.url.as_str(), 0, &enabled_preview_sources).next()
+    else {
+        return Ok(());
+    };
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    match url_match
+        .get_file_preview(redis_connection_manager.clone())
+        .await
+    {
+        Ok((file_preview, anchor)) => {
+            send_file_preview(
+                ctx,
+                &target,
+                &guild_config,
+                file_preview,
+                anchor,
+                Some(DELAYED_PREVIEW_NOTICE),
+            )
+            .await
+        }
+        Err(error)
+            if is_transient_error(&*error) && retry.attempt + 1 < PREVIEW_RETRY_MAX_ATTEMPTS =>
+        {
+            redis_push_preview_retry(
+                &mut redis_connection_manager,
+                &PreviewRetry {
+                    attempt: retry.attempt + 1,
+                    ..retry
+                },
+            )
+            .await
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Starts retrying queued previews in the background, once per process.
+pub(crate) fn spawn_preview_retry_worker(ctx: Context) {
+    if IS_RETRY_WORKER_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut redis_connection_manager = ctx
+            .data::<SerenityGlobalData>()
+            .redis_connection_manager
+            .clone();
+
+        let mut interval = tokio::time::interval(PREVIEW_RETRY_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let retries = match redis_take_due_preview_retries(&mut redis_connection_manager).await
+            {
+                Ok(retries) => retries,
+                Err(error) => {
+                    println!("Error while taking file preview retries: {:?}", error);
+                    continue;
+                }
+            };
+
+            for retry in retries {
+                let (url, message_id) = (retry.url.clone(), retry.message_id);
+
+                if let Err(error) = retry_preview(&ctx, retry).await {
+                    println!(
+                        "Dropped delayed file preview of {} in message {}: {:?}",
+                        url, message_id, error
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_transient_errors() {
+        let rate_limited = request_failed_error(StatusCode::TOO_MANY_REQUESTS, "Failed.");
+        let server_error = request_failed_error(StatusCode::BAD_GATEWAY, "Failed.");
+        let not_found = request_failed_error(StatusCode::NOT_FOUND, "Failed.");
+
+        assert!(is_transient_error(&*rate_limited));
+        assert!(is_transient_error(&*server_error));
+        assert!(!is_transient_error(&*not_found));
+        assert_eq!(not_found.to_string(), rate_limited.to_string());
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+/// Number of recent scans the percentiles are computed from.
+const SCAN_SAMPLE_CAPACITY: usize = 1024;
+
+/// Scans exceeding the budget are logged, as every message in every guild is scanned.
+const SCAN_TIME_BUDGET: Duration = Duration::from_millis(2);
+
+static SCAN_SAMPLES: Lazy<Mutex<VecDeque<Duration>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(SCAN_SAMPLE_CAPACITY)));
+
+static SKIPPED_MESSAGE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Counts a message that was rejected by the pre-checks before scanning.
+pub(super) fn record_skipped_message() {
+    SKIPPED_MESSAGE_COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(super) fn record_scan_time(scan_time: Duration) {
+    if scan_time > SCAN_TIME_BUDGET {
+        println!(
+            "Scanning a message for preview URLs took {:?}, exceeding the budget of {:?}.",
+            scan_time, SCAN_TIME_BUDGET
+        );
+    }
+
+    let mut scan_samples = SCAN_SAMPLES.lock().unwrap();
+
+    if scan_samples.len() == SCAN_SAMPLE_CAPACITY {
+        scan_samples.pop_front();
+    }
+
+    scan_samples.push_back(scan_time);
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ScanMetrics {
+    skipped_messages: u64,
+    samples: usize,
+    p50_micros: u64,
+    p90_micros: u64,
+    p99_micros: u64,
+    max_micros: u64,
+}
+
+pub(crate) fn get_scan_metrics() -> ScanMetrics {
+    let mut scan_samples: Vec<Duration> = SCAN_SAMPLES.lock().unwrap().iter().copied().collect();
+    scan_samples.sort_unstable();
+
+    let percentile = |percentile: usize| {
+        scan_samples
+            .get((scan_samples.len() * percentile / 100).min(scan_samples.len().saturating_sub(1)))
+            .map_or(0, |scan_time| scan_time.as_micros() as u64)
+    };
+
+    ScanMetrics {
+        skipped_messages: SKIPPED_MESSAGE_COUNT.load(Ordering::Relaxed),
+        samples: scan_samples.len(),
+        p50_micros: percentile(50),
+        p90_micros: percentile(90),
+        p99_micros: percentile(99),
+        max_micros: percentile(100),
+    }
+}
@@ -0,0 +1,92 @@
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
+use serenity::all::Timestamp;
+
+use crate::bot::github_api::RepositoryMetadata;
+use crate::config::runtime_config;
+
+/// Repositories younger than this are typical of scams, which are deleted and re-created frequently.
+const NEW_REPOSITORY_MAX_AGE_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// File names that scams commonly lure people into running, e.g. `free-nitro.js` or `token-grabber.py`.
+static SUSPICIOUS_FILE_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(
+        r"(?:free|gift|claim)[-_ ]?nitro|nitro[-_ ]?(?:gen|generator|free|gift|sniper)|token[-_ ]?(?:grab|log|steal)|discord[-_ ]?(?:token|nuke)|free[-_ ]?robux|steam[-_ ]?gift|airdrop|wallet[-_ ]?drain",
+    )
+    .case_insensitive(true)
+    .build()
+    .unwrap()
+});
+
+enum ScreeningFinding {
+    Blocklisted,
+    NewRepository { age_days: i64 },
+    SuspiciousFileName,
+}
+
+impl ScreeningFinding {
+    fn describe(&self) -> String {
+        match self {
+            Self::Blocklisted => "it is on the blocklist of this bot".to_owned(),
+            Self::NewRepository { age_days: 0 } => "it was created less than a day ago".to_owned(),
+            Self::NewRepository { age_days: 1 } => "it was created a day ago".to_owned(),
+            Self::NewRepository { age_days } => format!("it was created {} days ago", age_days),
+            Self::SuspiciousFileName => "the file name is typical of scams".to_owned(),
+        }
+    }
+}
+
+/// Matches entries in the format `owner` or `owner/repo`, ignoring case like GitHub does.
+fn is_blocklisted(owner: &str, repository: &str) -> bool {
+    let owner = owner.to_ascii_lowercase();
+    let full_name = format!("{}/{}", owner, repository.to_ascii_lowercase());
+
+    runtime_config()
+        .screening_blocklist
+        .iter()
+        .any(|entry| *entry == owner || *entry == full_name)
+}
+
+/// Checks a previewed repository file for signs of a scam, returning a warning to be shown above the preview.
+/// The age of the repository is only checked if its metadata is available.
+pub(super) fn screen_repository_file(
+    owner: &str,
+    repository: &str,
+    file_path: &str,
+    repository_metadata: Option<&RepositoryMetadata>,
+) -> Option<String> {
+    let mut findings = Vec::new();
+
+    if is_blocklisted(owner, repository) {
+        findings.push(ScreeningFinding::Blocklisted);
+    }
+
+    if let Some(created_at) =
+        repository_metadata.and_then(|repository_metadata| repository_metadata.created_at)
+    {
+        let age_seconds = Timestamp::now().unix_timestamp() - created_at.unix_timestamp();
+
+        if age_seconds < NEW_REPOSITORY_MAX_AGE_SECONDS {
+            findings.push(ScreeningFinding::NewRepository {
+                age_days: age_seconds.max(0) / (24 * 60 * 60),
+            });
+        }
+    }
+
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+
+    if SUSPICIOUS_FILE_NAME_REGEX.is_match(file_name) {
+        findings.push(ScreeningFinding::SuspiciousFileName);
+    }
+
+    if findings.is_empty() {
+        return None;
+    }
+
+    let reasons: Vec<String> = findings.iter().map(ScreeningFinding::describe).collect();
+
+    Some(format!(
+        "⚠️ **This repository may be malicious**, as {}. Don't run any of its code unless you trust it.",
+        reasons.join(" and ")
+    ))
+}
@@ -0,0 +1,216 @@
+use std::io::Cursor;
+use std::ops::Deref;
+use std::time::{Duration, Instant};
+
+use ab_glyph::{Font, FontRef, ScaleFont};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use imageproc::drawing::draw_text_mut;
+use once_cell::sync::Lazy;
+
+use crate::bot::guild_config::AttachmentTheme;
+
+use super::highlight::{highlight_lines, HighlightedLines};
+
+/// Longer selections are cut off, as the image would become too small to read once Discord scales it down.
+pub(super) const SNIPPET_IMAGE_MAX_LINES: usize = 60;
+
+/// Longer lines are cut off and end with an ellipsis.
+const SNIPPET_IMAGE_MAX_COLUMNS: usize = 120;
+
+/// Rendering gives up after this long, e.g. on pathological input for the regular expressions of a syntax.
+const RENDER_TIME_BUDGET: Duration = Duration::from_secs(2);
+
+const FONT_SCALE: f32 = 28.0;
+const LINE_HEIGHT: u32 = 40;
+const PADDING: u32 = 48;
+
+/// Space between the line numbers and the code, in columns.
+const GUTTER_SPACING: usize = 2;
+
+static CODE_FONT: Lazy<FontRef> = Lazy::new(|| {
+    let font_data = include_bytes!("../../../assets/font/DejaVuSansMono.ttf");
+    FontRef::try_from_slice(font_data).unwrap()
+});
+
+fn get_rgba([red, green, blue]: [u8; 3]) -> Rgba<u8> {
+    Rgba([red, green, blue, 255])
+}
+
+/// Halfway between the foreground and the background, for line numbers.
+fn get_dimmed_rgba(foreground: [u8; 3], background: [u8; 3]) -> Rgba<u8> {
+    let mut color = [0; 3];
+
+    for (channel, (foreground, background)) in
+        color.iter_mut().zip(foreground.iter().zip(background))
+    {
+        *channel = ((*foreground as u16 + background as u16) / 2) as u8;
+    }
+
+    get_rgba(color)
+}
+
+/// Cuts off the regions of a line after [`SNIPPET_IMAGE_MAX_COLUMNS`] characters, ending it with an ellipsis.
+fn truncate_regions(regions: &[([u8; 3], String)]) -> Vec<([u8; 3], String)> {
+    let mut remaining_columns = SNIPPET_IMAGE_MAX_COLUMNS;
+    let mut truncated_regions = Vec::with_capacity(regions.len());
+
+    for (color, text) in regions {
+        let column_count = text.chars().count();
+
+        if column_count <= remaining_columns {
+            remaining_columns -= column_count;
+            truncated_regions.push((*color, text.clone()));
+            continue;
+        }
+
+        let mut text: String = text
+            .chars()
+            .take(remaining_columns.saturating_sub(1))
+            .collect();
+        text.push('…');
+        truncated_regions.push((*color, text));
+        break;
+    }
+
+    truncated_regions
+}
+
+/// Draws highlighted lines with line numbers onto a background in the colors of the theme, encoded as PNG.
+fn draw_snippet_image(
+    highlighted_lines: &HighlightedLines,
+    first_line_number: usize,
+) -> Option<Vec<u8>> {
+    let lines: Vec<Vec<([u8; 3], String)>> = highlighted_lines
+        .lines
+        .iter()
+        .map(|regions| truncate_regions(regions.as_slice()))
+        .collect();
+
+    let line_number_length = (first_line_number + lines.len().max(1) - 1)
+        .to_string()
+        .len();
+    let code_offset = line_number_length + GUTTER_SPACING;
+
+    let max_column_count = lines
+        .iter()
+        .map(|regions| {
+            regions
+                .iter()
+                .map(|(_, text)| text.chars().count())
+                .sum::<usize>()
+        })
+        .max()
+        .unwrap_or(0);
+
+    // The font is monospaced, so every glyph advances by the same width.
+    let scaled_font = CODE_FONT.as_scaled(FONT_SCALE);
+    let column_width = scaled_font.h_advance(CODE_FONT.glyph_id('0'));
+
+    let width =
+        2 * PADDING + ((code_offset + max_column_count) as f32 * column_width).ceil() as u32;
+    let height = 2 * PADDING + lines.len() as u32 * LINE_HEIGHT;
+
+    let mut image = RgbaImage::from_pixel(width, height, get_rgba(highlighted_lines.background));
+    let line_number_color =
+        get_dimmed_rgba(highlighted_lines.foreground, highlighted_lines.background);
+
+    for (index, regions) in lines.iter().enumerate() {
+        let y = (PADDING + index as u32 * LINE_HEIGHT) as i32;
+
+        draw_text_mut(
+            &mut image,
+            line_number_color,
+            PADDING as i32,
+            y,
+            FONT_SCALE,
+            CODE_FONT.deref(),
+            format!(
+                "{:>width$}",
+                first_line_number + index,
+                width = line_number_length
+            )
+            .as_str(),
+        );
+
+        let mut column = code_offset;
+
+        for (color, text) in regions {
+            // Drawing each character on its own keeps the columns aligned, even for characters missing from the font.
+            for character in text.chars() {
+                if !character.is_whitespace() {
+                    draw_text_mut(
+                        &mut image,
+                        get_rgba(*color),
+                        PADDING as i32 + (column as f32 * column_width) as i32,
+                        y,
+                        FONT_SCALE,
+                        CODE_FONT.deref(),
+                        character.encode_utf8(&mut [0; 4]),
+                    );
+                }
+
+                column += 1;
+            }
+        }
+    }
+
+    let mut png_data = Vec::new();
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut Cursor::new(&mut png_data), ImageFormat::Png)
+        .ok()?;
+
+    Some(png_data)
+}
+
+/// Renders the lines as a highlighted PNG image for sharing outside of Discord,
+/// or returns `None` if highlighting doesn't finish within the time budget.
+/// Tabs must be expanded beforehand.
+pub(super) fn render_snippet_image(
+    lines: &[String],
+    first_line_number: usize,
+    language: &str,
+    theme: AttachmentTheme,
+) -> Option<Vec<u8>> {
+    let highlighted_lines = highlight_lines(
+        &lines[..lines.len().min(SNIPPET_IMAGE_MAX_LINES)],
+        language,
+        theme,
+        Instant::now() + RENDER_TIME_BUDGET,
+    )?;
+
+    draw_snippet_image(&highlighted_lines, first_line_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_long_lines() {
+        let regions = vec![
+            ([0; 3], "x".repeat(SNIPPET_IMAGE_MAX_COLUMNS - 1)),
+            ([255; 3], "yyy".to_owned()),
+        ];
+
+        let truncated_regions = truncate_regions(&regions);
+
+        assert_eq!(truncated_regions.len(), 2);
+        assert_eq!(truncated_regions[1].1, "…");
+    }
+
+    #[test]
+    fn renders_png_image() {
+        let png_data = render_snippet_image(
+            &["fn main() {".to_owned(), "}".to_owned()],
+            9,
+            "rs",
+            AttachmentTheme::Dark,
+        )
+        .unwrap();
+
+        let image = image::load_from_memory(&png_data).unwrap();
+
+        assert_eq!(image.height(), 2 * PADDING + 2 * LINE_HEIGHT);
+        assert!(image.width() > 2 * PADDING);
+    }
+}
@@ -0,0 +1,135 @@
+use std::error::Error;
+
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexSet};
+use reqwest::Url;
+use serenity::async_trait;
+
+use crate::bot::guild_config::PreviewSource;
+
+use super::anchor::Anchor;
+use super::gist::{to_canonical_gist_url, GistFilePreview};
+use super::github_repository_file::{to_canonical_url, GitHubRepositoryFilePreview};
+use super::FilePreview;
+
+/// A kind of link that file previews can be created for.
+/// Adding a source only requires implementing this trait and registering it in [`PREVIEW_SOURCE_REGISTRY`].
+#[async_trait]
+pub(super) trait PreviewSourceHandler: Sync + Send {
+    /// The identifier of the source, used for toggling it per guild.
+    fn source(&self) -> PreviewSource;
+
+    /// Matches the URLs of the source within message content.
+    fn url_pattern(&self) -> &'static str;
+
+    /// Rewrites alternative forms of a matched URL to the one that the source links to,
+    /// so that they are fetched, linked and recycled alike.
+    fn canonicalize(&self, url: Url) -> Result<Url, Box<dyn Error + Send + Sync>> {
+        Ok(url)
+    }
+
+    /// Extracts the part of the file that a matched URL points to.
+    fn parse(&self, url: &Url) -> Result<Anchor, Box<dyn Error + Send + Sync>>;
+
+    async fn fetch(&self, url: Url) -> Result<Box<dyn FilePreview>, Box<dyn Error + Send + Sync>>;
+}
+
+struct GitHubRepositoryFileSource;
+
+#[async_trait]
+impl PreviewSourceHandler for GitHubRepositoryFileSource {
+    fn source(&self) -> PreviewSource {
+        PreviewSource::GitHubRepositoryFile
+    }
+
+    fn url_pattern(&self) -> &'static str {
+        r"https://(?:github\.(?:com|dev)(?:/[^/\s]+){2}/(?:blob|blame)|raw\.githubusercontent\.com(?:/[^/\s]+){2})(?:/[^/\s]+)+#(?:L\d[^/\s]*|cell-\d+|page=\d+|symbol-[^/\s]+|/[^\s]*)"
+    }
+
+    fn canonicalize(&self, url: Url) -> Result<Url, Box<dyn Error + Send + Sync>> {
+        to_canonical_url(&url).ok_or_else(|| "Malformed GitHub repository URL.".into())
+    }
+
+    fn parse(&self, url: &Url) -> Result<Anchor, Box<dyn Error + Send + Sync>> {
+        Anchor::parse_fragment(url)
+    }
+
+    async fn fetch(&self, url: Url) -> Result<Box<dyn FilePreview>, Box<dyn Error + Send + Sync>> {
+        Ok(Box::new(GitHubRepositoryFilePreview::new(url).await?))
+    }
+}
+
+struct GistSource;
+
+#[async_trait]
+impl PreviewSourceHandler for GistSource {
+    fn source(&self) -> PreviewSource {
+        PreviewSource::Gist
+    }
+
+    fn url_pattern(&self) -> &'static str {
+        r"https://(?:gist\.github\.com(?:/[^/\s]+){2}(?:/[0-9a-f]{40})?#file\-|gist\.githubusercontent\.com(?:/[^/\s]+){2}/raw(?:/[^/\s]+){1,2}#L\d)[^\s]*"
+    }
+
+    fn canonicalize(&self, url: Url) -> Result<Url, Box<dyn Error + Send + Sync>> {
+        to_canonical_gist_url(&url).ok_or_else(|| "Malformed gist URL.".into())
+    }
+
+    fn parse(&self, url: &Url) -> Result<Anchor, Box<dyn Error + Send + Sync>> {
+        Anchor::parse_line_markers(url)
+    }
+
+    async fn fetch(&self, url: Url) -> Result<Box<dyn FilePreview>, Box<dyn Error + Send + Sync>> {
+        Ok(Box::new(GistFilePreview::new(url).await?))
+    }
+}
+
+pub(super) struct PreviewSourceRegistry {
+    handlers: Vec<Box<dyn PreviewSourceHandler>>,
+    /// URL regexes of all handlers, in the same order.
+    regexes: Vec<Regex>,
+    /// Detects which sources occur in the content in a single pass,
+    /// so that only their regexes have to run to find the positions of the URLs.
+    regex_set: RegexSet,
+}
+
+impl PreviewSourceRegistry {
+    fn new(handlers: Vec<Box<dyn PreviewSourceHandler>>) -> Self {
+        let regexes = handlers
+            .iter()
+            .map(|handler| Regex::new(handler.url_pattern()).unwrap())
+            .collect();
+
+        let regex_set =
+            RegexSet::new(handlers.iter().map(|handler| handler.url_pattern())).unwrap();
+
+        Self {
+            handlers,
+            regexes,
+            regex_set,
+        }
+    }
+
+    /// Returns the handlers whose URLs occur in the content, along with their regexes.
+    pub(super) fn matching_handlers<'a>(
+        &'a self,
+        content: &str,
+    ) -> impl Iterator<Item = (&'a dyn PreviewSourceHandler, &'a Regex)> {
+        let matched_handlers = self.regex_set.matches(content);
+
+        self.handlers
+            .iter()
+            .zip(self.regexes.iter())
+            .enumerate()
+            .filter(move |(index, _)| matched_handlers.matched(*index))
+            .map(|(_, (handler, regex))| (handler.as_ref(), regex))
+    }
+}
+
+/// All preview sources, in the order in which they are checked.
+pub(super) static PREVIEW_SOURCE_REGISTRY: Lazy<PreviewSourceRegistry> = Lazy::new(|| {
+    PreviewSourceRegistry::new(vec![
+        Box::new(GitHubRepositoryFileSource),
+        Box::new(GistSource),
+    ])
+});
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+/// Files in which whitespace changes the meaning or breaks the format, and which are therefore
+/// often discussed because of it, e.g. patches that don't apply or Makefiles with spaces instead of tabs.
+const DIFF_SENSITIVE_EXTENSIONS: [&str; 10] = [
+    "diff", "patch", "mk", "mak", "yml", "yaml", "md", "markdown", "py", "tsv",
+];
+
+const DIFF_SENSITIVE_FILE_NAMES: [&str; 5] = [
+    "Makefile",
+    "makefile",
+    "GNUmakefile",
+    ".editorconfig",
+    ".gitattributes",
+];
+
+const TRAILING_SPACE_MARKER: char = '·';
+const TAB_MARKER: char = '→';
+
+pub(super) fn is_diff_sensitive_file(file_path: &str) -> bool {
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+
+    DIFF_SENSITIVE_FILE_NAMES.contains(&file_name)
+        || file_name.rsplit_once('.').is_some_and(|(_, extension)| {
+            DIFF_SENSITIVE_EXTENSIONS
+                .iter()
+                .any(|sensitive_extension| extension.eq_ignore_ascii_case(sensitive_extension))
+        })
+}
+
+/// Converts CRLF and CR line endings to LF, returning the content and the converted line ending, if any.
+pub(super) fn normalize_line_endings(raw_content: &str) -> (Cow<'_, str>, Option<&'static str>) {
+    if !raw_content.contains('\r') {
+        return (Cow::Borrowed(raw_content), None);
+    }
+
+    let line_ending = if raw_content.contains("\r\n") {
+        "CRLF"
+    } else {
+        "CR"
+    };
+
+    (
+        Cow::Owned(raw_content.replace("\r\n", "\n").replace('\r', "\n")),
+        Some(line_ending),
+    )
+}
+
+/// Expands tabs like [`super::expand_tabs`], but starts them with an arrow, and replaces trailing spaces with dots.
+/// Returns the line and whether it had trailing whitespace.
+pub(super) fn visualize_whitespace(line: &str, tab_size: usize) -> (String, bool) {
+    let trailing_start = line.trim_end_matches([' ', '\t']).len();
+    let mut result = String::with_capacity(line.len());
+    let mut current_position = 0;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            '\t' => {
+                let spaces_to_add = tab_size - (current_position % tab_size);
+                result.push(TAB_MARKER);
+                result.push_str(&" ".repeat(spaces_to_add - 1));
+                current_position += spaces_to_add;
+            }
+            ' ' if index >= trailing_start => {
+                result.push(TRAILING_SPACE_MARKER);
+                current_position += 1;
+            }
+            _ => {
+                result.push(c);
+                current_position += 1;
+            }
+        }
+    }
+
+    (result, trailing_start < line.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(
+            normalize_line_endings("a\nb\n"),
+            (Cow::Borrowed("a\nb\n"), None)
+        );
+        assert_eq!(
+            normalize_line_endings("a\r\nb\r\n"),
+            (Cow::Owned("a\nb\n".to_owned()), Some("CRLF"))
+        );
+        assert_eq!(
+            normalize_line_endings("a\rb\r"),
+            (Cow::Owned("a\nb\n".to_owned()), Some("CR"))
+        );
+    }
+
+    #[test]
+    fn visualizes_tabs_and_trailing_spaces() {
+        assert_eq!(
+            visualize_whitespace("\tlet x = 1;  ", 4),
+            ("→   let x = 1;··".to_owned(), true)
+        );
+        assert_eq!(visualize_whitespace("a\tb", 4), ("a→  b".to_owned(), false));
+        assert_eq!(visualize_whitespace("x \t", 4), ("x·→ ".to_owned(), true));
+    }
+
+    #[test]
+    fn detects_diff_sensitive_files() {
+        assert!(is_diff_sensitive_file("src/Makefile"));
+        assert!(is_diff_sensitive_file("fix.PATCH"));
+        assert!(is_diff_sensitive_file(".github/workflows/ci.yml"));
+        assert!(!is_diff_sensitive_file("src/main.rs"));
+    }
+}
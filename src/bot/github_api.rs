@@ -0,0 +1,347 @@
+use once_cell::sync::Lazy;
+use redis::AsyncCommands;
+use regex::Regex;
+use reqwest::{header, RequestBuilder, Response, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serenity::all::Timestamp;
+
+use crate::config::config;
+use crate::outbound::send_limited;
+use crate::HTTP_CLIENT;
+
+static REPOSITORY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([A-Za-z0-9-]+)/([A-Za-z0-9._-]+)$").unwrap());
+
+/// Splits user input in the format `owner/repo`.
+pub(crate) fn parse_repository(repository: &str) -> Result<(&str, &str), String> {
+    let repository_captures = REPOSITORY_REGEX
+        .captures(repository.trim())
+        .ok_or("The repository must be in the format owner/repo.")?;
+
+    Ok((
+        repository_captures.get(1).unwrap().as_str(),
+        repository_captures.get(2).unwrap().as_str(),
+    ))
+}
+
+/// Creates a GET request to the GitHub REST API, with each segment of the path being percent-encoded.
+/// Authenticates using `GITHUB_TOKEN` if it is set, which raises the rate limit considerably.
+pub(crate) fn github_api_get(path_segments: &[&str]) -> RequestBuilder {
+    let mut url = Url::parse("https://api.github.com/").unwrap();
+    url.path_segments_mut().unwrap().extend(path_segments);
+
+    let request = HTTP_CLIENT
+        .get(url)
+        .header(header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+
+    match config().github_token.as_deref() {
+        Some(github_token) => request.bearer_auth(github_token),
+        None => request,
+    }
+}
+
+/// Maps unsuccessful responses to an error message that can be shown to the user.
+pub(crate) fn check_response_status(
+    response: &Response,
+    not_found_message: &str,
+) -> Result<(), String> {
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        StatusCode::NOT_FOUND | StatusCode::UNPROCESSABLE_ENTITY => {
+            Err(not_found_message.to_owned())
+        }
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+            Err("GitHub is rate limiting the bot. Please try again later.".to_owned())
+        }
+        _ => Err("GitHub responded with an error.".to_owned()),
+    }
+}
+
+/// Resolves a branch, tag or abbreviated commit to the full commit SHA.
+pub(crate) async fn resolve_commit_sha(
+    owner: &str,
+    repository: &str,
+    reference: &str,
+) -> Result<String, String> {
+    let response = send_limited(
+        github_api_get(&["repos", owner, repository, "commits", reference])
+            .header(header::ACCEPT, "application/vnd.github.sha"),
+    )
+    .await
+    .map_err(|_| "Failed to reach GitHub.")?;
+
+    check_response_status(&response, "The repository or reference could not be found.")?;
+
+    let commit_sha = response
+        .text()
+        .await
+        .map_err(|_| "Failed to receive the commit from GitHub.")?;
+
+    Ok(commit_sha.trim().to_owned())
+}
+
+/// Blame is only available in the GraphQL API, where objects other than commits resolve to an empty object.
+const BLAME_QUERY: &str =
+    "query($owner: String!, $repository: String!, $expression: String!, $path: String!) {
+  repository(owner: $owner, name: $repository) {
+    object(expression: $expression) {
+      ... on Commit {
+        blame(path: $path) {
+          ranges { startingLine endingLine commit { oid } }
+        }
+      }
+    }
+  }
+}";
+
+#[derive(Debug, Deserialize)]
+struct APIBlameResponse {
+    data: Option<APIBlameData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APIBlameData {
+    repository: Option<APIBlameRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APIBlameRepository {
+    object: Option<APIBlameObject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APIBlameObject {
+    #[serde(default)]
+    blame: Option<APIBlame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct APIBlame {
+    ranges: Vec<APIBlameRange>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct APIBlameRange {
+    starting_line: u32,
+    ending_line: u32,
+    commit: APIBlameCommit,
+}
+
+#[derive(Debug, Deserialize)]
+struct APIBlameCommit {
+    oid: String,
+}
+
+/// Finds the commit that last changed a line of a file at the reference.
+/// Requires `GITHUB_TOKEN`, as the GraphQL API can't be used anonymously.
+pub(crate) async fn get_blamed_commit_sha(
+    owner: &str,
+    repository: &str,
+    reference: &str,
+    path: &str,
+    line_number: u32,
+) -> Result<String, String> {
+    let github_token = config()
+        .github_token
+        .as_deref()
+        .ok_or("Blame is not available on this instance of the bot.")?;
+
+    let response = send_limited(
+        HTTP_CLIENT
+            .post("https://api.github.com/graphql")
+            .bearer_auth(github_token)
+            .json(&json!({
+                "query": BLAME_QUERY,
+                "variables": {
+                    "owner": owner,
+                    "repository": repository,
+                    "expression": reference,
+                    "path": path,
+                },
+            })),
+    )
+    .await
+    .map_err(|_| "Failed to reach GitHub.")?;
+
+    check_response_status(&response, "The repository could not be found.")?;
+
+    let blame_response = response
+        .json::<APIBlameResponse>()
+        .await
+        .map_err(|_| "Failed to receive the blame from GitHub.")?;
+
+    blame_response
+        .data
+        .and_then(|data| data.repository)
+        .and_then(|repository| repository.object)
+        .and_then(|object| object.blame)
+        .ok_or("The file or reference could not be found.")?
+        .ranges
+        .into_iter()
+        .find(|range| (range.starting_line..=range.ending_line).contains(&line_number))
+        .map(|range| range.commit.oid)
+        .ok_or_else(|| "The linked line doesn't exist in the file.".to_owned())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TreeEntryKind {
+    Blob,
+    Tree,
+    /// Submodules, which point to a commit in another repository.
+    Commit,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct TreeEntry {
+    pub(crate) path: String,
+    #[serde(rename = "type")]
+    pub(crate) kind: TreeEntryKind,
+    pub(crate) sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct APITreeResponse {
+    tree: Vec<TreeEntry>,
+}
+
+/// Lists the entries of a single tree. Commit SHAs are accepted as well, resolving to their root tree.
+pub(crate) async fn get_tree_entries(
+    owner: &str,
+    repository: &str,
+    tree_sha: &str,
+) -> Result<Vec<TreeEntry>, String> {
+    let response = send_limited(github_api_get(&[
+        "repos", owner, repository, "git", "trees", tree_sha,
+    ]))
+    .await
+    .map_err(|_| "Failed to reach GitHub.")?;
+
+    check_response_status(&response, "The directory could not be found.")?;
+
+    let tree_response: APITreeResponse = response
+        .json()
+        .await
+        .map_err(|_| "Failed to receive the directory from GitHub.")?;
+
+    Ok(tree_response.tree)
+}
+
+/// Repository metadata rarely changes in a meaningful way, so it is cached for a day.
+const REPOSITORY_METADATA_TTL: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Deserialize)]
+struct APILicense {
+    spdx_id: Option<String>,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct APIRepositoryResponse {
+    stargazers_count: u64,
+    language: Option<String>,
+    license: Option<APILicense>,
+    created_at: Timestamp,
+}
+
+/// Context about a repository for previews of its files.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct RepositoryMetadata {
+    pub(crate) stars: u64,
+    pub(crate) language: Option<String>,
+    /// The SPDX identifier of the license if it has one, its name otherwise.
+    pub(crate) license: Option<String>,
+    #[serde(default)]
+    pub(crate) created_at: Option<Timestamp>,
+}
+
+impl From<APIRepositoryResponse> for RepositoryMetadata {
+    fn from(response: APIRepositoryResponse) -> Self {
+        Self {
+            stars: response.stargazers_count,
+            language: response.language,
+            license: response.license.map(|license| {
+                license
+                    .spdx_id
+                    .filter(|spdx_id| spdx_id != "NOASSERTION")
+                    .unwrap_or(license.name)
+            }),
+            created_at: Some(response.created_at),
+        }
+    }
+}
+
+/// Formats large counts compactly, e.g. `1.2k` or `35k`.
+fn format_count(count: u64) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=9_999 => format!("{:.1}k", count as f64 / 1_000.0),
+        10_000..=999_999 => format!("{}k", count / 1_000),
+        _ => format!("{:.1}m", count as f64 / 1_000_000.0),
+    }
+}
+
+impl RepositoryMetadata {
+    /// A single line such as `★ 1.2k · Rust · MIT`.
+    pub(crate) fn format_footer(&self) -> String {
+        std::iter::once(format!("★ {}", format_count(self.stars)))
+            .chain(self.language.clone())
+            .chain(self.license.clone())
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+fn redis_repository_metadata_key(owner: &str, repository: &str) -> String {
+    format!(
+        "github_repository:{}/{}",
+        owner.to_ascii_lowercase(),
+        repository.to_ascii_lowercase()
+    )
+}
+
+/// Fetches the metadata of a repository, which is cached in Redis as it is requested for every preview.
+pub(crate) async fn get_repository_metadata(
+    connection: &mut redis::aio::ConnectionManager,
+    owner: &str,
+    repository: &str,
+) -> Result<RepositoryMetadata, String> {
+    let redis_key = redis_repository_metadata_key(owner, repository);
+
+    if let Some(repository_metadata) = connection
+        .get::<_, Option<String>>(redis_key.as_str())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|data| serde_json::from_str(data.as_str()).ok())
+    {
+        return Ok(repository_metadata);
+    }
+
+    let response = send_limited(github_api_get(&["repos", owner, repository]))
+        .await
+        .map_err(|_| "Failed to reach GitHub.")?;
+
+    check_response_status(&response, "The repository could not be found.")?;
+
+    let repository_metadata = RepositoryMetadata::from(
+        response
+            .json::<APIRepositoryResponse>()
+            .await
+            .map_err(|_| "Failed to receive the repository from GitHub.")?,
+    );
+
+    let _: Result<(), _> = connection
+        .set_ex(
+            redis_key,
+            serde_json::to_string(&repository_metadata)
+                .expect("Failed to serialize repository metadata."),
+            REPOSITORY_METADATA_TTL,
+        )
+        .await;
+
+    Ok(repository_metadata)
+}
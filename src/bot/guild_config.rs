@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, GuildId};
+
+use crate::bot::commands::color::parse::RgbColor;
+use crate::bot::commands::juxtapose::preview::DividerStyle;
+use crate::bot::file_preview::keyword_filter::{
+    compile_keyword_filter, KEYWORD_FILTER_MAX_PATTERNS,
+};
+
+pub(crate) const DISABLED_CHANNELS_MAX_COUNT: usize = 100;
+pub(crate) const JUXTAPOSE_WATERMARK_MAX_LENGTH: usize = 50;
+/// Discord shows at most 25 autocomplete choices, so more presets couldn't be selected.
+pub(crate) const JUXTAPOSE_PRESETS_MAX_COUNT: usize = 25;
+pub(crate) const JUXTAPOSE_PRESET_NAME_MAX_LENGTH: usize = 32;
+const JUXTAPOSE_LABEL_MAX_LENGTH: usize = 100;
+pub(crate) const LANGUAGE_ALIASES_MAX_COUNT: usize = 20;
+pub(crate) const LANGUAGE_ALIAS_MAX_LENGTH: usize = 16;
+
+/// Set of the guilds with a digest channel, so the digest doesn't need to load every guild config.
+const REDIS_DIGEST_GUILDS_KEY: &str = "digest_guilds";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum KeywordFilterAction {
+    #[default]
+    Block,
+    Redact,
+}
+
+impl KeywordFilterAction {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::Redact => "redact",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PreviewDisplayStyle {
+    #[default]
+    CodeBlock,
+    Embed,
+    Thread,
+}
+
+impl PreviewDisplayStyle {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::CodeBlock => "code_block",
+            Self::Embed => "embed",
+            Self::Thread => "thread",
+        }
+    }
+}
+
+/// Theme of the highlighted HTML document attached instead of plain text, for previews too long for a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AttachmentTheme {
+    Dark,
+    Light,
+}
+
+impl AttachmentTheme {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+        }
+    }
+}
+
+/// Which file previews show tabs and trailing whitespace, and mention converted line endings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum WhitespaceIndicators {
+    #[default]
+    Off,
+    /// Files whose format depends on whitespace, e.g. patches, Makefiles and YAML.
+    DiffSensitive,
+    All,
+}
+
+impl WhitespaceIndicators {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::DiffSensitive => "diff_sensitive",
+            Self::All => "all",
+        }
+    }
+}
+
+/// A kind of link that file previews are created for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum PreviewSource {
+    #[serde(rename = "github")]
+    GitHubRepositoryFile,
+    #[serde(rename = "gist")]
+    Gist,
+}
+
+impl PreviewSource {
+    pub(crate) const ALL: [Self; 2] = [Self::GitHubRepositoryFile, Self::Gist];
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::GitHubRepositoryFile => "github",
+            Self::Gist => "gist",
+        }
+    }
+
+    pub(crate) fn parse(source: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preview_source| preview_source.as_str() == source)
+    }
+}
+
+/// Labels and layout of a comparison a guild makes often, e.g. "Before" and "After", used by `/juxtapose-preset`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct JuxtaposePreset {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) left_label: Option<String>,
+    #[serde(default)]
+    pub(crate) right_label: Option<String>,
+    #[serde(default)]
+    pub(crate) is_vertical: bool,
+    #[serde(default)]
+    pub(crate) divider_thickness: Option<u32>,
+    /// Divider color in hex notation, parsed when the preset is used.
+    #[serde(default)]
+    pub(crate) divider_color: Option<String>,
+}
+
+impl JuxtaposePreset {
+    pub(crate) fn divider(&self) -> DividerStyle {
+        DividerStyle {
+            thickness: self.divider_thickness,
+            color: self
+                .divider_color
+                .as_deref()
+                .and_then(RgbColor::parse)
+                .unwrap_or(DividerStyle::default().color),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty()
+            || self.name.chars().count() > JUXTAPOSE_PRESET_NAME_MAX_LENGTH
+        {
+            return Err(format!(
+                "Preset names must not be empty or longer than {} characters.",
+                JUXTAPOSE_PRESET_NAME_MAX_LENGTH
+            ));
+        }
+
+        for label in [&self.left_label, &self.right_label].into_iter().flatten() {
+            if label.chars().count() > JUXTAPOSE_LABEL_MAX_LENGTH {
+                return Err(format!(
+                    "The labels of the preset {} must not be longer than {} characters.",
+                    self.name, JUXTAPOSE_LABEL_MAX_LENGTH
+                ));
+            }
+        }
+
+        if self
+            .divider_thickness
+            .is_some_and(|thickness| thickness > DividerStyle::MAX_THICKNESS)
+        {
+            return Err(format!(
+                "The divider of the preset {} must not be thicker than {} pixels.",
+                self.name,
+                DividerStyle::MAX_THICKNESS
+            ));
+        }
+
+        if let Some(divider_color) = &self.divider_color {
+            RgbColor::parse(divider_color).ok_or_else(|| {
+                format!("The divider color of the preset {} is invalid.", self.name)
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Strips the leading dot that extensions are often written with, e.g. `.zs`.
+pub(crate) fn normalize_alias_extension(extension: &str) -> String {
+    extension
+        .trim()
+        .trim_start_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// Extensions and languages end up in code block info strings and attachment names,
+/// so they are limited to characters that are valid in both, e.g. `c++` or `objective-c`.
+pub(crate) fn validate_language_alias(extension: &str, language: &str) -> Result<(), String> {
+    for (kind, value) in [("extension", extension), ("language", language)] {
+        if value.is_empty() || value.chars().count() > LANGUAGE_ALIAS_MAX_LENGTH {
+            return Err(format!(
+                "The {} of an alias must not be empty or longer than {} characters.",
+                kind, LANGUAGE_ALIAS_MAX_LENGTH
+            ));
+        }
+
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '#' | '-' | '_'))
+        {
+            return Err(format!(
+                "The {} {} may only contain letters, digits and the characters + # - _",
+                kind, value
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Per-guild settings, stored as a single JSON document in Redis.
+/// Missing fields fall back to their defaults, so new settings can be added without migrating existing entries.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct GuildConfig {
+    pub(crate) audit_channel_id: Option<ChannelId>,
+    /// Receives a weekly summary of the previews and juxtaposes in the guild, if set.
+    pub(crate) digest_channel_id: Option<ChannelId>,
+    pub(crate) keyword_filters: Vec<String>,
+    pub(crate) keyword_filter_action: KeywordFilterAction,
+    pub(crate) preview_display_style: PreviewDisplayStyle,
+    /// Attaches long previews as highlighted HTML documents instead of plain text files, if set.
+    pub(crate) attachment_theme: Option<AttachmentTheme>,
+    pub(crate) whitespace_indicators: WhitespaceIndicators,
+    pub(crate) disabled_preview_sources: Vec<PreviewSource>,
+    /// Channels in which links aren't previewed automatically.
+    pub(crate) disabled_channel_ids: Vec<ChannelId>,
+    /// Adds the stars, language and license of the repository below previews of GitHub repository files.
+    pub(crate) show_repository_metadata: bool,
+    /// Warns above previews of repositories that are blocklisted, were created recently or contain files named like scams.
+    pub(crate) link_screening: bool,
+    /// Refers to the existing preview when a link is posted again in the same channel, instead of previewing it again.
+    pub(crate) recycle_previews: bool,
+    /// Adds a short hash of the previewed lines below previews, to tell later whether the file has changed since.
+    pub(crate) show_content_hash: bool,
+    /// Text drawn in a corner of juxtapose previews, for attribution when they are shared outside of Discord.
+    pub(crate) juxtapose_watermark: Option<String>,
+    pub(crate) juxtapose_presets: Vec<JuxtaposePreset>,
+    /// Languages used to highlight files by their lowercase extension, merged over the configured aliases.
+    pub(crate) language_aliases: BTreeMap<String, String>,
+}
+
+impl GuildConfig {
+    /// Finds a preset by its name, ignoring case.
+    pub(crate) fn find_juxtapose_preset(&self, name: &str) -> Option<&JuxtaposePreset> {
+        self.juxtapose_presets
+            .iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub(crate) fn enabled_preview_sources(&self) -> Vec<PreviewSource> {
+        PreviewSource::ALL
+            .into_iter()
+            .filter(|preview_source| !self.disabled_preview_sources.contains(preview_source))
+            .collect()
+    }
+
+    fn redis_key(guild_id: GuildId) -> String {
+        format!("guild_config:{}", guild_id)
+    }
+
+    /// Checks the limits otherwise enforced by the config command, for configs submitted as a whole.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.keyword_filters.len() > KEYWORD_FILTER_MAX_PATTERNS {
+            return Err(format!(
+                "The keyword filter must not contain more than {} patterns.",
+                KEYWORD_FILTER_MAX_PATTERNS
+            ));
+        }
+
+        for pattern in &self.keyword_filters {
+            compile_keyword_filter(pattern)
+                .map_err(|error| format!("The pattern {} is invalid: {}", pattern, error))?;
+        }
+
+        if self.disabled_channel_ids.len() > DISABLED_CHANNELS_MAX_COUNT {
+            return Err(format!(
+                "File previews must not be disabled in more than {} channels.",
+                DISABLED_CHANNELS_MAX_COUNT
+            ));
+        }
+
+        if self
+            .juxtapose_watermark
+            .as_ref()
+            .is_some_and(|watermark| watermark.chars().count() > JUXTAPOSE_WATERMARK_MAX_LENGTH)
+        {
+            return Err(format!(
+                "The juxtapose watermark must not be longer than {} characters.",
+                JUXTAPOSE_WATERMARK_MAX_LENGTH
+            ));
+        }
+
+        if self.juxtapose_presets.len() > JUXTAPOSE_PRESETS_MAX_COUNT {
+            return Err(format!(
+                "There must not be more than {} juxtapose presets.",
+                JUXTAPOSE_PRESETS_MAX_COUNT
+            ));
+        }
+
+        for (index, preset) in self.juxtapose_presets.iter().enumerate() {
+            preset.validate()?;
+
+            if self.juxtapose_presets[..index]
+                .iter()
+                .any(|other_preset| other_preset.name.eq_ignore_ascii_case(&preset.name))
+            {
+                return Err(format!("The preset name {} is used twice.", preset.name));
+            }
+        }
+
+        if self.language_aliases.len() > LANGUAGE_ALIASES_MAX_COUNT {
+            return Err(format!(
+                "There must not be more than {} language aliases.",
+                LANGUAGE_ALIASES_MAX_COUNT
+            ));
+        }
+
+        for (extension, language) in &self.language_aliases {
+            validate_language_alias(extension, language)?;
+
+            if *extension != normalize_alias_extension(extension) {
+                return Err(format!(
+                    "The extension {} of an alias must be lowercase and without a leading dot.",
+                    extension
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn redis_get(
+        connection: &mut redis::aio::ConnectionManager,
+        guild_id: GuildId,
+    ) -> Result<Self, redis::RedisError> {
+        let data: Option<String> = connection.get(Self::redis_key(guild_id)).await?;
+
+        Ok(data
+            .and_then(|data| {
+                serde_json::from_str(data.as_str())
+                    .inspect_err(|err| println!("Error while parsing guild config: {:?}", err))
+                    .ok()
+            })
+            .unwrap_or_default())
+    }
+
+    pub(crate) async fn redis_set(
+        &self,
+        connection: &mut redis::aio::ConnectionManager,
+        guild_id: GuildId,
+    ) -> Result<(), redis::RedisError> {
+        let data = serde_json::to_string(self).expect("Failed to serialize guild config.");
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().set(Self::redis_key(guild_id), data).ignore();
+
+        if self.digest_channel_id.is_some() {
+            pipe.sadd(REDIS_DIGEST_GUILDS_KEY, guild_id.get()).ignore();
+        } else {
+            pipe.srem(REDIS_DIGEST_GUILDS_KEY, guild_id.get()).ignore();
+        }
+
+        pipe.query_async(connection).await
+    }
+
+    /// Stores the default config unless the guild already has one, returning whether it was stored.
+    pub(crate) async fn redis_init(
+        connection: &mut redis::aio::ConnectionManager,
+        guild_id: GuildId,
+    ) -> Result<bool, redis::RedisError> {
+        let data =
+            serde_json::to_string(&Self::default()).expect("Failed to serialize guild config.");
+        connection.set_nx(Self::redis_key(guild_id), data).await
+    }
+
+    pub(crate) async fn redis_delete(
+        connection: &mut redis::aio::ConnectionManager,
+        guild_id: GuildId,
+    ) -> Result<(), redis::RedisError> {
+        redis::pipe()
+            .atomic()
+            .del(Self::redis_key(guild_id))
+            .ignore()
+            .srem(REDIS_DIGEST_GUILDS_KEY, guild_id.get())
+            .ignore()
+            .query_async(connection)
+            .await
+    }
+
+    /// The guilds whose config has a digest channel.
+    pub(crate) async fn redis_get_digest_guild_ids(
+        connection: &mut redis::aio::ConnectionManager,
+    ) -> Result<Vec<GuildId>, redis::RedisError> {
+        let guild_ids: Vec<u64> = connection.smembers(REDIS_DIGEST_GUILDS_KEY).await?;
+
+        Ok(guild_ids
+            .into_iter()
+            .filter(|guild_id| *guild_id != 0)
+            .map(GuildId::new)
+            .collect())
+    }
+}
@@ -0,0 +1,211 @@
+use std::error::Error;
+
+use serenity::all::{
+    ButtonStyle, Colour, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, Guild, GuildId,
+    Permissions, UnavailableGuild,
+};
+use serenity::prelude::*;
+
+use crate::bot::audit_log::{send_audit_log, AuditLogEntry};
+use crate::bot::commands::config::create_config_embed;
+use crate::bot::custom_id::sign_custom_id;
+use crate::bot::guild_config::{GuildConfig, PreviewDisplayStyle};
+use crate::bot::guild_stats::redis_delete_guild_stats;
+use crate::SerenityGlobalData;
+
+pub const SETUP_DISPLAY_STYLE_CUSTOM_ID_PREFIX: &str = "setupDisplayStyle";
+pub const SETUP_SHOW_CONFIG_CUSTOM_ID_PREFIX: &str = "setupShowConfig";
+
+fn create_setup_embed(bot_name: &str) -> CreateEmbed<'static> {
+    CreateEmbed::new()
+        .title(format!("Thanks for adding {}!", bot_name))
+        .colour(Colour::BLURPLE)
+        .field(
+            "File Previews",
+            "Post a link to lines of a file on GitHub or in a gist, e.g. ending with `#L10-L20`, and the lines are previewed right below it.",
+            false,
+        )
+        .field(
+            "Juxtapose",
+            "Compare two images side by side with `/juxtapose`, which links to an interactive slider.",
+            false,
+        )
+        .field(
+            "Configuration",
+            "Pick how previews are shown using the buttons below. `/config` offers everything else, e.g. disabling previews in a channel.",
+            false,
+        )
+        .field(
+            "Privacy",
+            "`/forgetme` deletes all data stored about you.",
+            false,
+        )
+}
+
+fn create_setup_components() -> Vec<CreateActionRow<'static>> {
+    let display_style_buttons = [
+        (PreviewDisplayStyle::CodeBlock, "Code Block"),
+        (PreviewDisplayStyle::Embed, "Embed"),
+        (PreviewDisplayStyle::Thread, "Thread"),
+    ]
+    .into_iter()
+    .map(|(display_style, label)| {
+        CreateButton::new(sign_custom_id(
+            format!(
+                "{}:{}",
+                SETUP_DISPLAY_STYLE_CUSTOM_ID_PREFIX,
+                display_style.as_str()
+            )
+            .as_str(),
+        ))
+        .style(ButtonStyle::Secondary)
+        .label(label)
+    });
+
+    let show_config_button = CreateButton::new(sign_custom_id(SETUP_SHOW_CONFIG_CUSTOM_ID_PREFIX))
+        .style(ButtonStyle::Primary)
+        .emoji('⚙')
+        .label("Show Configuration");
+
+    vec![CreateActionRow::buttons(
+        display_style_buttons
+            .chain([show_config_button])
+            .collect::<Vec<_>>(),
+    )]
+}
+
+/// Stores the default configuration of a newly joined guild and posts a quick start guide to its system channel.
+pub(crate) async fn handle_guild_join(
+    ctx: &Context,
+    guild: &Guild,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    // Guilds that added the bot before keep their configuration.
+    GuildConfig::redis_init(&mut redis_connection_manager, guild.id).await?;
+
+    let Some(system_channel_id) = guild.system_channel_id else {
+        return Ok(());
+    };
+
+    let bot_name = ctx.cache.current_user().name.to_string();
+
+    system_channel_id
+        .send_message(
+            &ctx.http,
+            CreateMessage::new()
+                .embed(create_setup_embed(bot_name.as_str()))
+                .components(create_setup_components()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Deletes the data stored about a guild once the bot has been removed from it.
+/// Guilds that became unavailable due to an outage keep their data.
+pub(crate) async fn handle_guild_leave(
+    ctx: &Context,
+    incomplete: &UnavailableGuild,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if incomplete.unavailable {
+        return Ok(());
+    }
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    GuildConfig::redis_delete(&mut redis_connection_manager, incomplete.id).await?;
+    redis_delete_guild_stats(&mut redis_connection_manager, incomplete.id).await?;
+
+    Ok(())
+}
+
+fn can_manage_guild(interaction: &ComponentInteraction) -> bool {
+    interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD))
+}
+
+async fn run_setup_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    guild_id: GuildId,
+    custom_id: &str,
+) -> Result<CreateEmbed<'static>, String> {
+    if !can_manage_guild(interaction) {
+        return Err("You need the Manage Server permission to configure the bot.".to_owned());
+    }
+
+    let mut redis_connection_manager = ctx
+        .data::<SerenityGlobalData>()
+        .redis_connection_manager
+        .clone();
+
+    let mut guild_config = GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+        .await
+        .map_err(|_| "Failed to load the configuration.")?;
+
+    if let Some(display_style) = custom_id
+        .strip_prefix(SETUP_DISPLAY_STYLE_CUSTOM_ID_PREFIX)
+        .and_then(|display_style| display_style.strip_prefix(':'))
+    {
+        guild_config.preview_display_style = match display_style {
+            "code_block" => PreviewDisplayStyle::CodeBlock,
+            "embed" => PreviewDisplayStyle::Embed,
+            "thread" => PreviewDisplayStyle::Thread,
+            _ => return Err("Unknown display style.".to_owned()),
+        };
+
+        guild_config
+            .redis_set(&mut redis_connection_manager, guild_id)
+            .await
+            .map_err(|_| "Failed to save the configuration.")?;
+
+        send_audit_log(
+            &ctx.http,
+            Some(guild_id),
+            &guild_config,
+            AuditLogEntry::ConfigChanged {
+                changed_by_id: interaction.user.id,
+                change: format!("display_style {}", display_style).as_str(),
+            },
+        )
+        .await;
+    }
+
+    Ok(create_config_embed(&guild_config))
+}
+
+/// Applies the display style picked on the quick start guide, or shows the configuration, only to whoever pressed the button.
+pub async fn handle_setup_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let guild_id = interaction
+        .guild_id
+        .ok_or("The quick start guide can only be used in servers.")?;
+
+    let response = match run_setup_button(ctx, interaction, guild_id, custom_id).await {
+        Ok(embed) => CreateInteractionResponseMessage::new().embed(embed),
+        Err(error) => CreateInteractionResponseMessage::new().content(error),
+    };
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(response.ephemeral(true)),
+        )
+        .await?;
+
+    Ok(())
+}
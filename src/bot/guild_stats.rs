@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::time::SystemTime;
+
+use redis::AsyncCommands;
+use serde::Serialize;
+use serenity::all::GuildId;
+
+use crate::bot::guild_config::PreviewSource;
+
+/// Previews are counted per day, and the counts of this many days are summed up for the leaderboard.
+pub(crate) const PREVIEW_STATS_DAYS: u64 = 30;
+
+/// Daily counts expire once they no longer contribute to the leaderboard.
+const DAILY_STATS_TTL: i64 = (PREVIEW_STATS_DAYS as i64 + 1) * 24 * 60 * 60;
+
+pub(crate) const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Counters of the activity within a guild, stored as a hash in Redis, both in total and per day.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct GuildStats {
+    pub(crate) file_previews: u64,
+    pub(crate) juxtaposes: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum GuildStatsCounter {
+    FilePreviews,
+    Juxtaposes,
+}
+
+impl GuildStatsCounter {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::FilePreviews => "file_previews",
+            Self::Juxtaposes => "juxtaposes",
+        }
+    }
+}
+
+/// What previews are counted by, each stored as a sorted set per day.
+#[derive(Debug, Clone, Copy)]
+enum PreviewStatsDimension {
+    Sources,
+    Repositories,
+    Files,
+}
+
+impl PreviewStatsDimension {
+    const ALL: [Self; 3] = [Self::Sources, Self::Repositories, Self::Files];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sources => "sources",
+            Self::Repositories => "repositories",
+            Self::Files => "files",
+        }
+    }
+}
+
+/// Preview counts of a guild over a range of days, sorted by count in descending order.
+#[derive(Debug, Default)]
+pub(crate) struct PreviewStats {
+    pub(crate) sources: Vec<(String, u64)>,
+    /// Repositories as `owner/repository`.
+    pub(crate) repositories: Vec<(String, u64)>,
+    /// Files as `owner/repository/path`.
+    pub(crate) files: Vec<(String, u64)>,
+}
+
+/// Days since the UNIX epoch, by which daily counts are stored.
+pub(crate) fn get_unix_day() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECONDS_PER_DAY
+}
+
+/// The given number of days up to and including the current one.
+pub(crate) fn get_last_days(day_count: u64) -> RangeInclusive<u64> {
+    let today = get_unix_day();
+    (today + 1).saturating_sub(day_count)..=today
+}
+
+fn redis_guild_stats_key(guild_id: GuildId) -> String {
+    format!("guild_stats:{}", guild_id)
+}
+
+fn redis_daily_guild_stats_key(guild_id: GuildId, day: u64) -> String {
+    format!("guild_daily_stats:{}:{}", guild_id, day)
+}
+
+fn redis_preview_stats_key(
+    guild_id: GuildId,
+    dimension: PreviewStatsDimension,
+    day: u64,
+) -> String {
+    format!(
+        "guild_preview_stats:{}:{}:{}",
+        guild_id,
+        dimension.as_str(),
+        day
+    )
+}
+
+/// Days whose counts haven't expired yet, including the current one.
+fn get_unexpired_days() -> RangeInclusive<u64> {
+    get_last_days(PREVIEW_STATS_DAYS + 1)
+}
+
+/// Sums up the counts of each member across days, keeping the `limit` highest ones.
+fn merge_daily_counts(daily_counts: Vec<Vec<(String, u64)>>, limit: usize) -> Vec<(String, u64)> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+
+    for (member, count) in daily_counts.into_iter().flatten() {
+        *counts.entry(member).or_default() += count;
+    }
+
+    let mut counts: Vec<(String, u64)> = counts.into_iter().collect();
+    counts.sort_by(|(member_a, count_a), (member_b, count_b)| {
+        count_b.cmp(count_a).then_with(|| member_a.cmp(member_b))
+    });
+    counts.truncate(limit);
+
+    counts
+}
+
+pub(crate) async fn redis_increment_guild_stats(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+    counter: GuildStatsCounter,
+) -> Result<(), redis::RedisError> {
+    let daily_key = redis_daily_guild_stats_key(guild_id, get_unix_day());
+
+    redis::pipe()
+        .atomic()
+        .hincr(redis_guild_stats_key(guild_id), counter.as_str(), 1)
+        .ignore()
+        .hincr(&daily_key, counter.as_str(), 1)
+        .ignore()
+        .expire(&daily_key, DAILY_STATS_TTL)
+        .ignore()
+        .query_async(connection)
+        .await
+}
+
+/// Counts a preview by its source and, if it belongs to a repository, by the repository and file.
+pub(crate) async fn redis_record_guild_preview(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+    source: PreviewSource,
+    repository: Option<(&str, &str)>,
+    file_path: &str,
+) -> Result<(), redis::RedisError> {
+    let day = get_unix_day();
+    let mut counted_members = vec![(PreviewStatsDimension::Sources, source.as_str().to_owned())];
+
+    if let Some((owner, repository)) = repository {
+        counted_members.push((
+            PreviewStatsDimension::Repositories,
+            format!("{}/{}", owner, repository),
+        ));
+        counted_members.push((
+            PreviewStatsDimension::Files,
+            format!("{}/{}/{}", owner, repository, file_path),
+        ));
+    }
+
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+
+    for (dimension, member) in counted_members {
+        let key = redis_preview_stats_key(guild_id, dimension, day);
+
+        pipe.zincr(&key, member, 1)
+            .ignore()
+            .expire(&key, DAILY_STATS_TTL)
+            .ignore();
+    }
+
+    pipe.query_async(connection).await
+}
+
+/// Returns the preview counts of the given days, with at most `limit` repositories and files.
+/// Only the last [`PREVIEW_STATS_DAYS`] days are kept.
+pub(crate) async fn redis_get_preview_stats(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+    days: RangeInclusive<u64>,
+    limit: usize,
+) -> Result<PreviewStats, redis::RedisError> {
+    let mut pipe = redis::pipe();
+
+    for dimension in PreviewStatsDimension::ALL {
+        for day in days.clone() {
+            pipe.zrange_withscores(redis_preview_stats_key(guild_id, dimension, day), 0, -1);
+        }
+    }
+
+    let mut daily_counts: Vec<Vec<(String, u64)>> = pipe.query_async(connection).await?;
+    let days_per_dimension = daily_counts.len() / PreviewStatsDimension::ALL.len();
+
+    let files = daily_counts.split_off(2 * days_per_dimension);
+    let repositories = daily_counts.split_off(days_per_dimension);
+    let sources = daily_counts;
+
+    Ok(PreviewStats {
+        sources: merge_daily_counts(sources, PreviewSource::ALL.len()),
+        repositories: merge_daily_counts(repositories, limit),
+        files: merge_daily_counts(files, limit),
+    })
+}
+
+/// Deletes the counters and the daily counts of the guild.
+pub(crate) async fn redis_delete_guild_stats(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+) -> Result<(), redis::RedisError> {
+    let mut keys = vec![redis_guild_stats_key(guild_id)];
+
+    for day in get_unexpired_days() {
+        keys.push(redis_daily_guild_stats_key(guild_id, day));
+        keys.extend(
+            PreviewStatsDimension::ALL
+                .into_iter()
+                .map(|dimension| redis_preview_stats_key(guild_id, dimension, day)),
+        );
+    }
+
+    connection.del(keys).await
+}
+
+pub(crate) async fn redis_get_guild_stats(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+) -> Result<GuildStats, redis::RedisError> {
+    let (file_previews, juxtaposes): (Option<u64>, Option<u64>) = connection
+        .hget(
+            redis_guild_stats_key(guild_id),
+            &[
+                GuildStatsCounter::FilePreviews.as_str(),
+                GuildStatsCounter::Juxtaposes.as_str(),
+            ],
+        )
+        .await?;
+
+    Ok(GuildStats {
+        file_previews: file_previews.unwrap_or_default(),
+        juxtaposes: juxtaposes.unwrap_or_default(),
+    })
+}
+
+/// Sums up the daily counters of the given days. Only the last [`PREVIEW_STATS_DAYS`] days are kept.
+pub(crate) async fn redis_get_guild_stats_of_days(
+    connection: &mut redis::aio::ConnectionManager,
+    guild_id: GuildId,
+    days: RangeInclusive<u64>,
+) -> Result<GuildStats, redis::RedisError> {
+    let mut pipe = redis::pipe();
+
+    for day in days {
+        pipe.hget(
+            redis_daily_guild_stats_key(guild_id, day),
+            &[
+                GuildStatsCounter::FilePreviews.as_str(),
+                GuildStatsCounter::Juxtaposes.as_str(),
+            ],
+        );
+    }
+
+    let daily_stats: Vec<(Option<u64>, Option<u64>)> = pipe.query_async(connection).await?;
+
+    Ok(daily_stats.into_iter().fold(
+        GuildStats::default(),
+        |guild_stats, (file_previews, juxtaposes)| GuildStats {
+            file_previews: guild_stats.file_previews + file_previews.unwrap_or_default(),
+            juxtaposes: guild_stats.juxtaposes + juxtaposes.unwrap_or_default(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_daily_counts() {
+        let daily_counts = vec![
+            vec![("a/b".to_owned(), 2), ("c/d".to_owned(), 1)],
+            vec![("c/d".to_owned(), 3), ("e/f".to_owned(), 2)],
+            vec![("a/b".to_owned(), 2)],
+        ];
+
+        assert_eq!(
+            merge_daily_counts(daily_counts, 2),
+            [("a/b".to_owned(), 4), ("c/d".to_owned(), 4)]
+        );
+    }
+}
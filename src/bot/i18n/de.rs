@@ -0,0 +1,535 @@
+//! German translations.
+
+pub(super) const TRANSLATIONS: &[(&str, &str)] = &[
+    // admin
+    (
+        "Maintenance commands for the owners of the bot.",
+        "Wartungsbefehle für die Betreiber des Bots.",
+    ),
+    (
+        "Delete cache entries matching a pattern, e.g. for takedown requests.",
+        "Cache-Einträge löschen, die einem Muster entsprechen, z. B. für Löschanfragen.",
+    ),
+    (
+        "Redis glob pattern, e.g. juxtapose_payload:*.",
+        "Redis-Glob-Muster, z. B. juxtapose_payload:*.",
+    ),
+    (
+        "Delete all data stored about a user, like /forgetme.",
+        "Alle über einen Nutzer gespeicherten Daten löschen, wie /forgetme.",
+    ),
+    ("The ID of the user.", "Die ID des Nutzers."),
+    (
+        "Ignore all messages and interactions of a user or in a guild.",
+        "Alle Nachrichten und Interaktionen eines Nutzers oder auf einem Server ignorieren.",
+    ),
+    (
+        "Remove a user or guild from the blocklist.",
+        "Einen Nutzer oder Server von der Sperrliste entfernen.",
+    ),
+    (
+        "Whether the ID belongs to a user or a guild.",
+        "Ob die ID zu einem Nutzer oder einem Server gehört.",
+    ),
+    ("User", "Nutzer"),
+    ("Guild", "Server"),
+    (
+        "The ID of the user or guild.",
+        "Die ID des Nutzers oder Servers.",
+    ),
+    (
+        "Configure and diagnose the juxtapose viewer.",
+        "Den Juxtapose-Viewer konfigurieren und prüfen.",
+    ),
+    (
+        "Change the URL of the viewer until the next restart, then test it.",
+        "Die URL des Viewers bis zum nächsten Neustart ändern und ihn dann testen.",
+    ),
+    (
+        "Public URL of the viewer, e.g. https://example.com/view.",
+        "Öffentliche URL des Viewers, z. B. https://example.com/view.",
+    ),
+    (
+        "Check that the viewer and its API can be reached and accept signed links.",
+        "Prüfen, ob Viewer und API erreichbar sind und signierte Links akzeptieren.",
+    ),
+    (
+        "URL of the API if it isn't served next to the viewer, e.g. https://example.com/url.",
+        "URL der API, falls nicht neben dem Viewer, z. B. https://example.com/url.",
+    ),
+    (
+        "Roll out features gradually or turn them off.",
+        "Funktionen schrittweise einführen oder abschalten.",
+    ),
+    (
+        "Override the state of a feature for all guilds or a single guild.",
+        "Den Zustand einer Funktion für alle Server oder einen einzelnen Server überschreiben.",
+    ),
+    ("The feature.", "Die Funktion."),
+    (
+        "on, off, reset or a percentage of guilds such as 25%.",
+        "on, off, reset oder ein Anteil der Server wie 25%.",
+    ),
+    (
+        "The ID of the guild, all guilds if empty.",
+        "Die ID des Servers, alle Server falls leer.",
+    ),
+    (
+        "Show the state of all features.",
+        "Den Zustand aller Funktionen anzeigen.",
+    ),
+    // album
+    (
+        "Create an album of up to 10 images that can be viewed as a gallery.",
+        "Ein Album aus bis zu 10 Bildern erstellen, das als Galerie angesehen werden kann.",
+    ),
+    (
+        "Image number {} of the album.",
+        "Bild Nummer {} des Albums.",
+    ),
+    ("The title of the album.", "Der Titel des Albums."),
+    // backfill
+    (
+        "Post previews for links in the recent messages of a channel.",
+        "Vorschauen für Links in den letzten Nachrichten eines Kanals posten.",
+    ),
+    ("The channel to scan.", "Der zu durchsuchende Kanal."),
+    (
+        "The number of recent messages to scan. Defaults to 50.",
+        "Die Anzahl der zu durchsuchenden letzten Nachrichten. Standardmäßig 50.",
+    ),
+    // browse
+    (
+        "Browse the files of a GitHub repository and preview one of them.",
+        "Die Dateien eines GitHub-Repositorys durchsuchen und eine davon anzeigen.",
+    ),
+    (
+        "The repository in the format owner/repo.",
+        "Das Repository im Format owner/repo.",
+    ),
+    (
+        "The branch, tag or commit to browse. Defaults to the default branch.",
+        "Der Branch, Tag oder Commit zum Durchsuchen. Standardmäßig der Standard-Branch.",
+    ),
+    // color
+    (
+        "Show a color swatch and its conversions.",
+        "Ein Farbmuster und seine Umrechnungen anzeigen.",
+    ),
+    (
+        "The color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "Die Farbe in Hex- (#ff8800), rgb(255, 136, 0)- oder hsl(32, 100%, 50%)-Notation.",
+    ),
+    // config
+    (
+        "Configure the bot for this server.",
+        "Den Bot für diesen Server konfigurieren.",
+    ),
+    (
+        "Show the current configuration of this server.",
+        "Die aktuelle Konfiguration dieses Servers anzeigen.",
+    ),
+    (
+        "Set the channel that receives audit log entries.",
+        "Den Kanal festlegen, der Audit-Log-Einträge erhält.",
+    ),
+    (
+        "The audit log channel. Disables audit logging if omitted.",
+        "Der Audit-Log-Kanal. Deaktiviert das Audit-Log, wenn nicht angegeben.",
+    ),
+    (
+        "Set the channel that receives a weekly digest of previews and juxtaposes.",
+        "Den Kanal festlegen, der eine wöchentliche Übersicht der Vorschauen und Gegenüberstellungen erhält.",
+    ),
+    (
+        "The digest channel. Disables the digest if omitted.",
+        "Der Übersichtskanal. Deaktiviert die Übersicht, wenn nicht angegeben.",
+    ),
+    (
+        "Set how file previews are displayed.",
+        "Festlegen, wie Dateivorschauen angezeigt werden.",
+    ),
+    (
+        "A code block in the message, a rich embed, or a thread on the message.",
+        "Ein Codeblock in der Nachricht, ein Embed oder ein Thread an der Nachricht.",
+    ),
+    ("Code Block", "Codeblock"),
+    ("Embed", "Embed"),
+    ("Thread", "Thread"),
+    (
+        "Attach long file previews as highlighted HTML documents instead of plain text.",
+        "Lange Dateivorschauen als hervorgehobene HTML-Dokumente statt als reinen Text anhängen.",
+    ),
+    (
+        "The color theme of the document. Attaches plain text if omitted.",
+        "Das Farbschema des Dokuments. Hängt reinen Text an, wenn nicht angegeben.",
+    ),
+    ("Dark", "Dunkel"),
+    ("Light", "Hell"),
+    (
+        "Show tabs and trailing whitespace in file previews, and mention CRLF line endings.",
+        "Tabs und Leerzeichen am Zeilenende in Dateivorschauen zeigen und CRLF-Zeilenenden erwähnen.",
+    ),
+    (
+        "The files whose previews show whitespace.",
+        "Die Dateien, deren Vorschauen Leerzeichen zeigen.",
+    ),
+    ("None", "Keine"),
+    ("Whitespace-Sensitive Files", "Leerzeichensensible Dateien"),
+    ("All Files", "Alle Dateien"),
+    (
+        "Enable or disable file previews for a kind of link.",
+        "Dateivorschauen für eine Art von Link aktivieren oder deaktivieren.",
+    ),
+    ("The kind of link.", "Die Art von Link."),
+    ("GitHub Repository Files", "Dateien in GitHub-Repositorys"),
+    ("GitHub Gists", "GitHub-Gists"),
+    (
+        "Whether file previews are created for this kind of link.",
+        "Ob Dateivorschauen für diese Art von Link erstellt werden.",
+    ),
+    (
+        "Enable or disable automatic file previews in a channel.",
+        "Automatische Dateivorschauen in einem Kanal aktivieren oder deaktivieren.",
+    ),
+    ("The channel.", "Der Kanal."),
+    (
+        "Whether links in this channel are previewed automatically.",
+        "Ob für Links in diesem Kanal automatisch Vorschauen erstellt werden.",
+    ),
+    (
+        "Show or hide the stars, language and license of repositories below file previews.",
+        "Sterne, Sprache und Lizenz von Repositorys unter Dateivorschauen ein- oder ausblenden.",
+    ),
+    (
+        "Whether repository metadata is shown below previews of GitHub repository files.",
+        "Ob Repository-Metadaten unter Vorschauen von Dateien in GitHub-Repositorys stehen.",
+    ),
+    (
+        "Warn above file previews of repositories that look like scams.",
+        "Über Dateivorschauen von Repositorys warnen, die nach Betrug aussehen.",
+    ),
+    (
+        "Whether previews of blocklisted, new or suspiciously named files carry a warning.",
+        "Ob Vorschauen gesperrter, neuer oder verdächtig benannter Dateien eine Warnung tragen.",
+    ),
+    (
+        "Refer to the existing preview when a link is posted again in the same channel.",
+        "Auf die vorhandene Vorschau verweisen, wenn ein Link im selben Kanal erneut gepostet wird.",
+    ),
+    (
+        "Whether links posted again are answered with a link to their recent preview.",
+        "Ob erneut gepostete Links mit einem Link zu ihrer letzten Vorschau beantwortet werden.",
+    ),
+    (
+        "Show a hash of the previewed lines below file previews.",
+        "Einen Hash der angezeigten Zeilen unter Dateivorschauen anzeigen.",
+    ),
+    (
+        "Whether previews end with a BLAKE3 hash to tell later if the file has changed.",
+        "Ob Vorschauen mit einem BLAKE3-Hash enden, um später Änderungen an der Datei zu erkennen.",
+    ),
+    (
+        "Set a text drawn in a corner of juxtapose previews, e.g. for attribution.",
+        "Einen Text festlegen, der in einer Ecke von Juxtapose-Vorschauen steht, z. B. als Quellenangabe.",
+    ),
+    (
+        "The watermark text. Removes the watermark if omitted.",
+        "Der Text des Wasserzeichens. Entfernt das Wasserzeichen, wenn weggelassen.",
+    ),
+    (
+        "Manage presets of labels and layout for /juxtapose-preset.",
+        "Vorlagen mit Beschriftungen und Layout für /juxtapose-preset verwalten.",
+    ),
+    (
+        "Save a preset, replacing any preset with the same name.",
+        "Eine Vorlage speichern und eine gleichnamige Vorlage ersetzen.",
+    ),
+    ("Delete a preset.", "Eine Vorlage löschen."),
+    (
+        "Manage the languages that files are highlighted as by their extension.",
+        "Verwalten, als welche Sprache Dateien anhand ihrer Endung hervorgehoben werden.",
+    ),
+    (
+        "Highlight files with an extension as another language, e.g. .zs as java.",
+        "Dateien mit einer Endung als andere Sprache hervorheben, z. B. .zs als java.",
+    ),
+    ("The file extension, e.g. zs.", "Die Dateiendung, z. B. zs."),
+    (
+        "The language or extension that Discord highlights, e.g. java.",
+        "Die Sprache oder Endung, die Discord hervorhebt, z. B. java.",
+    ),
+    (
+        "Remove the alias of a file extension.",
+        "Den Alias einer Dateiendung entfernen.",
+    ),
+    (
+        "Manage patterns that block or redact file previews.",
+        "Muster verwalten, die Dateivorschauen blockieren oder schwärzen.",
+    ),
+    (
+        "Add a case-insensitive regular expression to the keyword filter.",
+        "Dem Schlüsselwortfilter einen regulären Ausdruck hinzufügen (ohne Groß-/Kleinschreibung).",
+    ),
+    ("The regular expression.", "Der reguläre Ausdruck."),
+    (
+        "Remove a regular expression from the keyword filter.",
+        "Einen regulären Ausdruck aus dem Schlüsselwortfilter entfernen.",
+    ),
+    (
+        "Set what happens to file previews matching the keyword filter.",
+        "Festlegen, was mit Dateivorschauen passiert, die der Schlüsselwortfilter erfasst.",
+    ),
+    (
+        "Either block the whole preview or redact the matches.",
+        "Entweder die ganze Vorschau blockieren oder die Treffer schwärzen.",
+    ),
+    ("Block", "Blockieren"),
+    ("Redact", "Schwärzen"),
+    // forgetme
+    (
+        "Delete all data the bot has stored about you.",
+        "Alle Daten löschen, die der Bot über dich gespeichert hat.",
+    ),
+    // ghdiff
+    (
+        "Preview how a file in a GitHub repository changed between two references.",
+        "Anzeigen, wie sich eine Datei in einem GitHub-Repository zwischen zwei Referenzen geändert hat.",
+    ),
+    (
+        "The path of the file within the repository.",
+        "Der Pfad der Datei im Repository.",
+    ),
+    (
+        "The branch, tag or commit to compare against.",
+        "Der Branch, Tag oder Commit, mit dem verglichen wird.",
+    ),
+    (
+        "The branch, tag or commit containing the changes.",
+        "Der Branch, Tag oder Commit mit den Änderungen.",
+    ),
+    // ghsearch
+    (
+        "Search code in a GitHub repository.",
+        "Code in einem GitHub-Repository suchen.",
+    ),
+    ("The text to search for.", "Der zu suchende Text."),
+    // hash
+    (
+        "Compute the SHA-256 and BLAKE3 checksums of a file.",
+        "Die SHA-256- und BLAKE3-Prüfsummen einer Datei berechnen.",
+    ),
+    (
+        "The file to compute the checksums of.",
+        "Die Datei, deren Prüfsummen berechnet werden.",
+    ),
+    (
+        "The URL of the file to compute the checksums of, if no file is attached.",
+        "Die URL der Datei, deren Prüfsummen berechnet werden, falls keine Datei angehängt ist.",
+    ),
+    // help
+    (
+        "Learn what the bot can do and how to use it.",
+        "Erfahren, was der Bot kann und wie er verwendet wird.",
+    ),
+    (
+        "The topic to show first.",
+        "Das Thema, das zuerst angezeigt wird.",
+    ),
+    ("File Previews", "Dateivorschauen"),
+    ("Juxtapose", "Gegenüberstellung"),
+    ("Tools", "Werkzeuge"),
+    ("Configuration", "Konfiguration"),
+    ("Privacy", "Datenschutz"),
+    // imagetool
+    ("The image to process.", "Das zu bearbeitende Bild."),
+    (
+        "Resize, crop, rotate, flip or convert an image.",
+        "Ein Bild skalieren, zuschneiden, drehen, spiegeln oder konvertieren.",
+    ),
+    ("Resize an image.", "Ein Bild skalieren."),
+    ("The new width in pixels.", "Die neue Breite in Pixeln."),
+    (
+        "The new height in pixels. Preserves the aspect ratio if omitted.",
+        "Die neue Höhe in Pixeln. Behält das Seitenverhältnis bei, wenn nicht angegeben.",
+    ),
+    (
+        "Crop an image to a rectangle.",
+        "Ein Bild auf ein Rechteck zuschneiden.",
+    ),
+    (
+        "The left edge of the rectangle in pixels.",
+        "Der linke Rand des Rechtecks in Pixeln.",
+    ),
+    (
+        "The top edge of the rectangle in pixels.",
+        "Der obere Rand des Rechtecks in Pixeln.",
+    ),
+    (
+        "The width of the rectangle in pixels.",
+        "Die Breite des Rechtecks in Pixeln.",
+    ),
+    (
+        "The height of the rectangle in pixels.",
+        "Die Höhe des Rechtecks in Pixeln.",
+    ),
+    (
+        "Rotate an image clockwise.",
+        "Ein Bild im Uhrzeigersinn drehen.",
+    ),
+    ("The clockwise rotation.", "Die Drehung im Uhrzeigersinn."),
+    ("Mirror an image.", "Ein Bild spiegeln."),
+    (
+        "The axis to mirror the image along.",
+        "Die Achse, an der das Bild gespiegelt wird.",
+    ),
+    ("Horizontal", "Horizontal"),
+    ("Vertical", "Vertikal"),
+    (
+        "Convert an image to another format.",
+        "Ein Bild in ein anderes Format konvertieren.",
+    ),
+    ("The new format.", "Das neue Format."),
+    ("WebP (lossless)", "WebP (verlustfrei)"),
+    // juxtapose
+    (
+        "Create a juxtapose by uploading two images.",
+        "Eine Gegenüberstellung aus zwei hochgeladenen Bildern erstellen.",
+    ),
+    (
+        "The image on the left side (or top).",
+        "Das Bild auf der linken Seite (oder oben).",
+    ),
+    (
+        "The image on the right side (or bottom).",
+        "Das Bild auf der rechten Seite (oder unten).",
+    ),
+    (
+        "The label on the left side (or top).",
+        "Die Beschriftung auf der linken Seite (oder oben).",
+    ),
+    (
+        "The label on the right side (or bottom).",
+        "Die Beschriftung auf der rechten Seite (oder unten).",
+    ),
+    (
+        "Whether or not the juxtapose should be vertical instead of horizontal. Defaults to false.",
+        "Ob die Gegenüberstellung vertikal statt horizontal sein soll. Standardmäßig nein.",
+    ),
+    (
+        "The thickness of the divider in pixels. Scales with the image size by default.",
+        "Die Dicke der Trennlinie in Pixeln. Skaliert standardmäßig mit der Bildgröße.",
+    ),
+    (
+        "The color of the divider in hex, rgb() or hsl() notation. Defaults to white.",
+        "Die Farbe der Trennlinie in Hex-, rgb()- oder hsl()-Notation. Standardmäßig weiß.",
+    ),
+    (
+        "Describes the preview for screen readers. Generated from the labels by default.",
+        "Beschreibt die Vorschau für Screenreader. Wird standardmäßig aus den Beschriftungen erzeugt.",
+    ),
+    (
+        "The frame of animated images to compare, starting at 1. Defaults to the first frame.",
+        "Das zu vergleichende Einzelbild animierter Bilder, beginnend bei 1. Standardmäßig das erste.",
+    ),
+    // juxtapose_from_message
+    (
+        "Recreate the interactive link of a previous juxtapose.",
+        "Den interaktiven Link einer früheren Gegenüberstellung neu erstellen.",
+    ),
+    (
+        "The link to the message containing the juxtapose.",
+        "Der Link zur Nachricht mit der Gegenüberstellung.",
+    ),
+    (
+        "Whether or not the juxtapose is vertical instead of horizontal. Defaults to false.",
+        "Ob die Gegenüberstellung vertikal statt horizontal ist. Standardmäßig nein.",
+    ),
+    // juxtapose_images
+    ("Juxtapose Images", "Bilder gegenüberstellen"),
+    // juxtapose_preset
+    (
+        "Create a juxtapose using labels and layout saved by this server.",
+        "Eine Gegenüberstellung mit den auf diesem Server gespeicherten Beschriftungen und Layout erstellen.",
+    ),
+    ("The name of the preset.", "Der Name der Vorlage."),
+    // palette
+    (
+        "Show a labeled strip of up to 8 colors.",
+        "Einen beschrifteten Streifen aus bis zu 8 Farben anzeigen.",
+    ),
+    (
+        "A color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "Eine Farbe in Hex- (#ff8800), rgb(255, 136, 0)- oder hsl(32, 100%, 50%)-Notation.",
+    ),
+    // permalink
+    (
+        "Create a permanent link to lines of a file on GitHub and preview them.",
+        "Einen dauerhaften Link zu Zeilen einer Datei auf GitHub erstellen und anzeigen.",
+    ),
+    (
+        "A single line or a range of lines, e.g. 10-20.",
+        "Eine einzelne Zeile oder ein Zeilenbereich, z. B. 10-20.",
+    ),
+    (
+        "A branch, tag or commit. Defaults to the default branch.",
+        "Ein Branch, Tag oder Commit. Standardmäßig der Standard-Branch.",
+    ),
+    // preview_links
+    ("Preview Links", "Linkvorschau erstellen"),
+    // qr
+    ("Generate a QR code.", "Einen QR-Code erzeugen."),
+    (
+        "The text or URL to encode.",
+        "Der zu kodierende Text oder die URL.",
+    ),
+    (
+        "The approximate width and height of the image in pixels.",
+        "Die ungefähre Breite und Höhe des Bildes in Pixeln.",
+    ),
+    (
+        "How much of the code can be damaged or covered while remaining readable.",
+        "Wie viel des Codes beschädigt oder verdeckt sein darf, ohne unlesbar zu werden.",
+    ),
+    ("Low (7%)", "Niedrig (7 %)"),
+    ("Medium (15%)", "Mittel (15 %)"),
+    ("Quartile (25%)", "Quartil (25 %)"),
+    ("High (30%)", "Hoch (30 %)"),
+    (
+        "An image to place in the center of the code.",
+        "Ein Bild, das in der Mitte des Codes platziert wird.",
+    ),
+    // remove_preview
+    ("Remove Bot Preview", "Bot-Vorschau entfernen"),
+    // spritesheet
+    (
+        "Work with sprite sheets and texture atlases.",
+        "Mit Spritesheets und Texturatlanten arbeiten.",
+    ),
+    (
+        "Split an image into a grid of equally sized tiles.",
+        "Ein Bild in ein Raster gleich großer Kacheln aufteilen.",
+    ),
+    ("The sprite sheet.", "Das Spritesheet."),
+    (
+        "The number of tiles per row.",
+        "Die Anzahl der Kacheln pro Zeile.",
+    ),
+    (
+        "The number of tiles per column.",
+        "Die Anzahl der Kacheln pro Spalte.",
+    ),
+    // stats
+    (
+        "Show usage statistics of the bot.",
+        "Nutzungsstatistiken des Bots anzeigen.",
+    ),
+    (
+        "Show how often a juxtapose has been opened.",
+        "Anzeigen, wie oft eine Gegenüberstellung geöffnet wurde.",
+    ),
+    (
+        "Show the most previewed repositories and files of this server in the last 30 days.",
+        "Die in den letzten 30 Tagen am häufigsten angezeigten Repositorys und Dateien des Servers zeigen.",
+    ),
+];
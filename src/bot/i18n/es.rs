@@ -0,0 +1,550 @@
+//! Spanish translations.
+
+pub(super) const TRANSLATIONS: &[(&str, &str)] = &[
+    // admin
+    (
+        "Maintenance commands for the owners of the bot.",
+        "Comandos de mantenimiento para los propietarios del bot.",
+    ),
+    (
+        "Delete cache entries matching a pattern, e.g. for takedown requests.",
+        "Eliminar entradas de caché que coincidan con un patrón, p. ej. por solicitudes de retirada.",
+    ),
+    (
+        "Redis glob pattern, e.g. juxtapose_payload:*.",
+        "Patrón glob de Redis, p. ej. juxtapose_payload:*.",
+    ),
+    (
+        "Delete all data stored about a user, like /forgetme.",
+        "Eliminar todos los datos guardados sobre un usuario, como /forgetme.",
+    ),
+    ("The ID of the user.", "El ID del usuario."),
+    (
+        "Ignore all messages and interactions of a user or in a guild.",
+        "Ignorar todos los mensajes e interacciones de un usuario o en un servidor.",
+    ),
+    (
+        "Remove a user or guild from the blocklist.",
+        "Quitar un usuario o servidor de la lista de bloqueo.",
+    ),
+    (
+        "Whether the ID belongs to a user or a guild.",
+        "Si el ID pertenece a un usuario o a un servidor.",
+    ),
+    ("User", "Usuario"),
+    ("Guild", "Servidor"),
+    (
+        "The ID of the user or guild.",
+        "El ID del usuario o servidor.",
+    ),
+    (
+        "Configure and diagnose the juxtapose viewer.",
+        "Configurar y diagnosticar el visor de yuxtaposiciones.",
+    ),
+    (
+        "Change the URL of the viewer until the next restart, then test it.",
+        "Cambiar la URL del visor hasta el próximo reinicio y luego probarlo.",
+    ),
+    (
+        "Public URL of the viewer, e.g. https://example.com/view.",
+        "URL pública del visor, p. ej. https://example.com/view.",
+    ),
+    (
+        "Check that the viewer and its API can be reached and accept signed links.",
+        "Comprobar que el visor y su API son accesibles y aceptan enlaces firmados.",
+    ),
+    (
+        "URL of the API if it isn't served next to the viewer, e.g. https://example.com/url.",
+        "URL de la API si no está junto al visor, p. ej. https://example.com/url.",
+    ),
+    (
+        "Roll out features gradually or turn them off.",
+        "Desplegar funciones gradualmente o desactivarlas.",
+    ),
+    (
+        "Override the state of a feature for all guilds or a single guild.",
+        "Sobrescribir el estado de una función para todos los servidores o uno solo.",
+    ),
+    ("The feature.", "La función."),
+    (
+        "on, off, reset or a percentage of guilds such as 25%.",
+        "on, off, reset o un porcentaje de servidores como 25%.",
+    ),
+    (
+        "The ID of the guild, all guilds if empty.",
+        "El ID del servidor, todos los servidores si está vacío.",
+    ),
+    (
+        "Show the state of all features.",
+        "Mostrar el estado de todas las funciones.",
+    ),
+    // album
+    (
+        "Create an album of up to 10 images that can be viewed as a gallery.",
+        "Crear un álbum de hasta 10 imágenes que se puede ver como galería.",
+    ),
+    (
+        "Image number {} of the album.",
+        "Imagen número {} del álbum.",
+    ),
+    ("The title of the album.", "El título del álbum."),
+    // backfill
+    (
+        "Post previews for links in the recent messages of a channel.",
+        "Publicar vistas previas de los enlaces en los mensajes recientes de un canal.",
+    ),
+    ("The channel to scan.", "El canal que se analizará."),
+    (
+        "The number of recent messages to scan. Defaults to 50.",
+        "El número de mensajes recientes que se analizarán. 50 por defecto.",
+    ),
+    // browse
+    (
+        "Browse the files of a GitHub repository and preview one of them.",
+        "Explorar los archivos de un repositorio de GitHub y previsualizar uno de ellos.",
+    ),
+    (
+        "The repository in the format owner/repo.",
+        "El repositorio en el formato owner/repo.",
+    ),
+    (
+        "The branch, tag or commit to browse. Defaults to the default branch.",
+        "La rama, etiqueta o commit que se explorará. Por defecto, la rama predeterminada.",
+    ),
+    // color
+    (
+        "Show a color swatch and its conversions.",
+        "Mostrar una muestra de color y sus conversiones.",
+    ),
+    (
+        "The color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "El color en notación hex (#ff8800), rgb(255, 136, 0) o hsl(32, 100%, 50%).",
+    ),
+    // config
+    (
+        "Configure the bot for this server.",
+        "Configurar el bot para este servidor.",
+    ),
+    (
+        "Show the current configuration of this server.",
+        "Mostrar la configuración actual de este servidor.",
+    ),
+    (
+        "Set the channel that receives audit log entries.",
+        "Establecer el canal que recibe las entradas del registro de auditoría.",
+    ),
+    (
+        "The audit log channel. Disables audit logging if omitted.",
+        "El canal del registro de auditoría. Lo desactiva si se omite.",
+    ),
+    (
+        "Set the channel that receives a weekly digest of previews and juxtaposes.",
+        "Establecer el canal que recibe un resumen semanal de vistas previas y yuxtaposiciones.",
+    ),
+    (
+        "The digest channel. Disables the digest if omitted.",
+        "El canal del resumen. Desactiva el resumen si se omite.",
+    ),
+    (
+        "Set how file previews are displayed.",
+        "Establecer cómo se muestran las vistas previas de archivos.",
+    ),
+    (
+        "A code block in the message, a rich embed, or a thread on the message.",
+        "Un bloque de código en el mensaje, un embed o un hilo en el mensaje.",
+    ),
+    ("Code Block", "Bloque de código"),
+    ("Embed", "Embed"),
+    ("Thread", "Hilo"),
+    (
+        "Attach long file previews as highlighted HTML documents instead of plain text.",
+        "Adjuntar las vistas previas largas como documentos HTML resaltados en lugar de texto plano.",
+    ),
+    (
+        "The color theme of the document. Attaches plain text if omitted.",
+        "El tema de colores del documento. Adjunta texto plano si se omite.",
+    ),
+    ("Dark", "Oscuro"),
+    ("Light", "Claro"),
+    (
+        "Show tabs and trailing whitespace in file previews, and mention CRLF line endings.",
+        "Mostrar tabulaciones y espacios finales en las vistas previas, y avisar de finales de línea CRLF.",
+    ),
+    (
+        "The files whose previews show whitespace.",
+        "Los archivos cuyas vistas previas muestran los espacios.",
+    ),
+    ("None", "Ninguno"),
+    (
+        "Whitespace-Sensitive Files",
+        "Archivos sensibles a espacios",
+    ),
+    ("All Files", "Todos los archivos"),
+    (
+        "Enable or disable file previews for a kind of link.",
+        "Activar o desactivar las vistas previas de archivos para un tipo de enlace.",
+    ),
+    ("The kind of link.", "El tipo de enlace."),
+    (
+        "GitHub Repository Files",
+        "Archivos de repositorios de GitHub",
+    ),
+    ("GitHub Gists", "Gists de GitHub"),
+    (
+        "Whether file previews are created for this kind of link.",
+        "Si se crean vistas previas de archivos para este tipo de enlace.",
+    ),
+    (
+        "Enable or disable automatic file previews in a channel.",
+        "Activar o desactivar las vistas previas automáticas de archivos en un canal.",
+    ),
+    ("The channel.", "El canal."),
+    (
+        "Whether links in this channel are previewed automatically.",
+        "Si los enlaces de este canal se previsualizan automáticamente.",
+    ),
+    (
+        "Show or hide the stars, language and license of repositories below file previews.",
+        "Mostrar u ocultar estrellas, lenguaje y licencia de los repositorios bajo las vistas previas.",
+    ),
+    (
+        "Whether repository metadata is shown below previews of GitHub repository files.",
+        "Si los metadatos del repositorio se muestran bajo las vistas previas de archivos de GitHub.",
+    ),
+    (
+        "Warn above file previews of repositories that look like scams.",
+        "Advertir sobre las vistas previas de repositorios que parecen estafas.",
+    ),
+    (
+        "Whether previews of blocklisted, new or suspiciously named files carry a warning.",
+        "Si las vistas previas de archivos bloqueados, nuevos o con nombre sospechoso llevan un aviso.",
+    ),
+    (
+        "Refer to the existing preview when a link is posted again in the same channel.",
+        "Remitir a la vista previa existente cuando un enlace se publica de nuevo en el mismo canal.",
+    ),
+    (
+        "Whether links posted again are answered with a link to their recent preview.",
+        "Si los enlaces publicados de nuevo se responden con un enlace a su vista previa reciente.",
+    ),
+    (
+        "Show a hash of the previewed lines below file previews.",
+        "Mostrar un hash de las líneas previsualizadas bajo las vistas previas de archivos.",
+    ),
+    (
+        "Whether previews end with a BLAKE3 hash to tell later if the file has changed.",
+        "Si las vistas previas terminan con un hash BLAKE3 para saber después si el archivo cambió.",
+    ),
+    (
+        "Set a text drawn in a corner of juxtapose previews, e.g. for attribution.",
+        "Establecer un texto en una esquina de las vistas previas de yuxtaposición, p. ej. para atribución.",
+    ),
+    (
+        "The watermark text. Removes the watermark if omitted.",
+        "El texto de la marca de agua. La elimina si se omite.",
+    ),
+    (
+        "Manage presets of labels and layout for /juxtapose-preset.",
+        "Gestionar ajustes predefinidos de etiquetas y diseño para /juxtapose-preset.",
+    ),
+    (
+        "Save a preset, replacing any preset with the same name.",
+        "Guardar un ajuste predefinido, reemplazando el que tenga el mismo nombre.",
+    ),
+    ("Delete a preset.", "Eliminar un ajuste predefinido."),
+    (
+        "Manage the languages that files are highlighted as by their extension.",
+        "Gestionar los lenguajes con los que se resaltan los archivos según su extensión.",
+    ),
+    (
+        "Highlight files with an extension as another language, e.g. .zs as java.",
+        "Resaltar los archivos con una extensión como otro lenguaje, p. ej. .zs como java.",
+    ),
+    (
+        "The file extension, e.g. zs.",
+        "La extensión de archivo, p. ej. zs.",
+    ),
+    (
+        "The language or extension that Discord highlights, e.g. java.",
+        "El lenguaje o la extensión que Discord resalta, p. ej. java.",
+    ),
+    (
+        "Remove the alias of a file extension.",
+        "Eliminar el alias de una extensión de archivo.",
+    ),
+    (
+        "Manage patterns that block or redact file previews.",
+        "Gestionar patrones que bloquean o censuran las vistas previas de archivos.",
+    ),
+    (
+        "Add a case-insensitive regular expression to the keyword filter.",
+        "Añadir una expresión regular sin distinción de mayúsculas al filtro de palabras clave.",
+    ),
+    ("The regular expression.", "La expresión regular."),
+    (
+        "Remove a regular expression from the keyword filter.",
+        "Quitar una expresión regular del filtro de palabras clave.",
+    ),
+    (
+        "Set what happens to file previews matching the keyword filter.",
+        "Establecer qué ocurre con las vistas previas que coinciden con el filtro de palabras clave.",
+    ),
+    (
+        "Either block the whole preview or redact the matches.",
+        "Bloquear toda la vista previa o censurar las coincidencias.",
+    ),
+    ("Block", "Bloquear"),
+    ("Redact", "Censurar"),
+    // forgetme
+    (
+        "Delete all data the bot has stored about you.",
+        "Eliminar todos los datos que el bot ha guardado sobre ti.",
+    ),
+    // ghdiff
+    (
+        "Preview how a file in a GitHub repository changed between two references.",
+        "Previsualizar cómo cambió un archivo de un repositorio de GitHub entre dos referencias.",
+    ),
+    (
+        "The path of the file within the repository.",
+        "La ruta del archivo dentro del repositorio.",
+    ),
+    (
+        "The branch, tag or commit to compare against.",
+        "La rama, etiqueta o commit con el que comparar.",
+    ),
+    (
+        "The branch, tag or commit containing the changes.",
+        "La rama, etiqueta o commit que contiene los cambios.",
+    ),
+    // ghsearch
+    (
+        "Search code in a GitHub repository.",
+        "Buscar código en un repositorio de GitHub.",
+    ),
+    ("The text to search for.", "El texto que se buscará."),
+    // hash
+    (
+        "Compute the SHA-256 and BLAKE3 checksums of a file.",
+        "Calcular las sumas de comprobación SHA-256 y BLAKE3 de un archivo.",
+    ),
+    (
+        "The file to compute the checksums of.",
+        "El archivo del que calcular las sumas de comprobación.",
+    ),
+    (
+        "The URL of the file to compute the checksums of, if no file is attached.",
+        "La URL del archivo del que calcular las sumas de comprobación, si no se adjunta ninguno.",
+    ),
+    // help
+    (
+        "Learn what the bot can do and how to use it.",
+        "Descubre lo que puede hacer el bot y cómo usarlo.",
+    ),
+    (
+        "The topic to show first.",
+        "El tema que se muestra primero.",
+    ),
+    ("File Previews", "Vistas previas de archivos"),
+    ("Juxtapose", "Yuxtaposición"),
+    ("Tools", "Herramientas"),
+    ("Configuration", "Configuración"),
+    ("Privacy", "Privacidad"),
+    // imagetool
+    ("The image to process.", "La imagen que se procesará."),
+    (
+        "Resize, crop, rotate, flip or convert an image.",
+        "Redimensionar, recortar, girar, voltear o convertir una imagen.",
+    ),
+    ("Resize an image.", "Redimensionar una imagen."),
+    ("The new width in pixels.", "El nuevo ancho en píxeles."),
+    (
+        "The new height in pixels. Preserves the aspect ratio if omitted.",
+        "El nuevo alto en píxeles. Mantiene la relación de aspecto si se omite.",
+    ),
+    (
+        "Crop an image to a rectangle.",
+        "Recortar una imagen a un rectángulo.",
+    ),
+    (
+        "The left edge of the rectangle in pixels.",
+        "El borde izquierdo del rectángulo en píxeles.",
+    ),
+    (
+        "The top edge of the rectangle in pixels.",
+        "El borde superior del rectángulo en píxeles.",
+    ),
+    (
+        "The width of the rectangle in pixels.",
+        "El ancho del rectángulo en píxeles.",
+    ),
+    (
+        "The height of the rectangle in pixels.",
+        "El alto del rectángulo en píxeles.",
+    ),
+    (
+        "Rotate an image clockwise.",
+        "Girar una imagen en el sentido de las agujas del reloj.",
+    ),
+    (
+        "The clockwise rotation.",
+        "La rotación en el sentido de las agujas del reloj.",
+    ),
+    ("Mirror an image.", "Voltear una imagen."),
+    (
+        "The axis to mirror the image along.",
+        "El eje sobre el que se voltea la imagen.",
+    ),
+    ("Horizontal", "Horizontal"),
+    ("Vertical", "Vertical"),
+    (
+        "Convert an image to another format.",
+        "Convertir una imagen a otro formato.",
+    ),
+    ("The new format.", "El nuevo formato."),
+    ("WebP (lossless)", "WebP (sin pérdida)"),
+    // juxtapose
+    (
+        "Create a juxtapose by uploading two images.",
+        "Crear una yuxtaposición subiendo dos imágenes.",
+    ),
+    (
+        "The image on the left side (or top).",
+        "La imagen del lado izquierdo (o de arriba).",
+    ),
+    (
+        "The image on the right side (or bottom).",
+        "La imagen del lado derecho (o de abajo).",
+    ),
+    (
+        "The label on the left side (or top).",
+        "La etiqueta del lado izquierdo (o de arriba).",
+    ),
+    (
+        "The label on the right side (or bottom).",
+        "La etiqueta del lado derecho (o de abajo).",
+    ),
+    (
+        "Whether or not the juxtapose should be vertical instead of horizontal. Defaults to false.",
+        "Si la yuxtaposición debe ser vertical en lugar de horizontal. No por defecto.",
+    ),
+    (
+        "The thickness of the divider in pixels. Scales with the image size by default.",
+        "El grosor del divisor en píxeles. Por defecto, se adapta al tamaño de la imagen.",
+    ),
+    (
+        "The color of the divider in hex, rgb() or hsl() notation. Defaults to white.",
+        "El color del divisor en notación hex, rgb() o hsl(). Blanco por defecto.",
+    ),
+    (
+        "Describes the preview for screen readers. Generated from the labels by default.",
+        "Describe la vista previa para lectores de pantalla. Por defecto, se genera con las etiquetas.",
+    ),
+    (
+        "The frame of animated images to compare, starting at 1. Defaults to the first frame.",
+        "El fotograma de las imágenes animadas que se comparará, desde 1. El primero por defecto.",
+    ),
+    // juxtapose_from_message
+    (
+        "Recreate the interactive link of a previous juxtapose.",
+        "Volver a crear el enlace interactivo de una yuxtaposición anterior.",
+    ),
+    (
+        "The link to the message containing the juxtapose.",
+        "El enlace al mensaje que contiene la yuxtaposición.",
+    ),
+    (
+        "Whether or not the juxtapose is vertical instead of horizontal. Defaults to false.",
+        "Si la yuxtaposición es vertical en lugar de horizontal. No por defecto.",
+    ),
+    // juxtapose_images
+    ("Juxtapose Images", "Yuxtaponer imágenes"),
+    // juxtapose_preset
+    (
+        "Create a juxtapose using labels and layout saved by this server.",
+        "Crear una yuxtaposición con las etiquetas y el diseño guardados en este servidor.",
+    ),
+    (
+        "The name of the preset.",
+        "El nombre del ajuste predefinido.",
+    ),
+    // palette
+    (
+        "Show a labeled strip of up to 8 colors.",
+        "Mostrar una franja etiquetada de hasta 8 colores.",
+    ),
+    (
+        "A color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "Un color en notación hex (#ff8800), rgb(255, 136, 0) o hsl(32, 100%, 50%).",
+    ),
+    // permalink
+    (
+        "Create a permanent link to lines of a file on GitHub and preview them.",
+        "Crear un enlace permanente a líneas de un archivo en GitHub y previsualizarlas.",
+    ),
+    (
+        "A single line or a range of lines, e.g. 10-20.",
+        "Una sola línea o un rango de líneas, p. ej. 10-20.",
+    ),
+    (
+        "A branch, tag or commit. Defaults to the default branch.",
+        "Una rama, etiqueta o commit. Por defecto, la rama predeterminada.",
+    ),
+    // preview_links
+    ("Preview Links", "Previsualizar enlaces"),
+    // qr
+    ("Generate a QR code.", "Generar un código QR."),
+    (
+        "The text or URL to encode.",
+        "El texto o la URL que se codificará.",
+    ),
+    (
+        "The approximate width and height of the image in pixels.",
+        "El ancho y alto aproximados de la imagen en píxeles.",
+    ),
+    (
+        "How much of the code can be damaged or covered while remaining readable.",
+        "Cuánto del código puede dañarse o cubrirse sin dejar de ser legible.",
+    ),
+    ("Low (7%)", "Bajo (7 %)"),
+    ("Medium (15%)", "Medio (15 %)"),
+    ("Quartile (25%)", "Cuartil (25 %)"),
+    ("High (30%)", "Alto (30 %)"),
+    (
+        "An image to place in the center of the code.",
+        "Una imagen que se colocará en el centro del código.",
+    ),
+    // remove_preview
+    ("Remove Bot Preview", "Quitar vista previa del bot"),
+    // spritesheet
+    (
+        "Work with sprite sheets and texture atlases.",
+        "Trabajar con hojas de sprites y atlas de texturas.",
+    ),
+    (
+        "Split an image into a grid of equally sized tiles.",
+        "Dividir una imagen en una cuadrícula de mosaicos del mismo tamaño.",
+    ),
+    ("The sprite sheet.", "La hoja de sprites."),
+    (
+        "The number of tiles per row.",
+        "El número de mosaicos por fila.",
+    ),
+    (
+        "The number of tiles per column.",
+        "El número de mosaicos por columna.",
+    ),
+    // stats
+    (
+        "Show usage statistics of the bot.",
+        "Mostrar las estadísticas de uso del bot.",
+    ),
+    (
+        "Show how often a juxtapose has been opened.",
+        "Mostrar cuántas veces se ha abierto una yuxtaposición.",
+    ),
+    (
+        "Show the most previewed repositories and files of this server in the last 30 days.",
+        "Mostrar los repositorios y archivos más previsualizados del servidor en los últimos 30 días.",
+    ),
+];
@@ -0,0 +1,541 @@
+//! French translations.
+
+pub(super) const TRANSLATIONS: &[(&str, &str)] = &[
+    // admin
+    (
+        "Maintenance commands for the owners of the bot.",
+        "Commandes de maintenance pour les propriétaires du bot.",
+    ),
+    (
+        "Delete cache entries matching a pattern, e.g. for takedown requests.",
+        "Supprimer les entrées du cache correspondant à un motif, p. ex. pour un retrait.",
+    ),
+    (
+        "Redis glob pattern, e.g. juxtapose_payload:*.",
+        "Motif glob Redis, p. ex. juxtapose_payload:*.",
+    ),
+    (
+        "Delete all data stored about a user, like /forgetme.",
+        "Supprimer toutes les données enregistrées sur un utilisateur, comme /forgetme.",
+    ),
+    ("The ID of the user.", "L'ID de l'utilisateur."),
+    (
+        "Ignore all messages and interactions of a user or in a guild.",
+        "Ignorer tous les messages et interactions d'un utilisateur ou d'un serveur.",
+    ),
+    (
+        "Remove a user or guild from the blocklist.",
+        "Retirer un utilisateur ou un serveur de la liste de blocage.",
+    ),
+    (
+        "Whether the ID belongs to a user or a guild.",
+        "Si l'ID appartient à un utilisateur ou à un serveur.",
+    ),
+    ("User", "Utilisateur"),
+    ("Guild", "Serveur"),
+    (
+        "The ID of the user or guild.",
+        "L'ID de l'utilisateur ou du serveur.",
+    ),
+    (
+        "Configure and diagnose the juxtapose viewer.",
+        "Configurer et diagnostiquer la visionneuse de juxtapositions.",
+    ),
+    (
+        "Change the URL of the viewer until the next restart, then test it.",
+        "Changer l'URL de la visionneuse jusqu'au prochain redémarrage, puis la tester.",
+    ),
+    (
+        "Public URL of the viewer, e.g. https://example.com/view.",
+        "URL publique de la visionneuse, p. ex. https://example.com/view.",
+    ),
+    (
+        "Check that the viewer and its API can be reached and accept signed links.",
+        "Vérifier que la visionneuse et son API sont joignables et acceptent les liens signés.",
+    ),
+    (
+        "URL of the API if it isn't served next to the viewer, e.g. https://example.com/url.",
+        "URL de l'API si elle n'est pas à côté de la visionneuse, p. ex. https://example.com/url.",
+    ),
+    (
+        "Roll out features gradually or turn them off.",
+        "Déployer des fonctionnalités progressivement ou les désactiver.",
+    ),
+    (
+        "Override the state of a feature for all guilds or a single guild.",
+        "Remplacer l'état d'une fonctionnalité pour tous les serveurs ou un seul serveur.",
+    ),
+    ("The feature.", "La fonctionnalité."),
+    (
+        "on, off, reset or a percentage of guilds such as 25%.",
+        "on, off, reset ou un pourcentage de serveurs comme 25%.",
+    ),
+    (
+        "The ID of the guild, all guilds if empty.",
+        "L'ID du serveur, tous les serveurs si vide.",
+    ),
+    (
+        "Show the state of all features.",
+        "Afficher l'état de toutes les fonctionnalités.",
+    ),
+    // album
+    (
+        "Create an album of up to 10 images that can be viewed as a gallery.",
+        "Créer un album de 10 images maximum, consultable sous forme de galerie.",
+    ),
+    (
+        "Image number {} of the album.",
+        "Image numéro {} de l'album.",
+    ),
+    ("The title of the album.", "Le titre de l'album."),
+    // backfill
+    (
+        "Post previews for links in the recent messages of a channel.",
+        "Publier des aperçus des liens dans les messages récents d'un salon.",
+    ),
+    ("The channel to scan.", "Le salon à analyser."),
+    (
+        "The number of recent messages to scan. Defaults to 50.",
+        "Le nombre de messages récents à analyser. 50 par défaut.",
+    ),
+    // browse
+    (
+        "Browse the files of a GitHub repository and preview one of them.",
+        "Parcourir les fichiers d'un dépôt GitHub et afficher l'aperçu de l'un d'eux.",
+    ),
+    (
+        "The repository in the format owner/repo.",
+        "Le dépôt au format owner/repo.",
+    ),
+    (
+        "The branch, tag or commit to browse. Defaults to the default branch.",
+        "La branche, le tag ou le commit à parcourir. Par défaut, la branche par défaut.",
+    ),
+    // color
+    (
+        "Show a color swatch and its conversions.",
+        "Afficher un échantillon de couleur et ses conversions.",
+    ),
+    (
+        "The color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "La couleur en notation hex (#ff8800), rgb(255, 136, 0) ou hsl(32, 100%, 50%).",
+    ),
+    // config
+    (
+        "Configure the bot for this server.",
+        "Configurer le bot pour ce serveur.",
+    ),
+    (
+        "Show the current configuration of this server.",
+        "Afficher la configuration actuelle de ce serveur.",
+    ),
+    (
+        "Set the channel that receives audit log entries.",
+        "Définir le salon qui reçoit les entrées du journal d'audit.",
+    ),
+    (
+        "The audit log channel. Disables audit logging if omitted.",
+        "Le salon du journal d'audit. Désactive le journal d'audit s'il est omis.",
+    ),
+    (
+        "Set the channel that receives a weekly digest of previews and juxtaposes.",
+        "Définir le salon qui reçoit un résumé hebdomadaire des aperçus et juxtapositions.",
+    ),
+    (
+        "The digest channel. Disables the digest if omitted.",
+        "Le salon du résumé. Désactive le résumé s'il est omis.",
+    ),
+    (
+        "Set how file previews are displayed.",
+        "Définir l'affichage des aperçus de fichiers.",
+    ),
+    (
+        "A code block in the message, a rich embed, or a thread on the message.",
+        "Un bloc de code dans le message, un embed ou un fil sur le message.",
+    ),
+    ("Code Block", "Bloc de code"),
+    ("Embed", "Embed"),
+    ("Thread", "Fil"),
+    (
+        "Attach long file previews as highlighted HTML documents instead of plain text.",
+        "Joindre les longs aperçus de fichiers en documents HTML colorés plutôt qu'en texte brut.",
+    ),
+    (
+        "The color theme of the document. Attaches plain text if omitted.",
+        "Le thème de couleurs du document. Joint du texte brut si omis.",
+    ),
+    ("Dark", "Sombre"),
+    ("Light", "Clair"),
+    (
+        "Show tabs and trailing whitespace in file previews, and mention CRLF line endings.",
+        "Afficher tabulations et espaces de fin dans les aperçus, et signaler les fins de ligne CRLF.",
+    ),
+    (
+        "The files whose previews show whitespace.",
+        "Les fichiers dont les aperçus affichent les espaces.",
+    ),
+    ("None", "Aucun"),
+    (
+        "Whitespace-Sensitive Files",
+        "Fichiers sensibles aux espaces",
+    ),
+    ("All Files", "Tous les fichiers"),
+    (
+        "Enable or disable file previews for a kind of link.",
+        "Activer ou désactiver les aperçus de fichiers pour un type de lien.",
+    ),
+    ("The kind of link.", "Le type de lien."),
+    ("GitHub Repository Files", "Fichiers de dépôts GitHub"),
+    ("GitHub Gists", "Gists GitHub"),
+    (
+        "Whether file previews are created for this kind of link.",
+        "Si des aperçus de fichiers sont créés pour ce type de lien.",
+    ),
+    (
+        "Enable or disable automatic file previews in a channel.",
+        "Activer ou désactiver les aperçus automatiques de fichiers dans un salon.",
+    ),
+    ("The channel.", "Le salon."),
+    (
+        "Whether links in this channel are previewed automatically.",
+        "Si les liens de ce salon reçoivent automatiquement un aperçu.",
+    ),
+    (
+        "Show or hide the stars, language and license of repositories below file previews.",
+        "Afficher ou masquer les étoiles, le langage et la licence des dépôts sous les aperçus.",
+    ),
+    (
+        "Whether repository metadata is shown below previews of GitHub repository files.",
+        "Si les métadonnées du dépôt sont affichées sous les aperçus de fichiers GitHub.",
+    ),
+    (
+        "Warn above file previews of repositories that look like scams.",
+        "Avertir au-dessus des aperçus de dépôts qui ressemblent à des arnaques.",
+    ),
+    (
+        "Whether previews of blocklisted, new or suspiciously named files carry a warning.",
+        "Si les aperçus de fichiers bloqués, récents ou au nom suspect portent un avertissement.",
+    ),
+    (
+        "Refer to the existing preview when a link is posted again in the same channel.",
+        "Renvoyer à l'aperçu existant lorsqu'un lien est à nouveau publié dans le même salon.",
+    ),
+    (
+        "Whether links posted again are answered with a link to their recent preview.",
+        "Si les liens publiés à nouveau reçoivent un lien vers leur aperçu récent.",
+    ),
+    (
+        "Show a hash of the previewed lines below file previews.",
+        "Afficher un hachage des lignes prévisualisées sous les aperçus de fichiers.",
+    ),
+    (
+        "Whether previews end with a BLAKE3 hash to tell later if the file has changed.",
+        "Si les aperçus se terminent par un hachage BLAKE3 pour savoir plus tard si le fichier a changé.",
+    ),
+    (
+        "Set a text drawn in a corner of juxtapose previews, e.g. for attribution.",
+        "Définir un texte affiché dans un coin des aperçus de juxtaposition, p. ex. pour l'attribution.",
+    ),
+    (
+        "The watermark text. Removes the watermark if omitted.",
+        "Le texte du filigrane. Supprime le filigrane s'il est omis.",
+    ),
+    (
+        "Manage presets of labels and layout for /juxtapose-preset.",
+        "Gérer les préréglages de libellés et de disposition pour /juxtapose-preset.",
+    ),
+    (
+        "Save a preset, replacing any preset with the same name.",
+        "Enregistrer un préréglage, en remplaçant celui du même nom.",
+    ),
+    ("Delete a preset.", "Supprimer un préréglage."),
+    (
+        "Manage the languages that files are highlighted as by their extension.",
+        "Gérer les langages utilisés pour colorer les fichiers selon leur extension.",
+    ),
+    (
+        "Highlight files with an extension as another language, e.g. .zs as java.",
+        "Colorer les fichiers d'une extension comme un autre langage, p. ex. .zs comme java.",
+    ),
+    (
+        "The file extension, e.g. zs.",
+        "L'extension de fichier, p. ex. zs.",
+    ),
+    (
+        "The language or extension that Discord highlights, e.g. java.",
+        "Le langage ou l'extension que Discord colore, p. ex. java.",
+    ),
+    (
+        "Remove the alias of a file extension.",
+        "Supprimer l'alias d'une extension de fichier.",
+    ),
+    (
+        "Manage patterns that block or redact file previews.",
+        "Gérer les motifs qui bloquent ou censurent les aperçus de fichiers.",
+    ),
+    (
+        "Add a case-insensitive regular expression to the keyword filter.",
+        "Ajouter une expression régulière insensible à la casse au filtre de mots-clés.",
+    ),
+    ("The regular expression.", "L'expression régulière."),
+    (
+        "Remove a regular expression from the keyword filter.",
+        "Retirer une expression régulière du filtre de mots-clés.",
+    ),
+    (
+        "Set what happens to file previews matching the keyword filter.",
+        "Définir ce qui arrive aux aperçus correspondant au filtre de mots-clés.",
+    ),
+    (
+        "Either block the whole preview or redact the matches.",
+        "Bloquer tout l'aperçu ou censurer les correspondances.",
+    ),
+    ("Block", "Bloquer"),
+    ("Redact", "Censurer"),
+    // forgetme
+    (
+        "Delete all data the bot has stored about you.",
+        "Supprimer toutes les données que le bot a enregistrées sur vous.",
+    ),
+    // ghdiff
+    (
+        "Preview how a file in a GitHub repository changed between two references.",
+        "Afficher les modifications d'un fichier d'un dépôt GitHub entre deux références.",
+    ),
+    (
+        "The path of the file within the repository.",
+        "Le chemin du fichier dans le dépôt.",
+    ),
+    (
+        "The branch, tag or commit to compare against.",
+        "La branche, le tag ou le commit servant de base à la comparaison.",
+    ),
+    (
+        "The branch, tag or commit containing the changes.",
+        "La branche, le tag ou le commit contenant les modifications.",
+    ),
+    // ghsearch
+    (
+        "Search code in a GitHub repository.",
+        "Rechercher du code dans un dépôt GitHub.",
+    ),
+    ("The text to search for.", "Le texte à rechercher."),
+    // hash
+    (
+        "Compute the SHA-256 and BLAKE3 checksums of a file.",
+        "Calculer les sommes de contrôle SHA-256 et BLAKE3 d'un fichier.",
+    ),
+    (
+        "The file to compute the checksums of.",
+        "Le fichier dont calculer les sommes de contrôle.",
+    ),
+    (
+        "The URL of the file to compute the checksums of, if no file is attached.",
+        "L'URL du fichier dont calculer les sommes de contrôle, si aucun fichier n'est joint.",
+    ),
+    // help
+    (
+        "Learn what the bot can do and how to use it.",
+        "Découvrir ce que le bot sait faire et comment l'utiliser.",
+    ),
+    (
+        "The topic to show first.",
+        "Le sujet à afficher en premier.",
+    ),
+    ("File Previews", "Aperçus de fichiers"),
+    ("Juxtapose", "Juxtaposition"),
+    ("Tools", "Outils"),
+    ("Configuration", "Configuration"),
+    ("Privacy", "Confidentialité"),
+    // imagetool
+    ("The image to process.", "L'image à traiter."),
+    (
+        "Resize, crop, rotate, flip or convert an image.",
+        "Redimensionner, recadrer, pivoter, retourner ou convertir une image.",
+    ),
+    ("Resize an image.", "Redimensionner une image."),
+    ("The new width in pixels.", "La nouvelle largeur en pixels."),
+    (
+        "The new height in pixels. Preserves the aspect ratio if omitted.",
+        "La nouvelle hauteur en pixels. Conserve les proportions si elle est omise.",
+    ),
+    (
+        "Crop an image to a rectangle.",
+        "Recadrer une image selon un rectangle.",
+    ),
+    (
+        "The left edge of the rectangle in pixels.",
+        "Le bord gauche du rectangle en pixels.",
+    ),
+    (
+        "The top edge of the rectangle in pixels.",
+        "Le bord supérieur du rectangle en pixels.",
+    ),
+    (
+        "The width of the rectangle in pixels.",
+        "La largeur du rectangle en pixels.",
+    ),
+    (
+        "The height of the rectangle in pixels.",
+        "La hauteur du rectangle en pixels.",
+    ),
+    (
+        "Rotate an image clockwise.",
+        "Pivoter une image dans le sens horaire.",
+    ),
+    (
+        "The clockwise rotation.",
+        "La rotation dans le sens horaire.",
+    ),
+    ("Mirror an image.", "Retourner une image en miroir."),
+    (
+        "The axis to mirror the image along.",
+        "L'axe selon lequel retourner l'image.",
+    ),
+    ("Horizontal", "Horizontal"),
+    ("Vertical", "Vertical"),
+    (
+        "Convert an image to another format.",
+        "Convertir une image dans un autre format.",
+    ),
+    ("The new format.", "Le nouveau format."),
+    ("WebP (lossless)", "WebP (sans perte)"),
+    // juxtapose
+    (
+        "Create a juxtapose by uploading two images.",
+        "Créer une juxtaposition en envoyant deux images.",
+    ),
+    (
+        "The image on the left side (or top).",
+        "L'image du côté gauche (ou du haut).",
+    ),
+    (
+        "The image on the right side (or bottom).",
+        "L'image du côté droit (ou du bas).",
+    ),
+    (
+        "The label on the left side (or top).",
+        "Le libellé du côté gauche (ou du haut).",
+    ),
+    (
+        "The label on the right side (or bottom).",
+        "Le libellé du côté droit (ou du bas).",
+    ),
+    (
+        "Whether or not the juxtapose should be vertical instead of horizontal. Defaults to false.",
+        "Si la juxtaposition doit être verticale plutôt qu'horizontale. Non par défaut.",
+    ),
+    (
+        "The thickness of the divider in pixels. Scales with the image size by default.",
+        "L'épaisseur du séparateur en pixels. S'adapte à la taille de l'image par défaut.",
+    ),
+    (
+        "The color of the divider in hex, rgb() or hsl() notation. Defaults to white.",
+        "La couleur du séparateur en notation hex, rgb() ou hsl(). Blanc par défaut.",
+    ),
+    (
+        "Describes the preview for screen readers. Generated from the labels by default.",
+        "Décrit l'aperçu pour les lecteurs d'écran. Généré à partir des libellés par défaut.",
+    ),
+    (
+        "The frame of animated images to compare, starting at 1. Defaults to the first frame.",
+        "L'image des images animées à comparer, à partir de 1. La première par défaut.",
+    ),
+    // juxtapose_from_message
+    (
+        "Recreate the interactive link of a previous juxtapose.",
+        "Recréer le lien interactif d'une juxtaposition précédente.",
+    ),
+    (
+        "The link to the message containing the juxtapose.",
+        "Le lien vers le message contenant la juxtaposition.",
+    ),
+    (
+        "Whether or not the juxtapose is vertical instead of horizontal. Defaults to false.",
+        "Si la juxtaposition est verticale plutôt qu'horizontale. Non par défaut.",
+    ),
+    // juxtapose_images
+    ("Juxtapose Images", "Juxtaposer les images"),
+    // juxtapose_preset
+    (
+        "Create a juxtapose using labels and layout saved by this server.",
+        "Créer une juxtaposition avec les libellés et la disposition enregistrés sur ce serveur.",
+    ),
+    ("The name of the preset.", "Le nom du préréglage."),
+    // palette
+    (
+        "Show a labeled strip of up to 8 colors.",
+        "Afficher une bande étiquetée de 8 couleurs maximum.",
+    ),
+    (
+        "A color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "Une couleur en notation hex (#ff8800), rgb(255, 136, 0) ou hsl(32, 100%, 50%).",
+    ),
+    // permalink
+    (
+        "Create a permanent link to lines of a file on GitHub and preview them.",
+        "Créer un lien permanent vers des lignes d'un fichier sur GitHub et les afficher.",
+    ),
+    (
+        "A single line or a range of lines, e.g. 10-20.",
+        "Une seule ligne ou une plage de lignes, p. ex. 10-20.",
+    ),
+    (
+        "A branch, tag or commit. Defaults to the default branch.",
+        "Une branche, un tag ou un commit. Par défaut, la branche par défaut.",
+    ),
+    // preview_links
+    ("Preview Links", "Aperçu des liens"),
+    // qr
+    ("Generate a QR code.", "Générer un code QR."),
+    ("The text or URL to encode.", "Le texte ou l'URL à encoder."),
+    (
+        "The approximate width and height of the image in pixels.",
+        "La largeur et la hauteur approximatives de l'image en pixels.",
+    ),
+    (
+        "How much of the code can be damaged or covered while remaining readable.",
+        "La part du code pouvant être endommagée ou masquée tout en restant lisible.",
+    ),
+    ("Low (7%)", "Faible (7 %)"),
+    ("Medium (15%)", "Moyen (15 %)"),
+    ("Quartile (25%)", "Quartile (25 %)"),
+    ("High (30%)", "Élevé (30 %)"),
+    (
+        "An image to place in the center of the code.",
+        "Une image à placer au centre du code.",
+    ),
+    // remove_preview
+    ("Remove Bot Preview", "Supprimer l'aperçu du bot"),
+    // spritesheet
+    (
+        "Work with sprite sheets and texture atlases.",
+        "Travailler avec des feuilles de sprites et des atlas de textures.",
+    ),
+    (
+        "Split an image into a grid of equally sized tiles.",
+        "Découper une image en une grille de tuiles de même taille.",
+    ),
+    ("The sprite sheet.", "La feuille de sprites."),
+    (
+        "The number of tiles per row.",
+        "Le nombre de tuiles par ligne.",
+    ),
+    (
+        "The number of tiles per column.",
+        "Le nombre de tuiles par colonne.",
+    ),
+    // stats
+    (
+        "Show usage statistics of the bot.",
+        "Afficher les statistiques d'utilisation du bot.",
+    ),
+    (
+        "Show how often a juxtapose has been opened.",
+        "Afficher combien de fois une juxtaposition a été ouverte.",
+    ),
+    (
+        "Show the most previewed repositories and files of this server in the last 30 days.",
+        "Afficher les dépôts et fichiers les plus prévisualisés du serveur ces 30 derniers jours.",
+    ),
+];
@@ -0,0 +1,523 @@
+//! Japanese translations.
+
+pub(super) const TRANSLATIONS: &[(&str, &str)] = &[
+    // admin
+    (
+        "Maintenance commands for the owners of the bot.",
+        "ボットの所有者向けのメンテナンスコマンド。",
+    ),
+    (
+        "Delete cache entries matching a pattern, e.g. for takedown requests.",
+        "パターンに一致するキャッシュを削除します（削除依頼への対応など）。",
+    ),
+    (
+        "Redis glob pattern, e.g. juxtapose_payload:*.",
+        "Redis の glob パターン（例: juxtapose_payload:*）。",
+    ),
+    (
+        "Delete all data stored about a user, like /forgetme.",
+        "ユーザーについて保存されたすべてのデータを削除します（/forgetme と同様）。",
+    ),
+    ("The ID of the user.", "ユーザーの ID。"),
+    (
+        "Ignore all messages and interactions of a user or in a guild.",
+        "ユーザーまたはサーバーのすべてのメッセージとインタラクションを無視します。",
+    ),
+    (
+        "Remove a user or guild from the blocklist.",
+        "ユーザーまたはサーバーをブロックリストから削除します。",
+    ),
+    (
+        "Whether the ID belongs to a user or a guild.",
+        "ID がユーザーとサーバーのどちらのものか。",
+    ),
+    ("User", "ユーザー"),
+    ("Guild", "サーバー"),
+    (
+        "The ID of the user or guild.",
+        "ユーザーまたはサーバーの ID。",
+    ),
+    (
+        "Configure and diagnose the juxtapose viewer.",
+        "比較ビューアーを設定・診断します。",
+    ),
+    (
+        "Change the URL of the viewer until the next restart, then test it.",
+        "次の再起動までビューアーの URL を変更し、テストします。",
+    ),
+    (
+        "Public URL of the viewer, e.g. https://example.com/view.",
+        "ビューアーの公開 URL（例: https://example.com/view）。",
+    ),
+    (
+        "Check that the viewer and its API can be reached and accept signed links.",
+        "ビューアーと API に接続でき、署名付きリンクを受け付けるか確認します。",
+    ),
+    (
+        "URL of the API if it isn't served next to the viewer, e.g. https://example.com/url.",
+        "ビューアーと別の場所にある場合の API の URL（例: https://example.com/url）。",
+    ),
+    (
+        "Roll out features gradually or turn them off.",
+        "機能を段階的に展開、または無効にします。",
+    ),
+    (
+        "Override the state of a feature for all guilds or a single guild.",
+        "すべてのサーバーまたは単一のサーバーで機能の状態を上書きします。",
+    ),
+    ("The feature.", "機能。"),
+    (
+        "on, off, reset or a percentage of guilds such as 25%.",
+        "on、off、reset、または 25% のようなサーバーの割合。",
+    ),
+    (
+        "The ID of the guild, all guilds if empty.",
+        "サーバーの ID。空の場合はすべてのサーバー。",
+    ),
+    (
+        "Show the state of all features.",
+        "すべての機能の状態を表示します。",
+    ),
+    // album
+    (
+        "Create an album of up to 10 images that can be viewed as a gallery.",
+        "ギャラリーとして閲覧できる最大 10 枚の画像のアルバムを作成します。",
+    ),
+    (
+        "Image number {} of the album.",
+        "アルバムの {} 枚目の画像。",
+    ),
+    ("The title of the album.", "アルバムのタイトル。"),
+    // backfill
+    (
+        "Post previews for links in the recent messages of a channel.",
+        "チャンネルの最近のメッセージ内のリンクのプレビューを投稿します。",
+    ),
+    ("The channel to scan.", "スキャンするチャンネル。"),
+    (
+        "The number of recent messages to scan. Defaults to 50.",
+        "スキャンする最近のメッセージの数。既定値は 50。",
+    ),
+    // browse
+    (
+        "Browse the files of a GitHub repository and preview one of them.",
+        "GitHub リポジトリのファイルを閲覧し、その一つをプレビューします。",
+    ),
+    (
+        "The repository in the format owner/repo.",
+        "owner/repo 形式のリポジトリ。",
+    ),
+    (
+        "The branch, tag or commit to browse. Defaults to the default branch.",
+        "閲覧するブランチ、タグ、またはコミット。既定はデフォルトブランチ。",
+    ),
+    // color
+    (
+        "Show a color swatch and its conversions.",
+        "カラースウォッチとその変換を表示します。",
+    ),
+    (
+        "The color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "hex (#ff8800)、rgb(255, 136, 0)、hsl(32, 100%, 50%) 表記の色。",
+    ),
+    // config
+    (
+        "Configure the bot for this server.",
+        "このサーバーでのボットの設定を行います。",
+    ),
+    (
+        "Show the current configuration of this server.",
+        "このサーバーの現在の設定を表示します。",
+    ),
+    (
+        "Set the channel that receives audit log entries.",
+        "監査ログを受け取るチャンネルを設定します。",
+    ),
+    (
+        "The audit log channel. Disables audit logging if omitted.",
+        "監査ログのチャンネル。省略すると監査ログを無効にします。",
+    ),
+    (
+        "Set the channel that receives a weekly digest of previews and juxtaposes.",
+        "プレビューと比較画像の週間ダイジェストを受け取るチャンネルを設定します。",
+    ),
+    (
+        "The digest channel. Disables the digest if omitted.",
+        "ダイジェストのチャンネル。省略するとダイジェストを無効にします。",
+    ),
+    (
+        "Set how file previews are displayed.",
+        "ファイルプレビューの表示方法を設定します。",
+    ),
+    (
+        "A code block in the message, a rich embed, or a thread on the message.",
+        "メッセージ内のコードブロック、埋め込み、またはメッセージのスレッド。",
+    ),
+    ("Code Block", "コードブロック"),
+    ("Embed", "埋め込み"),
+    ("Thread", "スレッド"),
+    (
+        "Attach long file previews as highlighted HTML documents instead of plain text.",
+        "長いファイルプレビューをプレーンテキストではなくハイライト付きの HTML 文書として添付します。",
+    ),
+    (
+        "The color theme of the document. Attaches plain text if omitted.",
+        "文書のカラーテーマ。省略するとプレーンテキストを添付します。",
+    ),
+    ("Dark", "ダーク"),
+    ("Light", "ライト"),
+    (
+        "Show tabs and trailing whitespace in file previews, and mention CRLF line endings.",
+        "ファイルプレビューでタブと行末の空白を表示し、CRLF 改行を通知します。",
+    ),
+    (
+        "The files whose previews show whitespace.",
+        "空白を表示するプレビューの対象ファイル。",
+    ),
+    ("None", "なし"),
+    ("Whitespace-Sensitive Files", "空白が重要なファイル"),
+    ("All Files", "すべてのファイル"),
+    (
+        "Enable or disable file previews for a kind of link.",
+        "リンクの種類ごとにファイルプレビューを有効または無効にします。",
+    ),
+    ("The kind of link.", "リンクの種類。"),
+    ("GitHub Repository Files", "GitHub リポジトリのファイル"),
+    ("GitHub Gists", "GitHub Gist"),
+    (
+        "Whether file previews are created for this kind of link.",
+        "この種類のリンクにファイルプレビューを作成するかどうか。",
+    ),
+    (
+        "Enable or disable automatic file previews in a channel.",
+        "チャンネルでの自動ファイルプレビューを有効または無効にします。",
+    ),
+    ("The channel.", "チャンネル。"),
+    (
+        "Whether links in this channel are previewed automatically.",
+        "このチャンネルのリンクを自動的にプレビューするかどうか。",
+    ),
+    (
+        "Show or hide the stars, language and license of repositories below file previews.",
+        "ファイルプレビューの下にリポジトリのスター数、言語、ライセンスを表示または非表示にします。",
+    ),
+    (
+        "Whether repository metadata is shown below previews of GitHub repository files.",
+        "GitHub リポジトリのファイルのプレビューの下にリポジトリ情報を表示するかどうか。",
+    ),
+    (
+        "Warn above file previews of repositories that look like scams.",
+        "詐欺のように見えるリポジトリのファイルプレビューに警告を表示します。",
+    ),
+    (
+        "Whether previews of blocklisted, new or suspiciously named files carry a warning.",
+        "ブロックリスト入り、新規、または不審な名前のファイルのプレビューに警告を付けるかどうか。",
+    ),
+    (
+        "Refer to the existing preview when a link is posted again in the same channel.",
+        "同じチャンネルでリンクが再投稿されたとき、既存のプレビューを参照します。",
+    ),
+    (
+        "Whether links posted again are answered with a link to their recent preview.",
+        "再投稿されたリンクに最近のプレビューへのリンクで返信するかどうか。",
+    ),
+    (
+        "Show a hash of the previewed lines below file previews.",
+        "ファイルプレビューの下にプレビューした行のハッシュを表示します。",
+    ),
+    (
+        "Whether previews end with a BLAKE3 hash to tell later if the file has changed.",
+        "後でファイルが変更されたか確認できるよう、プレビューの末尾にBLAKE3ハッシュを付けるかどうか。",
+    ),
+    (
+        "Set a text drawn in a corner of juxtapose previews, e.g. for attribution.",
+        "比較画像のプレビューの隅に表示するテキストを設定します（クレジット表記など）。",
+    ),
+    (
+        "The watermark text. Removes the watermark if omitted.",
+        "透かしのテキスト。省略すると透かしを削除します。",
+    ),
+    (
+        "Manage presets of labels and layout for /juxtapose-preset.",
+        "/juxtapose-preset 用のラベルとレイアウトのプリセットを管理します。",
+    ),
+    (
+        "Save a preset, replacing any preset with the same name.",
+        "プリセットを保存します。同じ名前のプリセットは置き換えられます。",
+    ),
+    ("Delete a preset.", "プリセットを削除します。"),
+    (
+        "Manage the languages that files are highlighted as by their extension.",
+        "拡張子ごとにファイルをどの言語としてハイライトするかを管理します。",
+    ),
+    (
+        "Highlight files with an extension as another language, e.g. .zs as java.",
+        "拡張子のファイルを別の言語としてハイライトします（例: .zs を java として）。",
+    ),
+    (
+        "The file extension, e.g. zs.",
+        "ファイルの拡張子（例: zs）。",
+    ),
+    (
+        "The language or extension that Discord highlights, e.g. java.",
+        "Discord がハイライトする言語または拡張子（例: java）。",
+    ),
+    (
+        "Remove the alias of a file extension.",
+        "ファイル拡張子のエイリアスを削除します。",
+    ),
+    (
+        "Manage patterns that block or redact file previews.",
+        "ファイルプレビューをブロックまたは伏せ字にするパターンを管理します。",
+    ),
+    (
+        "Add a case-insensitive regular expression to the keyword filter.",
+        "大文字と小文字を区別しない正規表現をキーワードフィルターに追加します。",
+    ),
+    ("The regular expression.", "正規表現。"),
+    (
+        "Remove a regular expression from the keyword filter.",
+        "キーワードフィルターから正規表現を削除します。",
+    ),
+    (
+        "Set what happens to file previews matching the keyword filter.",
+        "キーワードフィルターに一致したファイルプレビューの扱いを設定します。",
+    ),
+    (
+        "Either block the whole preview or redact the matches.",
+        "プレビュー全体をブロックするか、一致部分を伏せ字にします。",
+    ),
+    ("Block", "ブロック"),
+    ("Redact", "伏せ字"),
+    // forgetme
+    (
+        "Delete all data the bot has stored about you.",
+        "ボットがあなたについて保存したすべてのデータを削除します。",
+    ),
+    // ghdiff
+    (
+        "Preview how a file in a GitHub repository changed between two references.",
+        "GitHub リポジトリのファイルが二つの参照の間でどう変わったかをプレビューします。",
+    ),
+    (
+        "The path of the file within the repository.",
+        "リポジトリ内のファイルのパス。",
+    ),
+    (
+        "The branch, tag or commit to compare against.",
+        "比較対象のブランチ、タグ、またはコミット。",
+    ),
+    (
+        "The branch, tag or commit containing the changes.",
+        "変更を含むブランチ、タグ、またはコミット。",
+    ),
+    // ghsearch
+    (
+        "Search code in a GitHub repository.",
+        "GitHub リポジトリ内のコードを検索します。",
+    ),
+    ("The text to search for.", "検索するテキスト。"),
+    // hash
+    (
+        "Compute the SHA-256 and BLAKE3 checksums of a file.",
+        "ファイルの SHA-256 と BLAKE3 のチェックサムを計算します。",
+    ),
+    (
+        "The file to compute the checksums of.",
+        "チェックサムを計算するファイル。",
+    ),
+    (
+        "The URL of the file to compute the checksums of, if no file is attached.",
+        "ファイルが添付されていない場合、チェックサムを計算するファイルの URL。",
+    ),
+    // help
+    (
+        "Learn what the bot can do and how to use it.",
+        "ボットでできることと使い方を確認します。",
+    ),
+    ("The topic to show first.", "最初に表示するトピック。"),
+    ("File Previews", "ファイルプレビュー"),
+    ("Juxtapose", "比較画像"),
+    ("Tools", "ツール"),
+    ("Configuration", "設定"),
+    ("Privacy", "プライバシー"),
+    // imagetool
+    ("The image to process.", "処理する画像。"),
+    (
+        "Resize, crop, rotate, flip or convert an image.",
+        "画像のリサイズ、切り抜き、回転、反転、変換を行います。",
+    ),
+    ("Resize an image.", "画像をリサイズします。"),
+    ("The new width in pixels.", "新しい幅（ピクセル）。"),
+    (
+        "The new height in pixels. Preserves the aspect ratio if omitted.",
+        "新しい高さ（ピクセル）。省略するとアスペクト比を維持します。",
+    ),
+    (
+        "Crop an image to a rectangle.",
+        "画像を長方形に切り抜きます。",
+    ),
+    (
+        "The left edge of the rectangle in pixels.",
+        "長方形の左端（ピクセル）。",
+    ),
+    (
+        "The top edge of the rectangle in pixels.",
+        "長方形の上端（ピクセル）。",
+    ),
+    (
+        "The width of the rectangle in pixels.",
+        "長方形の幅（ピクセル）。",
+    ),
+    (
+        "The height of the rectangle in pixels.",
+        "長方形の高さ（ピクセル）。",
+    ),
+    ("Rotate an image clockwise.", "画像を時計回りに回転します。"),
+    ("The clockwise rotation.", "時計回りの回転角度。"),
+    ("Mirror an image.", "画像を反転します。"),
+    ("The axis to mirror the image along.", "画像を反転する軸。"),
+    ("Horizontal", "水平"),
+    ("Vertical", "垂直"),
+    (
+        "Convert an image to another format.",
+        "画像を別の形式に変換します。",
+    ),
+    ("The new format.", "新しい形式。"),
+    ("WebP (lossless)", "WebP（ロスレス）"),
+    // juxtapose
+    (
+        "Create a juxtapose by uploading two images.",
+        "2 枚の画像をアップロードして比較画像を作成します。",
+    ),
+    (
+        "The image on the left side (or top).",
+        "左側（または上側）の画像。",
+    ),
+    (
+        "The image on the right side (or bottom).",
+        "右側（または下側）の画像。",
+    ),
+    (
+        "The label on the left side (or top).",
+        "左側（または上側）のラベル。",
+    ),
+    (
+        "The label on the right side (or bottom).",
+        "右側（または下側）のラベル。",
+    ),
+    (
+        "Whether or not the juxtapose should be vertical instead of horizontal. Defaults to false.",
+        "比較画像を水平ではなく垂直にするかどうか。既定値は false。",
+    ),
+    (
+        "The thickness of the divider in pixels. Scales with the image size by default.",
+        "区切り線の太さ（ピクセル）。既定では画像サイズに合わせて調整されます。",
+    ),
+    (
+        "The color of the divider in hex, rgb() or hsl() notation. Defaults to white.",
+        "hex、rgb()、hsl() 表記の区切り線の色。既定値は白。",
+    ),
+    (
+        "Describes the preview for screen readers. Generated from the labels by default.",
+        "スクリーンリーダー向けのプレビューの説明。既定ではラベルから生成されます。",
+    ),
+    (
+        "The frame of animated images to compare, starting at 1. Defaults to the first frame.",
+        "比較するアニメーション画像のフレーム（1 から）。既定は最初のフレーム。",
+    ),
+    // juxtapose_from_message
+    (
+        "Recreate the interactive link of a previous juxtapose.",
+        "以前の比較画像のインタラクティブなリンクを再作成します。",
+    ),
+    (
+        "The link to the message containing the juxtapose.",
+        "比較画像を含むメッセージへのリンク。",
+    ),
+    (
+        "Whether or not the juxtapose is vertical instead of horizontal. Defaults to false.",
+        "比較画像が水平ではなく垂直かどうか。既定値は false。",
+    ),
+    // juxtapose_images
+    ("Juxtapose Images", "画像を比較"),
+    // juxtapose_preset
+    (
+        "Create a juxtapose using labels and layout saved by this server.",
+        "このサーバーで保存されたラベルとレイアウトを使って比較画像を作成します。",
+    ),
+    ("The name of the preset.", "プリセットの名前。"),
+    // palette
+    (
+        "Show a labeled strip of up to 8 colors.",
+        "最大 8 色のラベル付きカラーストリップを表示します。",
+    ),
+    (
+        "A color in hex (#ff8800), rgb(255, 136, 0) or hsl(32, 100%, 50%) notation.",
+        "hex (#ff8800)、rgb(255, 136, 0)、hsl(32, 100%, 50%) 表記の色。",
+    ),
+    // permalink
+    (
+        "Create a permanent link to lines of a file on GitHub and preview them.",
+        "GitHub 上のファイルの行への固定リンクを作成してプレビューします。",
+    ),
+    (
+        "A single line or a range of lines, e.g. 10-20.",
+        "1 行または行の範囲（例: 10-20）。",
+    ),
+    (
+        "A branch, tag or commit. Defaults to the default branch.",
+        "ブランチ、タグ、またはコミット。既定はデフォルトブランチ。",
+    ),
+    // preview_links
+    ("Preview Links", "リンクをプレビュー"),
+    // qr
+    ("Generate a QR code.", "QR コードを生成します。"),
+    (
+        "The text or URL to encode.",
+        "エンコードするテキストまたは URL。",
+    ),
+    (
+        "The approximate width and height of the image in pixels.",
+        "画像のおおよその幅と高さ（ピクセル）。",
+    ),
+    (
+        "How much of the code can be damaged or covered while remaining readable.",
+        "読み取り可能なまま、コードがどれだけ破損または隠れてもよいか。",
+    ),
+    ("Low (7%)", "低 (7%)"),
+    ("Medium (15%)", "中 (15%)"),
+    ("Quartile (25%)", "やや高 (25%)"),
+    ("High (30%)", "高 (30%)"),
+    (
+        "An image to place in the center of the code.",
+        "コードの中央に配置する画像。",
+    ),
+    // remove_preview
+    ("Remove Bot Preview", "ボットのプレビューを削除"),
+    // spritesheet
+    (
+        "Work with sprite sheets and texture atlases.",
+        "スプライトシートとテクスチャアトラスを扱います。",
+    ),
+    (
+        "Split an image into a grid of equally sized tiles.",
+        "画像を同じサイズのタイルのグリッドに分割します。",
+    ),
+    ("The sprite sheet.", "スプライトシート。"),
+    ("The number of tiles per row.", "1 行あたりのタイル数。"),
+    ("The number of tiles per column.", "1 列あたりのタイル数。"),
+    // stats
+    (
+        "Show usage statistics of the bot.",
+        "ボットの利用統計を表示します。",
+    ),
+    (
+        "Show how often a juxtapose has been opened.",
+        "比較画像が開かれた回数を表示します。",
+    ),
+    (
+        "Show the most previewed repositories and files of this server in the last 30 days.",
+        "過去30日間にこのサーバーで最もプレビューされたリポジトリとファイルを表示します。",
+    ),
+];
@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption};
+
+mod de;
+mod es;
+mod fr;
+mod ja;
+
+/// Translations of the user interface, keyed by the English text. Text without a translation
+/// falls back to English, which is also what clients in unsupported languages see.
+struct Catalog {
+    /// Locale code as used by Discord.
+    locale: &'static str,
+    translations: &'static [(&'static str, &'static str)],
+}
+
+const CATALOGS: &[Catalog] = &[
+    Catalog {
+        locale: "de",
+        translations: de::TRANSLATIONS,
+    },
+    Catalog {
+        locale: "es-ES",
+        translations: es::TRANSLATIONS,
+    },
+    Catalog {
+        locale: "fr",
+        translations: fr::TRANSLATIONS,
+    },
+    Catalog {
+        locale: "ja",
+        translations: ja::TRANSLATIONS,
+    },
+];
+
+/// Returns the translation of the English text into the given locale, if there is one.
+pub(crate) fn translate(locale: &str, text: &str) -> Option<&'static str> {
+    CATALOGS
+        .iter()
+        .find(|catalog| catalog.locale == locale)?
+        .translations
+        .iter()
+        .find(|(english, _)| *english == text)
+        .map(|(_, translation)| *translation)
+}
+
+/// Returns the locale codes and translations of the English text for all locales that have one.
+pub(crate) fn localizations(text: &str) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
+    CATALOGS
+        .iter()
+        .filter_map(move |catalog| Some((catalog.locale, translate(catalog.locale, text)?)))
+}
+
+/// Creates a slash command whose description is shown in the client language of the user.
+pub(crate) fn localized_command(
+    name: &'static str,
+    description: &'static str,
+) -> CreateCommand<'static> {
+    localizations(description).fold(
+        CreateCommand::new(name).description(description),
+        |command, (locale, translation)| command.description_localized(locale, translation),
+    )
+}
+
+/// Creates a message command whose name is shown in the client language of the user. Interactions
+/// always carry the English name, so handlers can keep matching on it.
+pub(crate) fn localized_message_command(name: &'static str) -> CreateCommand<'static> {
+    localizations(name).fold(
+        CreateCommand::new(name),
+        |command, (locale, translation)| command.name_localized(locale, translation),
+    )
+}
+
+/// Creates a command option whose description is shown in the client language of the user.
+pub(crate) fn localized_option(
+    kind: CommandOptionType,
+    name: impl Into<Cow<'static, str>>,
+    description: &'static str,
+) -> CreateCommandOption<'static> {
+    localizations(description).fold(
+        CreateCommandOption::new(kind, name, description),
+        |option, (locale, translation)| option.description_localized(locale, translation),
+    )
+}
+
+pub(crate) trait LocalizedChoices {
+    /// Adds a string choice whose name is shown in the client language of the user.
+    fn add_localized_string_choice(self, name: &'static str, value: &'static str) -> Self;
+}
+
+impl LocalizedChoices for CreateCommandOption<'static> {
+    fn add_localized_string_choice(self, name: &'static str, value: &'static str) -> Self {
+        self.add_string_choice_localized(name, value, localizations(name))
+    }
+}
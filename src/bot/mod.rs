@@ -1,3 +1,17 @@
+pub(crate) mod activity;
+pub(crate) mod audit_log;
+pub(crate) mod backfill;
+pub(crate) mod blocklist;
+pub(crate) mod bot_lists;
 pub(crate) mod commands;
+pub(crate) mod custom_id;
+pub(crate) mod digest;
 pub(crate) mod event_handler;
 pub(crate) mod file_preview;
+pub(crate) mod github_api;
+pub(crate) mod guild_config;
+pub(crate) mod guild_lifecycle;
+pub(crate) mod guild_stats;
+pub(crate) mod i18n;
+pub(crate) mod owner;
+pub(crate) mod user_data;
@@ -0,0 +1,37 @@
+use std::collections::HashSet;
+
+use serenity::all::UserId;
+use serenity::prelude::*;
+use tokio::sync::OnceCell;
+
+static BOT_OWNER_IDS: OnceCell<HashSet<UserId>> = OnceCell::const_new();
+
+/// Checks whether the user owns the bot application, either directly or as a member of the owning team.
+/// The owners are fetched once and kept for the lifetime of the process.
+pub(crate) async fn is_bot_owner(ctx: &Context, user_id: UserId) -> bool {
+    let bot_owner_ids = BOT_OWNER_IDS
+        .get_or_try_init(|| async {
+            let application_info = ctx.http.get_current_application_info().await?;
+
+            let mut bot_owner_ids: HashSet<UserId> = application_info
+                .owner
+                .iter()
+                .map(|owner| owner.id)
+                .collect();
+
+            if let Some(team) = &application_info.team {
+                bot_owner_ids.extend(team.members.iter().map(|member| member.user.id));
+            }
+
+            Ok::<_, serenity::Error>(bot_owner_ids)
+        })
+        .await;
+
+    match bot_owner_ids {
+        Ok(bot_owner_ids) => bot_owner_ids.contains(&user_id),
+        Err(error) => {
+            println!("Error while fetching the bot owners: {:?}", error);
+            false
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use redis::AsyncCommands;
+use serenity::all::UserId;
+
+use crate::web::api_album_response::APIAlbumResponse;
+use crate::web::api_juxtapose_response::APIJuxtaposeResponse;
+use crate::web::cache_warming::redis_remove_recent_juxtapose;
+use crate::web::view_counter::redis_delete_view_count;
+
+/// The index is only needed as long as the data it refers to can exist, see the view counter.
+const USER_INDEX_TTL: i64 = 365 * 24 * 60 * 60;
+
+fn redis_user_juxtaposes_key(user_id: UserId) -> String {
+    format!("user_juxtaposes:{}", user_id)
+}
+
+fn redis_user_albums_key(user_id: UserId) -> String {
+    format!("user_albums:{}", user_id)
+}
+
+async fn redis_record_user_entry(
+    connection: &mut redis::aio::ConnectionManager,
+    key: String,
+    data: &str,
+) -> Result<(), redis::RedisError> {
+    redis::pipe()
+        .atomic()
+        .sadd(&key, data)
+        .ignore()
+        .expire(&key, USER_INDEX_TTL)
+        .ignore()
+        .query_async(connection)
+        .await
+}
+
+/// Remembers which juxtaposes a user created, so their data can be deleted on request.
+pub(crate) async fn redis_record_user_juxtapose(
+    connection: &mut redis::aio::ConnectionManager,
+    user_id: UserId,
+    juxtapose_data: &str,
+) -> Result<(), redis::RedisError> {
+    redis_record_user_entry(
+        connection,
+        redis_user_juxtaposes_key(user_id),
+        juxtapose_data,
+    )
+    .await
+}
+
+/// Remembers which albums a user created, so their data can be deleted on request.
+pub(crate) async fn redis_record_user_album(
+    connection: &mut redis::aio::ConnectionManager,
+    user_id: UserId,
+    album_data: &str,
+) -> Result<(), redis::RedisError> {
+    redis_record_user_entry(connection, redis_user_albums_key(user_id), album_data).await
+}
+
+pub(crate) struct ForgetUserSummary {
+    pub(crate) juxtapose_count: usize,
+    pub(crate) album_count: usize,
+    pub(crate) deleted_key_count: usize,
+}
+
+/// Deletes all data stored for a user.
+pub(crate) async fn redis_forget_user(
+    connection: &mut redis::aio::ConnectionManager,
+    user_id: UserId,
+) -> Result<ForgetUserSummary, redis::RedisError> {
+    let key = redis_user_juxtaposes_key(user_id);
+    let juxtaposes: Vec<String> = connection.smembers(&key).await?;
+
+    let mut deleted_key_count = 0;
+
+    for juxtapose_data in juxtaposes.iter() {
+        deleted_key_count +=
+            APIJuxtaposeResponse::redis_cache_delete(connection, juxtapose_data.as_str()).await?;
+        deleted_key_count += redis_delete_view_count(connection, juxtapose_data.as_str()).await?;
+        redis_remove_recent_juxtapose(connection, juxtapose_data.as_str()).await?;
+    }
+
+    deleted_key_count += connection.del::<_, usize>(&key).await?;
+
+    let key = redis_user_albums_key(user_id);
+    let albums: Vec<String> = connection.smembers(&key).await?;
+
+    for album_data in albums.iter() {
+        deleted_key_count +=
+            APIAlbumResponse::redis_cache_delete(connection, album_data.as_str()).await?;
+    }
+
+    deleted_key_count += connection.del::<_, usize>(&key).await?;
+
+    Ok(ForgetUserSummary {
+        juxtapose_count: juxtaposes.len(),
+        album_count: albums.len(),
+        deleted_key_count,
+    })
+}
@@ -0,0 +1,710 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use axum::http::HeaderValue;
+use once_cell::sync::OnceCell;
+use redis::IntoConnectionInfo;
+use reqwest::Url;
+use serenity::prelude::Token;
+
+use crate::feature_flags::{FeatureFlag, FeatureFlagState};
+
+static CONFIG: OnceCell<Config> = OnceCell::new();
+static RUNTIME_CONFIG: OnceCell<ArcSwap<RuntimeConfig>> = OnceCell::new();
+
+/// Built-in file extension aliases for languages that Discord doesn't highlight by their extension.
+const BUILTIN_LANGUAGE_ALIASES: [(&str, &str); 7] = [
+    ("vsh", "glsl"),
+    ("fsh", "glsl"),
+    ("gsh", "glsl"),
+    ("csh", "glsl"),
+    ("vert", "glsl"),
+    ("frag", "glsl"),
+    ("inc", "glsl"),
+];
+
+/// Files are read into memory as a whole, so even operators can't allow previews of larger files.
+pub(crate) const PREVIEW_MAX_FILE_SIZE: u64 = 4_194_304;
+
+/// Returns the configuration loaded at startup.
+pub(crate) fn config() -> &'static Config {
+    CONFIG.get().expect("Configuration has not been loaded.")
+}
+
+/// Returns the current runtime configuration, which may be replaced by [`reload_runtime_config`] at any time.
+pub(crate) fn runtime_config() -> Arc<RuntimeConfig> {
+    RUNTIME_CONFIG
+        .get()
+        .expect("Configuration has not been loaded.")
+        .load_full()
+}
+
+pub(crate) enum ApiListener {
+    #[cfg(unix)]
+    UnixSocket(String),
+    TcpPort(u16),
+}
+
+/// Credentials of the Discord application, used for logging into the dashboard.
+pub(crate) struct DashboardOAuth2Config {
+    pub(crate) client_id: String,
+    pub(crate) client_secret: String,
+    /// The URL of the `/auth/callback` route, as registered in the Discord Developer Portal.
+    pub(crate) redirect_url: Url,
+}
+
+pub(crate) struct Config {
+    pub(crate) bot_token: Token,
+    pub(crate) blake3_key_material: String,
+    pub(crate) redis_url: String,
+    pub(crate) api_listener: ApiListener,
+    pub(crate) cors_origins: CorsOrigins,
+    pub(crate) access_log_format: AccessLogFormat,
+    pub(crate) serve_viewer: bool,
+    pub(crate) serve_dashboard: bool,
+    pub(crate) github_token: Option<String>,
+    /// Number of recent messages per channel scanned for links when joining a guild, zero if disabled.
+    pub(crate) backfill_on_join_limit: u8,
+    pub(crate) dashboard_oauth2: Option<DashboardOAuth2Config>,
+    /// Requests to the same host that may be in flight at once.
+    pub(crate) outbound_max_concurrency_per_host: u8,
+    /// Default states of features, which are enabled if they aren't listed.
+    pub(crate) feature_flags: HashMap<FeatureFlag, FeatureFlagState>,
+    /// Tokens of bot lists that the guild count is posted to.
+    pub(crate) topgg_token: Option<String>,
+    pub(crate) discords_token: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AccessLogFormat {
+    Disabled,
+    Text,
+    Json,
+}
+
+pub(crate) enum CorsOriginPattern {
+    Exact(HeaderValue),
+    /// Matches every subdomain of a domain, e.g. `https://*.example.com`.
+    /// `domain_suffix` includes the leading dot, so the domain itself isn't matched.
+    WildcardSubdomain {
+        scheme: String,
+        domain_suffix: String,
+    },
+}
+
+impl CorsOriginPattern {
+    pub(crate) fn matches(&self, origin: &HeaderValue) -> bool {
+        match self {
+            Self::Exact(exact_origin) => exact_origin == origin,
+            Self::WildcardSubdomain {
+                scheme,
+                domain_suffix,
+            } => origin
+                .to_str()
+                .ok()
+                .and_then(|origin| origin.strip_prefix(scheme.as_str()))
+                .and_then(|origin| origin.strip_prefix("://"))
+                .and_then(|host| host.strip_suffix(domain_suffix.as_str()))
+                .is_some_and(|subdomain| {
+                    !subdomain.is_empty() && !subdomain.contains(['/', ':', '@'])
+                }),
+        }
+    }
+}
+
+pub(crate) enum CorsOrigins {
+    Any,
+    List(Vec<CorsOriginPattern>),
+}
+
+/// Limits of the files that previews are fetched for.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PreviewFileLimits {
+    /// In bytes, at most [`PREVIEW_MAX_FILE_SIZE`].
+    pub(crate) max_size: u64,
+    pub(crate) max_lines: Option<u32>,
+}
+
+impl Default for PreviewFileLimits {
+    fn default() -> Self {
+        Self {
+            max_size: PREVIEW_MAX_FILE_SIZE,
+            max_lines: None,
+        }
+    }
+}
+
+/// Non-secret settings that can be reloaded without restarting the bot or dropping the gateway connection.
+#[derive(Clone)]
+pub(crate) struct RuntimeConfig {
+    pub(crate) juxtapose_base_url: Url,
+    pub(crate) album_base_url: Url,
+    pub(crate) language_aliases: HashMap<String, String>,
+    pub(crate) presence_text: Option<String>,
+    /// Number of lines that a linked line range in a file preview is truncated to.
+    pub(crate) preview_max_lines: u32,
+    /// Width in columns that lines of file previews are wrapped at, including line numbers. Disabled if `None`.
+    pub(crate) preview_max_line_width: Option<u32>,
+    /// Limits of files on full commit SHAs, whose content can't change.
+    pub(crate) commit_file_limits: PreviewFileLimits,
+    /// Limits of files on branches and other references whose content can change after the preview was posted.
+    pub(crate) branch_file_limits: PreviewFileLimits,
+    /// Lowercase owners and repositories in the format `owner` or `owner/repo` that link screening warns about.
+    pub(crate) screening_blocklist: Vec<String>,
+    /// Hosts that outbound requests are limited to, including subdomains. Empty if all hosts are allowed.
+    pub(crate) outbound_allowed_hosts: Vec<String>,
+    /// Hosts that outbound requests must never reach, including subdomains.
+    pub(crate) outbound_denied_hosts: Vec<String>,
+}
+
+fn get_required(errors: &mut Vec<String>, name: &str) -> Option<String> {
+    match env::var(name) {
+        Ok(value) if !value.is_empty() => Some(value),
+        Ok(_) => {
+            errors.push(format!("{} must not be empty.", name));
+            None
+        }
+        Err(_) => {
+            errors.push(format!("{} is missing.", name));
+            None
+        }
+    }
+}
+
+fn get_optional(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Variables of a reloaded `.env` file, which take precedence over the environment of the process.
+/// The environment itself is never modified after startup, since other threads may read it concurrently.
+#[derive(Default)]
+struct Environment {
+    overrides: HashMap<String, String>,
+}
+
+impl Environment {
+    fn get_optional(&self, name: &str) -> Option<String> {
+        match self.overrides.get(name) {
+            Some(value) => Some(value.clone()).filter(|value| !value.is_empty()),
+            None => get_optional(name),
+        }
+    }
+}
+
+fn get_optional_bool(errors: &mut Vec<String>, name: &str) -> bool {
+    match get_optional(name).as_deref() {
+        None | Some("false") | Some("0") => false,
+        Some("true") | Some("1") => true,
+        Some(_) => {
+            errors.push(format!("{} must be either true or false.", name));
+            false
+        }
+    }
+}
+
+fn parse_api_listener(errors: &mut Vec<String>) -> Option<ApiListener> {
+    #[cfg(unix)]
+    if let Some(socket_path) = get_optional("SOCKET_PATH") {
+        return Some(ApiListener::UnixSocket(socket_path));
+    }
+
+    match get_optional("PORT").map(|port| port.parse::<u16>()) {
+        Some(Ok(port)) => Some(ApiListener::TcpPort(port)),
+        Some(Err(_)) => {
+            errors.push("PORT is not a valid port number.".to_owned());
+            None
+        }
+        None => {
+            #[cfg(unix)]
+            errors.push("SOCKET_PATH or PORT must be set.".to_owned());
+
+            #[cfg(not(unix))]
+            errors.push("PORT must be set.".to_owned());
+
+            None
+        }
+    }
+}
+
+fn parse_access_log_format(errors: &mut Vec<String>) -> AccessLogFormat {
+    match get_optional("ACCESS_LOG_FORMAT").as_deref() {
+        None | Some("text") => AccessLogFormat::Text,
+        Some("json") => AccessLogFormat::Json,
+        Some("off") => AccessLogFormat::Disabled,
+        Some(_) => {
+            errors.push("ACCESS_LOG_FORMAT must be one of text, json or off.".to_owned());
+            AccessLogFormat::Disabled
+        }
+    }
+}
+
+fn parse_backfill_on_join_limit(errors: &mut Vec<String>) -> u8 {
+    match get_optional("BACKFILL_ON_JOIN_LIMIT").map(|limit| limit.parse::<u8>()) {
+        None => 0,
+        Some(Ok(limit)) if limit <= 100 => limit,
+        Some(_) => {
+            errors.push("BACKFILL_ON_JOIN_LIMIT must be a number between 0 and 100.".to_owned());
+            0
+        }
+    }
+}
+
+fn parse_preview_max_lines(errors: &mut Vec<String>, environment: &Environment) -> u32 {
+    match environment
+        .get_optional("PREVIEW_MAX_LINES")
+        .map(|limit| limit.parse::<u32>())
+    {
+        None => 500,
+        Some(Ok(limit)) if (1..=10_000).contains(&limit) => limit,
+        Some(_) => {
+            errors.push("PREVIEW_MAX_LINES must be a number between 1 and 10000.".to_owned());
+            500
+        }
+    }
+}
+
+/// Defaults to a width that fits the code blocks of the mobile client in landscape orientation.
+fn parse_preview_max_line_width(
+    errors: &mut Vec<String>,
+    environment: &Environment,
+) -> Option<u32> {
+    match environment
+        .get_optional("PREVIEW_MAX_LINE_WIDTH")
+        .map(|width| width.parse::<u32>())
+    {
+        None => Some(110),
+        Some(Ok(0)) => None,
+        Some(Ok(width)) if (20..=1000).contains(&width) => Some(width),
+        Some(_) => {
+            errors.push(
+                "PREVIEW_MAX_LINE_WIDTH must be 0 or a number between 20 and 1000.".to_owned(),
+            );
+            Some(110)
+        }
+    }
+}
+
+fn parse_outbound_max_concurrency_per_host(errors: &mut Vec<String>) -> u8 {
+    match get_optional("OUTBOUND_MAX_CONCURRENCY_PER_HOST").map(|limit| limit.parse::<u8>()) {
+        None => 4,
+        Some(Ok(limit)) if (1..=64).contains(&limit) => limit,
+        Some(_) => {
+            errors.push(
+                "OUTBOUND_MAX_CONCURRENCY_PER_HOST must be a number between 1 and 64.".to_owned(),
+            );
+            4
+        }
+    }
+}
+
+/// Parses `<prefix>_MAX_FILE_SIZE` and `<prefix>_MAX_FILE_LINES`, using the defaults for unset variables.
+fn parse_preview_file_limits(
+    errors: &mut Vec<String>,
+    environment: &Environment,
+    prefix: &str,
+    defaults: PreviewFileLimits,
+) -> PreviewFileLimits {
+    let max_size_name = format!("{}_MAX_FILE_SIZE", prefix);
+    let max_lines_name = format!("{}_MAX_FILE_LINES", prefix);
+
+    let max_size = match environment
+        .get_optional(max_size_name.as_str())
+        .map(|limit| limit.parse::<u64>())
+    {
+        None => defaults.max_size,
+        Some(Ok(limit)) if (1..=PREVIEW_MAX_FILE_SIZE).contains(&limit) => limit,
+        Some(_) => {
+            errors.push(format!(
+                "{} must be a number between 1 and {}.",
+                max_size_name, PREVIEW_MAX_FILE_SIZE
+            ));
+            defaults.max_size
+        }
+    };
+
+    let max_lines = match environment
+        .get_optional(max_lines_name.as_str())
+        .map(|limit| limit.parse::<u32>())
+    {
+        None => defaults.max_lines,
+        Some(Ok(limit)) if limit > 0 => Some(limit),
+        Some(_) => {
+            errors.push(format!("{} must be a positive number.", max_lines_name));
+            defaults.max_lines
+        }
+    };
+
+    PreviewFileLimits {
+        max_size,
+        max_lines,
+    }
+}
+
+/// Parses a comma-separated list of GitHub owners and repositories such as `scammer,someone/free-nitro`.
+fn parse_screening_blocklist(errors: &mut Vec<String>, environment: &Environment) -> Vec<String> {
+    environment
+        .get_optional("SCREENING_BLOCKLIST")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let is_valid = match entry.split_once('/') {
+                Some((owner, repository)) => {
+                    !owner.is_empty() && !repository.is_empty() && !repository.contains('/')
+                }
+                None => true,
+            };
+
+            if !is_valid {
+                errors.push(format!(
+                    "SCREENING_BLOCKLIST contains the malformed entry \"{}\".",
+                    entry
+                ));
+                return None;
+            }
+
+            Some(entry.to_ascii_lowercase())
+        })
+        .collect()
+}
+
+fn parse_dashboard_oauth2(errors: &mut Vec<String>) -> Option<DashboardOAuth2Config> {
+    match (
+        get_optional("DISCORD_CLIENT_ID"),
+        get_optional("DISCORD_CLIENT_SECRET"),
+        get_optional("DASHBOARD_REDIRECT_URL"),
+    ) {
+        (None, None, None) => None,
+        (Some(client_id), Some(client_secret), Some(redirect_url)) => {
+            match Url::parse(redirect_url.as_str()) {
+                Ok(redirect_url) => Some(DashboardOAuth2Config {
+                    client_id,
+                    client_secret,
+                    redirect_url,
+                }),
+                Err(_) => {
+                    errors.push("DASHBOARD_REDIRECT_URL is not a valid URL.".to_owned());
+                    None
+                }
+            }
+        }
+        _ => {
+            errors.push(
+                "DISCORD_CLIENT_ID, DISCORD_CLIENT_SECRET and DASHBOARD_REDIRECT_URL must be set together."
+                    .to_owned(),
+            );
+            None
+        }
+    }
+}
+
+/// Parses a comma-separated list of hosts such as `example.com,*.example.org`, where subdomains always match.
+fn parse_host_list(errors: &mut Vec<String>, environment: &Environment, name: &str) -> Vec<String> {
+    environment
+        .get_optional(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+        .filter_map(|host| {
+            let host = host.trim_start_matches("*.").to_ascii_lowercase();
+
+            if host.contains(['*', '/', ':']) {
+                errors.push(format!("{} contains the invalid host \"{}\".", name, host));
+                return None;
+            }
+
+            Some(host)
+        })
+        .collect()
+}
+
+/// Parses aliases in the format `zs=java,skript=vb`.
+fn parse_language_aliases(
+    errors: &mut Vec<String>,
+    language_aliases_string: &str,
+) -> HashMap<String, String> {
+    language_aliases_string
+        .split(',')
+        .map(str::trim)
+        .filter(|language_alias| !language_alias.is_empty())
+        .filter_map(|language_alias| match language_alias.split_once('=') {
+            Some((extension, language)) if !extension.is_empty() && !language.is_empty() => {
+                Some((extension.to_ascii_lowercase(), language.to_owned()))
+            }
+            _ => {
+                errors.push(format!(
+                    "LANGUAGE_ALIASES contains the malformed alias \"{}\".",
+                    language_alias
+                ));
+                None
+            }
+        })
+        .collect()
+}
+
+/// Parses feature flags in the format `render_image=off,pdf_pages=25%`.
+fn parse_feature_flags(errors: &mut Vec<String>) -> HashMap<FeatureFlag, FeatureFlagState> {
+    get_optional("FEATURE_FLAGS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|feature_flag| !feature_flag.is_empty())
+        .filter_map(|feature_flag| {
+            match feature_flag.split_once('=').and_then(|(name, state)| {
+                Some((
+                    FeatureFlag::parse(name.trim())?,
+                    FeatureFlagState::parse(state)?,
+                ))
+            }) {
+                Some(feature_flag) => Some(feature_flag),
+                None => {
+                    errors.push(format!(
+                        "FEATURE_FLAGS contains the malformed flag \"{}\".",
+                        feature_flag
+                    ));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Parses either `*` or a comma-separated list of origins, which may contain wildcard subdomains.
+fn parse_cors_origins(errors: &mut Vec<String>) -> CorsOrigins {
+    let Some(cors_origins_string) = get_optional("CORS_ORIGIN") else {
+        return CorsOrigins::Any;
+    };
+
+    if cors_origins_string.trim() == "*" {
+        return CorsOrigins::Any;
+    }
+
+    let patterns = cors_origins_string
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .filter_map(|origin| {
+            let pattern = match origin.split_once("://*.") {
+                Some((scheme, domain))
+                    if !scheme.is_empty() && !domain.is_empty() && !domain.contains('*') =>
+                {
+                    Some(CorsOriginPattern::WildcardSubdomain {
+                        scheme: scheme.to_ascii_lowercase(),
+                        domain_suffix: format!(".{}", domain.to_ascii_lowercase()),
+                    })
+                }
+                None if !origin.contains('*') => origin.parse().ok().map(CorsOriginPattern::Exact),
+                _ => None,
+            };
+
+            if pattern.is_none() {
+                errors.push(format!(
+                    "CORS_ORIGIN contains the invalid origin \"{}\".",
+                    origin
+                ));
+                return None;
+            }
+
+            pattern
+        })
+        .collect();
+
+    CorsOrigins::List(patterns)
+}
+
+impl RuntimeConfig {
+    fn from_environment(errors: &mut Vec<String>, environment: &Environment) -> Option<Self> {
+        let juxtapose_base_url = Url::parse(
+            environment
+                .get_optional("JUXTAPOSE_BASE_URL")
+                .as_deref()
+                .unwrap_or("http://localhost"),
+        )
+        .inspect_err(|_| errors.push("JUXTAPOSE_BASE_URL is not a valid URL.".to_owned()))
+        .ok();
+
+        let album_base_url = Url::parse(
+            environment
+                .get_optional("ALBUM_BASE_URL")
+                .as_deref()
+                .unwrap_or("http://localhost"),
+        )
+        .inspect_err(|_| errors.push("ALBUM_BASE_URL is not a valid URL.".to_owned()))
+        .ok();
+
+        let mut language_aliases: HashMap<String, String> = BUILTIN_LANGUAGE_ALIASES
+            .iter()
+            .map(|(extension, language)| ((*extension).to_owned(), (*language).to_owned()))
+            .collect();
+
+        if let Some(language_aliases_string) = environment.get_optional("LANGUAGE_ALIASES") {
+            language_aliases.extend(parse_language_aliases(
+                errors,
+                language_aliases_string.as_str(),
+            ));
+        }
+
+        let commit_file_limits =
+            parse_preview_file_limits(errors, environment, "PREVIEW", PreviewFileLimits::default());
+
+        // Branches fall back to the limits of commits, so that a single pair of variables limits both.
+        let branch_file_limits =
+            parse_preview_file_limits(errors, environment, "PREVIEW_BRANCH", commit_file_limits);
+
+        Some(Self {
+            juxtapose_base_url: juxtapose_base_url?,
+            album_base_url: album_base_url?,
+            language_aliases,
+            presence_text: environment.get_optional("PRESENCE_TEXT"),
+            preview_max_lines: parse_preview_max_lines(errors, environment),
+            preview_max_line_width: parse_preview_max_line_width(errors, environment),
+            commit_file_limits,
+            branch_file_limits,
+            screening_blocklist: parse_screening_blocklist(errors, environment),
+            outbound_allowed_hosts: parse_host_list(errors, environment, "OUTBOUND_ALLOWED_HOSTS"),
+            outbound_denied_hosts: parse_host_list(errors, environment, "OUTBOUND_DENIED_HOSTS"),
+        })
+    }
+}
+
+/// Re-reads the `.env` file and replaces the runtime configuration if it is valid.
+/// Settings outside of [`RuntimeConfig`] keep their values from startup. Variables that aren't in the `.env` file
+/// keep the value that the process started with, since the environment of the process can't be changed from outside.
+pub(crate) fn reload_runtime_config() -> Result<(), Vec<String>> {
+    let overrides = match dotenvy::dotenv_iter() {
+        Ok(variables) => variables
+            .collect::<Result<HashMap<String, String>, _>>()
+            .map_err(|error| vec![format!("The .env file is invalid: {}", error)])?,
+        Err(error) if error.not_found() => {
+            println!(
+                "No .env file found, reloading the environment that the process started with."
+            );
+            HashMap::new()
+        }
+        Err(error) => return Err(vec![format!("Failed to re-read the .env file: {}", error)]),
+    };
+
+    let mut errors = Vec::new();
+
+    match RuntimeConfig::from_environment(&mut errors, &Environment { overrides }) {
+        Some(runtime_config) if errors.is_empty() => {
+            RUNTIME_CONFIG
+                .get()
+                .expect("Configuration has not been loaded.")
+                .store(Arc::new(runtime_config));
+
+            Ok(())
+        }
+        _ => Err(errors),
+    }
+}
+
+/// Replaces the base URL of the juxtapose viewer until the runtime configuration is reloaded or the bot restarts.
+pub(crate) fn set_juxtapose_base_url(juxtapose_base_url: Url) {
+    RUNTIME_CONFIG
+        .get()
+        .expect("Configuration has not been loaded.")
+        .rcu(|current| RuntimeConfig {
+            juxtapose_base_url: juxtapose_base_url.clone(),
+            ..RuntimeConfig::clone(current)
+        });
+}
+
+impl Config {
+    /// Parses and validates all environment variables, collecting every error instead of stopping at the first one.
+    pub(crate) fn from_env() -> Result<(Self, RuntimeConfig), Vec<String>> {
+        let mut errors = Vec::new();
+
+        let bot_token = Token::from_env("BOT_TOKEN")
+            .inspect_err(|_| errors.push("BOT_TOKEN is missing or invalid.".to_owned()))
+            .ok();
+
+        let blake3_key_material = get_required(&mut errors, "BLAKE3_KEY_MATERIAL");
+
+        let redis_url =
+            get_optional("REDIS_URL").unwrap_or_else(|| "redis://127.0.0.1/".to_owned());
+
+        if redis_url.as_str().into_connection_info().is_err() {
+            errors.push("REDIS_URL is not a valid Redis URL.".to_owned());
+        }
+
+        let api_listener = parse_api_listener(&mut errors);
+
+        let cors_origins = parse_cors_origins(&mut errors);
+
+        let access_log_format = parse_access_log_format(&mut errors);
+
+        let serve_viewer = get_optional_bool(&mut errors, "SERVE_VIEWER");
+
+        let serve_dashboard = get_optional_bool(&mut errors, "SERVE_DASHBOARD");
+
+        let github_token = get_optional("GITHUB_TOKEN");
+
+        let backfill_on_join_limit = parse_backfill_on_join_limit(&mut errors);
+
+        let dashboard_oauth2 = parse_dashboard_oauth2(&mut errors);
+
+        let outbound_max_concurrency_per_host =
+            parse_outbound_max_concurrency_per_host(&mut errors);
+
+        let feature_flags = parse_feature_flags(&mut errors);
+
+        let topgg_token = get_optional("TOPGG_TOKEN");
+
+        let discords_token = get_optional("DISCORDS_TOKEN");
+
+        let runtime_config = RuntimeConfig::from_environment(&mut errors, &Environment::default());
+
+        match (bot_token, blake3_key_material, api_listener, runtime_config) {
+            (
+                Some(bot_token),
+                Some(blake3_key_material),
+                Some(api_listener),
+                Some(runtime_config),
+            ) if errors.is_empty() => Ok((
+                Self {
+                    bot_token,
+                    blake3_key_material,
+                    redis_url,
+                    api_listener,
+                    cors_origins,
+                    access_log_format,
+                    serve_viewer,
+                    serve_dashboard,
+                    github_token,
+                    backfill_on_join_limit,
+                    dashboard_oauth2,
+                    outbound_max_concurrency_per_host,
+                    feature_flags,
+                    topgg_token,
+                    discords_token,
+                },
+                runtime_config,
+            )),
+            _ => Err(errors),
+        }
+    }
+
+    /// Loads the configuration into the global state, exiting the process with a list of all errors if it is invalid.
+    pub(crate) fn load() -> &'static Self {
+        match Self::from_env() {
+            Ok((config, runtime_config)) => {
+                RUNTIME_CONFIG.get_or_init(|| ArcSwap::from_pointee(runtime_config));
+                CONFIG.get_or_init(|| config)
+            }
+            Err(errors) => {
+                eprintln!("Invalid configuration:");
+
+                for error in errors {
+                    eprintln!("- {}", error);
+                }
+
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use serenity::all::GuildId;
+
+use crate::config::config;
+
+/// Sets the state of a feature for all guilds, taking precedence over `FEATURE_FLAGS`.
+const REDIS_FEATURE_FLAGS_KEY: &str = "feature_flags";
+
+/// Features that can be rolled out gradually or turned off without a deploy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum FeatureFlag {
+    /// The Render button on file previews.
+    RenderImage,
+    /// Previews of PDF pages linked with a `#page=<number>` fragment.
+    PdfPages,
+    /// Retrying previews that failed due to a transient error in the background.
+    PreviewRetries,
+}
+
+impl FeatureFlag {
+    pub(crate) const ALL: [Self; 3] = [Self::RenderImage, Self::PdfPages, Self::PreviewRetries];
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::RenderImage => "render_image",
+            Self::PdfPages => "pdf_pages",
+            Self::PreviewRetries => "preview_retries",
+        }
+    }
+
+    pub(crate) fn parse(flag: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|feature_flag| feature_flag.as_str() == flag)
+    }
+}
+
+/// Whether a feature is enabled, either for everyone or for a share of guilds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FeatureFlagState {
+    On,
+    Off,
+    /// Enabled for the given percentage of guilds, picked by a hash of the guild ID and the feature.
+    Rollout(u8),
+}
+
+impl FeatureFlagState {
+    /// Parses `on`, `off` or a percentage such as `25%`.
+    pub(crate) fn parse(state: &str) -> Option<Self> {
+        match state.trim().to_ascii_lowercase().as_str() {
+            "on" => Some(Self::On),
+            "off" => Some(Self::Off),
+            state => match state.strip_suffix('%')?.parse::<u8>().ok()? {
+                0 => Some(Self::Off),
+                100 => Some(Self::On),
+                percentage if percentage < 100 => Some(Self::Rollout(percentage)),
+                _ => None,
+            },
+        }
+    }
+
+    fn is_enabled_for(&self, feature_flag: FeatureFlag, guild_id: Option<GuildId>) -> bool {
+        match self {
+            Self::On => true,
+            Self::Off => false,
+            // Direct messages only get features that are rolled out to everyone.
+            Self::Rollout(percentage) => guild_id
+                .is_some_and(|guild_id| get_rollout_bucket(feature_flag, guild_id) < *percentage),
+        }
+    }
+}
+
+impl fmt::Display for FeatureFlagState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::On => f.write_str("on"),
+            Self::Off => f.write_str("off"),
+            Self::Rollout(percentage) => write!(f, "{}%", percentage),
+        }
+    }
+}
+
+/// Maps the guild to a stable bucket between 0 and 99, which differs between features,
+/// so that the same guilds don't always get every new feature first.
+fn get_rollout_bucket(feature_flag: FeatureFlag, guild_id: GuildId) -> u8 {
+    let hash = blake3::hash(format!("{}:{}", feature_flag.as_str(), guild_id).as_bytes());
+    let mut bucket_bytes = [0; 8];
+    bucket_bytes.copy_from_slice(&hash.as_bytes()[..8]);
+
+    (u64::from_le_bytes(bucket_bytes) % 100) as u8
+}
+
+/// Hash of the guilds whose state of the feature is overridden, which takes precedence over everything else.
+fn redis_feature_flag_guilds_key(feature_flag: FeatureFlag) -> String {
+    format!("{}:{}", REDIS_FEATURE_FLAGS_KEY, feature_flag.as_str())
+}
+
+/// The state of the feature from `FEATURE_FLAGS`, enabled if it isn't listed there.
+pub(crate) fn get_default_feature_flag_state(feature_flag: FeatureFlag) -> FeatureFlagState {
+    config()
+        .feature_flags
+        .get(&feature_flag)
+        .copied()
+        .unwrap_or(FeatureFlagState::On)
+}
+
+/// Checks whether the feature is enabled in the guild, using the override of the guild, the override for
+/// all guilds and `FEATURE_FLAGS` in that order. Falls back to `FEATURE_FLAGS` if Redis isn't available.
+pub(crate) async fn is_feature_enabled(
+    connection: &mut redis::aio::ConnectionManager,
+    feature_flag: FeatureFlag,
+    guild_id: Option<GuildId>,
+) -> bool {
+    let (global_state, guild_state): (Option<String>, Option<String>) = redis::pipe()
+        .hget(REDIS_FEATURE_FLAGS_KEY, feature_flag.as_str())
+        .hget(
+            redis_feature_flag_guilds_key(feature_flag),
+            guild_id.map_or(0, GuildId::get),
+        )
+        .query_async(connection)
+        .await
+        .unwrap_or_default();
+
+    [guild_state, global_state]
+        .into_iter()
+        .flatten()
+        .find_map(|state| FeatureFlagState::parse(state.as_str()))
+        .unwrap_or_else(|| get_default_feature_flag_state(feature_flag))
+        .is_enabled_for(feature_flag, guild_id)
+}
+
+/// Overrides the state of the feature in a guild, or in all guilds if `guild_id` is `None`.
+/// Removes the override if `state` is `None`.
+pub(crate) async fn redis_set_feature_flag_override(
+    connection: &mut redis::aio::ConnectionManager,
+    feature_flag: FeatureFlag,
+    guild_id: Option<GuildId>,
+    state: Option<FeatureFlagState>,
+) -> Result<(), redis::RedisError> {
+    let (key, field) = match guild_id {
+        Some(guild_id) => (
+            redis_feature_flag_guilds_key(feature_flag),
+            guild_id.to_string(),
+        ),
+        None => (
+            REDIS_FEATURE_FLAGS_KEY.to_owned(),
+            feature_flag.as_str().to_owned(),
+        ),
+    };
+
+    match state {
+        Some(state) => {
+            redis::cmd("HSET")
+                .arg(key)
+                .arg(field)
+                .arg(state.to_string())
+                .query_async(connection)
+                .await
+        }
+        None => {
+            redis::cmd("HDEL")
+                .arg(key)
+                .arg(field)
+                .query_async(connection)
+                .await
+        }
+    }
+}
+
+/// The override of the feature for all guilds and the number of guilds with an override.
+pub(crate) async fn redis_get_feature_flag_overrides(
+    connection: &mut redis::aio::ConnectionManager,
+    feature_flag: FeatureFlag,
+) -> Result<(Option<FeatureFlagState>, usize), redis::RedisError> {
+    let (global_state, guild_override_count): (Option<String>, usize) = redis::pipe()
+        .hget(REDIS_FEATURE_FLAGS_KEY, feature_flag.as_str())
+        .hlen(redis_feature_flag_guilds_key(feature_flag))
+        .query_async(connection)
+        .await?;
+
+    Ok((
+        global_state.and_then(|state| FeatureFlagState::parse(state.as_str())),
+        guild_override_count,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_states() {
+        assert_eq!(FeatureFlagState::parse("ON"), Some(FeatureFlagState::On));
+        assert_eq!(
+            FeatureFlagState::parse("25%"),
+            Some(FeatureFlagState::Rollout(25))
+        );
+        assert_eq!(FeatureFlagState::parse("100%"), Some(FeatureFlagState::On));
+        assert_eq!(FeatureFlagState::parse("0%"), Some(FeatureFlagState::Off));
+        assert_eq!(FeatureFlagState::parse("101%"), None);
+        assert_eq!(FeatureFlagState::parse("25"), None);
+    }
+
+    #[test]
+    fn rolls_out_to_share_of_guilds() {
+        let enabled_count = (1..=1000)
+            .filter(|guild_id| {
+                FeatureFlagState::Rollout(30)
+                    .is_enabled_for(FeatureFlag::RenderImage, Some(GuildId::new(*guild_id)))
+            })
+            .count();
+
+        assert!((200..400).contains(&enabled_count));
+        assert!(!FeatureFlagState::Rollout(99).is_enabled_for(FeatureFlag::RenderImage, None));
+    }
+}
@@ -1,30 +1,75 @@
 use std::env;
 use std::sync::Arc;
+use std::time::Duration;
 
-use axum::http::HeaderValue;
 use bot::event_handler::Handler;
+use config::{config, reload_runtime_config, runtime_config, AccessLogFormat, ApiListener, Config};
 use once_cell::sync::Lazy;
-use serenity::all::{Cache, Http};
+use outbound::{create_redirect_policy, GuardedResolver};
+use serenity::all::{ActivityData, Cache, Http, ShardManager};
 use serenity::prelude::*;
-use tower_http::cors::CorsLayer;
-use web::api_juxtapose_url_handler;
+use tower_http::trace::TraceLayer;
+use web::access_log::{attach_access_log_request, log_response};
+use web::auth::{issue_service_token, ServiceTokenScope};
+use web::cors::create_cors_layer;
+use web::{
+    api_admin_handler, api_album_handler, api_auth_handler, api_dashboard_handler,
+    api_debug_handler, api_events_handler, api_juxtapose_card_handler, api_juxtapose_image_handler,
+    api_juxtapose_url_handler, api_stats_handler,
+};
+use web::{dashboard_handler, viewer_handler};
 
 mod bot;
+mod config;
+mod feature_flags;
+mod outbound;
+mod retry;
+mod signing;
 mod web;
 
+pub(crate) const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/117.0.0.0 Safari/537.36";
+
 pub(crate) static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
     reqwest::ClientBuilder::new()
-    .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/117.0.0.0 Safari/537.36")
-    .build()
-    .expect("Failed to build HTTP client.")
+        .user_agent(USER_AGENT)
+        .dns_resolver(Arc::new(GuardedResolver))
+        .redirect(create_redirect_policy())
+        .build()
+        .expect("Failed to build HTTP client.")
 });
 
 pub(crate) static BLAKE3_JUXTAPOSE_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
     blake3::derive_key(
         "utilBOT 2023-10-15 12:11:06 juxtapose MAC v1",
-        env::var("BLAKE3_KEY_MATERIAL")
-            .expect("BLAKE3_KEY_MATERIAL is missing.")
-            .as_bytes(),
+        config().blake3_key_material.as_bytes(),
+    )
+});
+
+pub(crate) static BLAKE3_ALBUM_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
+    blake3::derive_key(
+        "previewBOT 2026-10-17 21:08:14 album MAC v1",
+        config().blake3_key_material.as_bytes(),
+    )
+});
+
+pub(crate) static BLAKE3_CUSTOM_ID_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
+    blake3::derive_key(
+        "previewBOT 2026-10-17 12:40:21 component custom ID MAC v1",
+        config().blake3_key_material.as_bytes(),
+    )
+});
+
+pub(crate) static BLAKE3_DASHBOARD_SESSION_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
+    blake3::derive_key(
+        "previewBOT 2026-10-17 18:21:49 dashboard session ID v1",
+        config().blake3_key_material.as_bytes(),
+    )
+});
+
+pub(crate) static BLAKE3_SERVICE_TOKEN_KEY: Lazy<[u8; 32]> = Lazy::new(|| {
+    blake3::derive_key(
+        "previewBOT 2026-10-17 14:02:37 service token MAC v1",
+        config().blake3_key_material.as_bytes(),
     )
 });
 
@@ -37,6 +82,7 @@ pub struct APIJuxtaposeUrlHandlerState {
     redis_connection_manager: redis::aio::ConnectionManager,
     serenity_cache: Arc<Cache>,
     serenity_http: Arc<Http>,
+    shard_manager: Arc<ShardManager>,
 }
 
 #[tokio::main]
@@ -45,14 +91,42 @@ async fn main() {
         .inspect(|path| println!("Loaded environment variables from {}.", path.display()))
         .ok();
 
+    let config = Config::load();
+
+    /* Service Tokens */
+
+    let arguments: Vec<String> = env::args().collect();
+
+    if let Some(position) = arguments
+        .iter()
+        .position(|argument| argument == "--issue-service-token")
+    {
+        let scopes: Option<Vec<ServiceTokenScope>> = arguments
+            .get(position + 1)
+            .map(|scopes| scopes.split(',').map(ServiceTokenScope::parse).collect())
+            .unwrap_or_default();
+
+        let validity_days = arguments
+            .get(position + 2)
+            .map_or(Ok(365), |days| days.parse::<u64>());
+
+        match (scopes, validity_days) {
+            (Some(scopes), Ok(validity_days)) if !scopes.is_empty() => println!(
+                "{}",
+                issue_service_token(
+                    scopes.as_slice(),
+                    Duration::from_secs(validity_days * 24 * 60 * 60)
+                )
+            ),
+            _ => eprintln!("Usage: --issue-service-token <stats,admin,preview,events,debug> [validity in days]"),
+        }
+
+        return;
+    }
+
     /* Redis */
 
-    let redis_client = redis::Client::open(
-        env::var("REDIS_URL")
-            .as_deref()
-            .unwrap_or("redis://127.0.0.1/"),
-    )
-    .unwrap();
+    let redis_client = redis::Client::open(config.redis_url.as_str()).unwrap();
 
     let redis_connection_manager = redis::aio::ConnectionManager::new(redis_client)
         .await
@@ -60,8 +134,9 @@ async fn main() {
 
     /* Serenity */
 
-    let token = Token::from_env("BOT_TOKEN").expect("BOT_TOKEN is missing.");
-    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+    let token = config.bot_token.clone();
+    let intents =
+        GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
 
     let mut serenity_client = Client::builder(token, intents)
         .event_handler(Handler)
@@ -73,47 +148,164 @@ async fn main() {
 
     /* HTTP API */
 
-    let cors = CorsLayer::new()
-        .allow_methods([axum::http::Method::GET])
-        .allow_origin(
-            env::var("CORS_ORIGIN")
-                .as_deref()
-                .unwrap_or("*")
-                .parse::<HeaderValue>()
-                .unwrap(),
-        );
-
-    let app = axum::Router::new().route(
-        "/url",
-        axum::routing::get(api_juxtapose_url_handler::handler)
-            .with_state(APIJuxtaposeUrlHandlerState {
-                redis_connection_manager,
-                serenity_cache: serenity_client.cache.clone(),
-                serenity_http: serenity_client.http.clone(),
-            })
-            .layer(cors),
-    );
+    let cors = create_cors_layer(&config.cors_origins);
+
+    let mut app = axum::Router::new()
+        .route(
+            "/url",
+            axum::routing::get(api_juxtapose_url_handler::handler),
+        )
+        .route("/album", axum::routing::get(api_album_handler::handler))
+        .route(
+            "/image",
+            axum::routing::get(api_juxtapose_image_handler::handler),
+        )
+        .route(
+            "/card",
+            axum::routing::get(api_juxtapose_card_handler::handler),
+        )
+        .route(
+            "/stats/juxtapose/{key}",
+            axum::routing::get(api_stats_handler::juxtapose_handler),
+        )
+        .route(
+            "/stats/scan",
+            axum::routing::get(api_stats_handler::scan_handler),
+        )
+        .route(
+            "/stats/processing",
+            axum::routing::get(api_stats_handler::processing_handler),
+        )
+        .route(
+            "/stats/discord",
+            axum::routing::get(api_stats_handler::discord_handler),
+        )
+        .route(
+            "/stats/cache",
+            axum::routing::get(api_stats_handler::cache_handler),
+        )
+        .route(
+            "/debug/info",
+            axum::routing::get(api_debug_handler::info_handler),
+        )
+        .route(
+            "/events",
+            axum::routing::get(api_events_handler::events_handler),
+        )
+        .route(
+            "/auth/login",
+            axum::routing::get(api_auth_handler::login_handler),
+        )
+        .route(
+            "/auth/callback",
+            axum::routing::get(api_auth_handler::callback_handler),
+        )
+        .route(
+            "/auth/logout",
+            axum::routing::post(api_auth_handler::logout_handler),
+        )
+        .route(
+            "/guilds",
+            axum::routing::get(api_dashboard_handler::list_guilds_handler),
+        )
+        .route(
+            "/guilds/{guild_id}/config",
+            axum::routing::get(api_dashboard_handler::get_config_handler)
+                .put(api_dashboard_handler::put_config_handler),
+        )
+        .route(
+            "/guilds/{guild_id}/stats",
+            axum::routing::get(api_dashboard_handler::get_stats_handler),
+        )
+        .route(
+            "/guilds/{guild_id}/events",
+            axum::routing::get(api_events_handler::guild_events_handler),
+        )
+        .route(
+            "/admin/cache",
+            axum::routing::delete(api_admin_handler::purge_cache_handler),
+        )
+        .with_state(APIJuxtaposeUrlHandlerState {
+            redis_connection_manager,
+            serenity_cache: serenity_client.cache.clone(),
+            serenity_http: serenity_client.http.clone(),
+            shard_manager: serenity_client.shard_manager.clone(),
+        });
+
+    if config.serve_viewer {
+        app = app.route("/view", axum::routing::get(viewer_handler::handler));
+    }
+
+    if config.serve_dashboard {
+        app = app
+            .route(
+                "/dashboard",
+                axum::routing::get(dashboard_handler::redirect_handler),
+            )
+            .route(
+                "/dashboard/",
+                axum::routing::get(dashboard_handler::index_handler),
+            )
+            .route(
+                "/dashboard/{*path}",
+                axum::routing::get(dashboard_handler::file_handler),
+            );
+    }
+
+    app = app.layer(cors);
+
+    if config.access_log_format != AccessLogFormat::Disabled {
+        app = app
+            .layer(axum::middleware::from_fn(attach_access_log_request))
+            .layer(TraceLayer::new_for_http().on_response(log_response));
+    }
 
     /* Start HTTP API */
 
     tokio::spawn(async move {
-        #[cfg(unix)]
-        if let Ok(socket_path_string) = env::var("SOCKET_PATH") {
-            web::serve::serve_unix_listener(app, socket_path_string.as_str()).await;
-            return;
+        match &config.api_listener {
+            #[cfg(unix)]
+            ApiListener::UnixSocket(socket_path_string) => {
+                web::serve::serve_unix_listener(app, socket_path_string.as_str()).await
+            }
+            ApiListener::TcpPort(port) => web::serve::serve_tcp_listener(app, *port).await,
         }
+    });
 
-        if let Ok(port_string) = env::var("PORT") {
-            web::serve::serve_tcp_listener(app, port_string.as_str()).await;
-            return;
-        }
+    /* Reload Configuration */
 
-        #[cfg(unix)]
-        panic!("SOCKET_PATH or PORT must be set.");
+    #[cfg(unix)]
+    {
+        let shard_manager = serenity_client.shard_manager.clone();
 
-        #[cfg(not(unix))]
-        panic!("PORT must be set.");
-    });
+        tokio::spawn(async move {
+            let mut hangup_signal =
+                tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+                    .expect("Failed to listen for SIGHUP.");
+
+            while hangup_signal.recv().await.is_some() {
+                if let Err(errors) = reload_runtime_config() {
+                    println!(
+                        "Invalid configuration, keeping the previous one:\n{}",
+                        errors.join("\n")
+                    );
+
+                    continue;
+                }
+
+                println!("Reloaded configuration.");
+
+                let activity = runtime_config()
+                    .presence_text
+                    .as_deref()
+                    .map(ActivityData::custom);
+
+                for runner in shard_manager.runners.lock().await.values() {
+                    runner.runner_tx.set_activity(activity.clone());
+                }
+            }
+        });
+    }
 
     /* Start Serenity */
 
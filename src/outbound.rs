@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{header, redirect, RequestBuilder, Response, Url};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::{config, runtime_config};
+use crate::{HTTP_CLIENT, USER_AGENT};
+
+/// Schemes and ports that outbound requests may use.
+const ALLOWED_SCHEME_PORTS: [(&str, u16); 2] = [("https", 443), ("http", 80)];
+
+pub(crate) const MAX_REDIRECTS: usize = 5;
+
+/// Why an outbound request was refused before it was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutboundUrlError {
+    SchemeOrPort,
+    HostDenied,
+    ForbiddenAddress,
+    TooManyRedirects,
+    /// A redirect left the hosts expected by the caller.
+    UnexpectedRedirectHost,
+}
+
+impl fmt::Display for OutboundUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SchemeOrPort => "The URL uses a scheme or port that isn't allowed.",
+            Self::HostDenied => "The host of the URL isn't allowed.",
+            Self::ForbiddenAddress => "The host of the URL resolves to a private address.",
+            Self::TooManyRedirects => "The URL redirects too often.",
+            Self::UnexpectedRedirectHost => "The URL redirects to an unexpected host.",
+        })
+    }
+}
+
+impl Error for OutboundUrlError {}
+
+/// Whether the address belongs to the host itself, a private network or a reserved range,
+/// which must not be reachable through user-provided URLs.
+fn is_forbidden_ipv4(ip: Ipv4Addr) -> bool {
+    let [first_octet, second_octet, ..] = ip.octets();
+
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // 0.0.0.0/8 (this network)
+        || first_octet == 0
+        // 100.64.0.0/10 (shared address space, carrier-grade NAT)
+        || (first_octet == 100 && (64..128).contains(&second_octet))
+        // 198.18.0.0/15 (benchmarking)
+        || (first_octet == 198 && (18..20).contains(&second_octet))
+        // 240.0.0.0/4 (reserved)
+        || first_octet >= 240
+}
+
+fn is_forbidden_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return is_forbidden_ipv4(ipv4);
+    }
+
+    let segments = ip.segments();
+
+    // 64:ff9b::/96 (NAT64), which translates to the IPv4 address in the last 32 bits
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [.., high, low] = segments;
+        return is_forbidden_ipv4(Ipv4Addr::from((u32::from(high) << 16) | u32::from(low)));
+    }
+
+    let first_segment = segments[0];
+
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // fc00::/7 (unique local)
+        || (first_segment & 0xfe00) == 0xfc00
+        // fe80::/10 (link-local)
+        || (first_segment & 0xffc0) == 0xfe80
+        // 2001:db8::/32 (documentation)
+        || (first_segment == 0x2001 && segments[1] == 0x0db8)
+}
+
+pub(crate) fn is_forbidden_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ipv4) => is_forbidden_ipv4(ipv4),
+        IpAddr::V6(ipv6) => is_forbidden_ipv6(ipv6),
+    }
+}
+
+/// Matches the host itself and all of its subdomains.
+fn matches_host(host: &str, pattern: &str) -> bool {
+    host == pattern
+        || host
+            .strip_suffix(pattern)
+            .is_some_and(|subdomain| subdomain.ends_with('.'))
+}
+
+/// Checks the scheme, port and host of a URL against the allow and deny lists.
+/// Addresses of domains are checked when they are resolved, see [`GuardedResolver`].
+pub(crate) fn check_outbound_url(url: &Url) -> Result<(), OutboundUrlError> {
+    let scheme_port = (
+        url.scheme(),
+        url.port_or_known_default().unwrap_or_default(),
+    );
+
+    if !ALLOWED_SCHEME_PORTS.contains(&scheme_port) {
+        return Err(OutboundUrlError::SchemeOrPort);
+    }
+
+    let host = url
+        .host_str()
+        .ok_or(OutboundUrlError::HostDenied)?
+        .to_ascii_lowercase();
+
+    // IP addresses aren't resolved, IPv6 addresses are enclosed in brackets.
+    if host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .is_ok_and(is_forbidden_ip)
+    {
+        return Err(OutboundUrlError::ForbiddenAddress);
+    }
+
+    let runtime_config = runtime_config();
+
+    if runtime_config
+        .outbound_denied_hosts
+        .iter()
+        .any(|pattern| matches_host(&host, pattern))
+        || (!runtime_config.outbound_allowed_hosts.is_empty()
+            && !runtime_config
+                .outbound_allowed_hosts
+                .iter()
+                .any(|pattern| matches_host(&host, pattern)))
+    {
+        return Err(OutboundUrlError::HostDenied);
+    }
+
+    Ok(())
+}
+
+/// Follows at most [`MAX_REDIRECTS`] redirects, checking every hop like the initial URL.
+pub(crate) fn create_redirect_policy() -> redirect::Policy {
+    redirect::Policy::custom(|attempt| {
+        if attempt.previous().len() > MAX_REDIRECTS {
+            return attempt.error(OutboundUrlError::TooManyRedirects);
+        }
+
+        match check_outbound_url(attempt.url()) {
+            Ok(()) => attempt.follow(),
+            Err(error) => attempt.error(error),
+        }
+    })
+}
+
+/// Resolves domains using the system resolver, dropping forbidden addresses,
+/// so that a domain can't point requests at internal services.
+pub(crate) struct GuardedResolver;
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| !is_forbidden_ip(address.ip()))
+                .collect();
+
+            if addresses.is_empty() {
+                return Err(OutboundUrlError::ForbiddenAddress.into());
+            }
+
+            let addresses: Addrs = Box::new(addresses.into_iter());
+            Ok(addresses)
+        })
+    }
+}
+
+/// Requests in flight per host, created on demand and dropped once no request to the host is in flight.
+static HOST_SEMAPHORES: Lazy<Mutex<HashMap<String, Arc<Semaphore>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Waits until fewer than `OUTBOUND_MAX_CONCURRENCY_PER_HOST` requests to the host of the URL are in flight.
+async fn acquire_host_permit(url: &Url) -> OwnedSemaphorePermit {
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+
+    let semaphore = {
+        let mut host_semaphores = HOST_SEMAPHORES.lock().unwrap();
+
+        // Permits hold a reference to their semaphore, so unused semaphores are only referenced by the map.
+        host_semaphores.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+
+        host_semaphores
+            .entry(host)
+            .or_insert_with(|| {
+                Arc::new(Semaphore::new(
+                    config().outbound_max_concurrency_per_host as usize,
+                ))
+            })
+            .clone()
+    };
+
+    // The semaphore is never closed.
+    semaphore.acquire_owned().await.unwrap()
+}
+
+/// Sends a request once the limit of concurrent requests to its host allows it, so that a message
+/// with many links, or many messages at once, don't trip the abuse detection of upstream hosts.
+/// The limit applies until the response headers arrive, including redirects followed by the client.
+pub(crate) async fn send_limited(request: RequestBuilder) -> reqwest::Result<Response> {
+    let (client, request) = request.build_split();
+    let request = request?;
+
+    let _permit = acquire_host_permit(request.url()).await;
+
+    client.execute(request).await
+}
+
+/// Creates a GET request to a URL that may have been provided by a user, after checking it.
+pub(crate) fn guarded_get(url: Url) -> Result<RequestBuilder, OutboundUrlError> {
+    check_outbound_url(&url)?;
+    Ok(HTTP_CLIENT.get(url))
+}
+
+/// Returns redirects to the caller instead of following them.
+static NO_REDIRECT_HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::ClientBuilder::new()
+        .user_agent(USER_AGENT)
+        .dns_resolver(Arc::new(GuardedResolver))
+        .redirect(redirect::Policy::none())
+        .build()
+        .expect("Failed to build HTTP client.")
+});
+
+/// Sends a GET request, following at most [`MAX_REDIRECTS`] redirects explicitly.
+/// Every hop must pass [`check_outbound_url`] and stay within the given hosts, including their subdomains.
+pub(crate) async fn get_following_redirects(
+    mut url: Url,
+    allowed_hosts: &[&str],
+) -> Result<Response, Box<dyn Error + Send + Sync>> {
+    for _ in 0..=MAX_REDIRECTS {
+        check_outbound_url(&url)?;
+
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+
+        if !allowed_hosts
+            .iter()
+            .any(|allowed_host| matches_host(&host, allowed_host))
+        {
+            return Err(OutboundUrlError::UnexpectedRedirectHost.into());
+        }
+
+        let response = send_limited(NO_REDIRECT_HTTP_CLIENT.get(url.clone())).await?;
+
+        if !response.status().is_redirection() {
+            return Ok(response);
+        }
+
+        let location = response
+            .headers()
+            .get(header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .ok_or("The redirect doesn't have a location.")?;
+
+        url = url.join(location)?;
+    }
+
+    Err(OutboundUrlError::TooManyRedirects.into())
+}
@@ -0,0 +1,16 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Returns a random number taken from the randomly seeded [`RandomState`] to avoid pulling in an RNG.
+/// Good enough for jitter and sampling, but not for anything security-related.
+pub(crate) fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// Exponential backoff with full jitter: a random delay between zero and `base * 2^attempt`.
+pub(crate) fn jittered_backoff(base: Duration, attempt: u32) -> Duration {
+    let max_delay = base.saturating_mul(2u32.saturating_pow(attempt));
+
+    max_delay.mul_f64((random_u64() as f64) / (u64::MAX as f64))
+}
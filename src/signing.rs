@@ -0,0 +1,254 @@
+use std::mem::size_of;
+
+use base64::{engine::general_purpose, Engine};
+use serenity::all::{ChannelId, MessageId};
+
+/// Length of the truncated BLAKE3 MAC in bytes.
+const MAC_LENGTH: usize = 16;
+
+/// Length of the encoded message and channel IDs in bytes.
+const DATA_LENGTH: usize = 2 * size_of::<u64>();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SignedPayloadError {
+    MalformedData,
+    MalformedMac,
+    InvalidMac,
+}
+
+/// A payload in the `d` and `m` parameters of viewer URLs, both encoded as unpadded URL-safe base64.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EncodedSignedPayload {
+    pub(crate) data: String,
+    pub(crate) mac: String,
+}
+
+/// Reference to a message created by the bot, such as a juxtapose or an album, which is signed so that
+/// viewer URLs can't be forged to make the bot fetch arbitrary messages.
+/// The key determines the kind of message, as every kind encodes the same data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SignedPayload {
+    pub(crate) message_id: MessageId,
+    pub(crate) channel_id: ChannelId,
+}
+
+fn compute_mac(key: &[u8; 32], data_bytes: &[u8]) -> [u8; MAC_LENGTH] {
+    let mut mac = [0u8; MAC_LENGTH];
+
+    blake3::Hasher::new_keyed(key)
+        .update(data_bytes)
+        .finalize_xof()
+        .fill(&mut mac);
+
+    mac
+}
+
+/// Rejects IDs that aren't valid snowflakes, as constructing them would panic.
+fn parse_id(id_bytes: &[u8]) -> Result<u64, SignedPayloadError> {
+    let id = u64::from_le_bytes(
+        id_bytes
+            .try_into()
+            .map_err(|_| SignedPayloadError::MalformedData)?,
+    );
+
+    if id == 0 || id == u64::MAX {
+        return Err(SignedPayloadError::MalformedData);
+    }
+
+    Ok(id)
+}
+
+impl SignedPayload {
+    pub(crate) fn new(message_id: MessageId, channel_id: ChannelId) -> Self {
+        Self {
+            message_id,
+            channel_id,
+        }
+    }
+
+    fn to_bytes(self) -> [u8; DATA_LENGTH] {
+        let mut data_bytes = [0u8; DATA_LENGTH];
+        data_bytes[..size_of::<u64>()].copy_from_slice(&self.message_id.get().to_le_bytes());
+        data_bytes[size_of::<u64>()..].copy_from_slice(&self.channel_id.get().to_le_bytes());
+
+        data_bytes
+    }
+
+    fn from_bytes(data_bytes: &[u8]) -> Result<Self, SignedPayloadError> {
+        if data_bytes.len() != DATA_LENGTH {
+            return Err(SignedPayloadError::MalformedData);
+        }
+
+        let (message_id_bytes, channel_id_bytes) = data_bytes.split_at(size_of::<u64>());
+
+        Ok(Self {
+            message_id: MessageId::new(parse_id(message_id_bytes)?),
+            channel_id: ChannelId::new(parse_id(channel_id_bytes)?),
+        })
+    }
+
+    pub(crate) fn encode(self, key: &[u8; 32]) -> EncodedSignedPayload {
+        let data_bytes = self.to_bytes();
+
+        EncodedSignedPayload {
+            data: general_purpose::URL_SAFE_NO_PAD.encode(data_bytes),
+            mac: general_purpose::URL_SAFE_NO_PAD.encode(compute_mac(key, &data_bytes)),
+        }
+    }
+
+    /// Verifies the MAC in constant time before the data is parsed.
+    pub(crate) fn decode(
+        key: &[u8; 32],
+        data: &str,
+        mac: &str,
+    ) -> Result<Self, SignedPayloadError> {
+        let data_bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(data)
+            .map_err(|_| SignedPayloadError::MalformedData)?;
+
+        let mac_bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(mac)
+            .map_err(|_| SignedPayloadError::MalformedMac)?;
+
+        let mac_bytes: &[u8; MAC_LENGTH] = mac_bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| SignedPayloadError::MalformedMac)?;
+
+        if !constant_time_eq::constant_time_eq_16(mac_bytes, &compute_mac(key, &data_bytes)) {
+            return Err(SignedPayloadError::InvalidMac);
+        }
+
+        Self::from_bytes(data_bytes.as_slice())
+    }
+
+    /// Decodes the data without verifying it, which is only acceptable for data the bot stored itself,
+    /// such as the keys of the API cache.
+    pub(crate) fn decode_trusted_data(data: &str) -> Result<Self, SignedPayloadError> {
+        let data_bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(data)
+            .map_err(|_| SignedPayloadError::MalformedData)?;
+
+        Self::from_bytes(data_bytes.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    const KEY: [u8; 32] = [7; 32];
+    const OTHER_KEY: [u8; 32] = [8; 32];
+
+    fn snowflake() -> impl Strategy<Value = u64> {
+        1..u64::MAX
+    }
+
+    fn payload() -> impl Strategy<Value = SignedPayload> {
+        (snowflake(), snowflake()).prop_map(|(message_id, channel_id)| {
+            SignedPayload::new(MessageId::new(message_id), ChannelId::new(channel_id))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips(payload in payload()) {
+            let encoded = payload.encode(&KEY);
+
+            prop_assert_eq!(
+                SignedPayload::decode(&KEY, &encoded.data, &encoded.mac),
+                Ok(payload)
+            );
+            prop_assert_eq!(SignedPayload::decode_trusted_data(&encoded.data), Ok(payload));
+        }
+
+        #[test]
+        fn keeps_the_data_layout_of_existing_urls(payload in payload()) {
+            let data_bytes = general_purpose::URL_SAFE_NO_PAD
+                .decode(payload.encode(&KEY).data)
+                .unwrap();
+
+            prop_assert_eq!(
+                data_bytes,
+                [
+                    payload.message_id.get().to_le_bytes(),
+                    payload.channel_id.get().to_le_bytes(),
+                ]
+                .concat()
+            );
+        }
+
+        #[test]
+        fn rejects_other_keys(payload in payload()) {
+            let encoded = payload.encode(&KEY);
+
+            prop_assert_eq!(
+                SignedPayload::decode(&OTHER_KEY, &encoded.data, &encoded.mac),
+                Err(SignedPayloadError::InvalidMac)
+            );
+        }
+
+        #[test]
+        fn rejects_tampered_data(
+            payload in payload(),
+            byte_index in 0..DATA_LENGTH,
+            bit in 0..8u8,
+        ) {
+            let encoded = payload.encode(&KEY);
+
+            let mut data_bytes = general_purpose::URL_SAFE_NO_PAD.decode(&encoded.data).unwrap();
+            data_bytes[byte_index] ^= 1 << bit;
+            let tampered_data = general_purpose::URL_SAFE_NO_PAD.encode(data_bytes);
+
+            prop_assert_eq!(
+                SignedPayload::decode(&KEY, &tampered_data, &encoded.mac),
+                Err(SignedPayloadError::InvalidMac)
+            );
+        }
+
+        #[test]
+        fn never_panics_on_arbitrary_input(data in ".*", mac in ".*") {
+            let _ = SignedPayload::decode(&KEY, &data, &mac);
+            let _ = SignedPayload::decode_trusted_data(&data);
+        }
+
+        #[test]
+        fn never_panics_on_arbitrary_signed_bytes(data_bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            // A valid MAC for arbitrary bytes reaches the parser, as if the key had leaked.
+            let data = general_purpose::URL_SAFE_NO_PAD.encode(&data_bytes);
+            let mac = general_purpose::URL_SAFE_NO_PAD.encode(compute_mac(&KEY, &data_bytes));
+
+            let result = SignedPayload::decode(&KEY, &data, &mac);
+
+            if data_bytes.len() != DATA_LENGTH {
+                prop_assert_eq!(result, Err(SignedPayloadError::MalformedData));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_snowflakes() {
+        for (message_id, channel_id) in [(0, 1), (1, 0), (u64::MAX, 1), (1, u64::MAX)] {
+            let data_bytes = [message_id.to_le_bytes(), channel_id.to_le_bytes()].concat();
+
+            assert_eq!(
+                SignedPayload::from_bytes(&data_bytes),
+                Err(SignedPayloadError::MalformedData)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_macs() {
+        let encoded = SignedPayload::new(MessageId::new(1), ChannelId::new(2)).encode(&KEY);
+
+        for mac in ["", "not base64!", "AAAA", &encoded.mac[1..]] {
+            assert_eq!(
+                SignedPayload::decode(&KEY, &encoded.data, mac),
+                Err(SignedPayloadError::MalformedMac)
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use tracing::Span;
+
+use crate::config::{config, AccessLogFormat};
+
+/// Response extension set by handlers that are backed by the Redis cache.
+#[derive(Clone, Copy)]
+pub(crate) enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+impl CacheStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hit => "hit",
+            Self::Miss => "miss",
+        }
+    }
+}
+
+/// Prefers the headers set by a reverse proxy, as the peer address is only available for TCP listeners.
+fn get_client_ip(request: &Request) -> Option<String> {
+    let headers = request.headers();
+
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+        })
+        .map(|client_ip| client_ip.trim().to_owned())
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(address)| address.ip().to_string())
+        })
+}
+
+/// The details of a request that are logged, carried to [`log_response`] in the extensions of the response.
+#[derive(Clone)]
+struct AccessLogRequest {
+    method: Method,
+    path: String,
+    client_ip: Option<String>,
+}
+
+/// Attaches the details of the request to its response, as the `TraceLayer` only passes the response to [`log_response`].
+pub(crate) async fn attach_access_log_request(request: Request, next: Next) -> Response {
+    let access_log_request = AccessLogRequest {
+        method: request.method().clone(),
+        path: request.uri().path().to_owned(),
+        client_ip: get_client_ip(&request),
+    };
+
+    let mut response = next.run(request).await;
+    response.extensions_mut().insert(access_log_request);
+
+    response
+}
+
+/// Writes the access log entry of a response, used as the `on_response` callback of the `TraceLayer`.
+pub(crate) fn log_response(response: &Response, latency: Duration, _span: &Span) {
+    let Some(AccessLogRequest {
+        method,
+        path,
+        client_ip,
+    }) = response.extensions().get::<AccessLogRequest>()
+    else {
+        return;
+    };
+
+    let cache_status = response
+        .extensions()
+        .get::<CacheStatus>()
+        .map(CacheStatus::as_str);
+
+    match config().access_log_format {
+        AccessLogFormat::Disabled => {}
+        AccessLogFormat::Text => println!(
+            "{} {} {} {:.2}ms client={} cache={}",
+            method,
+            path,
+            response.status().as_u16(),
+            latency.as_secs_f64() * 1000.0,
+            client_ip.as_deref().unwrap_or("-"),
+            cache_status.unwrap_or("-"),
+        ),
+        AccessLogFormat::Json => println!(
+            "{}",
+            serde_json::json!({
+                "method": method.as_str(),
+                "path": path,
+                "status": response.status().as_u16(),
+                "latency_ms": latency.as_secs_f64() * 1000.0,
+                "client_ip": client_ip,
+                "cache": cache_status,
+            })
+        ),
+    }
+}
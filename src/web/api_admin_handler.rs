@@ -0,0 +1,44 @@
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::APIJuxtaposeUrlHandlerState;
+
+use super::auth::{require_scope, ServiceTokenScope};
+use super::cache_purge::redis_purge_cache;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct APIPurgeCacheRequest {
+    pattern: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIPurgeCacheResponse {
+    deleted: usize,
+}
+
+pub(crate) async fn purge_cache_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Query(params): Query<APIPurgeCacheRequest>,
+    headers: HeaderMap,
+) -> Result<Json<APIPurgeCacheResponse>, (StatusCode, String)> {
+    require_scope(&headers, ServiceTokenScope::Admin)
+        .map_err(|status_code| (status_code, status_code.to_string()))?;
+
+    let deleted = redis_purge_cache(&mut redis_connection_manager, params.pattern.as_str())
+        .await
+        .map_err(|error| (StatusCode::BAD_REQUEST, error))?;
+
+    println!(
+        "Purged {} cache entries matching {} via the HTTP API.",
+        deleted, params.pattern
+    );
+
+    Ok(Json(APIPurgeCacheResponse { deleted }))
+}
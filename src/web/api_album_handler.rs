@@ -0,0 +1,94 @@
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
+use serenity::all::{Http, UserId};
+
+use crate::signing::SignedPayload;
+use crate::{APIJuxtaposeUrlHandlerState, BLAKE3_ALBUM_KEY};
+
+use super::{
+    access_log::CacheStatus,
+    api_album_response::{APIAlbumImage, APIAlbumPayload, APIAlbumResponse, ALBUM_MAX_IMAGES},
+    api_juxtapose_request::APIJuxtaposeRequest,
+    api_juxtapose_response::APIJuxtaposeResponse,
+};
+
+/// Builds the response from the attachments of the album message, bypassing the cache.
+/// The title of the album is the content of the message.
+pub(crate) async fn fetch_album_response(
+    serenity_http: &Http,
+    current_user_id: UserId,
+    payload: SignedPayload,
+) -> Result<APIAlbumResponse, StatusCode> {
+    let album_message = serenity_http
+        .get_message(payload.channel_id, payload.message_id)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    if album_message.author.id != current_user_id {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let images: Vec<APIAlbumImage> = album_message
+        .attachments
+        .iter()
+        .take(ALBUM_MAX_IMAGES)
+        .map(|attachment| APIAlbumImage {
+            url: attachment.url.to_string(),
+            label: attachment.description.as_ref().map(ToString::to_string),
+        })
+        .collect();
+
+    if images.is_empty() {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    Ok(APIAlbumResponse {
+        title: Some(album_message.content.to_string()).filter(|title| !title.is_empty()),
+        images,
+    })
+}
+
+pub(crate) async fn handler(
+    State(APIJuxtaposeUrlHandlerState {
+        serenity_http,
+        serenity_cache,
+        mut redis_connection_manager,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Query(params): Query<APIJuxtaposeRequest>,
+) -> Result<(HeaderMap, Extension<CacheStatus>, impl IntoResponse), StatusCode> {
+    let payload = params.verify(&BLAKE3_ALBUM_KEY)?;
+
+    let (response_data, expire_unix_ts, cache_status) = if let Some(response_data) =
+        APIAlbumResponse::redis_cache_get_data(&mut redis_connection_manager, params.data.as_str())
+            .await
+    {
+        let expire_unix_ts = APIAlbumResponse::redis_cache_get_expire(
+            &mut redis_connection_manager,
+            params.data.as_str(),
+        )
+        .await?;
+
+        (response_data, expire_unix_ts as u64, CacheStatus::Hit)
+    } else {
+        let current_user_id = serenity_cache.current_user().id;
+
+        let response_data = fetch_album_response(&serenity_http, current_user_id, payload).await?;
+
+        let expire_unix_ts = response_data
+            .redis_cache_set(&mut redis_connection_manager, params.data.as_str())
+            .await?;
+
+        (response_data, expire_unix_ts as u64, CacheStatus::Miss)
+    };
+
+    Ok((
+        APIJuxtaposeResponse::get_cache_headers(expire_unix_ts),
+        Extension(cache_status),
+        Json(APIAlbumPayload::from(response_data)),
+    ))
+}
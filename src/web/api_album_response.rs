@@ -0,0 +1,145 @@
+use std::error::Error;
+
+use axum::http::StatusCode;
+use redis::AsyncCommands;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// An album can't contain more images than a single message can hold attachments.
+pub(crate) const ALBUM_MAX_IMAGES: usize = 10;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct APIAlbumImage {
+    pub(crate) url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) label: Option<String>,
+}
+
+/// The images of an album in the order they were submitted in, for gallery and slider viewers.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct APIAlbumResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) title: Option<String>,
+    pub(crate) images: Vec<APIAlbumImage>,
+}
+
+/// Versioned form of the album data, used both in the API cache and as the response of `/album`.
+/// Follows the same rules as [`APIJuxtaposePayload`](super::api_juxtapose_response::APIJuxtaposePayload).
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "version")]
+pub(crate) enum APIAlbumPayload {
+    #[serde(rename = "1")]
+    V1(APIAlbumResponse),
+}
+
+impl APIAlbumPayload {
+    pub(crate) fn into_latest(self) -> APIAlbumResponse {
+        match self {
+            Self::V1(response_data) => response_data,
+        }
+    }
+}
+
+impl From<APIAlbumResponse> for APIAlbumPayload {
+    fn from(response_data: APIAlbumResponse) -> Self {
+        Self::V1(response_data)
+    }
+}
+
+fn get_url_expire_unix_ts(url: &str) -> Result<i64, Box<dyn Error + Send + Sync>> {
+    Ok(i64::from_str_radix(
+        &Url::parse(url)?
+            .query_pairs()
+            .find(|(key, _)| key == "ex")
+            .ok_or("Expire parameter of URL not found.")?
+            .1,
+        16,
+    )?)
+}
+
+impl APIAlbumResponse {
+    fn redis_cache_key(key: &str) -> String {
+        format!("album_payload:{}", key)
+    }
+
+    /// The cache entry expires together with the first of the image URLs.
+    fn get_expire_unix_ts(&self) -> Result<i64, Box<dyn Error + Send + Sync>> {
+        let expire_unix_timestamps = self
+            .images
+            .iter()
+            .map(|image| get_url_expire_unix_ts(image.url.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        expire_unix_timestamps
+            .into_iter()
+            .min()
+            .ok_or_else(|| "Album contains no images.".into())
+    }
+
+    pub(crate) async fn redis_cache_set(
+        &self,
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) -> Result<i64, StatusCode> {
+        let payload = serde_json::to_string(&APIAlbumPayload::from(self.clone()))
+            .expect("Failed to serialize album payload.");
+
+        let unix_ts = self.get_expire_unix_ts().map_err(|err| {
+            println!("Error while getting expire timestamp: {:?}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        let cache_key = Self::redis_cache_key(key);
+
+        let _: () = redis::pipe()
+            .atomic()
+            .set(&cache_key, payload)
+            .ignore()
+            .expire_at(&cache_key, unix_ts)
+            .ignore()
+            .query_async(connection)
+            .await
+            .map_err(|err| {
+                println!("Error while setting album cache: {:?}", err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+        Ok(unix_ts)
+    }
+
+    pub(crate) async fn redis_cache_delete(
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) -> Result<usize, redis::RedisError> {
+        connection.del(Self::redis_cache_key(key)).await
+    }
+
+    pub(crate) async fn redis_cache_get_data(
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) -> Option<Self> {
+        let payload = connection
+            .get::<String, Option<String>>(Self::redis_cache_key(key))
+            .await
+            .ok()??;
+
+        serde_json::from_str::<APIAlbumPayload>(payload.as_str())
+            .inspect_err(|err| println!("Error while parsing album payload: {:?}", err))
+            .ok()
+            .map(APIAlbumPayload::into_latest)
+    }
+
+    pub(crate) async fn redis_cache_get_expire(
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) -> Result<usize, StatusCode> {
+        redis::cmd("EXPIRETIME")
+            .arg(Self::redis_cache_key(key))
+            .query_async(connection)
+            .await
+            .map_err(|err| {
+                println!("Error while getting expire timestamp: {:?}", err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })
+    }
+}
@@ -0,0 +1,155 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{AppendHeaders, IntoResponse, Redirect},
+};
+use redis::AsyncCommands;
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::config::config;
+use crate::APIJuxtaposeUrlHandlerState;
+
+use super::dashboard_auth::redis_dashboard_user_key;
+use super::dashboard_session::{
+    create_cookie, dashboard_oauth2_config, generate_unguessable_id, get_cookie, request_tokens,
+    revoke_token, DashboardSession, SESSION_COOKIE_NAME, SESSION_TTL, STATE_COOKIE_NAME,
+};
+
+/// The login has to be completed within this time.
+const STATE_COOKIE_MAX_AGE: u64 = 10 * 60;
+
+/// Redirects to the Discord authorization page, binding the request to the browser using the state cookie.
+pub(crate) async fn login_handler() -> Result<impl IntoResponse, StatusCode> {
+    let oauth2_config = dashboard_oauth2_config()?;
+
+    let state = generate_unguessable_id(b"state");
+
+    let mut authorize_url = Url::parse("https://discord.com/oauth2/authorize").unwrap();
+    authorize_url
+        .query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", oauth2_config.client_id.as_str())
+        .append_pair("scope", "identify guilds")
+        .append_pair("redirect_uri", oauth2_config.redirect_url.as_str())
+        .append_pair("state", state.as_str())
+        .append_pair("prompt", "none");
+
+    Ok((
+        AppendHeaders([(
+            header::SET_COOKIE,
+            create_cookie(STATE_COOKIE_NAME, state.as_str(), STATE_COOKIE_MAX_AGE),
+        )]),
+        Redirect::to(authorize_url.as_str()),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CallbackParams {
+    code: String,
+    state: String,
+}
+
+/// Exchanges the authorization code for tokens and starts a session.
+pub(crate) async fn callback_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Query(params): Query<CallbackParams>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, StatusCode> {
+    let oauth2_config = dashboard_oauth2_config()?;
+
+    let expected_state = get_cookie(&headers, STATE_COOKIE_NAME).ok_or(StatusCode::BAD_REQUEST)?;
+
+    if !constant_time_eq::constant_time_eq(expected_state.as_bytes(), params.state.as_bytes()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let session: DashboardSession = request_tokens(&[
+        ("grant_type", "authorization_code"),
+        ("code", params.code.as_str()),
+        ("redirect_uri", oauth2_config.redirect_url.as_str()),
+    ])
+    .await?
+    .into();
+
+    let session_id = generate_unguessable_id(params.code.as_bytes());
+
+    session
+        .redis_set(&mut redis_connection_manager, session_id.as_str())
+        .await
+        .map_err(|err| {
+            println!("Error while setting dashboard session: {:?}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // The callback route is located at `/auth/callback` relative to the root of the HTTP API.
+    let root_url = oauth2_config
+        .redirect_url
+        .join(if config().serve_dashboard {
+            "../dashboard/"
+        } else {
+            ".."
+        })
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((
+        AppendHeaders([
+            (
+                header::SET_COOKIE,
+                create_cookie(SESSION_COOKIE_NAME, session_id.as_str(), SESSION_TTL),
+            ),
+            (header::SET_COOKIE, create_cookie(STATE_COOKIE_NAME, "", 0)),
+        ]),
+        Redirect::to(root_url.as_str()),
+    ))
+}
+
+/// Revokes the tokens of the session at Discord and deletes the session.
+pub(crate) async fn logout_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, StatusCode> {
+    dashboard_oauth2_config()?;
+
+    let session_id = get_cookie(&headers, SESSION_COOKIE_NAME).ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if let Some(session) = DashboardSession::redis_get(&mut redis_connection_manager, session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        // Revoking is best-effort, the session is deleted either way.
+        if let Err(status_code) =
+            revoke_token(session.refresh_token.as_str(), "refresh_token").await
+        {
+            println!("Failed to revoke dashboard refresh token: {}", status_code);
+        }
+
+        if let Err(status_code) = revoke_token(session.access_token.as_str(), "access_token").await
+        {
+            println!("Failed to revoke dashboard access token: {}", status_code);
+        }
+
+        let _: () = redis_connection_manager
+            .del(redis_dashboard_user_key(session.access_token.as_str()))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        DashboardSession::redis_delete(&mut redis_connection_manager, session_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok((
+        AppendHeaders([(
+            header::SET_COOKIE,
+            create_cookie(SESSION_COOKIE_NAME, "", 0),
+        )]),
+        StatusCode::NO_CONTENT,
+    ))
+}
@@ -0,0 +1,147 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Serialize;
+use serenity::all::GuildId;
+
+use crate::bot::audit_log::{send_audit_log, AuditLogEntry};
+use crate::bot::guild_config::GuildConfig;
+use crate::bot::guild_stats::{redis_get_guild_stats, GuildStats};
+use crate::APIJuxtaposeUrlHandlerState;
+
+use super::dashboard_auth::{get_dashboard_user, require_guild_admin};
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIDashboardGuild {
+    id: GuildId,
+    name: String,
+}
+
+/// Lists the guilds the dashboard user may manage the bot in, omitting those the bot is not a member of.
+pub(crate) async fn list_guilds_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        serenity_cache,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<APIDashboardGuild>>, StatusCode> {
+    let dashboard_user = get_dashboard_user(&headers, &mut redis_connection_manager).await?;
+
+    let guilds = dashboard_user
+        .admin_guild_ids
+        .into_iter()
+        .filter_map(|guild_id| {
+            serenity_cache
+                .guild(guild_id)
+                .map(|guild| APIDashboardGuild {
+                    id: guild_id,
+                    name: guild.name.to_string(),
+                })
+        })
+        .collect();
+
+    Ok(Json(guilds))
+}
+
+pub(crate) async fn get_config_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        serenity_cache,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Path(guild_id): Path<GuildId>,
+    headers: HeaderMap,
+) -> Result<Json<GuildConfig>, StatusCode> {
+    require_guild_admin(
+        &headers,
+        &mut redis_connection_manager,
+        &serenity_cache,
+        guild_id,
+    )
+    .await?;
+
+    let guild_config = GuildConfig::redis_get(&mut redis_connection_manager, guild_id)
+        .await
+        .map_err(|err| {
+            println!("Error while getting guild config: {:?}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(guild_config))
+}
+
+pub(crate) async fn put_config_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        serenity_cache,
+        serenity_http,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Path(guild_id): Path<GuildId>,
+    headers: HeaderMap,
+    Json(guild_config): Json<GuildConfig>,
+) -> Result<Json<GuildConfig>, (StatusCode, String)> {
+    let user_id = require_guild_admin(
+        &headers,
+        &mut redis_connection_manager,
+        &serenity_cache,
+        guild_id,
+    )
+    .await
+    .map_err(|status_code| (status_code, String::new()))?;
+
+    guild_config
+        .validate()
+        .map_err(|error| (StatusCode::UNPROCESSABLE_ENTITY, error))?;
+
+    guild_config
+        .redis_set(&mut redis_connection_manager, guild_id)
+        .await
+        .map_err(|err| {
+            println!("Error while setting guild config: {:?}", err);
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        })?;
+
+    send_audit_log(
+        &serenity_http,
+        Some(guild_id),
+        &guild_config,
+        AuditLogEntry::ConfigChanged {
+            changed_by_id: user_id,
+            change: "dashboard",
+        },
+    )
+    .await;
+
+    Ok(Json(guild_config))
+}
+
+pub(crate) async fn get_stats_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        serenity_cache,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Path(guild_id): Path<GuildId>,
+    headers: HeaderMap,
+) -> Result<Json<GuildStats>, StatusCode> {
+    require_guild_admin(
+        &headers,
+        &mut redis_connection_manager,
+        &serenity_cache,
+        guild_id,
+    )
+    .await?;
+
+    let guild_stats = redis_get_guild_stats(&mut redis_connection_manager, guild_id)
+        .await
+        .map_err(|err| {
+            println!("Error while getting guild stats: {:?}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(guild_stats))
+}
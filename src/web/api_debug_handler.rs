@@ -0,0 +1,188 @@
+use std::time::Instant;
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use reqwest::Url;
+use serde::Serialize;
+
+use crate::config::{config, runtime_config, AccessLogFormat, ApiListener, CorsOrigins};
+use crate::feature_flags::{get_default_feature_flag_state, FeatureFlag};
+use crate::APIJuxtaposeUrlHandlerState;
+
+use super::auth::{require_scope, ServiceTokenScope};
+
+/// Replaces secrets in the response, which only tells whether they are set.
+const MASKED_SECRET: &str = "********";
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIDebugBuild {
+    version: &'static str,
+    git_sha: &'static str,
+    rustc_version: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIDebugFeatures {
+    serve_viewer: bool,
+    serve_dashboard: bool,
+    dashboard_login: bool,
+    github_token: bool,
+    topgg_posting: bool,
+    discords_posting: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIDebugShard {
+    id: u32,
+    stage: String,
+    latency_ms: Option<u128>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIDebugConfig {
+    redis_url: String,
+    api_listener: String,
+    cors_origins: String,
+    access_log_format: &'static str,
+    bot_token: &'static str,
+    blake3_key_material: &'static str,
+    github_token: Option<&'static str>,
+    discord_client_secret: Option<&'static str>,
+    backfill_on_join_limit: u8,
+    outbound_allowed_hosts: Vec<String>,
+    outbound_denied_hosts: Vec<String>,
+    outbound_max_concurrency_per_host: u8,
+    /// Default states of all features in the format of `FEATURE_FLAGS`, without the overrides in Redis.
+    feature_flags: Vec<String>,
+    juxtapose_base_url: String,
+    album_base_url: String,
+    preview_max_lines: u32,
+    language_alias_count: usize,
+    screening_blocklist_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIDebugInfoResponse {
+    build: APIDebugBuild,
+    features: APIDebugFeatures,
+    shards: Vec<APIDebugShard>,
+    /// Round trip of a `PING`, missing if Redis didn't respond.
+    redis_latency_ms: Option<f64>,
+    config: APIDebugConfig,
+}
+
+/// Masks the password of a URL, e.g. of a Redis server requiring authentication.
+fn mask_url_password(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some(MASKED_SECRET));
+            url.to_string()
+        }
+        Ok(_) => url.to_owned(),
+        Err(_) => MASKED_SECRET.to_owned(),
+    }
+}
+
+fn get_config_summary() -> APIDebugConfig {
+    let config = config();
+    let runtime_config = runtime_config();
+
+    APIDebugConfig {
+        redis_url: mask_url_password(config.redis_url.as_str()),
+        api_listener: match &config.api_listener {
+            #[cfg(unix)]
+            ApiListener::UnixSocket(socket_path) => format!("unix:{}", socket_path),
+            ApiListener::TcpPort(port) => format!("tcp:{}", port),
+        },
+        cors_origins: match &config.cors_origins {
+            CorsOrigins::Any => "*".to_owned(),
+            CorsOrigins::List(patterns) => format!("{} origins", patterns.len()),
+        },
+        access_log_format: match config.access_log_format {
+            AccessLogFormat::Disabled => "off",
+            AccessLogFormat::Text => "text",
+            AccessLogFormat::Json => "json",
+        },
+        bot_token: MASKED_SECRET,
+        blake3_key_material: MASKED_SECRET,
+        github_token: config.github_token.as_ref().map(|_| MASKED_SECRET),
+        discord_client_secret: config.dashboard_oauth2.as_ref().map(|_| MASKED_SECRET),
+        backfill_on_join_limit: config.backfill_on_join_limit,
+        outbound_allowed_hosts: runtime_config.outbound_allowed_hosts.clone(),
+        outbound_denied_hosts: runtime_config.outbound_denied_hosts.clone(),
+        outbound_max_concurrency_per_host: config.outbound_max_concurrency_per_host,
+        feature_flags: FeatureFlag::ALL
+            .into_iter()
+            .map(|feature_flag| {
+                format!(
+                    "{}={}",
+                    feature_flag.as_str(),
+                    get_default_feature_flag_state(feature_flag)
+                )
+            })
+            .collect(),
+        juxtapose_base_url: runtime_config.juxtapose_base_url.to_string(),
+        album_base_url: runtime_config.album_base_url.to_string(),
+        preview_max_lines: runtime_config.preview_max_lines,
+        language_alias_count: runtime_config.language_aliases.len(),
+        screening_blocklist_count: runtime_config.screening_blocklist.len(),
+    }
+}
+
+/// Describes the build, state and configuration of the running bot, for debugging it in production.
+pub(crate) async fn info_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        shard_manager,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    headers: HeaderMap,
+) -> Result<Json<APIDebugInfoResponse>, StatusCode> {
+    require_scope(&headers, ServiceTokenScope::Debug)?;
+
+    let config = config();
+
+    let ping_start_time = Instant::now();
+    let ping_result: Result<String, redis::RedisError> = redis::cmd("PING")
+        .query_async(&mut redis_connection_manager)
+        .await;
+    let redis_latency_ms = ping_result
+        .ok()
+        .map(|_| ping_start_time.elapsed().as_secs_f64() * 1000.0);
+
+    let mut shards: Vec<APIDebugShard> = shard_manager
+        .runners
+        .lock()
+        .await
+        .iter()
+        .map(|(shard_id, runner)| APIDebugShard {
+            id: shard_id.get().into(),
+            stage: runner.stage.to_string(),
+            latency_ms: runner.latency.map(|latency| latency.as_millis()),
+        })
+        .collect();
+
+    shards.sort_unstable_by_key(|shard| shard.id);
+
+    Ok(Json(APIDebugInfoResponse {
+        build: APIDebugBuild {
+            version: env!("CARGO_PKG_VERSION"),
+            git_sha: env!("PREVIEWBOT_GIT_SHA"),
+            rustc_version: env!("PREVIEWBOT_RUSTC_VERSION"),
+        },
+        features: APIDebugFeatures {
+            serve_viewer: config.serve_viewer,
+            serve_dashboard: config.serve_dashboard,
+            dashboard_login: config.dashboard_oauth2.is_some(),
+            github_token: config.github_token.is_some(),
+            topgg_posting: config.topgg_token.is_some(),
+            discords_posting: config.discords_token.is_some(),
+        },
+        shards,
+        redis_latency_ms,
+        config: get_config_summary(),
+    }))
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::{stream, Stream};
+use serenity::all::GuildId;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::bot::activity::{subscribe_activity, ActivityEvent};
+use crate::APIJuxtaposeUrlHandlerState;
+
+use super::auth::{require_scope, ServiceTokenScope};
+use super::dashboard_auth::require_guild_admin;
+
+/// Proxies close idle connections, so a comment is sent periodically.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Turns the activity events matching the filter into server-sent events.
+/// Events missed by slow clients are skipped, announced by a `lagged` event with the number of skipped events.
+fn activity_stream(
+    receiver: broadcast::Receiver<ActivityEvent>,
+    filter: impl Fn(&ActivityEvent) -> bool + Send + 'static,
+) -> impl Stream<Item = Result<Event, axum::Error>> {
+    stream::unfold((receiver, filter), |(mut receiver, filter)| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(activity_event) if filter(&activity_event) => {
+                    let event = Event::default()
+                        .event(activity_event.name())
+                        .json_data(&activity_event);
+
+                    return Some((event, (receiver, filter)));
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped_count)) => {
+                    let event = Event::default()
+                        .event("lagged")
+                        .data(skipped_count.to_string());
+
+                    return Some((Ok(event), (receiver, filter)));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// Streams the activity of all guilds.
+pub(crate) async fn events_handler(
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+    require_scope(&headers, ServiceTokenScope::Events)?;
+
+    Ok(Sse::new(activity_stream(subscribe_activity(), |_| true))
+        .keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL)))
+}
+
+/// Streams the activity of a single guild to its administrators.
+pub(crate) async fn guild_events_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        serenity_cache,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Path(guild_id): Path<GuildId>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+    require_guild_admin(
+        &headers,
+        &mut redis_connection_manager,
+        &serenity_cache,
+        guild_id,
+    )
+    .await?;
+
+    Ok(Sse::new(activity_stream(
+        subscribe_activity(),
+        move |activity_event| activity_event.guild_id() == Some(guild_id),
+    ))
+    .keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL)))
+}
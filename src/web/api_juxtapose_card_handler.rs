@@ -0,0 +1,124 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse},
+    Extension,
+};
+use serde::Deserialize;
+
+use crate::{config::runtime_config, APIJuxtaposeUrlHandlerState};
+
+use super::{
+    api_juxtapose_request::APIJuxtaposeRequest,
+    api_juxtapose_response::{APIJuxtaposeResponse, JuxtaposeOrientation},
+    api_juxtapose_url_handler::resolve_juxtapose,
+};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct APIJuxtaposeCardRequest {
+    #[serde(flatten)]
+    juxtapose: APIJuxtaposeRequest,
+    #[serde(rename = "o")]
+    orientation: Option<String>,
+    /// Kept as a string, as numbers can't be deserialized from queries next to flattened fields.
+    #[serde(rename = "dt")]
+    divider_thickness: Option<String>,
+    #[serde(rename = "dc")]
+    divider_color: Option<String>,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Serves an HTML shell with OpenGraph and Twitter card metadata, so juxtapose links unfurl outside of Discord.
+/// Browsers are redirected to the viewer at `JUXTAPOSE_BASE_URL`.
+pub(crate) async fn handler(
+    State(mut state): State<APIJuxtaposeUrlHandlerState>,
+    Query(params): Query<APIJuxtaposeCardRequest>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (response_data, expire_unix_ts, cache_status) =
+        resolve_juxtapose(&mut state, &params.juxtapose).await?;
+
+    let orientation = match (response_data.orientation, params.orientation.as_deref()) {
+        (Some(JuxtaposeOrientation::Vertical), _) | (None, Some("v")) => "v",
+        _ => "h",
+    };
+
+    let mut viewer_url = runtime_config().juxtapose_base_url.clone();
+    viewer_url.query_pairs_mut().extend_pairs(&[
+        ("d", params.juxtapose.data.as_str()),
+        ("m", params.juxtapose.mac.as_str()),
+        ("o", orientation),
+    ]);
+
+    if let Some(divider_thickness) = params.divider_thickness.as_deref() {
+        viewer_url
+            .query_pairs_mut()
+            .append_pair("dt", divider_thickness);
+    }
+
+    if let Some(divider_color) = params.divider_color.as_deref() {
+        viewer_url
+            .query_pairs_mut()
+            .append_pair("dc", divider_color);
+    }
+
+    let title = match (
+        &response_data.left_image_label,
+        &response_data.right_image_label,
+    ) {
+        (Some(left_image_label), Some(right_image_label)) => {
+            format!("{} vs. {}", left_image_label, right_image_label)
+        }
+        _ => "Juxtapose".to_owned(),
+    };
+
+    let title = escape_html(title.as_str());
+    let viewer_url = escape_html(viewer_url.as_str());
+
+    let image_tags = response_data
+        .preview_image_url
+        .as_deref()
+        .map(|preview_image_url| {
+            let preview_image_url = escape_html(preview_image_url);
+
+            format!(
+                r#"<meta property="og:image" content="{preview_image_url}">
+    <meta name="twitter:image" content="{preview_image_url}">
+    <meta name="twitter:card" content="summary_large_image">"#
+            )
+        })
+        .unwrap_or_default();
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <title>{title}</title>
+    <meta property="og:type" content="website">
+    <meta property="og:title" content="{title}">
+    <meta property="og:description" content="Compare two images side by side.">
+    <meta property="og:url" content="{viewer_url}">
+    <meta name="twitter:title" content="{title}">
+    {image_tags}
+    <meta http-equiv="refresh" content="0; url={viewer_url}">
+</head>
+<body>
+    <a href="{viewer_url}">Open juxtapose</a>
+</body>
+</html>
+"#
+    );
+
+    Ok((
+        APIJuxtaposeResponse::get_cache_headers(expire_unix_ts),
+        Extension(cache_status),
+        Html(html),
+    ))
+}
@@ -0,0 +1,102 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Extension,
+};
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::config::runtime_config;
+use crate::outbound::{guarded_get, send_limited};
+use crate::APIJuxtaposeUrlHandlerState;
+
+use super::{
+    api_juxtapose_request::APIJuxtaposeRequest, api_juxtapose_response::APIJuxtaposeResponse,
+    api_juxtapose_url_handler::resolve_juxtapose,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ImageSide {
+    Left,
+    Right,
+    Preview,
+}
+
+impl ImageSide {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Preview => "preview",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct APIJuxtaposeImageRequest {
+    #[serde(flatten)]
+    juxtapose: APIJuxtaposeRequest,
+    side: ImageSide,
+}
+
+/// Returns the URL at which [`handler`] serves an image of the juxtapose. Like the viewer served at `/view`
+/// requests `/url`, the API is expected next to the viewer at `JUXTAPOSE_BASE_URL`.
+pub(crate) fn get_proxied_image_url(request: &APIJuxtaposeRequest, side: ImageSide) -> String {
+    let juxtapose_base_url = runtime_config().juxtapose_base_url.clone();
+    let mut image_url = juxtapose_base_url
+        .join("image")
+        .unwrap_or(juxtapose_base_url);
+
+    image_url
+        .query_pairs_mut()
+        .append_pair("d", request.data.as_str())
+        .append_pair("m", request.mac.as_str())
+        .append_pair("side", side.as_str());
+
+    image_url.to_string()
+}
+
+/// Streams one of the images through the bot, so viewers neither depend on the CDN URL nor send their referrer to it.
+pub(crate) async fn handler(
+    State(mut state): State<APIJuxtaposeUrlHandlerState>,
+    Query(params): Query<APIJuxtaposeImageRequest>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (response_data, expire_unix_ts, cache_status) =
+        resolve_juxtapose(&mut state, &params.juxtapose).await?;
+
+    let image_url = match params.side {
+        ImageSide::Left => response_data.left_image_url,
+        ImageSide::Right => response_data.right_image_url,
+        ImageSide::Preview => response_data
+            .preview_image_url
+            .ok_or(StatusCode::NOT_FOUND)?,
+    };
+
+    let image_url = Url::parse(image_url.as_str()).map_err(|_| StatusCode::BAD_GATEWAY)?;
+    let image_request = guarded_get(image_url).map_err(|_| StatusCode::BAD_GATEWAY)?;
+
+    let image_response = send_limited(image_request)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| {
+            println!("Error while fetching juxtapose image: {:?}", err);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let mut headers = APIJuxtaposeResponse::get_cache_headers(expire_unix_ts);
+
+    for header_name in [header::CONTENT_TYPE, header::CONTENT_LENGTH] {
+        if let Some(header_value) = image_response.headers().get(&header_name) {
+            headers.insert(header_name, header_value.clone());
+        }
+    }
+
+    Ok((
+        headers,
+        Extension(cache_status),
+        Body::from_stream(image_response.bytes_stream()),
+    ))
+}
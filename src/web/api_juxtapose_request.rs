@@ -1,40 +1,20 @@
 use axum::http::StatusCode;
-use base64::{engine::general_purpose, Engine};
 use serde::Deserialize;
 
-use crate::BLAKE3_JUXTAPOSE_KEY;
+use crate::signing::SignedPayload;
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct APIJuxtaposeRequest {
     #[serde(rename = "d")]
     pub(crate) data: String,
     #[serde(rename = "m")]
-    mac: String,
+    pub(crate) mac: String,
 }
 
 impl APIJuxtaposeRequest {
-    pub(crate) fn is_decoded_data_valid(
-        &self,
-        decoded_data_bytes: &[u8],
-    ) -> Result<bool, StatusCode> {
-        let mac_bytes = general_purpose::URL_SAFE_NO_PAD
-            .decode(self.mac.as_str())
-            .map_err(|_| StatusCode::BAD_REQUEST)?;
-
-        let mac_bytes: &[u8; 16] = mac_bytes
-            .as_slice()
-            .try_into()
-            .map_err(|_| StatusCode::BAD_REQUEST)?;
-
-        let mut mac_calculated = [0u8; 16];
-        blake3::Hasher::new_keyed(&BLAKE3_JUXTAPOSE_KEY)
-            .update(decoded_data_bytes)
-            .finalize_xof()
-            .fill(&mut mac_calculated);
-
-        Ok(constant_time_eq::constant_time_eq_16(
-            mac_bytes,
-            &mac_calculated,
-        ))
+    /// Verifies the MAC with the key of the kind of link, as albums and juxtaposes encode the same data.
+    pub(crate) fn verify(&self, key: &[u8; 32]) -> Result<SignedPayload, StatusCode> {
+        SignedPayload::decode(key, self.data.as_str(), self.mac.as_str())
+            .map_err(|_| StatusCode::BAD_REQUEST)
     }
 }
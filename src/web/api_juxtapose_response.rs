@@ -1,14 +1,52 @@
 use axum::http::{HeaderMap, HeaderValue, StatusCode};
 use redis::AsyncCommands;
 use reqwest::Url;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     error::Error,
     time::{Duration, SystemTime},
 };
 
-#[derive(Debug, Serialize)]
+/// Layouts outlive the cached URLs, so they can be restored when the cache is rebuilt from the Discord message.
+const LAYOUT_TTL: i64 = 365 * 24 * 60 * 60;
+
+pub(crate) const REDIS_CACHE_KEY_PREFIX: &str = "juxtapose_payload:";
+pub(crate) const REDIS_LAYOUT_KEY_PREFIX: &str = "juxtapose_layout:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum JuxtaposeOrientation {
+    Horizontal,
+    Vertical,
+}
+
+impl JuxtaposeOrientation {
+    pub(crate) fn from_is_vertical(is_vertical: bool) -> Self {
+        if is_vertical {
+            Self::Vertical
+        } else {
+            Self::Horizontal
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Horizontal => "horizontal",
+            Self::Vertical => "vertical",
+        }
+    }
+
+    fn parse(orientation: &str) -> Option<Self> {
+        match orientation {
+            "horizontal" => Some(Self::Horizontal),
+            "vertical" => Some(Self::Vertical),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct APIJuxtaposeResponse {
     pub(crate) left_image_url: String,
     pub(crate) right_image_url: String,
@@ -16,9 +54,49 @@ pub(crate) struct APIJuxtaposeResponse {
     pub(crate) left_image_label: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) right_image_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) preview_image_url: Option<String>,
+    /// Unknown for juxtaposes created before the layout was stored, in which case the `o` parameter of the viewer URL applies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) orientation: Option<JuxtaposeOrientation>,
+    /// Initial position of the divider, between 0 and 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) divider_position: Option<f32>,
+}
+
+/// Versioned form of the juxtapose data, used both in the API cache and as the body of `/url`.
+/// New fields can be added to the current version as long as they are optional; incompatible changes
+/// require a new variant, which older cache entries are converted from in [`Self::into_latest`].
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "version")]
+pub(crate) enum APIJuxtaposePayload {
+    #[serde(rename = "1")]
+    V1(APIJuxtaposeResponse),
+}
+
+impl APIJuxtaposePayload {
+    pub(crate) fn into_latest(self) -> APIJuxtaposeResponse {
+        match self {
+            Self::V1(response_data) => response_data,
+        }
+    }
+}
+
+impl From<APIJuxtaposeResponse> for APIJuxtaposePayload {
+    fn from(response_data: APIJuxtaposeResponse) -> Self {
+        Self::V1(response_data)
+    }
 }
 
 impl APIJuxtaposeResponse {
+    fn redis_cache_key(key: &str) -> String {
+        format!("{}{}", REDIS_CACHE_KEY_PREFIX, key)
+    }
+
+    fn redis_layout_key(key: &str) -> String {
+        format!("{}{}", REDIS_LAYOUT_KEY_PREFIX, key)
+    }
+
     fn get_expire_unix_ts(&self) -> Result<i64, Box<dyn Error + Send + Sync>> {
         let left_ts = i64::from_str_radix(
             &Url::parse(self.left_image_url.as_str())?
@@ -63,59 +141,86 @@ impl APIJuxtaposeResponse {
         connection: &mut redis::aio::ConnectionManager,
         key: &str,
     ) -> Result<i64, StatusCode> {
-        let mut data = vec![
-            ("left_image", self.left_image_url.as_str()),
-            ("right_image", self.right_image_url.as_str()),
-        ];
-
-        if let Some(left_image_label) = &self.left_image_label {
-            data.push(("left_label", left_image_label.as_str()));
-        }
-
-        if let Some(right_image_label) = &self.right_image_label {
-            data.push(("right_label", right_image_label.as_str()));
-        }
-
-        let _: () = connection
-            .hset_multiple(key, &data)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let payload = serde_json::to_string(&APIJuxtaposePayload::from(self.clone()))
+            .expect("Failed to serialize juxtapose payload.");
 
         let unix_ts = self.get_expire_unix_ts().map_err(|err| {
             println!("Error while getting expire timestamp: {:?}", err);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
-        let _: () = connection.expire_at(key, unix_ts).await.map_err(|err| {
-            println!("Error while setting expire timestamp: {:?}", err);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        let cache_key = Self::redis_cache_key(key);
+
+        let _: () = redis::pipe()
+            .atomic()
+            .set(&cache_key, payload)
+            .ignore()
+            .expire_at(&cache_key, unix_ts)
+            .ignore()
+            .query_async(connection)
+            .await
+            .map_err(|err| {
+                println!("Error while setting juxtapose cache: {:?}", err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
 
         Ok(unix_ts)
     }
 
-    pub(crate) async fn redis_cache_get_data(
+    /// Converts an entry of the hash-based format used before versioning, which was stored at the bare key.
+    async fn redis_cache_migrate_legacy_data(
         connection: &mut redis::aio::ConnectionManager,
         key: &str,
     ) -> Option<Self> {
-        connection
+        let cached_urls = connection
             .hgetall::<&str, HashMap<String, String>>(key)
             .await
-            .ok()
-            .and_then(|cached_urls| {
-                match (
-                    cached_urls.get("left_image"),
-                    cached_urls.get("right_image"),
-                ) {
-                    (Some(left_image_url), Some(right_image_url)) => Some(APIJuxtaposeResponse {
-                        left_image_url: left_image_url.to_owned(),
-                        right_image_url: right_image_url.to_owned(),
-                        left_image_label: cached_urls.get("left_label").cloned(),
-                        right_image_label: cached_urls.get("right_label").cloned(),
-                    }),
-                    _ => None,
-                }
-            })
+            .ok()?;
+
+        let (Some(left_image_url), Some(right_image_url)) = (
+            cached_urls.get("left_image"),
+            cached_urls.get("right_image"),
+        ) else {
+            return None;
+        };
+
+        let response_data = APIJuxtaposeResponse {
+            left_image_url: left_image_url.to_owned(),
+            right_image_url: right_image_url.to_owned(),
+            left_image_label: cached_urls.get("left_label").cloned(),
+            right_image_label: cached_urls.get("right_label").cloned(),
+            preview_image_url: cached_urls.get("preview_image").cloned(),
+            orientation: cached_urls
+                .get("orientation")
+                .and_then(|orientation| JuxtaposeOrientation::parse(orientation)),
+            divider_position: cached_urls
+                .get("divider_position")
+                .and_then(|position| position.parse().ok()),
+        };
+
+        if response_data.redis_cache_set(connection, key).await.is_ok() {
+            let _: Result<(), _> = connection.del(key).await;
+        }
+
+        Some(response_data)
+    }
+
+    pub(crate) async fn redis_cache_get_data(
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) -> Option<Self> {
+        let payload = connection
+            .get::<String, Option<String>>(Self::redis_cache_key(key))
+            .await
+            .ok()?;
+
+        match payload {
+            Some(payload) => serde_json::from_str::<APIJuxtaposePayload>(payload.as_str())
+                .inspect_err(|err| println!("Error while parsing juxtapose payload: {:?}", err))
+                .ok()
+                .map(APIJuxtaposePayload::into_latest),
+            None => Self::redis_cache_migrate_legacy_data(connection, key).await,
+        }
     }
 
     pub(crate) async fn redis_cache_get_expire(
@@ -123,7 +228,7 @@ impl APIJuxtaposeResponse {
         key: &str,
     ) -> Result<usize, StatusCode> {
         redis::cmd("EXPIRETIME")
-            .arg(key)
+            .arg(Self::redis_cache_key(key))
             .query_async(connection)
             .await
             .map_err(|err| {
@@ -131,4 +236,72 @@ impl APIJuxtaposeResponse {
                 StatusCode::INTERNAL_SERVER_ERROR
             })
     }
+
+    /// Deletes the cached payload, including legacy entries, and the stored layout of a juxtapose.
+    /// Returns the number of deleted keys.
+    pub(crate) async fn redis_cache_delete(
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) -> Result<usize, redis::RedisError> {
+        connection
+            .del(&[
+                Self::redis_cache_key(key),
+                Self::redis_layout_key(key),
+                key.to_owned(),
+            ])
+            .await
+    }
+
+    /// Persists the layout independently of the cached URLs.
+    pub(crate) async fn redis_set_layout(
+        &self,
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) -> Result<(), redis::RedisError> {
+        let layout_key = Self::redis_layout_key(key);
+        let mut layout = Vec::new();
+
+        if let Some(orientation) = self.orientation {
+            layout.push(("orientation", orientation.as_str().to_owned()));
+        }
+
+        if let Some(divider_position) = self.divider_position {
+            layout.push(("divider_position", divider_position.to_string()));
+        }
+
+        if layout.is_empty() {
+            return Ok(());
+        }
+
+        redis::pipe()
+            .atomic()
+            .hset_multiple(&layout_key, &layout)
+            .ignore()
+            .expire(&layout_key, LAYOUT_TTL)
+            .ignore()
+            .query_async(connection)
+            .await
+    }
+
+    /// Restores the layout stored by [`Self::redis_set_layout`], e.g. after fetching the URLs from the Discord message.
+    pub(crate) async fn redis_get_layout(
+        &mut self,
+        connection: &mut redis::aio::ConnectionManager,
+        key: &str,
+    ) {
+        let Ok(layout) = connection
+            .hgetall::<String, HashMap<String, String>>(Self::redis_layout_key(key))
+            .await
+        else {
+            return;
+        };
+
+        self.orientation = layout
+            .get("orientation")
+            .and_then(|orientation| JuxtaposeOrientation::parse(orientation));
+
+        self.divider_position = layout
+            .get("divider_position")
+            .and_then(|position| position.parse().ok());
+    }
 }
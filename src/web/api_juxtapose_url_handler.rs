@@ -2,102 +2,132 @@ use axum::{
     extract::{Query, State},
     http::{HeaderMap, StatusCode},
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
-use base64::{engine::general_purpose, Engine};
-use serenity::all::{ChannelId, MessageId};
-use std::mem::size_of;
+use serenity::all::{Http, UserId};
 
-use crate::APIJuxtaposeUrlHandlerState;
+use crate::signing::SignedPayload;
+use crate::{APIJuxtaposeUrlHandlerState, BLAKE3_JUXTAPOSE_KEY};
 
 use super::{
-    api_juxtapose_request::APIJuxtaposeRequest, api_juxtapose_response::APIJuxtaposeResponse,
+    access_log::CacheStatus,
+    api_juxtapose_image_handler::{get_proxied_image_url, ImageSide},
+    api_juxtapose_request::APIJuxtaposeRequest,
+    api_juxtapose_response::{APIJuxtaposePayload, APIJuxtaposeResponse},
+    view_counter::redis_increment_view_count,
 };
 
-pub(crate) async fn handler(
-    State(APIJuxtaposeUrlHandlerState {
-        serenity_http,
-        serenity_cache,
-        mut redis_connection_manager,
-    }): State<APIJuxtaposeUrlHandlerState>,
-    Query(params): Query<APIJuxtaposeRequest>,
-) -> Result<(HeaderMap, impl IntoResponse), StatusCode> /* (StatusCode, &'static str) */ {
-    let data_bytes = general_purpose::URL_SAFE_NO_PAD
-        .decode(params.data.as_str())
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
+/// Builds the response from the attachments of the juxtapose message, bypassing the cache.
+pub(crate) async fn fetch_juxtapose_response(
+    serenity_http: &Http,
+    current_user_id: UserId,
+    payload: SignedPayload,
+) -> Result<APIJuxtaposeResponse, StatusCode> {
+    let juxtapose_message = serenity_http
+        .get_message(payload.channel_id, payload.message_id)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
 
-    if !params.is_decoded_data_valid(data_bytes.as_slice())? {
+    if juxtapose_message.author.id != current_user_id {
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    if let Some(response_data) = APIJuxtaposeResponse::redis_cache_get_data(
-        &mut redis_connection_manager,
-        params.data.as_str(),
-    )
-    .await
+    let preview_attachment = juxtapose_message.attachments.first();
+
+    let left_attachment = juxtapose_message
+        .attachments
+        .get(1)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let right_attachment = juxtapose_message
+        .attachments
+        .get(2)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(APIJuxtaposeResponse {
+        left_image_url: left_attachment.url.to_string(),
+        right_image_url: right_attachment.url.to_string(),
+        left_image_label: left_attachment
+            .description
+            .as_ref()
+            .map(ToString::to_string),
+        right_image_label: right_attachment
+            .description
+            .as_ref()
+            .map(ToString::to_string),
+        preview_image_url: preview_attachment.map(|attachment| attachment.url.to_string()),
+        orientation: None,
+        divider_position: None,
+    })
+}
+
+/// Validates the request and looks up the image URLs of the juxtapose, either from Redis or from the Discord message.
+/// Also returns the UNIX timestamp at which the URLs expire.
+pub(crate) async fn resolve_juxtapose(
+    APIJuxtaposeUrlHandlerState {
+        serenity_http,
+        serenity_cache,
+        redis_connection_manager,
+        ..
+    }: &mut APIJuxtaposeUrlHandlerState,
+    params: &APIJuxtaposeRequest,
+) -> Result<(APIJuxtaposeResponse, u64, CacheStatus), StatusCode> {
+    let payload = params.verify(&BLAKE3_JUXTAPOSE_KEY)?;
+
+    if let Some(response_data) =
+        APIJuxtaposeResponse::redis_cache_get_data(redis_connection_manager, params.data.as_str())
+            .await
     {
         let expire_unix_ts = APIJuxtaposeResponse::redis_cache_get_expire(
-            &mut redis_connection_manager,
+            redis_connection_manager,
             params.data.as_str(),
         )
         .await?;
 
-        Ok((
-            APIJuxtaposeResponse::get_cache_headers(expire_unix_ts as u64),
-            Json(response_data),
-        ))
+        Ok((response_data, expire_unix_ts as u64, CacheStatus::Hit))
     } else {
-        let mut data_ids = data_bytes.chunks_exact(size_of::<u64>()).map(|id| {
-            id.try_into()
-                .map(u64::from_le_bytes)
-                .map_err(|_| StatusCode::BAD_REQUEST)
-        });
-
-        let message_id =
-            MessageId::from(data_ids.next().ok_or(StatusCode::INTERNAL_SERVER_ERROR)??);
+        let current_user_id = serenity_cache.current_user().id;
 
-        let channel_id =
-            ChannelId::from(data_ids.next().ok_or(StatusCode::INTERNAL_SERVER_ERROR)??);
+        let mut response_data =
+            fetch_juxtapose_response(serenity_http, current_user_id, payload).await?;
 
-        let juxtapose_message = serenity_http
-            .get_message(channel_id, message_id)
-            .await
-            .map_err(|_| StatusCode::NOT_FOUND)?;
-
-        if juxtapose_message.author.id != serenity_cache.current_user().id {
-            return Err(StatusCode::BAD_REQUEST);
-        }
-
-        let left_attachment = juxtapose_message
-            .attachments
-            .get(1)
-            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        let right_attachment = juxtapose_message
-            .attachments
-            .get(2)
-            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        let response_data = APIJuxtaposeResponse {
-            left_image_url: left_attachment.url.to_string(),
-            right_image_url: right_attachment.url.to_string(),
-            left_image_label: left_attachment
-                .description
-                .as_ref()
-                .map(ToString::to_string),
-            right_image_label: right_attachment
-                .description
-                .as_ref()
-                .map(ToString::to_string),
-        };
+        response_data
+            .redis_get_layout(redis_connection_manager, params.data.as_str())
+            .await;
 
         let expire_unix_ts = response_data
-            .redis_cache_set(&mut redis_connection_manager, params.data.as_str())
+            .redis_cache_set(redis_connection_manager, params.data.as_str())
             .await?;
 
-        Ok((
-            APIJuxtaposeResponse::get_cache_headers(expire_unix_ts as u64),
-            Json(response_data),
-        ))
+        Ok((response_data, expire_unix_ts as u64, CacheStatus::Miss))
+    }
+}
+
+pub(crate) async fn handler(
+    State(mut state): State<APIJuxtaposeUrlHandlerState>,
+    Query(params): Query<APIJuxtaposeRequest>,
+) -> Result<(HeaderMap, Extension<CacheStatus>, impl IntoResponse), StatusCode> {
+    let (mut response_data, expire_unix_ts, cache_status) =
+        resolve_juxtapose(&mut state, &params).await?;
+
+    // Viewers load the images through the bot, so that they neither depend on the expiring CDN URLs
+    // nor send their referrer to Discord.
+    response_data.left_image_url = get_proxied_image_url(&params, ImageSide::Left);
+    response_data.right_image_url = get_proxied_image_url(&params, ImageSide::Right);
+    response_data.preview_image_url = response_data
+        .preview_image_url
+        .map(|_| get_proxied_image_url(&params, ImageSide::Preview));
+
+    // Only the JSON route counts as a view, as viewers request the images and cards separately.
+    if let Err(err) =
+        redis_increment_view_count(&mut state.redis_connection_manager, params.data.as_str()).await
+    {
+        println!("Error while incrementing view count: {:?}", err);
     }
+
+    Ok((
+        APIJuxtaposeResponse::get_cache_headers(expire_unix_ts),
+        Extension(cache_status),
+        Json(APIJuxtaposePayload::from(response_data)),
+    ))
 }
@@ -0,0 +1,70 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Serialize;
+
+use crate::bot::file_preview::backpressure::{get_backpressure_metrics, BackpressureMetrics};
+use crate::bot::file_preview::processing_limit::{get_processing_metrics, ProcessingMetrics};
+use crate::bot::file_preview::scan_metrics::{get_scan_metrics, ScanMetrics};
+use crate::APIJuxtaposeUrlHandlerState;
+
+use super::auth::{require_scope, ServiceTokenScope};
+use super::cache_janitor::{get_cache_janitor_metrics, CacheJanitorMetrics};
+use super::view_counter::redis_get_view_count;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct APIJuxtaposeStatsResponse {
+    views: u64,
+}
+
+pub(crate) async fn juxtapose_handler(
+    State(APIJuxtaposeUrlHandlerState {
+        mut redis_connection_manager,
+        ..
+    }): State<APIJuxtaposeUrlHandlerState>,
+    Path(key): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<APIJuxtaposeStatsResponse>, StatusCode> {
+    require_scope(&headers, ServiceTokenScope::Stats)?;
+
+    let views = redis_get_view_count(&mut redis_connection_manager, key.as_str())
+        .await
+        .map_err(|err| {
+            println!("Error while getting view count: {:?}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(APIJuxtaposeStatsResponse { views }))
+}
+
+pub(crate) async fn scan_handler(headers: HeaderMap) -> Result<Json<ScanMetrics>, StatusCode> {
+    require_scope(&headers, ServiceTokenScope::Stats)?;
+
+    Ok(Json(get_scan_metrics()))
+}
+
+pub(crate) async fn processing_handler(
+    headers: HeaderMap,
+) -> Result<Json<ProcessingMetrics>, StatusCode> {
+    require_scope(&headers, ServiceTokenScope::Stats)?;
+
+    Ok(Json(get_processing_metrics()))
+}
+
+pub(crate) async fn discord_handler(
+    headers: HeaderMap,
+) -> Result<Json<BackpressureMetrics>, StatusCode> {
+    require_scope(&headers, ServiceTokenScope::Stats)?;
+
+    Ok(Json(get_backpressure_metrics()))
+}
+
+pub(crate) async fn cache_handler(
+    headers: HeaderMap,
+) -> Result<Json<CacheJanitorMetrics>, StatusCode> {
+    require_scope(&headers, ServiceTokenScope::Stats)?;
+
+    Ok(Json(get_cache_janitor_metrics()))
+}
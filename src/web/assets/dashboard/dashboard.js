@@ -0,0 +1,232 @@
+"use strict";
+
+// All API routes are relative to the parent of the dashboard route.
+const API_BASE = "../";
+const ACTIVITY_MAX_ENTRIES = 100;
+
+const status = document.getElementById("status");
+const guildSelect = document.getElementById("guild-select");
+const form = document.getElementById("config-form");
+const configStatus = document.getElementById("config-status");
+
+let eventSource = null;
+
+async function api(method, path, body) {
+    const response = await fetch(API_BASE + path, {
+        method,
+        headers: body === undefined ? {} : { "Content-Type": "application/json" },
+        body: body === undefined ? undefined : JSON.stringify(body),
+    });
+
+    if (!response.ok) {
+        const error = new Error((await response.text()) || `The API responded with status ${response.status}.`);
+        error.status = response.status;
+        throw error;
+    }
+
+    return response.status === 204 ? null : response.json();
+}
+
+function setSectionsHidden(hidden) {
+    for (const id of ["stats", "config", "activity"]) {
+        document.getElementById(id).hidden = hidden;
+    }
+}
+
+function splitLines(value) {
+    return value
+        .split("\n")
+        .map((line) => line.trim())
+        .filter((line) => line.length > 0);
+}
+
+function parseLanguageAliases(value) {
+    return Object.fromEntries(
+        splitLines(value).map((line) => {
+            const [extension, language = ""] = line.split("=", 2);
+            return [extension.trim().replace(/^\./, "").toLowerCase(), language.trim()];
+        }),
+    );
+}
+
+function fillConfigForm(config) {
+    form.elements.preview_display_style.value = config.preview_display_style;
+    form.elements.keyword_filter_action.value = config.keyword_filter_action;
+    form.elements.attachment_theme.value = config.attachment_theme ?? "";
+    form.elements.whitespace_indicators.value = config.whitespace_indicators;
+    form.elements.audit_channel_id.value = config.audit_channel_id ?? "";
+    form.elements.digest_channel_id.value = config.digest_channel_id ?? "";
+    form.elements.disabled_channel_ids.value = config.disabled_channel_ids.join("\n");
+    form.elements.keyword_filters.value = config.keyword_filters.join("\n");
+    form.elements.show_repository_metadata.checked = config.show_repository_metadata;
+    form.elements.link_screening.checked = config.link_screening;
+    form.elements.recycle_previews.checked = config.recycle_previews;
+    form.elements.show_content_hash.checked = config.show_content_hash;
+    form.elements.juxtapose_watermark.value = config.juxtapose_watermark ?? "";
+    form.elements.juxtapose_presets.value = JSON.stringify(config.juxtapose_presets, null, 2);
+    form.elements.language_aliases.value = Object.entries(config.language_aliases)
+        .map(([extension, language]) => `${extension}=${language}`)
+        .join("\n");
+
+    for (const checkbox of form.elements.preview_source) {
+        checkbox.checked = !config.disabled_preview_sources.includes(checkbox.value);
+    }
+}
+
+function readConfigForm() {
+    return {
+        preview_display_style: form.elements.preview_display_style.value,
+        keyword_filter_action: form.elements.keyword_filter_action.value,
+        attachment_theme: form.elements.attachment_theme.value || null,
+        whitespace_indicators: form.elements.whitespace_indicators.value,
+        audit_channel_id: form.elements.audit_channel_id.value.trim() || null,
+        digest_channel_id: form.elements.digest_channel_id.value.trim() || null,
+        disabled_channel_ids: splitLines(form.elements.disabled_channel_ids.value),
+        keyword_filters: splitLines(form.elements.keyword_filters.value),
+        disabled_preview_sources: [...form.elements.preview_source]
+            .filter((checkbox) => !checkbox.checked)
+            .map((checkbox) => checkbox.value),
+        show_repository_metadata: form.elements.show_repository_metadata.checked,
+        link_screening: form.elements.link_screening.checked,
+        recycle_previews: form.elements.recycle_previews.checked,
+        show_content_hash: form.elements.show_content_hash.checked,
+        juxtapose_watermark: form.elements.juxtapose_watermark.value.trim() || null,
+        juxtapose_presets: JSON.parse(form.elements.juxtapose_presets.value.trim() || "[]"),
+        language_aliases: parseLanguageAliases(form.elements.language_aliases.value),
+    };
+}
+
+function describeActivity(event) {
+    switch (event.type) {
+        case "file_preview_created":
+            return `File preview created in #${event.channel_id}: ${event.url}`;
+        case "juxtapose_created":
+            return `Juxtapose created in #${event.channel_id}`;
+        case "file_preview_failed":
+            return `File preview failed in #${event.channel_id}: ${event.error}`;
+        case "command_failed":
+            return `/${event.command} failed in #${event.channel_id}: ${event.error}`;
+        default:
+            return event.type;
+    }
+}
+
+function addActivity(text, isError) {
+    const list = document.getElementById("activity-list");
+    const entry = document.createElement("li");
+    entry.textContent = `${new Date().toLocaleTimeString()} ${text}`;
+    entry.classList.toggle("error", isError);
+    list.prepend(entry);
+
+    while (list.children.length > ACTIVITY_MAX_ENTRIES) {
+        list.lastChild.remove();
+    }
+}
+
+function incrementStat(id) {
+    const element = document.getElementById(id);
+    element.textContent = String(Number(element.textContent) + 1);
+}
+
+function subscribeActivity(guildId) {
+    eventSource?.close();
+    document.getElementById("activity-list").replaceChildren();
+
+    eventSource = new EventSource(`${API_BASE}guilds/${guildId}/events`);
+
+    for (const type of ["file_preview_created", "juxtapose_created", "file_preview_failed", "command_failed"]) {
+        eventSource.addEventListener(type, (message) => {
+            const event = JSON.parse(message.data);
+            addActivity(describeActivity(event), type.endsWith("_failed"));
+
+            if (type === "file_preview_created") {
+                incrementStat("stats-file-previews");
+            } else if (type === "juxtapose_created") {
+                incrementStat("stats-juxtaposes");
+            }
+        });
+    }
+
+    eventSource.addEventListener("lagged", (message) => {
+        addActivity(`Skipped ${message.data} events.`, true);
+    });
+}
+
+async function selectGuild(guildId) {
+    setSectionsHidden(true);
+    status.hidden = false;
+    status.textContent = "Loading...";
+    configStatus.textContent = "";
+
+    try {
+        const [config, stats] = await Promise.all([
+            api("GET", `guilds/${guildId}/config`),
+            api("GET", `guilds/${guildId}/stats`),
+        ]);
+
+        fillConfigForm(config);
+        document.getElementById("stats-file-previews").textContent = String(stats.file_previews);
+        document.getElementById("stats-juxtaposes").textContent = String(stats.juxtaposes);
+        subscribeActivity(guildId);
+
+        status.hidden = true;
+        setSectionsHidden(false);
+    } catch (error) {
+        status.textContent = `Failed to load the server: ${error.message}`;
+    }
+}
+
+form.addEventListener("submit", async (event) => {
+    event.preventDefault();
+    configStatus.classList.remove("error");
+    configStatus.textContent = "Saving...";
+
+    try {
+        fillConfigForm(await api("PUT", `guilds/${guildSelect.value}/config`, readConfigForm()));
+        configStatus.textContent = "Saved.";
+    } catch (error) {
+        configStatus.classList.add("error");
+        configStatus.textContent = `Failed to save the configuration: ${error.message}`;
+    }
+});
+
+guildSelect.addEventListener("change", () => selectGuild(guildSelect.value));
+
+document.getElementById("logout").addEventListener("click", async () => {
+    eventSource?.close();
+    await api("POST", "auth/logout").catch(() => {});
+    location.reload();
+});
+
+async function initialize() {
+    let guilds;
+
+    try {
+        guilds = await api("GET", "guilds");
+    } catch (error) {
+        if (error.status === 401) {
+            status.textContent = "Log in to manage your servers.";
+            document.getElementById("login").hidden = false;
+        } else {
+            status.textContent = `Failed to load your servers: ${error.message}`;
+        }
+
+        return;
+    }
+
+    document.getElementById("logout").hidden = false;
+
+    if (guilds.length === 0) {
+        status.textContent = "There are no servers that you can manage and that the bot is a member of.";
+        return;
+    }
+
+    for (const guild of guilds) {
+        guildSelect.add(new Option(guild.name, guild.id));
+    }
+
+    guildSelect.hidden = false;
+    await selectGuild(guildSelect.value);
+}
+
+initialize();
@@ -0,0 +1,123 @@
+use std::time::{Duration, SystemTime};
+
+use axum::http::{header, HeaderMap, StatusCode};
+use base64::{engine::general_purpose, Engine};
+
+use crate::BLAKE3_SERVICE_TOKEN_KEY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ServiceTokenScope {
+    Stats,
+    Admin,
+    Preview,
+    Events,
+    Debug,
+}
+
+impl ServiceTokenScope {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stats => "stats",
+            Self::Admin => "admin",
+            Self::Preview => "preview",
+            Self::Events => "events",
+            Self::Debug => "debug",
+        }
+    }
+
+    pub(crate) fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "stats" => Some(Self::Stats),
+            "admin" => Some(Self::Admin),
+            "preview" => Some(Self::Preview),
+            "events" => Some(Self::Events),
+            "debug" => Some(Self::Debug),
+            _ => None,
+        }
+    }
+}
+
+fn get_mac(payload: &[u8]) -> [u8; 16] {
+    let mut mac = [0u8; 16];
+
+    blake3::Hasher::new_keyed(&BLAKE3_SERVICE_TOKEN_KEY)
+        .update(payload)
+        .finalize_xof()
+        .fill(&mut mac);
+
+    mac
+}
+
+/// Creates a token in the format `<payload>.<mac>`, where the payload contains the scopes and the expiry.
+/// The MAC uses its own derived key, so tokens can't be confused with the MACs of public juxtapose links.
+pub(crate) fn issue_service_token(scopes: &[ServiceTokenScope], validity: Duration) -> String {
+    let expire_unix_ts = (SystemTime::now() + validity)
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let payload = format!(
+        "{}:{}",
+        scopes
+            .iter()
+            .map(ServiceTokenScope::as_str)
+            .collect::<Vec<_>>()
+            .join(","),
+        expire_unix_ts
+    );
+
+    format!(
+        "{}.{}",
+        general_purpose::URL_SAFE_NO_PAD.encode(payload.as_bytes()),
+        general_purpose::URL_SAFE_NO_PAD.encode(get_mac(payload.as_bytes()))
+    )
+}
+
+/// Returns the scopes of the token if its MAC is valid and it hasn't expired.
+fn verify_service_token(token: &str) -> Option<Vec<ServiceTokenScope>> {
+    let (payload, mac) = token.split_once('.')?;
+
+    let payload = general_purpose::URL_SAFE_NO_PAD.decode(payload).ok()?;
+    let mac = general_purpose::URL_SAFE_NO_PAD.decode(mac).ok()?;
+    let mac: &[u8; 16] = mac.as_slice().try_into().ok()?;
+
+    if !constant_time_eq::constant_time_eq_16(mac, &get_mac(payload.as_slice())) {
+        return None;
+    }
+
+    let payload = String::from_utf8(payload).ok()?;
+    let (scopes, expire_unix_ts) = payload.rsplit_once(':')?;
+
+    let expire_time =
+        SystemTime::UNIX_EPOCH + Duration::from_secs(expire_unix_ts.parse::<u64>().ok()?);
+
+    if expire_time < SystemTime::now() {
+        return None;
+    }
+
+    Some(
+        scopes
+            .split(',')
+            .filter_map(ServiceTokenScope::parse)
+            .collect(),
+    )
+}
+
+/// Checks the `Authorization: Bearer` header of a request to a non-public endpoint.
+pub(crate) fn require_scope(
+    headers: &HeaderMap,
+    scope: ServiceTokenScope,
+) -> Result<(), StatusCode> {
+    let scopes = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(verify_service_token)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if scopes.contains(&scope) {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
@@ -0,0 +1,201 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use once_cell::sync::Lazy;
+use redis::AsyncCommands;
+use serde::Serialize;
+use serenity::all::Http;
+
+use crate::retry::random_u64;
+use crate::signing::SignedPayload;
+
+use super::api_juxtapose_response::{
+    APIJuxtaposeResponse, REDIS_CACHE_KEY_PREFIX, REDIS_LAYOUT_KEY_PREFIX,
+};
+use super::cache_warming::redis_remove_recent_juxtapose;
+use super::view_counter::redis_delete_view_count;
+
+/// The first run is delayed, so it doesn't compete with the reconciliation after startup.
+const JANITOR_INITIAL_DELAY: Duration = Duration::from_secs(10 * 60);
+const JANITOR_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Number of juxtaposes verified against the Discord API per run.
+const JANITOR_SAMPLE_SIZE: usize = 100;
+
+/// Pause between API requests, as the janitor must never compete with commands for the rate limit.
+const JANITOR_REQUEST_DELAY: Duration = Duration::from_secs(1);
+
+static IS_JANITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+static JANITOR_METRICS: Lazy<Mutex<CacheJanitorMetrics>> =
+    Lazy::new(|| Mutex::new(CacheJanitorMetrics::default()));
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct CacheJanitorMetrics {
+    last_run_unix_ts: Option<u64>,
+    /// Juxtaposes with a cached API response, which expire together with the image URLs.
+    cached_payloads: u64,
+    /// Juxtaposes with a stored layout, which approximates all juxtaposes created in the last year.
+    stored_layouts: u64,
+    sampled: u64,
+    orphaned: u64,
+    /// Share of the sampled juxtaposes whose message has been deleted.
+    orphan_rate: f64,
+    /// Orphaned juxtaposes removed since startup.
+    removed_total: u64,
+}
+
+pub(crate) fn get_cache_janitor_metrics() -> CacheJanitorMetrics {
+    JANITOR_METRICS.lock().unwrap().clone()
+}
+
+fn get_unix_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Counts the keys matching the pattern and picks a uniform random sample of them using reservoir sampling.
+async fn redis_scan_sample(
+    connection: &mut redis::aio::ConnectionManager,
+    pattern: &str,
+    sample_size: usize,
+) -> Result<(u64, Vec<String>), redis::RedisError> {
+    let mut keys = connection.scan_match::<_, String>(pattern).await?;
+    let mut key_count = 0;
+    let mut sample = Vec::with_capacity(sample_size);
+
+    while let Some(key) = keys.next_item().await {
+        if sample.len() < sample_size {
+            sample.push(key);
+        } else {
+            let index = (random_u64() % (key_count + 1)) as usize;
+
+            if index < sample_size {
+                sample[index] = key;
+            }
+        }
+
+        key_count += 1;
+    }
+
+    Ok((key_count, sample))
+}
+
+/// Only a definite 404 counts as deleted, so outages and missing permissions never remove data.
+async fn is_juxtapose_message_deleted(serenity_http: &Http, juxtapose_data: &str) -> Option<bool> {
+    let payload = SignedPayload::decode_trusted_data(juxtapose_data).ok()?;
+
+    match serenity_http
+        .get_message(payload.channel_id, payload.message_id)
+        .await
+    {
+        Ok(_) => Some(false),
+        Err(serenity::Error::Http(error))
+            if error.status_code().map(|status_code| status_code.as_u16()) == Some(404) =>
+        {
+            Some(true)
+        }
+        Err(_) => None,
+    }
+}
+
+async fn redis_remove_juxtapose(
+    connection: &mut redis::aio::ConnectionManager,
+    juxtapose_data: &str,
+) -> Result<(), redis::RedisError> {
+    APIJuxtaposeResponse::redis_cache_delete(connection, juxtapose_data).await?;
+    redis_delete_view_count(connection, juxtapose_data).await?;
+    redis_remove_recent_juxtapose(connection, juxtapose_data).await?;
+
+    Ok(())
+}
+
+/// Verifies a sample of the stored juxtaposes against Discord and removes those whose message has been deleted.
+async fn run_cache_janitor(
+    serenity_http: &Http,
+    connection: &mut redis::aio::ConnectionManager,
+) -> Result<(), redis::RedisError> {
+    let (cached_payloads, _) = redis_scan_sample(
+        connection,
+        format!("{}*", REDIS_CACHE_KEY_PREFIX).as_str(),
+        0,
+    )
+    .await?;
+
+    let (stored_layouts, sampled_layout_keys) = redis_scan_sample(
+        connection,
+        format!("{}*", REDIS_LAYOUT_KEY_PREFIX).as_str(),
+        JANITOR_SAMPLE_SIZE,
+    )
+    .await?;
+
+    let mut sampled = 0;
+    let mut orphaned = 0;
+
+    for layout_key in sampled_layout_keys {
+        let Some(juxtapose_data) = layout_key.strip_prefix(REDIS_LAYOUT_KEY_PREFIX) else {
+            continue;
+        };
+
+        tokio::time::sleep(JANITOR_REQUEST_DELAY).await;
+
+        match is_juxtapose_message_deleted(serenity_http, juxtapose_data).await {
+            Some(true) => {
+                sampled += 1;
+                orphaned += 1;
+                redis_remove_juxtapose(connection, juxtapose_data).await?;
+            }
+            Some(false) => sampled += 1,
+            None => {}
+        }
+    }
+
+    {
+        let mut metrics = JANITOR_METRICS.lock().unwrap();
+        metrics.last_run_unix_ts = Some(get_unix_ts());
+        metrics.cached_payloads = cached_payloads;
+        metrics.stored_layouts = stored_layouts;
+        metrics.sampled = sampled;
+        metrics.orphaned = orphaned;
+        metrics.orphan_rate = if sampled > 0 {
+            orphaned as f64 / sampled as f64
+        } else {
+            0.0
+        };
+        metrics.removed_total += orphaned;
+    }
+
+    println!(
+        "Cleaned up juxtapose cache, removed {} of {} sampled entries ({} stored layouts, {} cached payloads).",
+        orphaned, sampled, stored_layouts, cached_payloads
+    );
+
+    Ok(())
+}
+
+/// Starts the periodic cleanup of orphaned juxtapose keys in the background, once per process.
+pub(crate) fn spawn_cache_janitor(
+    serenity_http: Arc<Http>,
+    mut connection: redis::aio::ConnectionManager,
+) {
+    if IS_JANITOR_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        tokio::time::sleep(JANITOR_INITIAL_DELAY).await;
+
+        let mut interval = tokio::time::interval(JANITOR_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(error) = run_cache_janitor(&serenity_http, &mut connection).await {
+                println!("Error while cleaning up juxtapose cache: {:?}", error);
+            }
+        }
+    });
+}
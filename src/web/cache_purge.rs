@@ -0,0 +1,49 @@
+use redis::AsyncCommands;
+
+/// Key prefixes of caches that are rebuilt on demand, so purging them never loses data.
+pub(crate) const PURGEABLE_KEY_PREFIXES: [&str; 2] = ["juxtapose_payload:", "juxtapose_result:"];
+
+const DELETE_BATCH_SIZE: usize = 500;
+
+/// Deletes all cache entries matching a Redis glob pattern, which must start with one of [`PURGEABLE_KEY_PREFIXES`].
+/// Returns the number of deleted entries.
+pub(crate) async fn redis_purge_cache(
+    connection: &mut redis::aio::ConnectionManager,
+    pattern: &str,
+) -> Result<usize, String> {
+    if !PURGEABLE_KEY_PREFIXES
+        .iter()
+        .any(|prefix| pattern.starts_with(prefix))
+    {
+        return Err(format!(
+            "The pattern must start with one of {}.",
+            PURGEABLE_KEY_PREFIXES.join(", ")
+        ));
+    }
+
+    let keys: Vec<String> = {
+        let mut keys_iter = connection
+            .scan_match::<&str, String>(pattern)
+            .await
+            .map_err(|err| format!("Failed to scan cache entries: {:?}", err))?;
+
+        let mut keys = Vec::new();
+
+        while let Some(key) = keys_iter.next_item().await {
+            keys.push(key);
+        }
+
+        keys
+    };
+
+    let mut deleted_count = 0;
+
+    for keys_batch in keys.chunks(DELETE_BATCH_SIZE) {
+        deleted_count += connection
+            .del::<&[String], usize>(keys_batch)
+            .await
+            .map_err(|err| format!("Failed to delete cache entries: {:?}", err))?;
+    }
+
+    Ok(deleted_count)
+}
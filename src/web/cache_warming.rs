@@ -0,0 +1,184 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use axum::http::StatusCode;
+use redis::AsyncCommands;
+use serenity::all::{Http, UserId};
+
+use crate::retry::jittered_backoff;
+use crate::signing::SignedPayload;
+
+use super::api_juxtapose_response::APIJuxtaposeResponse;
+use super::api_juxtapose_url_handler::fetch_juxtapose_response;
+
+const CACHE_WRITE_MAX_ATTEMPTS: u32 = 5;
+const CACHE_WRITE_BACKOFF_BASE: Duration = Duration::from_millis(500);
+
+/// Sorted set of the data of recently created juxtaposes, scored by their creation time.
+const RECENT_JUXTAPOSES_KEY: &str = "juxtapose_recent";
+
+/// Discord CDN URLs expire after roughly a day, so cache entries of older juxtaposes would expire immediately anyway.
+const RECENT_JUXTAPOSES_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+static IS_RECONCILIATION_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn get_unix_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+async fn try_warm_cache(
+    connection: &mut redis::aio::ConnectionManager,
+    juxtapose_data: &str,
+    response_data: &APIJuxtaposeResponse,
+) -> Result<(), String> {
+    // Record the juxtapose first, so the reconciliation can still repair the entry if the cache write fails.
+    let _: () = connection
+        .zadd(RECENT_JUXTAPOSES_KEY, juxtapose_data, get_unix_ts())
+        .await
+        .map_err(|err| format!("{:?}", err))?;
+
+    response_data
+        .redis_set_layout(connection, juxtapose_data)
+        .await
+        .map_err(|err| format!("{:?}", err))?;
+
+    response_data
+        .redis_cache_set(connection, juxtapose_data)
+        .await
+        .map(|_| ())
+        .map_err(|status_code| status_code.to_string())
+}
+
+/// Writes the API cache entry of a new juxtapose in the background, retrying with backoff on failure.
+pub(crate) fn spawn_cache_warming(
+    mut connection: redis::aio::ConnectionManager,
+    juxtapose_data: String,
+    response_data: APIJuxtaposeResponse,
+) {
+    tokio::spawn(async move {
+        for attempt in 0..CACHE_WRITE_MAX_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(jittered_backoff(CACHE_WRITE_BACKOFF_BASE, attempt)).await;
+            }
+
+            match try_warm_cache(&mut connection, juxtapose_data.as_str(), &response_data).await {
+                Ok(()) => return,
+                Err(error) => println!(
+                    "Error while warming juxtapose cache (attempt {}/{}): {}",
+                    attempt + 1,
+                    CACHE_WRITE_MAX_ATTEMPTS,
+                    error
+                ),
+            }
+        }
+    });
+}
+
+pub(crate) async fn redis_remove_recent_juxtapose(
+    connection: &mut redis::aio::ConnectionManager,
+    juxtapose_data: &str,
+) -> Result<usize, redis::RedisError> {
+    connection.zrem(RECENT_JUXTAPOSES_KEY, juxtapose_data).await
+}
+
+/// Returns the data of the most recently created juxtapose, if any was created within the window.
+pub(crate) async fn redis_get_latest_recent_juxtapose(
+    connection: &mut redis::aio::ConnectionManager,
+) -> Result<Option<String>, redis::RedisError> {
+    let min_unix_ts = get_unix_ts().saturating_sub(RECENT_JUXTAPOSES_WINDOW.as_secs());
+
+    let latest_juxtaposes: Vec<String> = connection
+        .zrevrangebyscore_limit(RECENT_JUXTAPOSES_KEY, "+inf", min_unix_ts, 0, 1)
+        .await?;
+
+    Ok(latest_juxtaposes.into_iter().next())
+}
+
+/// Validates the cache entries of all recent juxtaposes and rebuilds missing or incomplete ones from Discord.
+async fn reconcile_recent_juxtaposes(
+    serenity_http: &Http,
+    current_user_id: UserId,
+    connection: &mut redis::aio::ConnectionManager,
+) -> Result<(), redis::RedisError> {
+    let min_unix_ts = get_unix_ts().saturating_sub(RECENT_JUXTAPOSES_WINDOW.as_secs());
+
+    let _: () = connection
+        .zrembyscore(RECENT_JUXTAPOSES_KEY, "-inf", min_unix_ts)
+        .await?;
+
+    let recent_juxtaposes: Vec<String> = connection
+        .zrangebyscore(RECENT_JUXTAPOSES_KEY, min_unix_ts, "+inf")
+        .await?;
+
+    let mut repaired_count = 0;
+
+    for juxtapose_data in recent_juxtaposes {
+        if APIJuxtaposeResponse::redis_cache_get_data(connection, juxtapose_data.as_str())
+            .await
+            .is_some()
+        {
+            continue;
+        }
+
+        // The data was stored by the bot after signing it, so it doesn't need to be verified again.
+        let Ok(payload) = SignedPayload::decode_trusted_data(juxtapose_data.as_str()) else {
+            let _: () = connection
+                .zrem(RECENT_JUXTAPOSES_KEY, juxtapose_data)
+                .await?;
+            continue;
+        };
+
+        match fetch_juxtapose_response(serenity_http, current_user_id, payload).await {
+            Ok(mut response_data) => {
+                response_data
+                    .redis_get_layout(connection, juxtapose_data.as_str())
+                    .await;
+
+                if response_data
+                    .redis_cache_set(connection, juxtapose_data.as_str())
+                    .await
+                    .is_ok()
+                {
+                    repaired_count += 1;
+                }
+            }
+            // The message has been deleted or isn't a juxtapose, so there is nothing left to repair.
+            Err(StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST) => {
+                let _: () = connection
+                    .zrem(RECENT_JUXTAPOSES_KEY, juxtapose_data)
+                    .await?;
+            }
+            Err(_) => {}
+        }
+    }
+
+    println!(
+        "Reconciled juxtapose cache, repaired {} entries.",
+        repaired_count
+    );
+
+    Ok(())
+}
+
+/// Starts the reconciliation of recent juxtaposes in the background, once per process.
+pub(crate) fn spawn_reconciliation(
+    serenity_http: Arc<Http>,
+    current_user_id: UserId,
+    mut connection: redis::aio::ConnectionManager,
+) {
+    if IS_RECONCILIATION_STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        if let Err(error) =
+            reconcile_recent_juxtaposes(&serenity_http, current_user_id, &mut connection).await
+        {
+            println!("Error while reconciling juxtapose cache: {:?}", error);
+        }
+    });
+}
@@ -0,0 +1,20 @@
+use axum::http::{header, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::config::CorsOrigins;
+
+pub(crate) fn create_cors_layer(cors_origins: &'static CorsOrigins) -> CorsLayer {
+    let allow_origin = match cors_origins {
+        CorsOrigins::Any => AllowOrigin::any(),
+        CorsOrigins::List(patterns) => AllowOrigin::predicate(move |origin, _| {
+            patterns.iter().any(|pattern| pattern.matches(origin))
+        }),
+    };
+
+    // The default `Vary` header of the layer already contains `Origin`, which keeps caches from
+    // serving a response that was allowed for one origin to another one.
+    CorsLayer::new()
+        .allow_methods([Method::GET, Method::PUT])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+        .allow_origin(allow_origin)
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use axum::http::{header, HeaderMap, StatusCode};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serenity::all::{GuildId, Permissions, UserId};
+use serenity::cache::Cache;
+
+use crate::HTTP_CLIENT;
+
+use super::dashboard_session::get_session_access_token;
+
+pub(crate) const DISCORD_API_BASE_URL: &str = "https://discord.com/api/v10";
+
+/// Guild memberships rarely change, but are cached briefly to stay below Discord's rate limits.
+const DASHBOARD_USER_CACHE_TTL: u64 = 60;
+
+#[derive(Debug, Deserialize)]
+struct APIUser {
+    id: UserId,
+}
+
+#[derive(Debug, Deserialize)]
+struct APIPartialGuild {
+    id: GuildId,
+    #[serde(default)]
+    owner: bool,
+    permissions: String,
+}
+
+/// The user behind an access token, and the guilds they may manage the bot in.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct DashboardUser {
+    pub(crate) user_id: UserId,
+    pub(crate) admin_guild_ids: Vec<GuildId>,
+}
+
+pub(super) fn redis_dashboard_user_key(access_token: &str) -> String {
+    format!(
+        "dashboard_user:{}",
+        blake3::hash(access_token.as_bytes()).to_hex()
+    )
+}
+
+fn get_bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+async fn discord_api_get<T: for<'de> Deserialize<'de>>(
+    access_token: &str,
+    path: &str,
+) -> Result<T, StatusCode> {
+    let response = HTTP_CLIENT
+        .get(format!("{}{}", DISCORD_API_BASE_URL, path))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|_| StatusCode::BAD_GATEWAY)?;
+
+    match response.status() {
+        status if status.is_success() => {}
+        reqwest::StatusCode::UNAUTHORIZED => return Err(StatusCode::UNAUTHORIZED),
+        reqwest::StatusCode::TOO_MANY_REQUESTS => return Err(StatusCode::TOO_MANY_REQUESTS),
+        _ => return Err(StatusCode::BAD_GATEWAY),
+    }
+
+    response.json().await.map_err(|_| StatusCode::BAD_GATEWAY)
+}
+
+/// Resolves the user of the access token and the guilds in which they may manage the bot,
+/// matching the default permissions of `/config`.
+async fn fetch_dashboard_user(access_token: &str) -> Result<DashboardUser, StatusCode> {
+    let user: APIUser = discord_api_get(access_token, "/users/@me").await?;
+    let guilds: Vec<APIPartialGuild> = discord_api_get(access_token, "/users/@me/guilds").await?;
+
+    let admin_guild_ids = guilds
+        .into_iter()
+        .filter(|guild| {
+            let permissions =
+                Permissions::from_bits_truncate(guild.permissions.parse().unwrap_or_default());
+
+            guild.owner
+                || permissions.contains(Permissions::ADMINISTRATOR)
+                || permissions.contains(Permissions::MANAGE_GUILD)
+        })
+        .map(|guild| guild.id)
+        .collect();
+
+    Ok(DashboardUser {
+        user_id: user.id,
+        admin_guild_ids,
+    })
+}
+
+/// Resolves the user of the Discord OAuth2 access token in the `Authorization: Bearer` header,
+/// or the one of the dashboard session in the cookie.
+pub(crate) async fn get_dashboard_user(
+    headers: &HeaderMap,
+    connection: &mut redis::aio::ConnectionManager,
+) -> Result<DashboardUser, StatusCode> {
+    let access_token = match get_bearer_token(headers) {
+        Some(access_token) => access_token.to_owned(),
+        None => get_session_access_token(headers, connection).await?,
+    };
+    let access_token = access_token.as_str();
+
+    let key = redis_dashboard_user_key(access_token);
+
+    let cached_dashboard_user: Option<String> = connection
+        .get(&key)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let dashboard_user = match cached_dashboard_user
+        .and_then(|cached_dashboard_user| serde_json::from_str(&cached_dashboard_user).ok())
+    {
+        Some(dashboard_user) => dashboard_user,
+        None => {
+            let dashboard_user = fetch_dashboard_user(access_token).await?;
+
+            let _: () = connection
+                .set_ex(
+                    &key,
+                    serde_json::to_string(&dashboard_user)
+                        .expect("Failed to serialize dashboard user."),
+                    DASHBOARD_USER_CACHE_TTL,
+                )
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            dashboard_user
+        }
+    };
+
+    Ok(dashboard_user)
+}
+
+/// Checks that the dashboard user may manage the guild, and that the bot is a member of it.
+/// Returns the ID of the user.
+pub(crate) async fn require_guild_admin(
+    headers: &HeaderMap,
+    connection: &mut redis::aio::ConnectionManager,
+    serenity_cache: &Arc<Cache>,
+    guild_id: GuildId,
+) -> Result<UserId, StatusCode> {
+    let dashboard_user = get_dashboard_user(headers, connection).await?;
+
+    if !dashboard_user.admin_guild_ids.contains(&guild_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    if serenity_cache.guild(guild_id).is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(dashboard_user.user_id)
+}
@@ -0,0 +1,49 @@
+use axum::{
+    extract::Path,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Redirect},
+};
+use include_dir::{include_dir, Dir};
+
+/// Minimal dashboard for self-hosters, built on the dashboard API routes next to it.
+static DASHBOARD_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/web/assets/dashboard");
+
+fn get_content_type(path: &str) -> &'static str {
+    match path.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Relative URLs in the dashboard require the trailing slash.
+pub(crate) async fn redirect_handler() -> Redirect {
+    Redirect::permanent("dashboard/")
+}
+
+pub(crate) async fn index_handler() -> impl IntoResponse {
+    file_handler(Path("index.html".to_owned())).await
+}
+
+pub(crate) async fn file_handler(Path(path): Path<String>) -> impl IntoResponse {
+    let Some(file) = DASHBOARD_DIR.get_file(path.as_str()) else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(get_content_type(path.as_str())),
+            ),
+            (header::CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+            (
+                header::CONTENT_SECURITY_POLICY,
+                HeaderValue::from_static("default-src 'self'; frame-ancestors 'none'"),
+            ),
+        ],
+        file.contents(),
+    ))
+}
@@ -0,0 +1,231 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use axum::http::{header, HeaderMap, StatusCode};
+use base64::{engine::general_purpose, Engine};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{config, DashboardOAuth2Config};
+use crate::{BLAKE3_DASHBOARD_SESSION_KEY, HTTP_CLIENT};
+
+use super::dashboard_auth::DISCORD_API_BASE_URL;
+
+pub(crate) const SESSION_COOKIE_NAME: &str = "previewbot_session";
+pub(crate) const STATE_COOKIE_NAME: &str = "previewbot_oauth2_state";
+
+/// Sessions outlive access tokens, which are refreshed as long as the session exists.
+pub(crate) const SESSION_TTL: u64 = 30 * 24 * 60 * 60;
+
+/// Access tokens are refreshed slightly before they expire, so they don't expire during a request.
+const ACCESS_TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+static ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the OAuth2 configuration, or 404 if the dashboard login is disabled.
+pub(crate) fn dashboard_oauth2_config() -> Result<&'static DashboardOAuth2Config, StatusCode> {
+    config()
+        .dashboard_oauth2
+        .as_ref()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Derives an identifier that can't be guessed without the key, from a process-wide counter,
+/// the current time and additional input such as the OAuth2 authorization code.
+pub(crate) fn generate_unguessable_id(input: &[u8]) -> String {
+    let mut hasher = blake3::Hasher::new_keyed(&BLAKE3_DASHBOARD_SESSION_KEY);
+
+    hasher
+        .update(&ID_COUNTER.fetch_add(1, Ordering::Relaxed).to_le_bytes())
+        .update(
+            &SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+                .to_le_bytes(),
+        )
+        .update(input);
+
+    general_purpose::URL_SAFE_NO_PAD.encode(hasher.finalize().as_bytes())
+}
+
+pub(crate) fn get_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find_map(|(cookie_name, cookie_value)| (cookie_name == name).then_some(cookie_value))
+}
+
+/// Builds a `Set-Cookie` header value, using an empty value and no lifetime to delete the cookie.
+pub(crate) fn create_cookie(name: &str, value: &str, max_age: u64) -> String {
+    let is_secure = dashboard_oauth2_config()
+        .is_ok_and(|oauth2_config| oauth2_config.redirect_url.scheme() == "https");
+
+    format!(
+        "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax{}",
+        name,
+        value,
+        max_age,
+        if is_secure { "; Secure" } else { "" }
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct APITokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: u64,
+}
+
+/// The Discord OAuth2 tokens of a user logged into the dashboard.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct DashboardSession {
+    pub(crate) access_token: String,
+    pub(crate) refresh_token: String,
+    expires_at_unix_ts: u64,
+}
+
+impl From<APITokenResponse> for DashboardSession {
+    fn from(token_response: APITokenResponse) -> Self {
+        let expires_at = SystemTime::now() + Duration::from_secs(token_response.expires_in);
+
+        Self {
+            access_token: token_response.access_token,
+            refresh_token: token_response.refresh_token,
+            expires_at_unix_ts: expires_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+}
+
+impl DashboardSession {
+    fn redis_key(session_id: &str) -> String {
+        format!("dashboard_session:{}", session_id)
+    }
+
+    fn is_expired(&self) -> bool {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.expires_at_unix_ts)
+            < SystemTime::now() + ACCESS_TOKEN_EXPIRY_MARGIN
+    }
+
+    pub(crate) async fn redis_get(
+        connection: &mut redis::aio::ConnectionManager,
+        session_id: &str,
+    ) -> Result<Option<Self>, redis::RedisError> {
+        let data: Option<String> = connection.get(Self::redis_key(session_id)).await?;
+
+        Ok(data.and_then(|data| serde_json::from_str(data.as_str()).ok()))
+    }
+
+    pub(crate) async fn redis_set(
+        &self,
+        connection: &mut redis::aio::ConnectionManager,
+        session_id: &str,
+    ) -> Result<(), redis::RedisError> {
+        let data = serde_json::to_string(self).expect("Failed to serialize dashboard session.");
+        connection
+            .set_ex(Self::redis_key(session_id), data, SESSION_TTL)
+            .await
+    }
+
+    pub(crate) async fn redis_delete(
+        connection: &mut redis::aio::ConnectionManager,
+        session_id: &str,
+    ) -> Result<(), redis::RedisError> {
+        connection.del(Self::redis_key(session_id)).await
+    }
+}
+
+/// Requests tokens from Discord, either by exchanging an authorization code or by refreshing.
+pub(crate) async fn request_tokens(form: &[(&str, &str)]) -> Result<APITokenResponse, StatusCode> {
+    let oauth2_config = dashboard_oauth2_config()?;
+
+    let response = HTTP_CLIENT
+        .post(format!("{}/oauth2/token", DISCORD_API_BASE_URL))
+        .basic_auth(
+            oauth2_config.client_id.as_str(),
+            Some(oauth2_config.client_secret.as_str()),
+        )
+        .form(form)
+        .send()
+        .await
+        .map_err(|_| StatusCode::BAD_GATEWAY)?;
+
+    match response.status() {
+        status if status.is_success() => {}
+        // Invalid or revoked codes and refresh tokens.
+        reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNAUTHORIZED => {
+            return Err(StatusCode::UNAUTHORIZED)
+        }
+        _ => return Err(StatusCode::BAD_GATEWAY),
+    }
+
+    response.json().await.map_err(|_| StatusCode::BAD_GATEWAY)
+}
+
+pub(crate) async fn revoke_token(token: &str, token_type_hint: &str) -> Result<(), StatusCode> {
+    let oauth2_config = dashboard_oauth2_config()?;
+
+    let response = HTTP_CLIENT
+        .post(format!("{}/oauth2/token/revoke", DISCORD_API_BASE_URL))
+        .basic_auth(
+            oauth2_config.client_id.as_str(),
+            Some(oauth2_config.client_secret.as_str()),
+        )
+        .form(&[("token", token), ("token_type_hint", token_type_hint)])
+        .send()
+        .await
+        .map_err(|_| StatusCode::BAD_GATEWAY)?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(StatusCode::BAD_GATEWAY)
+    }
+}
+
+/// Returns the access token of the session in the cookie of the request, refreshing it if it expired.
+/// Sessions whose refresh token was revoked are deleted.
+pub(crate) async fn get_session_access_token(
+    headers: &HeaderMap,
+    connection: &mut redis::aio::ConnectionManager,
+) -> Result<String, StatusCode> {
+    let session_id = get_cookie(headers, SESSION_COOKIE_NAME).ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let session = DashboardSession::redis_get(connection, session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if !session.is_expired() {
+        return Ok(session.access_token);
+    }
+
+    let refreshed_session: DashboardSession = match request_tokens(&[
+        ("grant_type", "refresh_token"),
+        ("refresh_token", session.refresh_token.as_str()),
+    ])
+    .await
+    {
+        Ok(token_response) => token_response.into(),
+        Err(StatusCode::UNAUTHORIZED) => {
+            DashboardSession::redis_delete(connection, session_id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Err(status_code) => return Err(status_code),
+    };
+
+    refreshed_session
+        .redis_set(connection, session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(refreshed_session.access_token)
+}
@@ -1,4 +1,25 @@
+pub(crate) mod access_log;
+pub(crate) mod api_admin_handler;
+pub(crate) mod api_album_handler;
+pub(crate) mod api_album_response;
+pub(crate) mod api_auth_handler;
+pub(crate) mod api_dashboard_handler;
+pub(crate) mod api_debug_handler;
+pub(crate) mod api_events_handler;
+pub(crate) mod api_juxtapose_card_handler;
+pub(crate) mod api_juxtapose_image_handler;
 pub(crate) mod api_juxtapose_request;
 pub(crate) mod api_juxtapose_response;
 pub(crate) mod api_juxtapose_url_handler;
+pub(crate) mod api_stats_handler;
+pub(crate) mod auth;
+pub(crate) mod cache_janitor;
+pub(crate) mod cache_purge;
+pub(crate) mod cache_warming;
+pub(crate) mod cors;
+pub(crate) mod dashboard_auth;
+pub(crate) mod dashboard_handler;
+pub(crate) mod dashboard_session;
 pub(crate) mod serve;
+pub(crate) mod view_counter;
+pub(crate) mod viewer_handler;
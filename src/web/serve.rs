@@ -35,14 +35,16 @@ pub(crate) async fn serve_unix_listener(app: Router, socket_path_string: &str) {
         .unwrap();
 }
 
-pub(crate) async fn serve_tcp_listener(app: Router, port_string: &str) {
-    let port: u16 = port_string.parse().expect("PORT is not a valid number.");
+pub(crate) async fn serve_tcp_listener(app: Router, port: u16) {
     let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port);
     let listener = TcpListener::bind(&addr).await.unwrap();
 
     println!("Running server on TCP port {port}...");
 
-    axum::serve(listener, app.into_make_service())
-        .await
-        .unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
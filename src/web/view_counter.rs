@@ -0,0 +1,40 @@
+use redis::AsyncCommands;
+
+/// View counters outlive the cached URLs, but are dropped if a juxtapose hasn't been opened for a year.
+const VIEW_COUNTER_TTL: i64 = 365 * 24 * 60 * 60;
+
+fn redis_key(juxtapose_data: &str) -> String {
+    format!("juxtapose_views:{}", juxtapose_data)
+}
+
+pub(crate) async fn redis_increment_view_count(
+    connection: &mut redis::aio::ConnectionManager,
+    juxtapose_data: &str,
+) -> Result<u64, redis::RedisError> {
+    let key = redis_key(juxtapose_data);
+
+    let (view_count,): (u64,) = redis::pipe()
+        .atomic()
+        .incr(&key, 1)
+        .expire(&key, VIEW_COUNTER_TTL)
+        .ignore()
+        .query_async(connection)
+        .await?;
+
+    Ok(view_count)
+}
+
+pub(crate) async fn redis_get_view_count(
+    connection: &mut redis::aio::ConnectionManager,
+    juxtapose_data: &str,
+) -> Result<u64, redis::RedisError> {
+    let view_count: Option<u64> = connection.get(redis_key(juxtapose_data)).await?;
+    Ok(view_count.unwrap_or(0))
+}
+
+pub(crate) async fn redis_delete_view_count(
+    connection: &mut redis::aio::ConnectionManager,
+    juxtapose_data: &str,
+) -> Result<usize, redis::RedisError> {
+    connection.del(redis_key(juxtapose_data)).await
+}
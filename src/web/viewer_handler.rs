@@ -0,0 +1,17 @@
+use axum::{
+    http::{header, HeaderValue},
+    response::{Html, IntoResponse},
+};
+
+/// Minimal juxtapose viewer for self-hosters, which fetches the image URLs from the `/url` route next to it.
+const VIEWER_HTML: &str = include_str!("assets/viewer.html");
+
+pub(crate) async fn handler() -> impl IntoResponse {
+    (
+        [(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=3600"),
+        )],
+        Html(VIEWER_HTML),
+    )
+}